@@ -0,0 +1,119 @@
+//! Tweens: time-based interpolation for animated widget properties.
+//!
+//! Widgets keep a `Tween` per animated value and sample it while drawing,
+//! and call `Renderer::request_next_frame` while it `is_running`, so it
+//! advances every frame until it settles.
+
+use std::time::{Duration, Instant};
+
+/// Easing curve applied to tween progress.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    Linear,
+    /// Fast start, gentle stop (cubic).
+    EaseOut,
+    /// Gentle start and stop (cubic).
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps linear progress `t` in 0.0–1.0 onto the curve.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// A value animating from one number to another over a fixed duration.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bexa_ui_core::animation::Tween;
+/// let mut width = Tween::new(240.0, Duration::from_millis(200));
+/// width.set_target(56.0);
+/// assert_eq!(width.target(), 56.0);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Tween {
+    from: f32,
+    to: f32,
+    start: Instant,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl Tween {
+    /// A tween resting at `value`; `duration` applies to later retargets.
+    pub fn new(value: f32, duration: Duration) -> Self {
+        Self {
+            from: value,
+            to: value,
+            start: Instant::now(),
+            duration,
+            easing: Easing::EaseOut,
+        }
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+    }
+
+    pub fn target(&self) -> f32 {
+        self.to
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value_at(Instant::now())
+    }
+
+    pub fn value_at(&self, now: Instant) -> f32 {
+        let t = self.progress_at(now);
+        self.from + (self.to - self.from) * self.easing.apply(t)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.progress_at(Instant::now()) < 1.0
+    }
+
+    /// Animates towards `to`, starting from the current value, so retargeting
+    /// mid-flight doesn't jump. A no-op if `to` is already the target or
+    /// isn't finite, so a NaN from collapsed math can't stick.
+    pub fn set_target(&mut self, to: f32) {
+        if to == self.to || !to.is_finite() {
+            return;
+        }
+        let now = Instant::now();
+        self.from = self.value_at(now);
+        self.to = to;
+        self.start = now;
+    }
+
+    /// Moves to `value` immediately, cancelling any running animation.
+    pub fn jump_to(&mut self, value: f32) {
+        self.from = value;
+        self.to = value;
+    }
+
+    fn progress_at(&self, now: Instant) -> f32 {
+        if self.duration.is_zero() || self.from == self.to {
+            return 1.0;
+        }
+        let elapsed = now.saturating_duration_since(self.start);
+        (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+}
@@ -0,0 +1,19 @@
+//! Closing the app from code: a menu item, a shortcut, a background thread.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Closes every window and returns from `App::run` on the next turn of the
+/// event loop, as closing the main window does. Callable from any thread.
+pub fn request_exit() {
+    REQUESTED.store(true, Ordering::Release);
+    // Wakes an idle loop
+    crate::redraw::request_redraw();
+}
+
+/// Takes the pending `request_exit`, if any. Called by the event loop once
+/// per turn; embedding hosts check it to close their own window.
+pub fn take_exit_request() -> bool {
+    REQUESTED.swap(false, Ordering::AcqRel)
+}
@@ -1,16 +1,209 @@
 use taffy::prelude::*;
 use winit::event::WindowEvent;
 use winit::event::KeyEvent;
-use winit::keyboard::ModifiersState;
+use winit::event::ElementState;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
 
+use glyphon::cosmic_text::Align;
+use glyphon::Metrics;
+
+use crate::renderer::{GradientDirection, ImageFit};
+
+/// Longest chord, in pixels, of the segments an `arc` is drawn with.
+const ARC_SEGMENT_LEN: f32 = 3.0;
+
+/// What a widget draws into, handed to `Widget::draw`.
+///
+/// The methods below are the supported drawing API for custom widgets:
+/// they keep their signatures as the renderer's command lists change.
+/// Coordinates are logical pixels in window space (`layout.location` is
+/// the widget's top-left corner), angles are radians clockwise from +x,
+/// and colors are linear RGBA in 0..1 except text, which is sRGB bytes.
+/// Everything drawn is cut to the active clips and follows the active
+/// transforms (e.g. inside a `Canvas`). Shapes land under all text of the
+/// same layer, whatever the call order.
+///
+/// `renderer` stays public for the lower-level calls (overlays, measured
+/// text, transforms), whose details may change between releases.
+///
+/// ```
+/// # use bexa_ui_core::{Widget, DrawContext, TextStyle, Metrics};
+/// struct Gauge(f32);
+///
+/// impl Widget for Gauge {
+///     fn draw(&self, ctx: &mut DrawContext) {
+///         let (x, y, w, h) = ctx.bounds();
+///         let center = (x + w / 2.0, y + h / 2.0);
+///         let radius = w.min(h) / 2.0 - 6.0;
+///         let start = std::f32::consts::PI * 0.75;
+///         let sweep = std::f32::consts::PI * 1.5;
+///         ctx.arc(center, radius, start, start + sweep, 6.0, [0.2, 0.22, 0.28, 1.0]);
+///         ctx.arc(center, radius, start, start + sweep * self.0, 6.0, ctx.theme.focus_ring);
+///         let style = TextStyle::new(Metrics::new(16.0, 20.0), [230, 230, 240]);
+///         ctx.text(&format!("{:.0}%", self.0 * 100.0), (x, center.1 - 10.0), (w, 20.0), &style);
+///     }
+/// }
+/// ```
 pub struct DrawContext<'a> {
     pub renderer: &'a mut crate::Renderer,
     pub layout: &'a Layout,
+    /// Active window theme; defaults for semantic colors (focus ring, errors, ...).
+    pub theme: &'a crate::Theme,
+}
+
+/// Look of text drawn with `DrawContext::text`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextStyle {
+    pub metrics: Metrics,
+    pub color: [u8; 3],
+    pub align: Align,
+    /// Font family by name; the default sans-serif when `None`.
+    pub font_family: Option<String>,
+}
+
+impl TextStyle {
+    /// Left-aligned text in the default font.
+    pub fn new(metrics: Metrics, color: [u8; 3]) -> Self {
+        Self {
+            metrics,
+            color,
+            align: Align::Left,
+            font_family: None,
+        }
+    }
+
+    pub fn with_align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn with_font_family(mut self, family: impl Into<String>) -> Self {
+        self.font_family = Some(family.into());
+        self
+    }
+}
+
+impl DrawContext<'_> {
+    /// The widget's rect: x, y, width, height.
+    pub fn bounds(&self) -> (f32, f32, f32, f32) {
+        (
+            self.layout.location.x,
+            self.layout.location.y,
+            self.layout.size.width,
+            self.layout.size.height,
+        )
+    }
+
+    pub fn rect(&mut self, rect: (f32, f32, f32, f32), color: [f32; 4]) {
+        self.renderer.fill_rect_rounded(rect, color, 0.0);
+    }
+
+    pub fn rounded_rect(&mut self, rect: (f32, f32, f32, f32), radius: f32, color: [f32; 4]) {
+        self.renderer.fill_rect_rounded(rect, color, radius);
+    }
+
+    /// Outline of a rect, drawn inside its edges.
+    pub fn stroke_rect(&mut self, rect: (f32, f32, f32, f32), radius: f32, width: f32, color: [f32; 4]) {
+        self.renderer.fill_rect_styled(rect, [0.0; 4], radius, width, color);
+    }
+
+    /// Rect blending from `start` to `end` along `direction`.
+    pub fn gradient_rect(
+        &mut self,
+        rect: (f32, f32, f32, f32),
+        radius: f32,
+        start: [f32; 4],
+        end: [f32; 4],
+        direction: GradientDirection,
+    ) {
+        self.renderer.fill_rect_gradient(rect, start, end, direction, radius);
+    }
+
+    /// Straight segment with square ends.
+    pub fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: [f32; 4]) {
+        self.renderer.draw_line(from, to, width, color);
+    }
+
+    /// Connected segments through `points`.
+    pub fn polyline(&mut self, points: &[(f32, f32)], width: f32, color: [f32; 4]) {
+        for pair in points.windows(2) {
+            self.renderer.draw_line(pair[0], pair[1], width, color);
+        }
+    }
+
+    pub fn circle(&mut self, center: (f32, f32), radius: f32, color: [f32; 4]) {
+        let rect = (center.0 - radius, center.1 - radius, radius * 2.0, radius * 2.0);
+        self.renderer.fill_rect_rounded(rect, color, radius);
+    }
+
+    /// Circle outline of thickness `width`, drawn inside `radius`.
+    pub fn ring(&mut self, center: (f32, f32), radius: f32, width: f32, color: [f32; 4]) {
+        let rect = (center.0 - radius, center.1 - radius, radius * 2.0, radius * 2.0);
+        self.renderer.fill_rect_styled(rect, [0.0; 4], radius, width, color);
+    }
+
+    /// Circular arc from angle `start` to `end`, centered on `radius`.
+    /// Drawn as short segments, so keep `color` opaque: translucent arcs
+    /// show their joints.
+    pub fn arc(&mut self, center: (f32, f32), radius: f32, start: f32, end: f32, width: f32, color: [f32; 4]) {
+        let sweep = end - start;
+        if radius <= 0.0 || sweep == 0.0 || !sweep.is_finite() {
+            return;
+        }
+        let steps = ((sweep.abs() * radius / ARC_SEGMENT_LEN).ceil() as usize).clamp(1, 512);
+        let point = |angle: f32| (center.0 + radius * angle.cos(), center.1 + radius * angle.sin());
+        let mut previous = point(start);
+        for step in 1..=steps {
+            let next = point(start + sweep * step as f32 / steps as f32);
+            self.renderer.draw_line(previous, next, width, color);
+            previous = next;
+        }
+    }
+
+    /// Image file at `path`, placed in `rect` according to `fit`.
+    pub fn image(&mut self, path: &str, rect: (f32, f32, f32, f32), fit: ImageFit) {
+        self.renderer.draw_image(path, rect, [1.0; 4], fit);
+    }
+
+    /// Text laid out in the box at `pos` of size `bounds`, wrapping at its
+    /// width.
+    pub fn text(&mut self, text: &str, pos: (f32, f32), bounds: (f32, f32), style: &TextStyle) {
+        match &style.font_family {
+            Some(family) => self.renderer.draw_text_with_font(
+                text,
+                pos,
+                style.color,
+                bounds,
+                style.metrics,
+                style.align,
+                family,
+            ),
+            None => self
+                .renderer
+                .draw_text(text, pos, style.color, bounds, style.metrics, style.align),
+        }
+    }
+
+    /// Draws `draw` cut to `rect` (and to the clips already active).
+    pub fn clipped(&mut self, rect: (f32, f32, f32, f32), draw: impl FnOnce(&mut Self)) {
+        self.renderer.push_clip_within(rect);
+        draw(self);
+        self.renderer.pop_clip();
+    }
 }
 
 pub struct EventContext<'a> {
     pub event: &'a WindowEvent,
     pub layout: &'a Layout,
+    /// Current cursor position in window coordinates. Already updated when a
+    /// `CursorMoved` is dispatched, and valid for clicks, which carry none.
+    pub cursor: (f32, f32),
+    /// The event is a left press that completes a double click on this
+    /// widget: it took the previous press too, less than `DOUBLE_CLICK_TIME`
+    /// ago and within `DOUBLE_CLICK_DISTANCE` of it. The first press was
+    /// delivered as a plain press; a third one starts over.
+    pub double_click: bool,
 }
 
 pub trait Widget {
@@ -20,13 +213,46 @@ pub trait Widget {
 
     fn draw(&self, _ctx: &mut DrawContext) {}
 
+    /// Draws on top of the children, with the same layout as `draw`.
+    fn draw_over(&self, _ctx: &mut DrawContext) {}
+
     fn handle_event(&mut self, _ctx: &mut EventContext) -> bool {
         false
     }
 
+    /// The cursor moved onto this widget's rect. The tree tracks what the
+    /// cursor is over, like `WidgetNode::on_pointer_enter`: a widget stays
+    /// hovered while the cursor is over its children, and hidden or
+    /// input-blocked branches and content scrolled out of view never are.
+    /// Runs before `handle_event` sees the `CursorMoved`.
+    fn on_mouse_enter(&mut self) {}
+
+    /// The cursor left this widget's rect or the window.
+    fn on_mouse_leave(&mut self) {}
+
     /// Called when this widget has focus and a key is pressed.
     /// Returns true if the event was consumed.
-    fn handle_key_event(&mut self, _event: &KeyEvent, _modifiers: ModifiersState) -> bool {
+    ///
+    /// The focused widget always sees a key before the window does. Only keys
+    /// it leaves unconsumed fall through to the global bindings: Tab /
+    /// Shift+Tab move focus, Enter / Space call `activate`, Escape clears
+    /// active state. Return false for Tab unless the widget wants to insert it
+    /// (like `Terminal`, or `TextInput::with_tab_inserts`).
+    ///
+    /// The default routes Enter / Space to `activate`, so widgets only
+    /// override this for extra keys. Overrides take precedence: a widget that
+    /// wants Enter or Space for something else (a newline, a typed space)
+    /// consumes it here; one that ignores them still gets `activate` through
+    /// the window fallback. Scroll containers are the exception: their
+    /// navigation keys, Space included, scroll them first (see `scroll_by_key`).
+    fn handle_key_event(&mut self, event: &KeyEvent, _modifiers: ModifiersState) -> bool {
+        if is_activation_key(event) {
+            // Holding the key down doesn't re-trigger the action
+            if !event.repeat {
+                self.activate();
+            }
+            return true;
+        }
         false
     }
 
@@ -36,14 +262,103 @@ pub trait Widget {
 
     fn set_focus(&mut self, _focused: bool) {}
 
+    /// The widget's primary action, as if clicked: press a button, toggle a
+    /// checkbox, open a select. Triggered by Enter / Space while focused.
     fn activate(&mut self) {}
 
     fn clear_active(&mut self) {}
 
+    /// A press of any button at `cursor` went to some other widget, or to
+    /// none. Widgets with open popups (dropdowns, popovers, context menus)
+    /// close them here, so clicking anywhere else dismisses them even when
+    /// another widget consumes the press.
+    fn dismiss(&mut self, cursor: (f32, f32)) {
+        let _ = cursor;
+    }
+
+    /// Crops the children to the widget's rect when `Some`, with the given
+    /// corner radius (0 for square corners). Independent of scrolling.
+    /// Presses and hover outside the rect don't reach the cropped children.
+    fn clip_children(&self) -> Option<f32> {
+        None
+    }
+
     fn is_scrollable(&self) -> bool {
         false
     }
 
+    /// Overrides `Theme::scrollbar_autohide` for this scroll container.
+    fn scrollbar_autohide(&self) -> Option<bool> {
+        None
+    }
+
+    /// Whether the child at `index` takes part in layout, drawing, events and
+    /// focus. Hidden children keep their widget state; switchers like
+    /// `Router` use this to show one child at a time.
+    fn is_child_visible(&self, index: usize) -> bool {
+        let _ = index;
+        true
+    }
+
+    /// While true, the children are still drawn but get no pointer events,
+    /// scrolling or focus; the widget's own `handle_event` still runs.
+    /// Used by `LoadingOverlay` to lock a subtree while it is busy.
+    fn blocks_child_input(&self) -> bool {
+        false
+    }
+
+    /// While true, Tab and Shift+Tab only move focus among the widget's
+    /// descendants, and focus moves into it; once it turns false, focus
+    /// goes back to the widget that had it before. `Modal` traps focus while
+    /// open.
+    fn traps_focus(&self) -> bool {
+        false
+    }
+
+    /// Part of the widget, as (x, y, w, h) from its top-left, that the scroll
+    /// containers around it should bring into view, e.g. a row selected
+    /// with the arrow keys below the fold. Taken by the window after each
+    /// key the widget handles.
+    fn take_scroll_target(&mut self) -> Option<(f32, f32, f32, f32)> {
+        None
+    }
+
     /// Called after text rendering to feed back measured pixel widths.
     fn update_measures(&mut self, _measures: &[Vec<f32>]) {}
+
+    /// Lets the pointer select and copy the text drawn by this widget and
+    /// its children, such as labels and table cells. A drag that starts
+    /// over it selects whole glyphs in reading order; Ctrl+C (Cmd+C on
+    /// macOS) copies the selection. Selection stays within one such widget.
+    fn selects_text(&self) -> bool {
+        false
+    }
+}
+
+/// True for a press of Enter or Space, the keys that trigger `Widget::activate`.
+pub fn is_activation_key(event: &KeyEvent) -> bool {
+    event.state == ElementState::Pressed
+        && matches!(
+            event.logical_key,
+            Key::Named(NamedKey::Enter) | Key::Named(NamedKey::Space)
+        )
+}
+
+/// True for a left mouse button press, the event that dismisses popups.
+pub fn is_left_press(event: &WindowEvent) -> bool {
+    is_press(event, MouseButton::Left)
+}
+
+/// True for a press of `button`. Every button reaches `Widget::handle_event`
+/// as its own `MouseInput`: right and middle presses dismiss popups too,
+/// but only left presses move focus or start a text selection.
+pub fn is_press(event: &WindowEvent, button: MouseButton) -> bool {
+    matches!(
+        event,
+        WindowEvent::MouseInput {
+            state: ElementState::Pressed,
+            button: pressed,
+            ..
+        } if *pressed == button
+    )
 }
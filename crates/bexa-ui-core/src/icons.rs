@@ -1,5 +1,5 @@
-/// Nerd Font icon codepoints (Symbols Nerd Font).
-/// See https://www.nerdfonts.com/cheat-sheet for the full list.
+//! Nerd Font icon codepoints (Symbols Nerd Font).
+//! See https://www.nerdfonts.com/cheat-sheet for the full list.
 
 // --- Files & Folders ---
 pub const FOLDER: &str = "\u{f07b}";
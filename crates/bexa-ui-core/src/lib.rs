@@ -2,26 +2,50 @@
 // SPDX-License-Identifier: GPL-3.0-or-later OR Commercial
 // See LICENSE and LICENSE-COMMERCIAL for details.
 
+pub mod animation;
+pub mod exit;
 pub mod framework;
 pub mod icons;
 pub mod reactive;
+pub mod redraw;
+pub mod registry;
 pub mod renderer;
+pub mod selection;
+pub mod shortcut;
+pub mod spawner;
 pub mod signal;
 pub mod theme;
 pub mod tree;
 pub mod widgets;
 
-pub use framework::{DrawContext, EventContext, Widget};
-pub use reactive::{create_effect, signal_changed};
-pub use renderer::{ImageFit, QuadCommand, Renderer, TextCommand};
+pub use animation::{Easing, Tween};
+pub use exit::{request_exit, take_exit_request};
+pub use framework::{is_activation_key, is_left_press, is_press, DrawContext, EventContext, TextStyle, Widget};
+pub use reactive::{
+    create_effect, debounce_signal, frame_task_count, on_frame, run_frame_tasks, signal_changed, throttle_signal,
+};
+pub use redraw::{mark_windows_for_redraw, request_redraw, set_redraw_waker, take_redraw_request, RedrawSchedule};
+pub use renderer::{
+    GlyphExtent, GradientDirection, ImageFit, QuadCommand, Renderer, RoundedClip, SelectableRegion, TextCommand, Transform,
+};
+pub use selection::TextSelection;
+pub use shortcut::KeyCombo;
 pub use signal::{Signal, SetSignal, IntoSignal, create_signal};
+pub use spawner::{UiSender, UiSpawner};
 pub use theme::Theme;
 pub use tree::{
-    build_taffy, clear_active_widgets, collect_focus_paths, dispatch_event, dispatch_scroll,
-    draw_widgets, handle_scrollbar_event, release_scrollbar_drag, scroll_root, sync_styles,
-    try_start_scrollbar_drag, update_widget_measures, widget_mut_at_path, WidgetNode,
+    build_taffy, clear_active_widgets, collect_focus_paths, dispatch_event, focus_trap_path, ClickTracker, DOUBLE_CLICK_DISTANCE, DOUBLE_CLICK_TIME, dispatch_scroll,
+    dismiss_popups, draw_widgets, handle_scrollbar_event, hovered_paths, is_path_interactive, is_path_visible,
+    notify_focus_transition, notify_pointer_transitions, release_scrollbar_drag, scroll_by_key, scroll_for_key, scroll_metrics, scroll_rect_into_view, scroll_root, scroll_to, sync_styles,
+    try_start_scrollbar_drag, update_widget_measures, widget_mut_at_path, widget_rect, IntoWidgetNode,
+    ScrollMetrics, WidgetNode,
+};
+pub use widgets::{
+    Bar, BarChart, BarMode, Button, Canvas, CaretStyle, CellContent, Checkbox, Column, Container, ContextMenu, Drawer, Flex, Icon, Image, Label, Legend, LineChart, loading_overlay, LoadingOverlay, Modal, place_overlay,
+    AnchorRect, popover, Popover, PopoverAlign, PopoverSide, RadioButton, RadioGroup, radio_group, router, Router, ScrollView, Select, Slider,
+    Spacer, Spinner, StatusBar, StatusSegment, Table, Tabs, tab_view, TextInput, Toggle, Toolbar, ToolbarOrientation, Tooltip,
+    TooltipPosition, TreeNode, TreeView,
 };
-pub use widgets::{Bar, BarChart, Button, Checkbox, Column, Container, Flex, Icon, Image, Label, Modal, RadioButton, radio_group, ScrollView, Select, Slider, Table, Tabs, TextInput, Toggle, Tooltip, TooltipPosition, TreeNode, TreeView};
 
 #[cfg(feature = "terminal")]
 pub use widgets::Terminal;
@@ -29,22 +53,77 @@ pub use widgets::Terminal;
 // Re-export text types so downstream crates don't need glyphon directly
 pub use glyphon::Metrics;
 pub use glyphon::cosmic_text::Align;
+// Layout sizes, for `with_flex_basis` without depending on taffy
+pub use taffy::style::Dimension;
+// Key types, for building `KeyCombo`s without depending on winit
+pub use winit::keyboard::{Key, ModifiersState, NamedKey};
 
 use std::sync::{Arc, Mutex};
 
 /// Request to open a new window from within a widget callback.
+///
+/// The new window's `root` can share signals with the window that opened it:
+/// every window of an app runs on the same UI thread, and a write through any
+/// `SetSignal` clone redraws them all (`mark_windows_for_redraw`), so it shows
+/// up in every window on the next frame, with no extra plumbing.
+///
+/// ```
+/// use bexa_ui_core::{build_taffy, create_signal, draw_widgets, ui, Label, Metrics, Renderer};
+/// use taffy::prelude::*;
+///
+/// // A main window and a detached inspector showing the same signal
+/// let (status, set_status) = create_signal("Idle".to_string());
+/// let metrics = Metrics::new(14.0, 20.0);
+/// let mut main_root = ui!(Label::new(status.clone(), metrics, [230, 230, 240]));
+/// let mut inspector_root = ui!(Label::new(status, metrics, [230, 230, 240]));
+///
+/// set_status.set("Deploying".to_string());
+///
+/// for root in [&mut main_root, &mut inspector_root] {
+///     let mut taffy = TaffyTree::new();
+///     let node = build_taffy(root, &mut taffy);
+///     let available = Size { width: AvailableSpace::Definite(320.0), height: AvailableSpace::Definite(80.0) };
+///     taffy.compute_layout(node, available).unwrap();
+///     let mut renderer = Renderer::new();
+///     draw_widgets(root, &taffy, &mut renderer);
+///     assert_eq!(renderer.text_commands[0].text, "Deploying");
+/// }
+/// ```
 pub struct WindowRequest {
     pub title: String,
     pub width: u32,
     pub height: u32,
     pub root: WidgetNode,
     pub theme: Theme,
+    /// Window clear color; `None` uses `theme.background`. Alpha < 1 requests
+    /// a transparent window where the platform supports it.
+    pub clear_color: Option<[f32; 4]>,
+    /// Presentation mode; `None` inherits the app's (`App::present_mode`).
+    pub present_mode: Option<PresentMode>,
+}
+
+/// How finished frames reach the screen, set with `App::present_mode`.
+/// Modes the display doesn't support fall back to `Fifo`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PresentMode {
+    /// Vsync: frames queue up and are shown one per refresh. Supported
+    /// everywhere.
+    #[default]
+    Fifo,
+    /// Each refresh shows the newest frame, dropping older ones: low
+    /// latency without tearing, frame rate uncapped.
+    Mailbox,
+    /// Frames are shown as soon as they are done: lowest latency and
+    /// uncapped, but may tear.
+    Immediate,
 }
 
 /// Shared handle for widgets to request new windows.
 pub type WindowRequests = Arc<Mutex<Vec<WindowRequest>>>;
 
 /// Create a new `WindowRequests` handle.
+// WidgetNode is not Send; the handle only lives on the UI thread.
+#[allow(clippy::arc_with_non_send_sync)]
 pub fn create_window_requests() -> WindowRequests {
     Arc::new(Mutex::new(Vec::new()))
 }
@@ -71,6 +150,7 @@ pub fn create_window_requests() -> WindowRequests {
 ///
 /// - `widget => { children... }` — container with children
 /// - `widget` — leaf node (no children), automatically wrapped
+/// - `node` — an existing `WidgetNode` (e.g. returned by a helper fn) is inserted as-is
 /// - `if (cond) { items... }` — conditional rendering (optional)
 /// - `if (cond) { items... } else { items... }` — conditional with alternative
 /// - Children can be bare widgets or nested `container => { ... }` expressions
@@ -83,9 +163,9 @@ macro_rules! ui {
         $crate::WidgetNode::new($widget, $crate::ui_items![ $( $child )* ])
     };
 
-    // Leaf: Widget (no children)
+    // Leaf: Widget (no children) or an already-built WidgetNode
     ( $widget:expr ) => {
-        $crate::WidgetNode::new($widget, vec![])
+        $crate::IntoWidgetNode::into_widget_node($widget)
     };
 }
 
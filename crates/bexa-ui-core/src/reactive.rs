@@ -1,8 +1,152 @@
-/// Reactive components system for BEXAUI
+//! Reactive components system for BEXAUI
+//!
+//! This module provides utilities for reactive programming patterns.
+
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+use crate::{create_signal, Signal};
+
+type FrameTask = Box<dyn FnMut(Instant) -> bool>;
+
+thread_local! {
+    static FRAME_TASKS: RefCell<Vec<FrameTask>> = RefCell::new(Vec::new());
+}
+
+/// Registers a task that runs once per frame on the UI thread.
+///
+/// The task receives the frame timestamp and returns `true` to stay
+/// registered, or `false` to be dropped after this frame. While no window
+/// needs a redraw, the loop idles and tasks run about every 100 ms; a task
+/// that needs every frame (e.g. a smooth animation) calls `request_redraw`.
+pub fn on_frame<F>(task: F)
+where
+    F: FnMut(Instant) -> bool + 'static,
+{
+    FRAME_TASKS.with(|tasks| tasks.borrow_mut().push(Box::new(task)));
+}
+
+/// Number of registered frame tasks on this thread.
+pub fn frame_task_count() -> usize {
+    FRAME_TASKS.with(|tasks| tasks.borrow().len())
+}
+
+/// Runs all registered frame tasks. Called by the event loop once per frame.
 ///
-/// This module provides utilities for reactive programming patterns.
+/// Tasks registered while this runs are kept and first run on the next frame.
+pub fn run_frame_tasks() {
+    let now = Instant::now();
+    let mut current = FRAME_TASKS.with(|tasks| std::mem::take(&mut *tasks.borrow_mut()));
+    current.retain_mut(|task| task(now));
+    FRAME_TASKS.with(|tasks| {
+        let mut tasks = tasks.borrow_mut();
+        let added = std::mem::replace(&mut *tasks, current);
+        tasks.extend(added);
+    });
+}
 
-use crate::Signal;
+/// Derived signal that follows `source` only after it stops changing for `duration`.
+///
+/// Useful for search boxes: the filter runs once typing pauses instead of on
+/// every keystroke. Driven by the frame clock (see [`run_frame_tasks`]);
+/// the task unregisters itself once the returned signal is dropped.
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// # use bexa_ui_core::{create_signal, debounce_signal, frame_task_count, run_frame_tasks};
+/// let (query, _set_query) = create_signal(String::new());
+/// let tasks = frame_task_count();
+/// let debounced = debounce_signal(query, Duration::from_millis(250));
+/// assert_eq!(frame_task_count(), tasks + 1);
+///
+/// drop(debounced);
+/// run_frame_tasks();
+/// assert_eq!(frame_task_count(), tasks);
+/// ```
+pub fn debounce_signal<T>(source: Signal<T>, duration: Duration) -> Signal<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    let (output, set_output) = create_signal(source.get());
+    let set_output = set_output.downgrade();
+    let mut last_seen = source.get();
+    let mut changed_at: Option<Instant> = None;
+
+    on_frame(move |now| {
+        let Some(set_output) = set_output.upgrade() else {
+            return false;
+        };
+        let current_differs = source.with(|v| *v != last_seen);
+        if current_differs {
+            last_seen = source.get();
+            changed_at = Some(now);
+        }
+        if let Some(at) = changed_at
+            && now.duration_since(at) >= duration
+        {
+            set_output.set(last_seen.clone());
+            changed_at = None;
+        }
+        true
+    });
+
+    output
+}
+
+/// Derived signal that follows `source` at most once per `duration`.
+///
+/// The first change propagates immediately; further changes inside the
+/// window are coalesced and the latest value is emitted when it closes.
+/// Like [`debounce_signal`], the task stops with the returned signal.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bexa_ui_core::{create_signal, frame_task_count, run_frame_tasks, throttle_signal};
+/// let (position, set_position) = create_signal(0);
+/// let tasks = frame_task_count();
+/// let throttled = throttle_signal(position, Duration::from_secs(60));
+///
+/// // The first change goes through on the next frame
+/// set_position.set(1);
+/// run_frame_tasks();
+/// assert_eq!(throttled.get(), 1);
+///
+/// drop(throttled);
+/// run_frame_tasks();
+/// assert_eq!(frame_task_count(), tasks);
+/// ```
+pub fn throttle_signal<T>(source: Signal<T>, duration: Duration) -> Signal<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    let (output, set_output) = create_signal(source.get());
+    let set_output = set_output.downgrade();
+    let mut last_emit: Option<Instant> = None;
+    let mut pending = false;
+    let mut last_seen = source.get();
+
+    on_frame(move |now| {
+        let Some(set_output) = set_output.upgrade() else {
+            return false;
+        };
+        if source.with(|v| *v != last_seen) {
+            last_seen = source.get();
+            pending = true;
+        }
+        if pending {
+            let ready = last_emit.is_none_or(|at| now.duration_since(at) >= duration);
+            if ready {
+                set_output.set(last_seen.clone());
+                last_emit = Some(now);
+                pending = false;
+            }
+        }
+        true
+    });
+
+    output
+}
 
 /// Effect system - observe Signal changes
 ///
@@ -0,0 +1,145 @@
+//! Redraws on demand: the event loop only draws a window again when
+//! something may have changed it.
+//!
+//! Window events (input, resizes, focus) redraw the window they reach. Every
+//! `SetSignal` write calls `request_redraw`, which redraws all windows, since
+//! signals are shared between them. Widgets that change with time alone ask
+//! from `draw` through `Renderer::request_next_frame` (spinners, running
+//! tweens) or `Renderer::request_redraw_at` (caret blink). Each window
+//! tracks what it still owes in a `RedrawSchedule`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+type Waker = Box<dyn Fn() + Send + Sync>;
+
+static REQUESTED: AtomicBool = AtomicBool::new(true);
+static WAKER: Mutex<Option<Waker>> = Mutex::new(None);
+
+/// Redraws every window on the next turn of the event loop. Callable from
+/// any thread, e.g. by a reader thread that filled a buffer a widget draws;
+/// it wakes the loop if it is idle.
+pub fn request_redraw() {
+    if !REQUESTED.swap(true, Ordering::AcqRel)
+        && let Some(wake) = WAKER.lock().unwrap().as_ref()
+    {
+        wake();
+    }
+}
+
+/// Takes the pending `request_redraw`, if any. Called by the event loop once
+/// per turn.
+pub fn take_redraw_request() -> bool {
+    REQUESTED.swap(false, Ordering::AcqRel)
+}
+
+/// Called by `request_redraw` to wake an idle event loop. `App::run` sets
+/// this; embedding hosts that redraw on demand can too.
+pub fn set_redraw_waker(waker: impl Fn() + Send + Sync + 'static) {
+    *WAKER.lock().unwrap() = Some(Box::new(waker));
+}
+
+/// Frames drawn after a change: the one showing it, and one more so text
+/// measured during it (`update_widget_measures`) reaches the layout.
+const FRAMES_PER_CHANGE: u8 = 2;
+
+/// When one window needs drawing again. Each window of an app keeps one.
+#[derive(Clone, Copy, Debug)]
+pub struct RedrawSchedule {
+    /// Frames still owed to events and signal writes
+    pending_frames: u8,
+    /// When a widget drawn last frame asked to be drawn again
+    redraw_at: Option<Instant>,
+}
+
+impl Default for RedrawSchedule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RedrawSchedule {
+    /// A schedule that draws the window's first frames.
+    pub fn new() -> Self {
+        Self { pending_frames: FRAMES_PER_CHANGE, redraw_at: None }
+    }
+
+    /// Schedules a redraw, after an event or signal write may have changed
+    /// what the window shows.
+    pub fn mark_dirty(&mut self) {
+        self.pending_frames = FRAMES_PER_CHANGE;
+    }
+
+    /// Whether the window has a change to show or a widget's redraw time
+    /// (e.g. a caret blink) has come.
+    pub fn wants_redraw(&self, now: Instant) -> bool {
+        self.pending_frames > 0 || self.redraw_at.is_some_and(|at| at <= now)
+    }
+
+    /// Records a drawn frame, with the renderer's `redraw_at` from it.
+    pub fn frame_drawn(&mut self, redraw_at: Option<Instant>) {
+        self.pending_frames = self.pending_frames.saturating_sub(1);
+        self.redraw_at = redraw_at;
+    }
+
+    /// Asks for a redraw at `at` too, e.g. for work put off until then;
+    /// an earlier `redraw_at` is kept. Call after `frame_drawn`, which
+    /// replaces it.
+    ///
+    /// ```
+    /// use bexa_ui_core::RedrawSchedule;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let now = Instant::now();
+    /// let mut schedule = RedrawSchedule::new();
+    /// schedule.frame_drawn(Some(now + Duration::from_millis(500)));
+    /// schedule.redraw_by(now + Duration::from_millis(16));
+    /// schedule.redraw_by(now + Duration::from_millis(100));
+    /// assert_eq!(schedule.redraw_at(), Some(now + Duration::from_millis(16)));
+    /// ```
+    pub fn redraw_by(&mut self, at: Instant) {
+        self.redraw_at = Some(self.redraw_at.map_or(at, |current| current.min(at)));
+    }
+
+    /// When a widget drawn last frame asked to be drawn again.
+    pub fn redraw_at(&self) -> Option<Instant> {
+        self.redraw_at
+    }
+}
+
+/// Takes the pending `request_redraw` and, if there was one, marks every
+/// window's schedule dirty: signals are shared between windows, so a write
+/// may change any of them. Returns whether there was a request.
+///
+/// ```
+/// use bexa_ui_core::{create_signal, mark_windows_for_redraw, RedrawSchedule};
+/// use std::time::Instant;
+///
+/// let (_count, set_count) = create_signal(0);
+/// let mut main = RedrawSchedule::new();
+/// let mut inspector = RedrawSchedule::new();
+///
+/// // Draw both windows until they are idle
+/// mark_windows_for_redraw([&mut main, &mut inspector]);
+/// for schedule in [&mut main, &mut inspector] {
+///     while schedule.wants_redraw(Instant::now()) {
+///         schedule.frame_drawn(None);
+///     }
+/// }
+/// assert!(!mark_windows_for_redraw([&mut main, &mut inspector]));
+///
+/// set_count.set(1);
+/// assert!(mark_windows_for_redraw([&mut main, &mut inspector]));
+/// assert!(main.wants_redraw(Instant::now()));
+/// assert!(inspector.wants_redraw(Instant::now()));
+/// ```
+pub fn mark_windows_for_redraw<'a>(schedules: impl IntoIterator<Item = &'a mut RedrawSchedule>) -> bool {
+    let requested = take_redraw_request();
+    if requested {
+        for schedule in schedules {
+            schedule.mark_dirty();
+        }
+    }
+    requested
+}
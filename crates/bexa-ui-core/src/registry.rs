@@ -0,0 +1,382 @@
+//! Widgets by name, for trees described as data instead of Rust code.
+//!
+//! A `UiSpec` names each widget and gives it `Props`; `build_widget_tree`
+//! turns it into a `WidgetNode` by looking every name up in a per-thread
+//! registry. The built-in entries cover layout and text, and
+//! `register_widget` adds custom widgets (or replaces a built-in), so they
+//! can appear in the same serialized trees:
+//!
+//! ```
+//! use bexa_ui_core::registry::{bind_signal, build_widget_tree, register_widget, Props, UiSpec};
+//! use bexa_ui_core::{create_signal, Label, Metrics};
+//!
+//! // A custom widget: a label with a fixed look, configured by props
+//! register_widget("Badge", |props| {
+//!     let text = props.text_signal("text").unwrap_or_else(|| create_signal(String::new()).0);
+//!     Box::new(Label::new(text, Metrics::new(12.0, 16.0), [255, 255, 255]).with_padding(6.0))
+//! });
+//!
+//! let (status, set_status) = create_signal("online".to_string());
+//! bind_signal("status", status);
+//!
+//! let spec = UiSpec::new("Flex")
+//!     .with_prop("direction", "row")
+//!     .with_child(UiSpec::new("Label").with_prop("text", "Server"))
+//!     .with_child(UiSpec::new("Badge").with_prop("text", "$status"));
+//! let tree = build_widget_tree(&spec).unwrap();
+//! # let _ = (tree, set_status);
+//!
+//! assert!(build_widget_tree(&UiSpec::new("Missing")).is_err());
+//! ```
+//!
+//! With the `serde` feature, specs load from JSON with `UiSpec::from_json_str`:
+//!
+//! ```json
+//! { "widget": "Container", "props": { "padding": 12, "background": [0.1, 0.1, 0.14] },
+//!   "children": [{ "widget": "Label", "props": { "text": "$status", "color": [200, 220, 255] } }] }
+//! ```
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use glyphon::Metrics;
+
+use crate::framework::Widget;
+use crate::signal::{IntoSignal, SetSignal, Signal};
+use crate::tree::WidgetNode;
+use crate::widgets::{Container, Flex, Label, Spacer};
+
+type WidgetFactory = Rc<dyn Fn(&Props) -> Box<dyn Widget>>;
+
+/// One property value. In JSON: a bool, a number, a string or an array.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum PropValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    List(Vec<PropValue>),
+}
+
+impl From<bool> for PropValue {
+    fn from(value: bool) -> Self {
+        PropValue::Bool(value)
+    }
+}
+
+impl From<f64> for PropValue {
+    fn from(value: f64) -> Self {
+        PropValue::Number(value)
+    }
+}
+
+impl From<f32> for PropValue {
+    fn from(value: f32) -> Self {
+        PropValue::Number(value as f64)
+    }
+}
+
+impl From<&str> for PropValue {
+    fn from(value: &str) -> Self {
+        PropValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for PropValue {
+    fn from(value: String) -> Self {
+        PropValue::Text(value)
+    }
+}
+
+impl<T: Into<PropValue>> From<Vec<T>> for PropValue {
+    fn from(values: Vec<T>) -> Self {
+        PropValue::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Key/value properties passed to a widget factory.
+///
+/// The typed getters return `None` when a key is missing or has another
+/// type, so factories fall back to their defaults. A string starting with
+/// `$` names a signal registered with `bind_signal`, read with `signal`
+/// or `text_signal`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Props {
+    values: HashMap<String, PropValue>,
+}
+
+impl Props {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<PropValue>) -> Self {
+        self.values.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&PropValue> {
+        self.values.get(key)
+    }
+
+    pub fn bool(&self, key: &str) -> Option<bool> {
+        match self.get(key)? {
+            PropValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn number(&self, key: &str) -> Option<f32> {
+        match self.get(key)? {
+            PropValue::Number(value) => Some(*value as f32),
+            _ => None,
+        }
+    }
+
+    pub fn text(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            PropValue::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// An RGB or RGBA color with channels in 0.0–1.0; alpha defaults to 1.
+    pub fn color(&self, key: &str) -> Option<[f32; 4]> {
+        let channels = self.numbers(key)?;
+        match channels[..] {
+            [r, g, b] => Some([r, g, b, 1.0]),
+            [r, g, b, a] => Some([r, g, b, a]),
+            _ => None,
+        }
+    }
+
+    /// A text color with channels in 0–255, like `Theme::text_primary`.
+    pub fn text_color(&self, key: &str) -> Option<[u8; 3]> {
+        let channels = self.numbers(key)?;
+        match channels[..] {
+            [r, g, b] => Some([r, g, b].map(|c| c.clamp(0.0, 255.0) as u8)),
+            _ => None,
+        }
+    }
+
+    /// The signal bound under the `$name` this prop holds.
+    pub fn signal<T: 'static>(&self, key: &str) -> Option<Signal<T>> {
+        lookup_binding(self.text(key)?.strip_prefix('$')?)
+    }
+
+    /// The write handle bound under the `$name` this prop holds, for
+    /// widgets that report changes (e.g. a text input's `SetSignal<String>`).
+    pub fn setter<T: 'static>(&self, key: &str) -> Option<SetSignal<T>> {
+        lookup_binding(self.text(key)?.strip_prefix('$')?)
+    }
+
+    /// Text for a widget: the bound signal for `$name`, otherwise the
+    /// literal string as a constant signal.
+    pub fn text_signal(&self, key: &str) -> Option<Signal<String>> {
+        let text = self.text(key)?;
+        match text.strip_prefix('$') {
+            Some(_) => self.signal(key),
+            None => Some(text.into_signal()),
+        }
+    }
+
+    fn numbers(&self, key: &str) -> Option<Vec<f32>> {
+        match self.get(key)? {
+            PropValue::List(items) => items
+                .iter()
+                .map(|item| match item {
+                    PropValue::Number(n) => Some(*n as f32),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+}
+
+/// A widget tree as data: a registered widget name, its props and children.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UiSpec {
+    pub widget: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub props: Props,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub children: Vec<UiSpec>,
+}
+
+impl UiSpec {
+    pub fn new(widget: impl Into<String>) -> Self {
+        Self {
+            widget: widget.into(),
+            props: Props::new(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn with_prop(mut self, key: impl Into<String>, value: impl Into<PropValue>) -> Self {
+        self.props = self.props.with(key, value);
+        self
+    }
+
+    pub fn with_child(mut self, child: UiSpec) -> Self {
+        self.children.push(child);
+        self
+    }
+}
+
+#[cfg(feature = "serde")]
+impl UiSpec {
+    pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    pub fn to_json_str(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// A `UiSpec` named a widget that isn't registered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownWidget(pub String);
+
+impl fmt::Display for UnknownWidget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no widget registered as \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for UnknownWidget {}
+
+struct Registry {
+    factories: HashMap<String, WidgetFactory>,
+    // Signal<T> and SetSignal<T> handles by name
+    bindings: HashMap<String, Vec<Box<dyn Any>>>,
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry::with_builtins());
+}
+
+impl Registry {
+    fn with_builtins() -> Self {
+        let mut registry = Self {
+            factories: HashMap::new(),
+            bindings: HashMap::new(),
+        };
+        registry.insert("Container", build_container);
+        registry.insert("Flex", build_flex);
+        registry.insert("Label", build_label);
+        registry.insert("Spacer", |props| {
+            Box::new(Spacer::new().with_grow(props.number("grow").unwrap_or(1.0)))
+        });
+        registry
+    }
+
+    fn insert(&mut self, name: &str, factory: impl Fn(&Props) -> Box<dyn Widget> + 'static) {
+        self.factories.insert(name.to_string(), Rc::new(factory));
+    }
+}
+
+/// `padding`, `gap`, `border_radius`, `background` (color), `scroll` and
+/// `clip` (bools).
+fn build_container(props: &Props) -> Box<dyn Widget> {
+    let mut container = Container::new()
+        .with_padding(props.number("padding").unwrap_or(0.0))
+        .with_gap(props.number("gap").unwrap_or(0.0))
+        .with_border_radius(props.number("border_radius").unwrap_or(0.0))
+        .with_clip(props.bool("clip").unwrap_or(false));
+    if let Some(color) = props.color("background") {
+        container = container.with_background_alpha(color);
+    }
+    if props.bool("scroll") == Some(true) {
+        container = container.with_scroll();
+    }
+    Box::new(container)
+}
+
+/// `direction` (`"row"` or `"column"`, the default), `gap` and `padding`
+/// (columns only).
+fn build_flex(props: &Props) -> Box<dyn Widget> {
+    let gap = props.number("gap").unwrap_or(0.0);
+    match props.text("direction") {
+        Some("row") => Box::new(Flex::row(gap)),
+        _ => Box::new(Flex::column(gap, props.number("padding").unwrap_or(0.0))),
+    }
+}
+
+/// `text` (literal or `$signal`), `font_size`, `line_height`, `color`
+/// (0–255 RGB) and `padding`.
+fn build_label(props: &Props) -> Box<dyn Widget> {
+    let font_size = props.number("font_size").unwrap_or(14.0);
+    let line_height = props.number("line_height").unwrap_or(font_size * 1.4);
+    let text = props.text_signal("text").unwrap_or_else(|| "".into_signal());
+    let mut label = Label::new(
+        text,
+        Metrics::new(font_size, line_height),
+        props.text_color("color").unwrap_or([220, 225, 235]),
+    );
+    if let Some(padding) = props.number("padding") {
+        label = label.with_padding(padding);
+    }
+    Box::new(label)
+}
+
+/// Registers a widget factory under `name` for this thread (the UI
+/// thread), replacing any earlier entry, built-ins included.
+pub fn register_widget(name: &str, factory: impl Fn(&Props) -> Box<dyn Widget> + 'static) {
+    REGISTRY.with(|registry| registry.borrow_mut().insert(name, factory));
+}
+
+/// Makes `signal` available to props as `"$name"`. Binding a `SetSignal`
+/// too (see `bind_setter`) lets input widgets write back to it.
+pub fn bind_signal<T: 'static>(name: &str, signal: Signal<T>) {
+    bind(name, signal);
+}
+
+pub fn bind_setter<T: 'static>(name: &str, setter: SetSignal<T>) {
+    bind(name, setter);
+}
+
+fn bind<H: 'static>(name: &str, handle: H) {
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        let handles = registry.bindings.entry(name.to_string()).or_default();
+        // One handle per type under a name; rebinding replaces it
+        handles.retain(|existing| !existing.is::<H>());
+        handles.push(Box::new(handle));
+    });
+}
+
+fn lookup_binding<H: Clone + 'static>(name: &str) -> Option<H> {
+    REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .bindings
+            .get(name)?
+            .iter()
+            .find_map(|handle| handle.downcast_ref::<H>().cloned())
+    })
+}
+
+/// Builds the widget tree described by `spec`.
+pub fn build_widget_tree(spec: &UiSpec) -> Result<WidgetNode, UnknownWidget> {
+    let factory = REGISTRY.with(|registry| registry.borrow().factories.get(&spec.widget).cloned());
+    let Some(factory) = factory else {
+        return Err(UnknownWidget(spec.widget.clone()));
+    };
+    // Called outside the borrow, so factories may look up bindings
+    let widget = factory(&spec.props);
+    let children = spec
+        .children
+        .iter()
+        .map(build_widget_tree)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(WidgetNode::from_boxed(widget, children))
+}
@@ -1,9 +1,18 @@
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Instant;
+
 use glyphon::Metrics;
 use glyphon::cosmic_text::Align;
 
+use crate::theme::Theme;
+
 /// Clip rectangle (x, y, width, height) in pixel coords.
 pub type ClipRect = (f32, f32, f32, f32);
 
+/// Rounded clip shape: a rect and its corner radius.
+pub type RoundedClip = (ClipRect, f32);
+
 #[derive(Clone, Copy)]
 pub struct QuadCommand {
     pub rect: (f32, f32, f32, f32),
@@ -11,7 +20,72 @@ pub struct QuadCommand {
     pub border_radius: f32,
     pub border_width: f32,
     pub border_color: [f32; 4],
+    /// Rotation about the rect center, in radians (clockwise on screen).
+    pub rotation: f32,
     pub clip: Option<ClipRect>,
+    /// Rounded mask applied on top of `clip`, from `push_clip_rounded`.
+    pub rounded_clip: Option<RoundedClip>,
+    /// Fill per corner (top-left, top-right, bottom-right, bottom-left),
+    /// blended across the quad; replaces `color` when set.
+    pub corner_colors: Option<[[f32; 4]; 4]>,
+}
+
+/// Axis a `fill_rect_gradient` blends along.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GradientDirection {
+    /// Start color on the left, end color on the right.
+    Horizontal,
+    /// Start color at the top, end color at the bottom.
+    Vertical,
+}
+
+/// Maps draw coordinates to screen pixels: `screen = point * scale + offset`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    pub offset: (f32, f32),
+    pub scale: f32,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform { offset: (0.0, 0.0), scale: 1.0 };
+
+    pub fn new(offset: (f32, f32), scale: f32) -> Self {
+        Self { offset, scale }
+    }
+
+    /// This transform followed by `outer`.
+    pub fn then(self, outer: Transform) -> Transform {
+        Transform {
+            offset: outer.apply(self.offset),
+            scale: self.scale * outer.scale,
+        }
+    }
+
+    pub fn apply(&self, point: (f32, f32)) -> (f32, f32) {
+        (
+            point.0 * self.scale + self.offset.0,
+            point.1 * self.scale + self.offset.1,
+        )
+    }
+
+    pub fn apply_rect(&self, rect: (f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+        let (x, y) = self.apply((rect.0, rect.1));
+        (x, y, rect.2 * self.scale, rect.3 * self.scale)
+    }
+
+    /// Maps a screen point back to draw coordinates (for hit-testing).
+    pub fn invert(&self, point: (f32, f32)) -> (f32, f32) {
+        (
+            (point.0 - self.offset.0) / self.scale,
+            (point.1 - self.offset.1) / self.scale,
+        )
+    }
 }
 
 pub struct TextCommand {
@@ -26,21 +100,55 @@ pub struct TextCommand {
     /// Char indices to measure pixel widths at.
     /// Results stored in Renderer::text_measures at the same command index.
     pub measure_chars: Vec<usize>,
+    /// Asks for the extent of every glyph cluster, stored in
+    /// Renderer::glyph_extents at the same command index.
+    pub measure_glyphs: bool,
+}
+
+/// Where one glyph cluster of a measured `TextCommand` landed, relative to
+/// the command's `pos`, in screen pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphExtent {
+    /// Byte range of the cluster in the command's text.
+    pub start: usize,
+    pub end: usize,
+    pub x: f32,
+    pub width: f32,
+    pub line_top: f32,
+    pub line_height: f32,
+}
+
+/// A widget's rect and the main-layer text commands it drew, whose text can
+/// be selected with the pointer (see `Widget::selects_text`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectableRegion {
+    /// Screen pixels, cut to the clip the widget was drawn in.
+    pub rect: ClipRect,
+    /// Indices into `Renderer::text_commands`.
+    pub texts: Range<usize>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ImageFit {
+    /// Stretched to the rect, ignoring the aspect ratio.
     Fill,
+    /// Scaled to fit inside the rect, letterboxed.
     Contain,
+    /// Scaled to cover the rect, cropped.
     Cover,
 }
 
 pub struct ImageCommand {
     pub rect: (f32, f32, f32, f32),
+    /// File to load, and the key the render layer caches the texture under.
     pub path: String,
+    /// Encoded image (PNG, JPEG, ...) decoded instead of reading `path`,
+    /// which is then only the cache key.
+    pub bytes: Option<Arc<[u8]>>,
     pub tint: [f32; 4],
     pub fit: ImageFit,
     pub clip: Option<ClipRect>,
+    pub rounded_clip: Option<RoundedClip>,
 }
 
 pub struct Renderer {
@@ -50,11 +158,32 @@ pub struct Renderer {
     /// Pixel widths measured by the render layer (indexed by TextCommand index).
     /// Each entry corresponds to `measure_chars` of the same TextCommand.
     pub text_measures: Vec<Vec<f32>>,
-    clip_stack: Vec<ClipRect>,
+    /// Glyph extents filled by the render layer for commands with
+    /// `measure_glyphs` (indexed by TextCommand index; empty for others).
+    pub glyph_extents: Vec<Vec<GlyphExtent>>,
+    /// Regions of selectable text, in draw order.
+    pub selectable_regions: Vec<SelectableRegion>,
+    /// Active clips, innermost last, each with the rounded mask in effect
+    clip_stack: Vec<(ClipRect, Option<RoundedClip>)>,
     /// Overlay commands drawn on top of everything (for dropdowns, tooltips, etc.)
     pub overlay_quad_commands: Vec<QuadCommand>,
     pub overlay_text_commands: Vec<TextCommand>,
+    /// Nesting depth of `begin_overlay`; while > 0, regular draw calls go to the overlay layer.
+    overlay_depth: usize,
+    /// Clip stacks saved by `begin_overlay` (overlay content starts unclipped).
+    saved_clip_stacks: Vec<Vec<(ClipRect, Option<RoundedClip>)>>,
+    /// Composed transforms pushed with `push_transform`; top is current.
+    transform_stack: Vec<Transform>,
     viewport_size: (f32, f32),
+    theme: Theme,
+    /// Earliest time a widget asked to be drawn again
+    redraw_at: Option<Instant>,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Renderer {
@@ -64,10 +193,17 @@ impl Renderer {
             text_commands: Vec::new(),
             image_commands: Vec::new(),
             text_measures: Vec::new(),
+            glyph_extents: Vec::new(),
+            selectable_regions: Vec::new(),
             clip_stack: Vec::new(),
             overlay_quad_commands: Vec::new(),
             overlay_text_commands: Vec::new(),
+            overlay_depth: 0,
+            saved_clip_stacks: Vec::new(),
+            transform_stack: Vec::new(),
             viewport_size: (0.0, 0.0),
+            theme: Theme::default(),
+            redraw_at: None,
         }
     }
 
@@ -76,9 +212,15 @@ impl Renderer {
         self.text_commands.clear();
         self.image_commands.clear();
         self.text_measures.clear();
+        self.glyph_extents.clear();
+        self.selectable_regions.clear();
         self.clip_stack.clear();
         self.overlay_quad_commands.clear();
         self.overlay_text_commands.clear();
+        self.overlay_depth = 0;
+        self.saved_clip_stacks.clear();
+        self.transform_stack.clear();
+        self.redraw_at = None;
     }
 
     pub fn set_viewport_size(&mut self, size: (f32, f32)) {
@@ -89,6 +231,33 @@ impl Renderer {
         self.viewport_size
     }
 
+    /// Theme handed to widgets through `DrawContext::theme`.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Asks for another frame right after this one, for content that
+    /// changes with time alone (spinners, running tweens). Windows are
+    /// otherwise only redrawn after events and signal writes.
+    pub fn request_next_frame(&mut self) {
+        self.request_redraw_at(Instant::now());
+    }
+
+    /// Asks for a frame at `at`, e.g. when a blinking caret toggles. The
+    /// earliest request of a frame wins.
+    pub fn request_redraw_at(&mut self, at: Instant) {
+        self.redraw_at = Some(self.redraw_at.map_or(at, |current| current.min(at)));
+    }
+
+    /// When a widget drawn this frame asked to be drawn again.
+    pub fn redraw_at(&self) -> Option<Instant> {
+        self.redraw_at
+    }
+
     /// Push a quad command to the overlay layer (drawn on top of everything).
     pub fn overlay_fill_rect_styled(
         &mut self,
@@ -98,13 +267,19 @@ impl Renderer {
         border_width: f32,
         border_color: [f32; 4],
     ) {
+        if !is_finite_rect(rect) {
+            return;
+        }
         self.overlay_quad_commands.push(QuadCommand {
             rect,
             color,
             border_radius,
             border_width,
             border_color,
+            rotation: 0.0,
             clip: None,
+            rounded_clip: None,
+            corner_colors: None,
         });
     }
 
@@ -128,10 +303,12 @@ impl Renderer {
             clip: None,
             font_family: None,
             measure_chars: vec![],
+            measure_glyphs: false,
         });
     }
 
     /// Push a text command with font to the overlay layer.
+    #[allow(clippy::too_many_arguments)]
     pub fn overlay_draw_text_with_font(
         &mut self,
         text: &str,
@@ -152,30 +329,146 @@ impl Renderer {
             clip: None,
             font_family: Some(font_family.to_string()),
             measure_chars: vec![],
+            measure_glyphs: false,
         });
     }
 
+    /// Redirect subsequent draw calls to the overlay layer until `end_overlay`.
+    /// Used to render whole widget subtrees on top (e.g. popover content).
+    pub fn begin_overlay(&mut self) {
+        self.overlay_depth += 1;
+        self.saved_clip_stacks.push(std::mem::take(&mut self.clip_stack));
+    }
+
+    pub fn end_overlay(&mut self) {
+        if self.overlay_depth == 0 {
+            return;
+        }
+        self.overlay_depth -= 1;
+        self.clip_stack = self.saved_clip_stacks.pop().unwrap_or_default();
+    }
+
+    fn quads(&mut self) -> &mut Vec<QuadCommand> {
+        if self.overlay_depth > 0 { &mut self.overlay_quad_commands } else { &mut self.quad_commands }
+    }
+
+    fn texts(&mut self) -> &mut Vec<TextCommand> {
+        if self.overlay_depth > 0 { &mut self.overlay_text_commands } else { &mut self.text_commands }
+    }
+
     pub fn push_clip(&mut self, clip: ClipRect) {
-        self.clip_stack.push(clip);
+        let rounded = self.current_rounded_clip();
+        self.clip_stack.push((clip, rounded));
+    }
+
+    /// Like `push_clip`, but limited to the current clip, so a region inside
+    /// a scroll viewport can't draw outside it.
+    pub fn push_clip_within(&mut self, clip: ClipRect) {
+        let clip = match self.current_clip() {
+            Some((px, py, pw, ph)) => {
+                let left = clip.0.max(px);
+                let top = clip.1.max(py);
+                let right = (clip.0 + clip.2).min(px + pw);
+                let bottom = (clip.1 + clip.3).min(py + ph);
+                (left, top, (right - left).max(0.0), (bottom - top).max(0.0))
+            }
+            None => clip,
+        };
+        let rounded = self.current_rounded_clip();
+        self.clip_stack.push((clip, rounded));
+    }
+
+    /// Like `push_clip_within`, but also masks quads and images to `clip`
+    /// with rounded corners (e.g. an avatar circle). Text is only cut by
+    /// the rectangle. Pop with `pop_clip`.
+    pub fn push_clip_rounded(&mut self, clip: ClipRect, radius: f32) {
+        self.push_clip_within(clip);
+        if radius > 0.0
+            && let Some(top) = self.clip_stack.last_mut()
+        {
+            top.1 = Some((clip, radius));
+        }
     }
 
     pub fn pop_clip(&mut self) {
         self.clip_stack.pop();
     }
 
-    fn current_clip(&self) -> Option<ClipRect> {
-        self.clip_stack.last().copied()
+    /// Innermost active clip rect, e.g. the viewport of a scroll view.
+    pub fn current_clip(&self) -> Option<ClipRect> {
+        self.clip_stack.last().map(|&(clip, _)| clip)
     }
 
-    pub fn fill_rect(&mut self, rect: (f32, f32, f32, f32), color: [f32; 3]) {
-        self.quad_commands.push(QuadCommand {
-            rect,
-            color: [color[0], color[1], color[2], 1.0],
-            border_radius: 0.0,
-            border_width: 0.0,
-            border_color: [0.0; 4],
-            clip: self.current_clip(),
+    /// Innermost rounded mask from `push_clip_rounded`, if any is active.
+    pub fn current_rounded_clip(&self) -> Option<RoundedClip> {
+        self.clip_stack.last().and_then(|&(_, rounded)| rounded)
+    }
+
+    /// Applies a transform to subsequent (non-overlay) draw calls until the
+    /// matching `pop_transform`. Nested transforms compose. Clip rects stay in
+    /// screen pixels.
+    pub fn push_transform(&mut self, transform: Transform) {
+        let composed = transform.then(self.transform());
+        self.transform_stack.push(composed);
+    }
+
+    pub fn pop_transform(&mut self) {
+        self.transform_stack.pop();
+    }
+
+    pub fn transform(&self) -> Transform {
+        self.transform_stack.last().copied().unwrap_or_default()
+    }
+
+    fn push_quad(
+        &mut self,
+        rect: (f32, f32, f32, f32),
+        color: [f32; 4],
+        border_radius: f32,
+        border_width: f32,
+        border_color: [f32; 4],
+        rotation: f32,
+    ) -> Option<&mut QuadCommand> {
+        if !is_finite_rect(rect) {
+            return None;
+        }
+        let t = self.transform();
+        let clip = self.current_clip();
+        let rounded_clip = self.current_rounded_clip();
+        let quads = self.quads();
+        quads.push(QuadCommand {
+            rect: t.apply_rect(rect),
+            color,
+            border_radius: border_radius * t.scale,
+            border_width: border_width * t.scale,
+            border_color,
+            rotation,
+            clip,
+            rounded_clip,
+            corner_colors: None,
         });
+        quads.last_mut()
+    }
+
+    fn transform_text(
+        &self,
+        pos: (f32, f32),
+        bounds: (f32, f32),
+        metrics: Metrics,
+    ) -> ((f32, f32), (f32, f32), Metrics) {
+        let t = self.transform();
+        if t == Transform::IDENTITY {
+            return (pos, bounds, metrics);
+        }
+        (
+            t.apply(pos),
+            (bounds.0 * t.scale, bounds.1 * t.scale),
+            Metrics::new(metrics.font_size * t.scale, metrics.line_height * t.scale),
+        )
+    }
+
+    pub fn fill_rect(&mut self, rect: (f32, f32, f32, f32), color: [f32; 3]) {
+        self.push_quad(rect, [color[0], color[1], color[2], 1.0], 0.0, 0.0, [0.0; 4], 0.0);
     }
 
     pub fn fill_rect_rounded(
@@ -184,14 +477,7 @@ impl Renderer {
         color: [f32; 4],
         border_radius: f32,
     ) {
-        self.quad_commands.push(QuadCommand {
-            rect,
-            color,
-            border_radius,
-            border_width: 0.0,
-            border_color: [0.0; 4],
-            clip: self.current_clip(),
-        });
+        self.push_quad(rect, color, border_radius, 0.0, [0.0; 4], 0.0);
     }
 
     pub fn fill_rect_styled(
@@ -202,14 +488,37 @@ impl Renderer {
         border_width: f32,
         border_color: [f32; 4],
     ) {
-        self.quad_commands.push(QuadCommand {
-            rect,
-            color,
-            border_radius,
-            border_width,
-            border_color,
-            clip: self.current_clip(),
-        });
+        self.push_quad(rect, color, border_radius, border_width, border_color, 0.0);
+    }
+
+    /// Rect blending from `start` to `end` along `direction`.
+    pub fn fill_rect_gradient(
+        &mut self,
+        rect: (f32, f32, f32, f32),
+        start: [f32; 4],
+        end: [f32; 4],
+        direction: GradientDirection,
+        border_radius: f32,
+    ) {
+        let corners = match direction {
+            GradientDirection::Horizontal => [start, end, end, start],
+            GradientDirection::Vertical => [start, start, end, end],
+        };
+        if let Some(quad) = self.push_quad(rect, start, border_radius, 0.0, [0.0; 4], 0.0) {
+            quad.corner_colors = Some(corners);
+        }
+    }
+
+    /// Straight line segment of the given thickness (square ends).
+    pub fn draw_line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: [f32; 4]) {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length <= f32::EPSILON {
+            return;
+        }
+        let (mx, my) = ((from.0 + to.0) / 2.0, (from.1 + to.1) / 2.0);
+        let rect = (mx - length / 2.0, my - width / 2.0, length, width);
+        self.push_quad(rect, color, 0.0, 0.0, [0.0; 4], dy.atan2(dx));
     }
 
     pub fn draw_text(
@@ -221,19 +530,23 @@ impl Renderer {
         metrics: Metrics,
         align: Align,
     ) {
-        self.text_commands.push(TextCommand {
+        let (pos, bounds, metrics) = self.transform_text(pos, bounds, metrics);
+        let clip = self.current_clip();
+        self.texts().push(TextCommand {
             text: text.to_string(),
             pos,
             color,
             bounds,
             metrics,
             align,
-            clip: self.current_clip(),
+            clip,
             font_family: None,
             measure_chars: vec![],
+            measure_glyphs: false,
         });
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn draw_text_with_font(
         &mut self,
         text: &str,
@@ -244,19 +557,26 @@ impl Renderer {
         align: Align,
         font_family: &str,
     ) {
-        self.text_commands.push(TextCommand {
+        let (pos, bounds, metrics) = self.transform_text(pos, bounds, metrics);
+        let clip = self.current_clip();
+        self.texts().push(TextCommand {
             text: text.to_string(),
             pos,
             color,
             bounds,
             metrics,
             align,
-            clip: self.current_clip(),
+            clip,
             font_family: Some(font_family.to_string()),
             measure_chars: vec![],
+            measure_glyphs: false,
         });
     }
 
+    /// Measured text always lands on the main layer: `text_measures` is
+    /// indexed by position in `text_commands`. Measures are screen pixels,
+    /// i.e. include any active `push_transform` scale.
+    #[allow(clippy::too_many_arguments)]
     pub fn draw_text_measured(
         &mut self,
         text: &str,
@@ -267,6 +587,7 @@ impl Renderer {
         align: Align,
         measure_chars: Vec<usize>,
     ) -> usize {
+        let (pos, bounds, metrics) = self.transform_text(pos, bounds, metrics);
         let idx = self.text_commands.len();
         self.text_commands.push(TextCommand {
             text: text.to_string(),
@@ -278,23 +599,83 @@ impl Renderer {
             clip: self.current_clip(),
             font_family: None,
             measure_chars,
+            measure_glyphs: false,
         });
         idx
     }
 
+    /// Makes the main-layer text commands drawn since index `start`
+    /// selectable within `rect` (draw coordinates), and asks for their glyph
+    /// extents. A region swallows those already marked inside it, so nested
+    /// selectable widgets select as one.
+    pub fn mark_text_selectable(&mut self, start: usize, rect: ClipRect) {
+        let end = self.text_commands.len();
+        if start >= end || self.overlay_depth > 0 {
+            return;
+        }
+        for command in &mut self.text_commands[start..end] {
+            command.measure_glyphs = true;
+        }
+        let (x, y, w, h) = self.transform().apply_rect(rect);
+        let rect = match self.current_clip() {
+            Some((cx, cy, cw, ch)) => {
+                let left = x.max(cx);
+                let top = y.max(cy);
+                let right = (x + w).min(cx + cw);
+                let bottom = (y + h).min(cy + ch);
+                (left, top, (right - left).max(0.0), (bottom - top).max(0.0))
+            }
+            None => (x, y, w, h),
+        };
+        self.selectable_regions.retain(|region| region.texts.start < start);
+        self.selectable_regions.push(SelectableRegion { rect, texts: start..end });
+    }
+
     pub fn draw_image(
         &mut self,
         path: &str,
         rect: (f32, f32, f32, f32),
         tint: [f32; 4],
         fit: ImageFit,
+    ) {
+        self.push_image(path, None, rect, tint, fit);
+    }
+
+    /// Like `draw_image`, from encoded bytes. The render layer decodes them
+    /// once per `key`, so the same key must always carry the same bytes.
+    pub fn draw_image_bytes(
+        &mut self,
+        key: &str,
+        bytes: &Arc<[u8]>,
+        rect: (f32, f32, f32, f32),
+        tint: [f32; 4],
+        fit: ImageFit,
+    ) {
+        self.push_image(key, Some(bytes.clone()), rect, tint, fit);
+    }
+
+    fn push_image(
+        &mut self,
+        path: &str,
+        bytes: Option<Arc<[u8]>>,
+        rect: (f32, f32, f32, f32),
+        tint: [f32; 4],
+        fit: ImageFit,
     ) {
         self.image_commands.push(ImageCommand {
-            rect,
+            rect: self.transform().apply_rect(rect),
             path: path.to_string(),
+            bytes,
             tint,
             fit,
             clip: self.current_clip(),
+            rounded_clip: self.current_rounded_clip(),
         });
     }
 }
+
+/// Geometry from a collapsed range (0/0, ∞-∞) comes out NaN or infinite;
+/// such quads are dropped rather than handed to the GPU.
+fn is_finite_rect(rect: (f32, f32, f32, f32)) -> bool {
+    rect.0.is_finite() && rect.1.is_finite() && rect.2.is_finite() && rect.3.is_finite()
+}
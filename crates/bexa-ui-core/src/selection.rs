@@ -0,0 +1,298 @@
+//! Pointer selection of read-only text, inside the widgets that opt in
+//! with `Widget::selects_text`.
+
+use arboard::Clipboard;
+
+use crate::renderer::{ClipRect, GlyphExtent, Renderer};
+
+/// One selectable text command, as last drawn.
+struct SelectableText {
+    text: String,
+    pos: (f32, f32),
+    clip: Option<ClipRect>,
+    glyphs: Vec<GlyphExtent>,
+    /// Starts a new row in reading order; texts on the same row are copied
+    /// tab-separated, rows newline-separated.
+    new_row: bool,
+}
+
+struct Region {
+    rect: ClipRect,
+    /// In reading order: rows top to bottom, left to right within a row.
+    texts: Vec<SelectableText>,
+}
+
+/// A position between glyphs: a text of the region and a byte offset in it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+struct TextPoint {
+    text: usize,
+    byte: usize,
+}
+
+/// The app's selection of read-only text.
+///
+/// Works from the previous frame's geometry: `update` takes the selectable
+/// regions and glyph extents the render layer measured, `begin` / `drag_to`
+/// hit-test the pointer against them, and `draw_highlight` puts the
+/// selection under the next frame's text. A selection never spans two
+/// regions, and is dropped when the text it covers changes.
+#[derive(Default)]
+pub struct TextSelection {
+    regions: Vec<Region>,
+    region: Option<usize>,
+    anchor: TextPoint,
+    focus: TextPoint,
+    dragging: bool,
+}
+
+impl TextSelection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes this frame's selectable text. Call once the render layer has
+    /// filled `Renderer::glyph_extents`.
+    pub fn update(&mut self, renderer: &Renderer) {
+        let regions: Vec<Region> = renderer
+            .selectable_regions
+            .iter()
+            .map(|region| {
+                let texts = region
+                    .texts
+                    .clone()
+                    .filter_map(|index| {
+                        let command = &renderer.text_commands[index];
+                        let glyphs = renderer.glyph_extents.get(index)?;
+                        (!glyphs.is_empty()).then(|| SelectableText {
+                            text: command.text.clone(),
+                            pos: command.pos,
+                            clip: command.clip,
+                            glyphs: glyphs.clone(),
+                            new_row: true,
+                        })
+                    })
+                    .collect();
+                Region { rect: region.rect, texts: reading_order(texts) }
+            })
+            .collect();
+
+        if let Some(index) = self.region
+            && !self.same_selected_text(index, regions.get(index))
+        {
+            self.clear();
+        }
+        self.regions = regions;
+    }
+
+    fn same_selected_text(&self, index: usize, new: Option<&Region>) -> bool {
+        let (Some(old), Some(new)) = (self.regions.get(index), new) else {
+            return false;
+        };
+        let (start, end) = self.ordered();
+        (start.text..=end.text).all(|i| match (old.texts.get(i), new.texts.get(i)) {
+            (Some(a), Some(b)) => a.text == b.text,
+            _ => false,
+        })
+    }
+
+    /// Starts a selection at `cursor` if it is over a selectable region;
+    /// otherwise clears the current one. Returns whether one started.
+    pub fn begin(&mut self, cursor: (f32, f32)) -> bool {
+        self.clear();
+        // Later regions are drawn on top
+        let Some(index) = self
+            .regions
+            .iter()
+            .rposition(|region| contains(region.rect, cursor))
+        else {
+            return false;
+        };
+        let Some(point) = hit_test(&self.regions[index], cursor) else {
+            return false;
+        };
+        self.region = Some(index);
+        self.anchor = point;
+        self.focus = point;
+        self.dragging = true;
+        true
+    }
+
+    /// Extends the selection being dragged to `cursor`, which may leave the
+    /// region: it then selects up to the nearest glyph.
+    pub fn drag_to(&mut self, cursor: (f32, f32)) {
+        if !self.dragging {
+            return;
+        }
+        let Some(region) = self.region.and_then(|index| self.regions.get(index)) else {
+            return;
+        };
+        if let Some(point) = hit_test(region, cursor) {
+            self.focus = point;
+        }
+    }
+
+    pub fn end_drag(&mut self) {
+        self.dragging = false;
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    pub fn clear(&mut self) {
+        self.region = None;
+        self.anchor = TextPoint::default();
+        self.focus = TextPoint::default();
+        self.dragging = false;
+    }
+
+    fn ordered(&self) -> (TextPoint, TextPoint) {
+        (self.anchor.min(self.focus), self.anchor.max(self.focus))
+    }
+
+    /// Selected byte range of text `index` of the selected region.
+    fn selected_range(&self, index: usize, text: &SelectableText) -> Option<(usize, usize)> {
+        let (start, end) = self.ordered();
+        if index < start.text || index > end.text {
+            return None;
+        }
+        let from = if index == start.text { start.byte } else { 0 };
+        let to = if index == end.text { end.byte } else { text.text.len() };
+        (from < to).then_some((from, to))
+    }
+
+    /// The selected text in reading order, or `None` when nothing is selected.
+    pub fn selected_text(&self) -> Option<String> {
+        let region = self.regions.get(self.region?)?;
+        let mut out = String::new();
+        let mut any = false;
+        for (index, text) in region.texts.iter().enumerate() {
+            let Some((from, to)) = self.selected_range(index, text) else {
+                continue;
+            };
+            if any {
+                out.push(if text.new_row { '\n' } else { '\t' });
+            }
+            out.push_str(&text.text[from..to]);
+            any = true;
+        }
+        any.then_some(out)
+    }
+
+    /// Copies the selected text to the system clipboard. Returns false when
+    /// nothing is selected.
+    pub fn copy_to_clipboard(&self) -> bool {
+        let Some(text) = self.selected_text() else {
+            return false;
+        };
+        if let Ok(mut cb) = Clipboard::new() {
+            let _ = cb.set_text(text);
+        }
+        true
+    }
+
+    /// Draws the selection in `Theme::selection`, one rect per selected run
+    /// of each line. Call after the widgets are drawn: on the main layer,
+    /// quads render under all text.
+    pub fn draw_highlight(&self, renderer: &mut Renderer) {
+        let Some(region) = self.region.and_then(|index| self.regions.get(index)) else {
+            return;
+        };
+        let color = renderer.theme().selection;
+        renderer.push_clip(region.rect);
+        for (index, text) in region.texts.iter().enumerate() {
+            let Some((from, to)) = self.selected_range(index, text) else {
+                continue;
+            };
+            if let Some(clip) = text.clip {
+                renderer.push_clip_within(clip);
+            }
+            // Merge the selected glyphs of each line into one run
+            let mut run: Option<(f32, f32, f32, f32)> = None;
+            for glyph in &text.glyphs {
+                if glyph.start < from || glyph.end > to {
+                    continue;
+                }
+                let (left, top) = (text.pos.0 + glyph.x, text.pos.1 + glyph.line_top);
+                match &mut run {
+                    Some((x, y, w, _)) if *y == top && (*x + *w - left).abs() < 1.0 => {
+                        *w = left + glyph.width - *x;
+                    }
+                    _ => {
+                        if let Some(rect) = run.take() {
+                            renderer.fill_rect_rounded(rect, color, 0.0);
+                        }
+                        run = Some((left, top, glyph.width, glyph.line_height));
+                    }
+                }
+            }
+            if let Some(rect) = run {
+                renderer.fill_rect_rounded(rect, color, 0.0);
+            }
+            if text.clip.is_some() {
+                renderer.pop_clip();
+            }
+        }
+        renderer.pop_clip();
+    }
+}
+
+fn contains((x, y, w, h): ClipRect, (px, py): (f32, f32)) -> bool {
+    px >= x && px < x + w && py >= y && py < y + h
+}
+
+/// Sorts texts into rows by their first line, then each row by x. A text
+/// joins a row when its first line overlaps the row's vertically.
+fn reading_order(mut texts: Vec<SelectableText>) -> Vec<SelectableText> {
+    let line = |text: &SelectableText| {
+        let glyph = &text.glyphs[0];
+        let top = text.pos.1 + glyph.line_top;
+        (top, top + glyph.line_height)
+    };
+    texts.sort_by(|a, b| line(a).0.total_cmp(&line(b).0));
+
+    let mut ordered = Vec::with_capacity(texts.len());
+    let mut row: Vec<SelectableText> = Vec::new();
+    let mut row_bottom = f32::NEG_INFINITY;
+    for text in texts {
+        let (top, bottom) = line(&text);
+        if top >= row_bottom {
+            flush_row(&mut row, &mut ordered);
+            row_bottom = bottom;
+        } else {
+            row_bottom = row_bottom.max(bottom);
+        }
+        row.push(text);
+    }
+    flush_row(&mut row, &mut ordered);
+    ordered
+}
+
+fn flush_row(row: &mut Vec<SelectableText>, ordered: &mut Vec<SelectableText>) {
+    let x = |text: &SelectableText| text.pos.0 + text.glyphs[0].x;
+    row.sort_by(|a, b| x(a).total_cmp(&x(b)));
+    for (i, mut text) in row.drain(..).enumerate() {
+        text.new_row = i == 0;
+        ordered.push(text);
+    }
+}
+
+/// The glyph boundary nearest `cursor`: the closest line first, then the
+/// closest glyph on it, then whichever of its edges is nearer.
+fn hit_test(region: &Region, (px, py): (f32, f32)) -> Option<TextPoint> {
+    let mut best: Option<((f32, f32), TextPoint)> = None;
+    for (index, text) in region.texts.iter().enumerate() {
+        for glyph in &text.glyphs {
+            let left = text.pos.0 + glyph.x;
+            let top = text.pos.1 + glyph.line_top;
+            let dy = (top - py).max(py - (top + glyph.line_height)).max(0.0);
+            let dx = (left - px).max(px - (left + glyph.width)).max(0.0);
+            if best.is_some_and(|(distance, _)| (dy, dx) >= distance) {
+                continue;
+            }
+            let byte = if px < left + glyph.width / 2.0 { glyph.start } else { glyph.end };
+            best = Some(((dy, dx), TextPoint { text: index, byte }));
+        }
+    }
+    best.map(|(_, point)| point)
+}
@@ -0,0 +1,119 @@
+//! Keyboard shortcuts: a key plus modifiers, and how to show it to users.
+
+use winit::event::{ElementState, KeyEvent};
+use winit::keyboard::{Key, ModifiersState, NamedKey, SmolStr};
+
+/// A key pressed together with a set of modifiers, like Ctrl+S.
+///
+/// `display` formats the combo the way the platform's menus do, for
+/// showing next to the action it triggers (see `Tooltip::with_shortcut`
+/// and `Toolbar::with_shortcut`):
+///
+/// ```
+/// use bexa_ui_core::KeyCombo;
+/// use winit::keyboard::ModifiersState;
+///
+/// let save = KeyCombo::char("s", ModifiersState::CONTROL | ModifiersState::SHIFT);
+/// if cfg!(target_os = "macos") {
+///     assert_eq!(save.display(), "⌃⇧S");
+/// } else {
+///     assert_eq!(save.display(), "Ctrl+Shift+S");
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyCombo {
+    pub key: Key,
+    pub modifiers: ModifiersState,
+}
+
+impl KeyCombo {
+    pub fn new(key: Key, modifiers: ModifiersState) -> Self {
+        Self { key, modifiers }
+    }
+
+    /// A character key, e.g. `KeyCombo::char("s", ModifiersState::CONTROL)`.
+    pub fn char(ch: &str, modifiers: ModifiersState) -> Self {
+        Self::new(Key::Character(SmolStr::new(ch.to_lowercase())), modifiers)
+    }
+
+    /// `ch` with the platform's command modifier: Cmd on macOS, Ctrl
+    /// elsewhere. The usual choice for app shortcuts like save or copy.
+    pub fn primary(ch: &str) -> Self {
+        let modifier = if cfg!(target_os = "macos") {
+            ModifiersState::SUPER
+        } else {
+            ModifiersState::CONTROL
+        };
+        Self::char(ch, modifier)
+    }
+
+    /// Whether `event` is a press of this combo. Letters match in either
+    /// case, so Shift only counts through `modifiers`.
+    pub fn matches(&self, event: &KeyEvent, modifiers: ModifiersState) -> bool {
+        if event.state != ElementState::Pressed || modifiers != self.modifiers {
+            return false;
+        }
+        match (&self.key, &event.logical_key) {
+            (Key::Character(want), Key::Character(got)) => want.to_lowercase() == got.to_lowercase(),
+            (want, got) => want == got,
+        }
+    }
+
+    /// Platform-style label: modifier symbols (⌃⌥⇧⌘) on macOS, "Ctrl+Alt+"
+    /// style prefixes elsewhere.
+    pub fn display(&self) -> String {
+        let key = key_label(&self.key);
+        let m = self.modifiers;
+        if cfg!(target_os = "macos") {
+            let mut out = String::new();
+            for (flag, symbol) in [
+                (ModifiersState::CONTROL, "⌃"),
+                (ModifiersState::ALT, "⌥"),
+                (ModifiersState::SHIFT, "⇧"),
+                (ModifiersState::SUPER, "⌘"),
+            ] {
+                if m.contains(flag) {
+                    out.push_str(symbol);
+                }
+            }
+            out + &key
+        } else {
+            let mut parts = Vec::new();
+            for (flag, name) in [
+                (ModifiersState::CONTROL, "Ctrl"),
+                (ModifiersState::ALT, "Alt"),
+                (ModifiersState::SHIFT, "Shift"),
+                (ModifiersState::SUPER, "Super"),
+            ] {
+                if m.contains(flag) {
+                    parts.push(name.to_string());
+                }
+            }
+            parts.push(key);
+            parts.join("+")
+        }
+    }
+}
+
+fn key_label(key: &Key) -> String {
+    match key {
+        Key::Character(c) => c.to_uppercase(),
+        Key::Named(named) => match named {
+            NamedKey::Enter => "Enter".into(),
+            NamedKey::Escape => "Esc".into(),
+            NamedKey::Tab => "Tab".into(),
+            NamedKey::Space => "Space".into(),
+            NamedKey::Backspace => "Backspace".into(),
+            NamedKey::Delete => "Del".into(),
+            NamedKey::ArrowUp => "Up".into(),
+            NamedKey::ArrowDown => "Down".into(),
+            NamedKey::ArrowLeft => "Left".into(),
+            NamedKey::ArrowRight => "Right".into(),
+            NamedKey::PageUp => "PgUp".into(),
+            NamedKey::PageDown => "PgDn".into(),
+            // F1..F35, Home, End, Insert and the rest read fine as named
+            other => format!("{other:?}"),
+        },
+        other => format!("{other:?}"),
+    }
+}
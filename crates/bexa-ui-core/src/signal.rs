@@ -1,7 +1,11 @@
 use std::cell::RefCell;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
 /// Read-only handle to a reactive value.
+///
+/// Clones share one value. Signals are single-threaded; since all windows of
+/// an app live on the UI thread, clones can be spread across the trees of
+/// several windows (see `WindowRequest`) and stay in sync.
 #[derive(Debug)]
 pub struct Signal<T> {
     inner: Rc<RefCell<T>>,
@@ -28,7 +32,8 @@ impl<T> Signal<T> {
     }
 }
 
-/// Write handle to a reactive value.
+/// Write handle to a reactive value. Writes redraw every window (see
+/// `request_redraw`).
 #[derive(Debug)]
 pub struct SetSignal<T> {
     inner: Rc<RefCell<T>>,
@@ -44,20 +49,47 @@ impl<T> SetSignal<T> {
     /// Replaces the current value.
     pub fn set(&self, value: T) {
         *self.inner.borrow_mut() = value;
+        crate::request_redraw();
     }
 
     /// Mutates the current value via a closure.
     pub fn update(&self, f: impl FnOnce(&mut T)) {
         f(&mut *self.inner.borrow_mut());
+        crate::request_redraw();
+    }
+
+    /// A writer that doesn't keep the value alive, for tasks that should
+    /// stop once every reader is gone.
+    pub(crate) fn downgrade(&self) -> WeakSetSignal<T> {
+        WeakSetSignal { inner: Rc::downgrade(&self.inner) }
+    }
+}
+
+/// Non-owning `SetSignal`; see `SetSignal::downgrade`.
+pub(crate) struct WeakSetSignal<T> {
+    inner: Weak<RefCell<T>>,
+}
+
+impl<T> WeakSetSignal<T> {
+    /// The writer, or `None` once all handles to the value were dropped.
+    pub(crate) fn upgrade(&self) -> Option<SetSignal<T>> {
+        self.inner.upgrade().map(|inner| SetSignal { inner })
     }
 }
 
 /// Creates a signal pair: `(reader, writer)`.
 ///
 /// ```
+/// # use bexa_ui_core::create_signal;
 /// let (count, set_count) = create_signal(0);
 /// set_count.set(42);
 /// assert_eq!(count.get(), 42);
+///
+/// // Clones, e.g. handed to another window's widgets, see the same value
+/// let (elsewhere, set_elsewhere) = (count.clone(), set_count.clone());
+/// set_elsewhere.update(|n| *n += 1);
+/// assert_eq!(count.get(), 43);
+/// assert_eq!(elsewhere.get(), 43);
 /// ```
 pub fn create_signal<T>(initial: T) -> (Signal<T>, SetSignal<T>) {
     let inner = Rc::new(RefCell::new(initial));
@@ -83,3 +115,16 @@ impl<T> IntoSignal<T> for Rc<RefCell<T>> {
         Signal { inner: self }
     }
 }
+
+/// Static text: wraps the string in a signal nobody writes to.
+impl IntoSignal<String> for &str {
+    fn into_signal(self) -> Signal<String> {
+        create_signal(self.to_string()).0
+    }
+}
+
+impl IntoSignal<String> for String {
+    fn into_signal(self) -> Signal<String> {
+        create_signal(self).0
+    }
+}
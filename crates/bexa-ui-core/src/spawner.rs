@@ -0,0 +1,173 @@
+//! Work handed to the UI thread from other threads.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+
+type UiTask = Box<dyn FnOnce() + Send>;
+type Waker = Box<dyn Fn() + Send + Sync>;
+
+static NEXT_CHANNEL: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    // Handlers of `UiSpawner::channel`, as `Box<dyn FnMut(T)>`. They hold
+    // signals, so they never leave the UI thread.
+    static CHANNEL_HANDLERS: RefCell<HashMap<u64, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+struct Shared {
+    tasks: Mutex<Vec<UiTask>>,
+    waker: Mutex<Option<Waker>>,
+    ui_thread: ThreadId,
+}
+
+/// Queues work from background threads (loaders, sockets, timers) to run
+/// on the UI thread, where signals can be set. Get one from `App::spawner`
+/// before `App::run`, and clone it into each thread.
+///
+/// Tasks run in the order they were queued, across all threads, when the
+/// event loop next goes idle (winit's `AboutToWait`), before frame tasks
+/// and the redraw, so what they set shows up in the next frame. Queuing
+/// wakes the loop even while every window is hidden. Tasks queued by a
+/// running task wait for the next turn. Tasks still queued when the loop
+/// exits are dropped without running.
+///
+/// Signals are not `Send`, so a closure that sets one can't cross threads.
+/// Register the setter once on the UI thread with `channel`, and send plain
+/// values from the thread instead:
+///
+/// ```
+/// # use bexa_ui_core::{create_signal, UiSpawner};
+/// let spawner = UiSpawner::new();
+/// let (status, set_status) = create_signal(String::from("Loading..."));
+/// let loaded = spawner.channel(move |count: usize| set_status.set(format!("{count} rows")));
+///
+/// std::thread::spawn(move || loaded.send(42)).join().unwrap();
+///
+/// // The event loop does this on its next turn
+/// spawner.run_pending();
+/// assert_eq!(status.get(), "42 rows");
+/// ```
+#[derive(Clone)]
+pub struct UiSpawner {
+    shared: Arc<Shared>,
+}
+
+impl Default for UiSpawner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UiSpawner {
+    /// A spawner whose UI thread is the current one.
+    pub fn new() -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                tasks: Mutex::new(Vec::new()),
+                waker: Mutex::new(None),
+                ui_thread: thread::current().id(),
+            }),
+        }
+    }
+
+    /// Runs `task` on the UI thread. Callable from any thread.
+    pub fn run_on_ui(&self, task: impl FnOnce() + Send + 'static) {
+        self.shared.tasks.lock().unwrap().push(Box::new(task));
+        if let Some(wake) = self.shared.waker.lock().unwrap().as_ref() {
+            wake();
+        }
+    }
+
+    /// Registers `handler` on the UI thread and returns a `Send` handle
+    /// whose `send` calls it there with each value, in order. The handler
+    /// is dropped once every clone of the sender is.
+    ///
+    /// # Panics
+    /// If called off the UI thread, where the handler could not stay.
+    pub fn channel<T: Send + 'static>(&self, handler: impl FnMut(T) + 'static) -> UiSender<T> {
+        assert_eq!(
+            thread::current().id(),
+            self.shared.ui_thread,
+            "UiSpawner::channel must be called on the UI thread"
+        );
+        let id = NEXT_CHANNEL.fetch_add(1, Ordering::Relaxed);
+        let handler: Box<dyn FnMut(T)> = Box::new(handler);
+        CHANNEL_HANDLERS.with(|handlers| handlers.borrow_mut().insert(id, Box::new(handler)));
+        UiSender {
+            token: Arc::new(ChannelToken {
+                id,
+                spawner: self.clone(),
+            }),
+            _value: PhantomData,
+        }
+    }
+
+    /// Called by the event loop when a task is queued, to wake it up.
+    /// `App::run` sets this; embedding hosts can too.
+    pub fn set_waker(&self, waker: impl Fn() + Send + Sync + 'static) {
+        *self.shared.waker.lock().unwrap() = Some(Box::new(waker));
+    }
+
+    /// Runs the queued tasks. Called by the event loop once per turn, on
+    /// the UI thread.
+    pub fn run_pending(&self) {
+        let tasks = std::mem::take(&mut *self.shared.tasks.lock().unwrap());
+        for task in tasks {
+            task();
+        }
+    }
+}
+
+/// Sends values to a handler on the UI thread; see `UiSpawner::channel`.
+pub struct UiSender<T> {
+    token: Arc<ChannelToken>,
+    // Only values cross threads, so the sender is `Send` for any `T: Send`
+    _value: PhantomData<fn(T)>,
+}
+
+impl<T> Clone for UiSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            token: self.token.clone(),
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<T: Send + 'static> UiSender<T> {
+    /// Queues a call of the handler with `value`. Callable from any thread.
+    pub fn send(&self, value: T) {
+        let id = self.token.id;
+        self.token.spawner.run_on_ui(move || {
+            // Taken out while it runs, so it can send to itself
+            let handler = CHANNEL_HANDLERS.with(|handlers| handlers.borrow_mut().remove(&id));
+            let Some(mut handler) = handler else {
+                return;
+            };
+            if let Some(handler) = handler.downcast_mut::<Box<dyn FnMut(T)>>() {
+                handler(value);
+            }
+            CHANNEL_HANDLERS.with(|handlers| handlers.borrow_mut().insert(id, handler));
+        });
+    }
+}
+
+struct ChannelToken {
+    id: u64,
+    spawner: UiSpawner,
+}
+
+impl Drop for ChannelToken {
+    fn drop(&mut self) {
+        // Queued behind any values still in flight
+        let id = self.id;
+        self.spawner.run_on_ui(move || {
+            CHANNEL_HANDLERS.with(|handlers| handlers.borrow_mut().remove(&id));
+        });
+    }
+}
@@ -1,4 +1,25 @@
-#[derive(Clone, Copy)]
+/// Color palette used by windows and widget defaults.
+///
+/// Presets: `ocean()` (the default), `dark()`, `light()` and
+/// `high_contrast()`; pass one to `App::theme`. Widgets draw with the theme
+/// of their window: `Button`, `TextInput`, `Select`, `Table`, `Slider`,
+/// `Toggle`, `Checkbox`, `RadioButton`, `Tabs`, `TreeView` and the charts
+/// take the colors their `with_*colors` builders don't set from it. Overlays
+/// (`Modal`, `Tooltip`, `Popover`, `ContextMenu`), `Drawer` and `Toolbar`
+/// keep their own dark colors.
+///
+/// With the `serde` feature a theme can be stored as data. Keys are the field
+/// names below; `[f32; N]` colors are RGB(A) in 0.0–1.0 and `[u8; 3]` text
+/// colors are 0–255. Missing keys fall back to `Theme::ocean()`:
+///
+/// ```toml
+/// background = [0.08, 0.08, 0.1]
+/// text_primary = [225, 225, 230]
+/// checkbox_border = [0.35, 0.38, 0.48, 1.0]
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Theme {
     pub background: [f32; 3],
     pub panel: [f32; 3],
@@ -17,9 +38,31 @@ pub struct Theme {
     pub radio_bg: [f32; 4],
     pub radio_border: [f32; 4],
     pub radio_dot: [f32; 4],
+    // Semantic roles
+    pub success: [f32; 4],
+    pub warning: [f32; 4],
+    pub error: [f32; 4],
+    pub info: [f32; 4],
+    pub border: [f32; 4],
+    pub hover: [f32; 4],
+    pub selection: [f32; 4],
+    pub focus_ring: [f32; 4],
+    // Scrollbars
+    pub scrollbar_track: [f32; 4],
+    pub scrollbar_thumb: [f32; 4],
+    /// Hide scrollbars while idle, fading them in on scroll or hover.
+    /// Widgets can override it (`Container::with_scrollbar_autohide`).
+    pub scrollbar_autohide: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::ocean()
+    }
 }
 
 impl Theme {
+    /// Blue-tinted dark palette.
     pub fn ocean() -> Self {
         Self {
             background: [0.12, 0.20, 0.30],
@@ -37,9 +80,21 @@ impl Theme {
             radio_bg: [0.16, 0.28, 0.38, 1.0],
             radio_border: [0.4, 0.55, 0.7, 1.0],
             radio_dot: [0.20, 0.65, 0.85, 1.0],
+            success: [0.25, 0.72, 0.45, 1.0],
+            warning: [0.92, 0.68, 0.22, 1.0],
+            error: [0.88, 0.32, 0.30, 1.0],
+            info: [0.30, 0.62, 0.90, 1.0],
+            border: [0.4, 0.55, 0.7, 1.0],
+            hover: [0.20, 0.34, 0.46, 1.0],
+            selection: [0.20, 0.45, 0.70, 1.0],
+            focus_ring: [0.3, 0.6, 0.9, 1.0],
+            scrollbar_track: [0.3, 0.3, 0.3, 0.15],
+            scrollbar_thumb: [0.6, 0.6, 0.6, 0.5],
+            scrollbar_autohide: false,
         }
     }
 
+    /// Neutral dark grays.
     pub fn dark() -> Self {
         Self {
             background: [0.08, 0.08, 0.10],
@@ -57,9 +112,21 @@ impl Theme {
             radio_bg: [0.15, 0.15, 0.20, 1.0],
             radio_border: [0.35, 0.38, 0.48, 1.0],
             radio_dot: [0.35, 0.55, 0.80, 1.0],
+            success: [0.30, 0.70, 0.45, 1.0],
+            warning: [0.88, 0.66, 0.25, 1.0],
+            error: [0.85, 0.33, 0.33, 1.0],
+            info: [0.35, 0.55, 0.80, 1.0],
+            border: [0.35, 0.38, 0.48, 1.0],
+            hover: [0.18, 0.18, 0.23, 1.0],
+            selection: [0.30, 0.45, 0.70, 1.0],
+            focus_ring: [0.45, 0.62, 0.90, 1.0],
+            scrollbar_track: [0.3, 0.3, 0.3, 0.15],
+            scrollbar_thumb: [0.6, 0.6, 0.6, 0.5],
+            scrollbar_autohide: false,
         }
     }
 
+    /// Dark text on light grays and white panels.
     pub fn light() -> Self {
         Self {
             background: [0.94, 0.94, 0.96],
@@ -77,6 +144,102 @@ impl Theme {
             radio_bg: [1.0, 1.0, 1.0, 1.0],
             radio_border: [0.70, 0.72, 0.78, 1.0],
             radio_dot: [0.22, 0.50, 0.78, 1.0],
+            success: [0.18, 0.60, 0.32, 1.0],
+            warning: [0.85, 0.55, 0.10, 1.0],
+            error: [0.80, 0.22, 0.20, 1.0],
+            info: [0.22, 0.50, 0.78, 1.0],
+            border: [0.70, 0.72, 0.78, 1.0],
+            hover: [0.90, 0.92, 0.96, 1.0],
+            selection: [0.22, 0.50, 0.78, 1.0],
+            focus_ring: [0.22, 0.50, 0.78, 1.0],
+            scrollbar_track: [0.5, 0.5, 0.55, 0.12],
+            scrollbar_thumb: [0.45, 0.47, 0.52, 0.5],
+            scrollbar_autohide: false,
+        }
+    }
+
+    /// White text and borders on black, saturated accents and a yellow focus
+    /// ring, for low vision. Scrollbars never hide.
+    pub fn high_contrast() -> Self {
+        Self {
+            background: [0.0, 0.0, 0.0],
+            panel: [0.0, 0.0, 0.0],
+            button: [0.0, 0.30, 0.70],
+            button_hover: [0.0, 0.40, 0.88],
+            button_active: [0.0, 0.22, 0.55],
+            button_focus: [0.0, 0.35, 0.80],
+            text_primary: [255, 255, 255],
+            text_secondary: [235, 235, 235],
+            checkbox_bg: [0.0, 0.0, 0.0, 1.0],
+            checkbox_checked_bg: [0.0, 0.40, 0.88, 1.0],
+            checkbox_border: [1.0, 1.0, 1.0, 1.0],
+            checkbox_check: [255, 255, 255],
+            radio_bg: [0.0, 0.0, 0.0, 1.0],
+            radio_border: [1.0, 1.0, 1.0, 1.0],
+            radio_dot: [1.0, 0.85, 0.0, 1.0],
+            success: [0.25, 0.95, 0.45, 1.0],
+            warning: [1.0, 0.85, 0.0, 1.0],
+            error: [1.0, 0.40, 0.40, 1.0],
+            info: [0.40, 0.78, 1.0, 1.0],
+            border: [1.0, 1.0, 1.0, 1.0],
+            hover: [0.25, 0.25, 0.25, 1.0],
+            selection: [0.0, 0.40, 0.88, 1.0],
+            focus_ring: [1.0, 0.85, 0.0, 1.0],
+            scrollbar_track: [1.0, 1.0, 1.0, 0.25],
+            scrollbar_thumb: [1.0, 1.0, 1.0, 0.85],
+            scrollbar_autohide: false,
+        }
+    }
+}
+
+/// `color` with full opacity, for theme colors kept without alpha.
+pub(crate) fn opaque(color: [f32; 3]) -> [f32; 4] {
+    [color[0], color[1], color[2], 1.0]
+}
+
+/// `base` moved a fraction `amount` of the way toward the text color
+/// `toward`: a shade lighter on dark themes and darker on light ones.
+pub(crate) fn tint(base: [f32; 3], toward: [u8; 3], amount: f32) -> [f32; 4] {
+    let channel = |i: usize| base[i] + (toward[i] as f32 / 255.0 - base[i]) * amount;
+    [channel(0), channel(1), channel(2), 1.0]
+}
+
+#[cfg(feature = "serde")]
+impl Theme {
+    /// Parses a theme from TOML. Missing keys keep their `ocean()` values.
+    pub fn from_toml_str(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    /// Serializes the theme to TOML, one key per field.
+    pub fn to_toml_str(&self) -> Result<String, toml::ser::Error> {
+        let mut value = toml::Value::try_from(self)?;
+        shorten_floats(&mut value);
+        toml::to_string(&value)
+    }
+
+    /// Parses a theme from JSON. Missing keys keep their `ocean()` values.
+    pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    /// Serializes the theme to pretty-printed JSON.
+    pub fn to_json_str(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// TOML stores floats as f64, so `0.12f32` would print as `0.11999999731779099`.
+/// All theme floats are f32: rewrite each as the f64 nearest its shortest f32
+/// form, which reads back to the identical f32.
+#[cfg(feature = "serde")]
+fn shorten_floats(value: &mut toml::Value) {
+    match value {
+        toml::Value::Float(f) => {
+            *f = (*f as f32).to_string().parse().unwrap_or(*f);
         }
+        toml::Value::Array(items) => items.iter_mut().for_each(shorten_floats),
+        toml::Value::Table(table) => table.iter_mut().for_each(|(_, v)| shorten_floats(v)),
+        _ => {}
     }
 }
@@ -1,13 +1,32 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
 use taffy::geometry::Point;
 use taffy::prelude::*;
-use winit::event::WindowEvent;
+use winit::event::{ElementState, KeyEvent, WindowEvent};
+use winit::keyboard::{Key, ModifiersState, NamedKey};
 
-use crate::framework::{DrawContext, EventContext, Widget};
+use crate::framework::{is_left_press, DrawContext, EventContext, Widget};
 use crate::renderer::Renderer;
+use crate::theme::Theme;
 
 const SCROLLBAR_WIDTH: f32 = 8.0;
 const SCROLLBAR_MARGIN: f32 = 2.0;
 const SCROLLBAR_MIN_THUMB: f32 = 20.0;
+/// Extra grab area left of the track, so the thin bar is easy to hit.
+const SCROLLBAR_GRAB_SLOP: f32 = 6.0;
+/// Auto-hide: bar width while not hovered, time shown after the last
+/// scroll or hover, and how long it then takes to fade out.
+const SCROLLBAR_THIN_WIDTH: f32 = 4.0;
+const SCROLLBAR_IDLE_TIMEOUT: Duration = Duration::from_millis(1000);
+const SCROLLBAR_FADE: Duration = Duration::from_millis(300);
+
+/// Longest gap between the two presses of a double click.
+pub const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+/// Farthest the cursor may move between the two presses of a double click.
+pub const DOUBLE_CLICK_DISTANCE: f32 = 4.0;
+/// Arrow-key scroll step, matching one wheel notch.
+const SCROLL_LINE: f32 = 40.0;
 
 pub struct WidgetNode {
     pub(crate) widget: Box<dyn Widget>,
@@ -18,20 +37,104 @@ pub struct WidgetNode {
     pub(crate) scrollbar_dragging: bool,
     pub(crate) scrollbar_drag_start_y: f32,
     pub(crate) scrollbar_drag_start_scroll: f32,
+    // Scrollbar auto-hide state; the cells are updated while drawing
+    pub(crate) scrollbar_hovered: bool,
+    pub(crate) scrollbar_seen_scroll: Cell<f32>,
+    pub(crate) scrollbar_active_at: Cell<Option<Instant>>,
+    // Interaction callbacks; boxed since most nodes have none
+    pub(crate) listeners: Option<Box<NodeListeners>>,
+}
+
+type Listener = Box<dyn FnMut()>;
+
+/// Callbacks attached with `WidgetNode::on_focus` and friends.
+#[derive(Default)]
+pub(crate) struct NodeListeners {
+    on_focus: Option<Listener>,
+    on_blur: Option<Listener>,
+    on_pointer_enter: Option<Listener>,
+    on_pointer_leave: Option<Listener>,
 }
 
 impl WidgetNode {
     pub fn new(widget: impl Widget + 'static, children: Vec<WidgetNode>) -> Self {
+        Self::from_boxed(Box::new(widget), children)
+    }
+
+    /// Like `new`, for widgets already boxed (e.g. built by name through
+    /// the `registry`).
+    pub fn from_boxed(widget: Box<dyn Widget>, children: Vec<WidgetNode>) -> Self {
         Self {
-            widget: Box::new(widget),
+            widget,
             children,
             node: None,
             scroll_y: 0.0,
             scrollbar_dragging: false,
             scrollbar_drag_start_y: 0.0,
             scrollbar_drag_start_scroll: 0.0,
+            scrollbar_hovered: false,
+            scrollbar_seen_scroll: Cell::new(0.0),
+            scrollbar_active_at: Cell::new(None),
+            listeners: None,
         }
     }
+
+    /// Called when focus moves into this subtree: onto this node's widget or
+    /// any descendant, coming from outside the subtree. Moving focus between
+    /// two widgets inside the subtree doesn't fire it again.
+    ///
+    /// Focus callbacks run after the widgets' own `set_focus`; for a click,
+    /// that is after the press went through `handle_event`.
+    pub fn on_focus(mut self, handler: impl FnMut() + 'static) -> Self {
+        self.listeners_mut().on_focus = Some(Box::new(handler));
+        self
+    }
+
+    /// Called when focus leaves this subtree, the counterpart of `on_focus`.
+    pub fn on_blur(mut self, handler: impl FnMut() + 'static) -> Self {
+        self.listeners_mut().on_blur = Some(Box::new(handler));
+        self
+    }
+
+    /// Called when the cursor moves over this node's rect. The node stays
+    /// hovered while the cursor is over a child, so nested nodes enter
+    /// outermost first and leave innermost first.
+    ///
+    /// Pointer callbacks run on `CursorMoved` before any widget's
+    /// `handle_event` sees the move, so widgets reacting to the same move
+    /// observe state the callbacks already updated.
+    pub fn on_pointer_enter(mut self, handler: impl FnMut() + 'static) -> Self {
+        self.listeners_mut().on_pointer_enter = Some(Box::new(handler));
+        self
+    }
+
+    /// Called when the cursor leaves this node's rect or the window.
+    pub fn on_pointer_leave(mut self, handler: impl FnMut() + 'static) -> Self {
+        self.listeners_mut().on_pointer_leave = Some(Box::new(handler));
+        self
+    }
+
+    fn listeners_mut(&mut self) -> &mut NodeListeners {
+        self.listeners.get_or_insert_with(Default::default)
+    }
+}
+
+/// Conversion into a `WidgetNode`, used by `ui!` so leaf items can be
+/// either plain widgets or subtrees that were already built.
+pub trait IntoWidgetNode {
+    fn into_widget_node(self) -> WidgetNode;
+}
+
+impl<W: Widget + 'static> IntoWidgetNode for W {
+    fn into_widget_node(self) -> WidgetNode {
+        WidgetNode::new(self, vec![])
+    }
+}
+
+impl IntoWidgetNode for WidgetNode {
+    fn into_widget_node(self) -> WidgetNode {
+        self
+    }
 }
 
 pub fn build_taffy(node: &mut WidgetNode, taffy: &mut TaffyTree) -> NodeId {
@@ -69,8 +172,15 @@ pub fn sync_styles(node: &mut WidgetNode, taffy: &mut TaffyTree, width: f32, hei
 
     taffy.set_style(node_id, style).expect("set style");
 
-    for child in &mut node.children {
+    for (index, child) in node.children.iter_mut().enumerate() {
         sync_styles(child, taffy, width, height, false);
+        if !node.widget.is_child_visible(index)
+            && let Some(child_id) = child.node
+        {
+            let mut hidden = taffy.style(child_id).expect("style").clone();
+            hidden.display = Display::None;
+            taffy.set_style(child_id, hidden).expect("set style");
+        }
     }
 }
 
@@ -86,6 +196,59 @@ pub fn collect_focus_paths(node: &WidgetNode, path: &mut Vec<usize>, out: &mut V
     }
 }
 
+/// Path of the innermost shown widget that traps focus
+/// (`Widget::traps_focus`), e.g. an open `Modal`; the first in tree order
+/// when several do.
+pub fn focus_trap_path(node: &WidgetNode) -> Option<Vec<usize>> {
+    let mut path = Vec::new();
+    find_focus_trap(node, &mut path).then_some(path)
+}
+
+/// Leaves `path` at the trap found under or at `node`, if any.
+fn find_focus_trap(node: &WidgetNode, path: &mut Vec<usize>) -> bool {
+    for (index, child) in node.children.iter().enumerate() {
+        if !node.widget.is_child_visible(index) {
+            continue;
+        }
+        path.push(index);
+        if find_focus_trap(child, path) {
+            return true;
+        }
+        path.pop();
+    }
+    node.widget.traps_focus()
+}
+
+/// Whether every widget along `path` is shown, i.e. no ancestor hides the
+/// branch through `is_child_visible`.
+pub fn is_path_visible(node: &WidgetNode, path: &[usize]) -> bool {
+    path_allows(node, path, |widget, index| widget.is_child_visible(index))
+}
+
+/// Whether the widget at `path` can take input and focus: it is visible and
+/// no ancestor blocks its children through `blocks_child_input`.
+pub fn is_path_interactive(node: &WidgetNode, path: &[usize]) -> bool {
+    path_allows(node, path, child_takes_input)
+}
+
+fn path_allows(node: &WidgetNode, path: &[usize], allows: impl Fn(&dyn Widget, usize) -> bool) -> bool {
+    let mut node = node;
+    for &index in path {
+        if !allows(node.widget.as_ref(), index) {
+            return false;
+        }
+        let Some(child) = node.children.get(index) else {
+            return false;
+        };
+        node = child;
+    }
+    true
+}
+
+fn child_takes_input(widget: &dyn Widget, index: usize) -> bool {
+    widget.is_child_visible(index) && !widget.blocks_child_input()
+}
+
 pub fn widget_mut_at_path<'a>(node: &'a mut WidgetNode, path: &[usize]) -> Option<&'a mut dyn Widget> {
     if path.is_empty() {
         return Some(node.widget.as_mut());
@@ -99,11 +262,45 @@ pub fn widget_mut_at_path<'a>(node: &'a mut WidgetNode, path: &[usize]) -> Optio
     widget_mut_at_path(&mut node.children[idx], &path[1..])
 }
 
+/// Returns the absolute on-screen rect `(x, y, w, h)` of the widget at `path`,
+/// accounting for the scroll offsets of its ancestors (same math as drawing).
+pub fn widget_rect(root: &WidgetNode, taffy: &TaffyTree, path: &[usize]) -> Option<(f32, f32, f32, f32)> {
+    widget_rect_offset(root, taffy, path, 0.0, 0.0)
+}
+
+fn widget_rect_offset(
+    node: &WidgetNode,
+    taffy: &TaffyTree,
+    path: &[usize],
+    parent_x: f32,
+    parent_y: f32,
+) -> Option<(f32, f32, f32, f32)> {
+    let node_id = node.node?;
+    let layout = taffy.layout(node_id).ok()?;
+    let abs_x = parent_x + layout.location.x;
+    let abs_y = parent_y + layout.location.y;
+
+    if path.is_empty() {
+        return Some((abs_x, abs_y, layout.size.width, layout.size.height));
+    }
+
+    let child = node.children.get(path[0])?;
+    widget_rect_offset(child, taffy, &path[1..], abs_x, abs_y - node.scroll_y)
+}
+
 pub fn draw_widgets(node: &WidgetNode, taffy: &TaffyTree, renderer: &mut Renderer) {
-    draw_widgets_offset(node, taffy, renderer, 0.0, 0.0);
+    let theme = *renderer.theme();
+    draw_widgets_offset(node, taffy, renderer, &theme, 0.0, 0.0);
 }
 
-fn draw_widgets_offset(node: &WidgetNode, taffy: &TaffyTree, renderer: &mut Renderer, parent_x: f32, parent_y: f32) {
+fn draw_widgets_offset(
+    node: &WidgetNode,
+    taffy: &TaffyTree,
+    renderer: &mut Renderer,
+    theme: &Theme,
+    parent_x: f32,
+    parent_y: f32,
+) {
     let Some(node_id) = node.node else {
         return;
     };
@@ -115,20 +312,32 @@ fn draw_widgets_offset(node: &WidgetNode, taffy: &TaffyTree, renderer: &mut Rend
     let mut absolute_layout = *layout;
     absolute_layout.location = Point { x: abs_x, y: abs_y };
 
+    let selects_text = node.widget.selects_text();
+    let first_text = renderer.text_commands.len();
+
     let mut ctx = DrawContext {
         renderer,
         layout: &absolute_layout,
+        theme,
     };
     node.widget.draw(&mut ctx);
 
+    let rect = (abs_x, abs_y, layout.size.width, layout.size.height);
+    let clip = node.widget.clip_children();
+    if let Some(radius) = clip {
+        renderer.push_clip_rounded(rect, radius);
+    }
+
     let is_scroll = node.widget.is_scrollable();
     if is_scroll {
         renderer.push_clip((abs_x, abs_y, layout.size.width, layout.size.height));
     }
 
     let child_y = abs_y - node.scroll_y;
-    for child in &node.children {
-        draw_widgets_offset(child, taffy, renderer, abs_x, child_y);
+    for (index, child) in node.children.iter().enumerate() {
+        if node.widget.is_child_visible(index) {
+            draw_widgets_offset(child, taffy, renderer, theme, abs_x, child_y);
+        }
     }
 
     if is_scroll {
@@ -137,42 +346,114 @@ fn draw_widgets_offset(node: &WidgetNode, taffy: &TaffyTree, renderer: &mut Rend
         // Draw scrollbar overlay (after pop_clip so it's not clipped with children)
         let container_h = layout.size.height;
         let content_h = content_height(node, taffy);
-        if content_h > container_h {
-            draw_scrollbar(renderer, abs_x, abs_y, layout.size.width, container_h, content_h, node.scroll_y);
+        let autohide = node.widget.scrollbar_autohide().unwrap_or(theme.scrollbar_autohide);
+        let (alpha, bar_w) = scrollbar_appearance(node, autohide, renderer);
+        if content_h > container_h && alpha > 0.0 {
+            draw_scrollbar(
+                renderer,
+                theme,
+                (abs_x, abs_y, layout.size.width, container_h),
+                content_h,
+                node.scroll_y,
+                bar_w,
+                alpha,
+            );
         }
     }
+
+    if clip.is_some() {
+        renderer.pop_clip();
+    }
+
+    let mut ctx = DrawContext {
+        renderer,
+        layout: &absolute_layout,
+        theme,
+    };
+    node.widget.draw_over(&mut ctx);
+
+    if selects_text {
+        renderer.mark_text_selectable(first_text, rect);
+    }
 }
 
+/// Scrollable height: the lowest child bottom edge plus the container's
+/// bottom padding and border, so the last child keeps the same spacing at
+/// the end of the scroll as the first does at the top. Gaps between
+/// children are already part of their positions; flex adds none after the
+/// last one.
 fn content_height(node: &WidgetNode, taffy: &TaffyTree) -> f32 {
     let mut h: f32 = 0.0;
-    for child in &node.children {
+    for (index, child) in node.children.iter().enumerate() {
+        // Pages a router or tab view hides take no space
+        if !node.widget.is_child_visible(index) {
+            continue;
+        }
         if let Some(child_id) = child.node {
             let cl = taffy.layout(child_id).expect("child layout");
             let bottom = cl.location.y + cl.size.height;
             h = h.max(bottom);
         }
     }
-    h
+    if h == 0.0 {
+        return 0.0;
+    }
+    match node.node.and_then(|id| taffy.layout(id).ok()) {
+        Some(layout) => h + layout.padding.bottom + layout.border.bottom,
+        None => h,
+    }
+}
+
+/// Opacity and width of a scrollbar. Always-visible bars are opaque and
+/// full width; auto-hiding ones stay thin until hovered and fade out once
+/// scrolling, hovering and dragging have been idle for a while; frames are
+/// requested for the fade.
+fn scrollbar_appearance(node: &WidgetNode, autohide: bool, renderer: &mut Renderer) -> (f32, f32) {
+    if !autohide {
+        return (1.0, SCROLLBAR_WIDTH);
+    }
+    let now = Instant::now();
+    let scrolled = node.scrollbar_seen_scroll.replace(node.scroll_y) != node.scroll_y;
+    let engaged = node.scrollbar_hovered || node.scrollbar_dragging;
+    if scrolled || engaged {
+        node.scrollbar_active_at.set(Some(now));
+    }
+    let width = if engaged { SCROLLBAR_WIDTH } else { SCROLLBAR_THIN_WIDTH };
+    let alpha = match node.scrollbar_active_at.get() {
+        Some(at) => {
+            let fade_start = at + SCROLLBAR_IDLE_TIMEOUT;
+            let fading = now.saturating_duration_since(fade_start);
+            let alpha = (1.0 - fading.as_secs_f32() / SCROLLBAR_FADE.as_secs_f32()).max(0.0);
+            if alpha > 0.0 {
+                renderer.request_redraw_at(fade_start);
+            }
+            alpha
+        }
+        None => 0.0,
+    };
+    (alpha, width)
 }
 
 fn draw_scrollbar(
     renderer: &mut Renderer,
-    container_x: f32,
-    container_y: f32,
-    container_w: f32,
-    container_h: f32,
+    theme: &Theme,
+    container: (f32, f32, f32, f32),
     content_h: f32,
     scroll_y: f32,
+    bar_w: f32,
+    alpha: f32,
 ) {
-    let track_x = container_x + container_w - SCROLLBAR_WIDTH - SCROLLBAR_MARGIN;
+    let (container_x, container_y, container_w, container_h) = container;
+    let track_x = container_x + container_w - bar_w - SCROLLBAR_MARGIN;
     let track_y = container_y + SCROLLBAR_MARGIN;
-    let track_h = container_h - SCROLLBAR_MARGIN * 2.0;
+    let track_h = (container_h - SCROLLBAR_MARGIN * 2.0).max(0.0);
+    let fade = |[r, g, b, a]: [f32; 4]| [r, g, b, a * alpha];
 
     // Track background
     renderer.fill_rect_rounded(
-        (track_x, track_y, SCROLLBAR_WIDTH, track_h),
-        [0.3, 0.3, 0.3, 0.15],
-        SCROLLBAR_WIDTH / 2.0,
+        (track_x, track_y, bar_w, track_h),
+        fade(theme.scrollbar_track),
+        bar_w / 2.0,
     );
 
     // Thumb
@@ -180,22 +461,94 @@ fn draw_scrollbar(
     let thumb_h = (ratio * track_h).max(SCROLLBAR_MIN_THUMB);
     let max_scroll = (content_h - container_h).max(0.0);
     let scroll_ratio = if max_scroll > 0.0 { scroll_y / max_scroll } else { 0.0 };
-    let thumb_y = track_y + scroll_ratio * (track_h - thumb_h);
+    let thumb_y = track_y + scroll_ratio * (track_h - thumb_h).max(0.0);
 
     renderer.fill_rect_rounded(
-        (track_x, thumb_y, SCROLLBAR_WIDTH, thumb_h),
-        [0.6, 0.6, 0.6, 0.5],
-        SCROLLBAR_WIDTH / 2.0,
+        (track_x, thumb_y, bar_w, thumb_h),
+        fade(theme.scrollbar_thumb),
+        bar_w / 2.0,
     );
 }
 
+/// Recognizes double clicks across dispatches; see
+/// `EventContext::double_click`. Keep one per tree.
+///
+/// ```
+/// use bexa_ui_core::{ClickTracker, DOUBLE_CLICK_DISTANCE};
+///
+/// // A left press at `at` taken by the widget at [0, 2]; returns whether
+/// // it double-clicked it
+/// fn press(clicks: &mut ClickTracker, at: (f32, f32)) -> bool {
+///     let mut double = false;
+///     clicks.press(at, |target| {
+///         double = target == Some(&[0, 2][..]);
+///         Some(vec![0, 2])
+///     });
+///     double
+/// }
+///
+/// let mut clicks = ClickTracker::new();
+/// assert!(!press(&mut clicks, (10.0, 10.0)));
+/// assert!(press(&mut clicks, (12.0, 11.0)));
+/// // The pair is done: a third press starts the next one
+/// assert!(!press(&mut clicks, (12.0, 11.0)));
+/// // Too far from it to pair up, so that one starts over instead
+/// let far = (12.0 + DOUBLE_CLICK_DISTANCE + 1.0, 11.0);
+/// assert!(!press(&mut clicks, far));
+/// assert!(press(&mut clicks, far));
+/// ```
+#[derive(Default)]
+pub struct ClickTracker {
+    /// When and where the last left press went, and the path that took it
+    last: Option<(Instant, (f32, f32), Vec<usize>)>,
+}
+
+impl ClickTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs a left press at `cursor` through `dispatch`, which gets the
+    /// path the press double-clicks, if any, and returns the path of the
+    /// widget that took it.
+    pub fn press(
+        &mut self,
+        cursor: (f32, f32),
+        dispatch: impl FnOnce(Option<&[usize]>) -> Option<Vec<usize>>,
+    ) -> Option<Vec<usize>> {
+        let target = self.target(cursor);
+        let consumed = dispatch(target.as_deref());
+        self.last = match &consumed {
+            // A double click's second press doesn't start the next one
+            Some(found) if target.as_ref() != Some(found) => Some((Instant::now(), cursor, found.clone())),
+            _ => None,
+        };
+        consumed
+    }
+
+    /// Path of the widget a left press at `cursor` would double-click.
+    fn target(&self, cursor: (f32, f32)) -> Option<Vec<usize>> {
+        let (at, (x, y), path) = self.last.as_ref()?;
+        let near = (cursor.0 - x).hypot(cursor.1 - y) <= DOUBLE_CLICK_DISTANCE;
+        (near && at.elapsed() < DOUBLE_CLICK_TIME).then(|| path.clone())
+    }
+}
+
+/// Dispatches `event` to the tree, children before their parents; returns
+/// the path of the widget that consumed it. Left presses go through
+/// `clicks` to flag double clicks.
 pub fn dispatch_event(
     node: &mut WidgetNode,
     taffy: &TaffyTree,
     event: &WindowEvent,
     path: &mut Vec<usize>,
+    cursor: (f32, f32),
+    clicks: &mut ClickTracker,
 ) -> Option<Vec<usize>> {
-    dispatch_event_offset(node, taffy, event, path, 0.0, 0.0)
+    if !is_left_press(event) {
+        return dispatch_event_offset(node, taffy, event, path, cursor, (0.0, 0.0), None);
+    }
+    clicks.press(cursor, |target| dispatch_event_offset(node, taffy, event, path, cursor, (0.0, 0.0), target))
 }
 
 fn dispatch_event_offset(
@@ -203,20 +556,30 @@ fn dispatch_event_offset(
     taffy: &TaffyTree,
     event: &WindowEvent,
     path: &mut Vec<usize>,
-    parent_x: f32,
-    parent_y: f32,
+    cursor: (f32, f32),
+    (parent_x, parent_y): (f32, f32),
+    double_click: Option<&[usize]>,
 ) -> Option<Vec<usize>> {
-    let Some(node_id) = node.node else {
-        return None;
-    };
+    let node_id = node.node?;
     let layout = taffy.layout(node_id).expect("layout");
     let abs_x = parent_x + layout.location.x;
     let abs_y = parent_y + layout.location.y;
 
+    // Children cropped away by `clip_children` can't take presses outside it
+    let clipped_out = node.widget.clip_children().is_some()
+        && matches!(event, WindowEvent::MouseInput { state: ElementState::Pressed, .. })
+        && !(cursor.0 >= abs_x
+            && cursor.0 < abs_x + layout.size.width
+            && cursor.1 >= abs_y
+            && cursor.1 < abs_y + layout.size.height);
+
     let child_y = abs_y - node.scroll_y;
     for (index, child) in node.children.iter_mut().enumerate() {
+        if clipped_out || !child_takes_input(node.widget.as_ref(), index) {
+            continue;
+        }
         path.push(index);
-        if let Some(found) = dispatch_event_offset(child, taffy, event, path, abs_x, child_y) {
+        if let Some(found) = dispatch_event_offset(child, taffy, event, path, cursor, (abs_x, child_y), double_click) {
             return Some(found);
         }
         path.pop();
@@ -228,6 +591,8 @@ fn dispatch_event_offset(
     let mut ctx = EventContext {
         event,
         layout: &absolute_layout,
+        cursor,
+        double_click: double_click == Some(path.as_slice()),
     };
     if node.widget.handle_event(&mut ctx) {
         return Some(path.clone());
@@ -271,7 +636,10 @@ fn dispatch_scroll_offset(
 
     // Try children first (deepest scrollable wins)
     let child_y = abs_y - node.scroll_y;
-    for child in &mut node.children {
+    for (index, child) in node.children.iter_mut().enumerate() {
+        if !child_takes_input(node.widget.as_ref(), index) {
+            continue;
+        }
         if dispatch_scroll_offset(child, delta_y, cx, cy, taffy, abs_x, child_y) {
             return true;
         }
@@ -287,22 +655,414 @@ fn dispatch_scroll_offset(
 }
 
 fn scroll_node(node: &mut WidgetNode, delta_y: f32, taffy: &TaffyTree) {
-    let Some(node_id) = node.node else { return; };
+    let Some(metrics) = node_scroll_metrics(node, taffy) else { return; };
+    node.scroll_y = (node.scroll_y - delta_y).clamp(0.0, metrics.max_scroll());
+}
+
+/// Scroll extent and position of a scroll container, from the last layout.
+///
+/// ```
+/// use bexa_ui_core::{build_taffy, scroll_metrics, Container, Flex, Label, Metrics, WidgetNode};
+/// use taffy::prelude::*;
+///
+/// // 15 labels, 28px tall with default padding, in a 100px scroll container
+/// fn list(container: Container) -> (WidgetNode, TaffyTree) {
+///     let lines = (0..15)
+///         .map(|i| WidgetNode::new(Label::new(format!("line {i}"), Metrics::new(14.0, 20.0), [200; 3]), vec![]))
+///         .collect();
+///     let list = WidgetNode::new(Flex::column(0.0, 0.0), lines);
+///     let mut root = WidgetNode::new(container.with_height(100.0).with_scroll(), vec![list]);
+///     let mut taffy = TaffyTree::new();
+///     let id = build_taffy(&mut root, &mut taffy);
+///     taffy.compute_layout(id, Size::MAX_CONTENT).unwrap();
+///     (root, taffy)
+/// }
+///
+/// let (root, taffy) = list(Container::new());
+/// let metrics = scroll_metrics(&root, &taffy, &[]).unwrap();
+/// assert_eq!(metrics.content_height, 420.0);
+/// assert_eq!(metrics.max_scroll(), 320.0);
+/// assert!(metrics.can_scroll_down() && !metrics.can_scroll_up());
+///
+/// // Bottom padding counts as content, so the last line isn't flush
+/// let (padded, taffy) = list(Container::new().with_padding(10.0));
+/// assert_eq!(scroll_metrics(&padded, &taffy, &[]).unwrap().content_height, 440.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScrollMetrics {
+    /// Height of the children, measured to the lowest bottom edge, plus the
+    /// container's bottom padding.
+    pub content_height: f32,
+    /// Visible height of the container.
+    pub viewport_height: f32,
+    /// Current scroll position, 0 at the top.
+    pub scroll_offset: f32,
+}
+
+impl ScrollMetrics {
+    pub fn max_scroll(&self) -> f32 {
+        (self.content_height - self.viewport_height).max(0.0)
+    }
+
+    /// Whether the content is taller than the container.
+    pub fn is_overflowing(&self) -> bool {
+        self.max_scroll() > 0.0
+    }
+
+    pub fn can_scroll_up(&self) -> bool {
+        self.scroll_offset > 0.0
+    }
+
+    /// False once scrolled to the bottom, e.g. to hide a "new messages
+    /// below" hint.
+    pub fn can_scroll_down(&self) -> bool {
+        self.scroll_offset < self.max_scroll()
+    }
+}
+
+/// Scroll metrics of the node at `path`, or `None` when it isn't a scroll
+/// container (or the path doesn't exist).
+pub fn scroll_metrics(root: &WidgetNode, taffy: &TaffyTree, path: &[usize]) -> Option<ScrollMetrics> {
+    let node = node_at_path(root, path)?;
+    node_scroll_metrics(node, taffy).filter(|_| node.widget.is_scrollable())
+}
+
+fn node_scroll_metrics(node: &WidgetNode, taffy: &TaffyTree) -> Option<ScrollMetrics> {
+    let layout = taffy.layout(node.node?).ok()?;
+    Some(ScrollMetrics {
+        content_height: content_height(node, taffy),
+        viewport_height: layout.size.height,
+        scroll_offset: node.scroll_y,
+    })
+}
+
+/// Scrolls the scrollable node at `path` (usually the focused one) for a
+/// navigation key: arrows by a line, PageUp/PageDown and Space (Shift+Space
+/// back) by its visible height, Home/End to the ends. Returns false for other
+/// keys and for nodes that don't scroll, leaving the key to other handlers.
+///
+/// The window tries this before the focused widget's `handle_key_event`, so
+/// Space pages a focusable scroll container instead of activating it.
+pub fn scroll_by_key(
+    root: &mut WidgetNode,
+    taffy: &TaffyTree,
+    path: &[usize],
+    event: &KeyEvent,
+    modifiers: ModifiersState,
+) -> bool {
+    event.state == ElementState::Pressed && scroll_for_key(root, taffy, path, &event.logical_key, modifiers)
+}
+
+/// `scroll_by_key` for a key press given by its logical key.
+///
+/// ```
+/// use bexa_ui_core::{scroll_for_key, scroll_metrics, Container};
+/// use winit::keyboard::{Key, ModifiersState, NamedKey};
+/// # use bexa_ui_core::{build_taffy, Flex, Label, Metrics, WidgetNode};
+/// # use taffy::prelude::*;
+/// # fn list(container: Container) -> (WidgetNode, TaffyTree) {
+/// #     let lines = (0..15)
+/// #         .map(|i| WidgetNode::new(Label::new(format!("line {i}"), Metrics::new(14.0, 20.0), [200; 3]), vec![]))
+/// #         .collect();
+/// #     let list = WidgetNode::new(Flex::column(0.0, 0.0), lines);
+/// #     let mut root = WidgetNode::new(container.with_height(100.0).with_scroll(), vec![list]);
+/// #     let mut taffy = TaffyTree::new();
+/// #     let id = build_taffy(&mut root, &mut taffy);
+/// #     taffy.compute_layout(id, Size::MAX_CONTENT).unwrap();
+/// #     (root, taffy)
+/// # }
+///
+/// // The list from the `ScrollMetrics` example, made focusable
+/// let (mut root, taffy) = list(Container::new().with_focusable());
+///
+/// // Space pages down by the visible height, Shift+Space back up
+/// let space = Key::Named(NamedKey::Space);
+/// assert!(scroll_for_key(&mut root, &taffy, &[], &space, ModifiersState::empty()));
+/// assert_eq!(scroll_metrics(&root, &taffy, &[]).unwrap().scroll_offset, 100.0);
+/// assert!(scroll_for_key(&mut root, &taffy, &[], &space, ModifiersState::SHIFT));
+/// assert_eq!(scroll_metrics(&root, &taffy, &[]).unwrap().scroll_offset, 0.0);
+///
+/// // Enter is left to the container (its `activate`)
+/// let enter = Key::Named(NamedKey::Enter);
+/// assert!(!scroll_for_key(&mut root, &taffy, &[], &enter, ModifiersState::empty()));
+/// ```
+pub fn scroll_for_key(
+    root: &mut WidgetNode,
+    taffy: &TaffyTree,
+    path: &[usize],
+    key: &Key,
+    modifiers: ModifiersState,
+) -> bool {
+    let Some(node) = node_mut_at_path(root, path) else {
+        return false;
+    };
+    let Some(node_id) = node.node.filter(|_| node.widget.is_scrollable()) else {
+        return false;
+    };
+    let page = taffy.layout(node_id).expect("layout").size.height;
+
+    // Positive deltas scroll up, as with the wheel
+    let delta = match key {
+        Key::Named(NamedKey::ArrowUp) => SCROLL_LINE,
+        Key::Named(NamedKey::ArrowDown) => -SCROLL_LINE,
+        Key::Named(NamedKey::PageUp) => page,
+        Key::Named(NamedKey::PageDown) => -page,
+        Key::Named(NamedKey::Space) if modifiers.shift_key() => page,
+        Key::Named(NamedKey::Space) => -page,
+        Key::Named(NamedKey::Home) => f32::INFINITY,
+        Key::Named(NamedKey::End) => f32::NEG_INFINITY,
+        _ => return false,
+    };
+    scroll_node(node, delta, taffy);
+    true
+}
+
+/// Scrolls the scroll containers around the node at `path`, innermost
+/// first, until the node is in view, e.g. a widget focused with Tab below
+/// the fold. Returns whether any of them moved.
+///
+/// ```
+/// use bexa_ui_core::{scroll_metrics, scroll_to, Container};
+/// # use bexa_ui_core::{build_taffy, Flex, Label, Metrics, WidgetNode};
+/// # use taffy::prelude::*;
+/// # fn list(container: Container) -> (WidgetNode, TaffyTree) {
+/// #     let lines = (0..15)
+/// #         .map(|i| WidgetNode::new(Label::new(format!("line {i}"), Metrics::new(14.0, 20.0), [200; 3]), vec![]))
+/// #         .collect();
+/// #     let list = WidgetNode::new(Flex::column(0.0, 0.0), lines);
+/// #     let mut root = WidgetNode::new(container.with_height(100.0).with_scroll(), vec![list]);
+/// #     let mut taffy = TaffyTree::new();
+/// #     let id = build_taffy(&mut root, &mut taffy);
+/// #     taffy.compute_layout(id, Size::MAX_CONTENT).unwrap();
+/// #     (root, taffy)
+/// # }
+///
+/// // The list from the `ScrollMetrics` example: 15 labels, 28px tall
+/// let (mut root, taffy) = list(Container::new());
+///
+/// // Line 10 spans 280..308, so its bottom lines up with the container's
+/// assert!(scroll_to(&mut root, &taffy, &[0, 10]));
+/// assert_eq!(scroll_metrics(&root, &taffy, &[]).unwrap().scroll_offset, 208.0);
+///
+/// // Already in view, nothing moves
+/// assert!(!scroll_to(&mut root, &taffy, &[0, 9]));
+///
+/// // Above the view, its top lines up with the container's
+/// assert!(scroll_to(&mut root, &taffy, &[0, 2]));
+/// assert_eq!(scroll_metrics(&root, &taffy, &[]).unwrap().scroll_offset, 56.0);
+/// ```
+pub fn scroll_to(root: &mut WidgetNode, taffy: &TaffyTree, path: &[usize]) -> bool {
+    let Some(size) = node_at_path(root, path)
+        .and_then(|node| node.node)
+        .and_then(|id| taffy.layout(id).ok())
+        .map(|layout| layout.size)
+    else {
+        return false;
+    };
+    scroll_rect_into_view(root, taffy, path, (0.0, 0.0, size.width, size.height))
+}
+
+/// Like `scroll_to`, for `rect` (x, y, w, h) relative to the top-left of the
+/// node at `path`, such as one row of a table. Only scrolls vertically; a
+/// rect taller than a container is lined up with its top.
+pub fn scroll_rect_into_view(
+    root: &mut WidgetNode,
+    taffy: &TaffyTree,
+    path: &[usize],
+    rect: (f32, f32, f32, f32),
+) -> bool {
+    let mut moved = false;
+    for depth in (0..path.len()).rev() {
+        let (outer, inner) = path.split_at(depth);
+        let Some(container) = node_at_path(root, outer) else {
+            return moved;
+        };
+        if !container.widget.is_scrollable() {
+            continue;
+        }
+        // Top of the rect within the container's content, through the
+        // nodes in between as they are scrolled now
+        let mut top = rect.1;
+        let mut node = container;
+        for (step, &index) in inner.iter().enumerate() {
+            if step > 0 {
+                top -= node.scroll_y;
+            }
+            let Some(child) = node.children.get(index) else {
+                return moved;
+            };
+            let Some(layout) = child.node.and_then(|id| taffy.layout(id).ok()) else {
+                return moved;
+            };
+            top += layout.location.y;
+            node = child;
+        }
+        let Some(metrics) = node_scroll_metrics(container, taffy) else {
+            continue;
+        };
+        let offset = metrics.scroll_offset;
+        let target = if top < offset || rect.3 > metrics.viewport_height {
+            top
+        } else if top + rect.3 > offset + metrics.viewport_height {
+            top + rect.3 - metrics.viewport_height
+        } else {
+            continue;
+        };
+        let target = target.clamp(0.0, metrics.max_scroll());
+        if let Some(container) = node_mut_at_path(root, outer)
+            && container.scroll_y != target
+        {
+            container.scroll_y = target;
+            moved = true;
+        }
+    }
+    moved
+}
+
+fn node_at_path<'a>(node: &'a WidgetNode, path: &[usize]) -> Option<&'a WidgetNode> {
+    match path.split_first() {
+        None => Some(node),
+        Some((&index, rest)) => node_at_path(node.children.get(index)?, rest),
+    }
+}
+
+fn node_mut_at_path<'a>(node: &'a mut WidgetNode, path: &[usize]) -> Option<&'a mut WidgetNode> {
+    match path.split_first() {
+        None => Some(node),
+        Some((&index, rest)) => node_mut_at_path(node.children.get_mut(index)?, rest),
+    }
+}
+
+/// Paths of the nodes whose rect contains `cursor`, ancestors before
+/// descendants. Hidden or input-blocked branches and content scrolled out
+/// of its container don't count, and while a focus trap (an open `Modal`)
+/// is shown only nodes inside it do.
+///
+/// ```
+/// use bexa_ui_core::{build_taffy, create_signal, hovered_paths, sync_styles, ui, Button, Container, Metrics, Modal};
+/// use taffy::prelude::*;
+///
+/// let (open, set_open) = create_signal(true);
+/// let mut root = ui! {
+///     Container::new() => {
+///         Modal::new(open, set_open.clone()) => {
+///             Button::new("OK", Metrics::new(14.0, 20.0)),
+///         },
+///         Container::new().with_height(600.0),
+///     }
+/// };
+/// let mut taffy = TaffyTree::new();
+/// let id = build_taffy(&mut root, &mut taffy);
+/// sync_styles(&mut root, &mut taffy, 800.0, 600.0, true);
+/// taffy.compute_layout(id, Size::MAX_CONTENT).unwrap();
+///
+/// // Behind the open modal's backdrop nothing is hovered
+/// assert!(hovered_paths(&root, &taffy, (700.0, 500.0)).is_empty());
+///
+/// set_open.set(false);
+/// assert_eq!(hovered_paths(&root, &taffy, (700.0, 500.0)), vec![vec![], vec![1]]);
+/// ```
+pub fn hovered_paths(root: &WidgetNode, taffy: &TaffyTree, cursor: (f32, f32)) -> Vec<Vec<usize>> {
+    let trap = focus_trap_path(root);
+    let mut out = Vec::new();
+    collect_hovered(root, taffy, cursor, trap.as_deref(), &mut Vec::new(), 0.0, 0.0, &mut out);
+    out
+}
+
+#[allow(clippy::too_many_arguments)]
+fn collect_hovered(
+    node: &WidgetNode,
+    taffy: &TaffyTree,
+    cursor: (f32, f32),
+    trap: Option<&[usize]>,
+    path: &mut Vec<usize>,
+    parent_x: f32,
+    parent_y: f32,
+    out: &mut Vec<Vec<usize>>,
+) {
+    let Some(node_id) = node.node else {
+        return;
+    };
     let layout = taffy.layout(node_id).expect("layout");
-    let container_h = layout.size.height;
+    let abs_x = parent_x + layout.location.x;
+    let abs_y = parent_y + layout.location.y;
+    let inside = cursor.0 >= abs_x
+        && cursor.0 < abs_x + layout.size.width
+        && cursor.1 >= abs_y
+        && cursor.1 < abs_y + layout.size.height;
+
+    // The trap's ancestors sit behind its backdrop
+    let in_trap = trap.is_none_or(|trap| path.starts_with(trap));
+    if inside && in_trap {
+        out.push(path.clone());
+    }
+    // Children may overflow (popups), except out of a scroll viewport or a clip
+    if !inside && (node.widget.is_scrollable() || node.widget.clip_children().is_some()) {
+        return;
+    }
 
-    // Content height = max bottom edge of all children
-    let mut content_h: f32 = 0.0;
-    for child in &node.children {
-        if let Some(child_id) = child.node {
-            let cl = taffy.layout(child_id).expect("child layout");
-            let bottom = cl.location.y + cl.size.height;
-            content_h = content_h.max(bottom);
+    let child_y = abs_y - node.scroll_y;
+    for (index, child) in node.children.iter().enumerate() {
+        if !child_takes_input(node.widget.as_ref(), index) {
+            continue;
+        }
+        path.push(index);
+        // Only the branch leading to the trap, and everything inside it
+        if trap.is_none_or(|trap| path.starts_with(trap) || trap.starts_with(path)) {
+            collect_hovered(child, taffy, cursor, trap, path, abs_x, child_y, out);
         }
+        path.pop();
     }
+}
 
-    let max_scroll = (content_h - container_h).max(0.0);
-    node.scroll_y = (node.scroll_y - delta_y).clamp(0.0, max_scroll);
+/// Calls `Widget::on_mouse_leave` and fires `on_pointer_leave` for nodes in
+/// `old` but not `new` (innermost first), then `Widget::on_mouse_enter` and
+/// `on_pointer_enter` for nodes new in `new` (outermost first). Both lists
+/// come from `hovered_paths`.
+pub fn notify_pointer_transitions(root: &mut WidgetNode, old: &[Vec<usize>], new: &[Vec<usize>]) {
+    for path in old.iter().rev().filter(|path| !new.contains(path)) {
+        if let Some(widget) = widget_mut_at_path(root, path) {
+            widget.on_mouse_leave();
+        }
+        fire_listener(root, path, |l| l.on_pointer_leave.as_mut());
+    }
+    for path in new.iter().filter(|path| !old.contains(path)) {
+        if let Some(widget) = widget_mut_at_path(root, path) {
+            widget.on_mouse_enter();
+        }
+        fire_listener(root, path, |l| l.on_pointer_enter.as_mut());
+    }
+}
+
+/// Fires `on_blur` on the subtrees focus left and `on_focus` on the ones
+/// it entered, going from the path focused before to the one focused now.
+pub fn notify_focus_transition(root: &mut WidgetNode, old: Option<&[usize]>, new: Option<&[usize]>) {
+    // Ancestors shared by both targets keep focus; below them it changes
+    let first_changed = match (old, new) {
+        (Some(old), Some(new)) => old.iter().zip(new).take_while(|(a, b)| a == b).count() + 1,
+        _ => 0,
+    };
+    if let Some(old) = old {
+        for depth in (first_changed..=old.len()).rev() {
+            fire_listener(root, &old[..depth], |l| l.on_blur.as_mut());
+        }
+    }
+    if let Some(new) = new {
+        for depth in first_changed..=new.len() {
+            fire_listener(root, &new[..depth], |l| l.on_focus.as_mut());
+        }
+    }
+}
+
+fn fire_listener(
+    root: &mut WidgetNode,
+    path: &[usize],
+    pick: impl FnOnce(&mut NodeListeners) -> Option<&mut Listener>,
+) {
+    let listeners = node_mut_at_path(root, path).and_then(|node| node.listeners.as_deref_mut());
+    if let Some(handler) = listeners.and_then(pick) {
+        handler();
+    }
 }
 
 /// Scrolls the root node (backward compat).
@@ -341,7 +1101,10 @@ fn handle_scrollbar_event_offset(
 
     // Check children first
     let child_y = abs_y - node.scroll_y;
-    for child in &mut node.children {
+    for (index, child) in node.children.iter_mut().enumerate() {
+        if !child_takes_input(node.widget.as_ref(), index) {
+            continue;
+        }
         if handle_scrollbar_event_offset(child, taffy, event, abs_x, child_y) {
             return true;
         }
@@ -365,24 +1128,36 @@ fn handle_scrollbar_event_offset(
 
     match event {
         WindowEvent::CursorMoved { position, .. } => {
-            let _cx = position.x as f32;
+            let cx = position.x as f32;
             let cy = position.y as f32;
+            let track_x = abs_x + layout.size.width - SCROLLBAR_WIDTH - SCROLLBAR_MARGIN;
+            node.scrollbar_hovered = cx >= track_x - SCROLLBAR_GRAB_SLOP
+                && cx <= abs_x + layout.size.width
+                && cy >= abs_y
+                && cy <= abs_y + container_h;
 
             if node.scrollbar_dragging {
                 // Update scroll based on drag delta
                 let delta_y = cy - node.scrollbar_drag_start_y;
-                let scroll_per_pixel = max_scroll / (track_h - thumb_h);
+                // A thumb that fills the track leaves no travel to divide by
+                let scroll_per_pixel = max_scroll / (track_h - thumb_h).max(1.0);
                 node.scroll_y = (node.scrollbar_drag_start_scroll + delta_y * scroll_per_pixel)
                     .clamp(0.0, max_scroll);
                 return true;
             }
             false
         }
+        WindowEvent::CursorLeft { .. } => {
+            node.scrollbar_hovered = false;
+            false
+        }
         _ => false,
     }
 }
 
 /// Start scrollbar drag if the press landed on the scrollbar thumb/track.
+/// A press on the track beside the thumb pages toward the cursor by the
+/// container's height instead, like PageUp / PageDown.
 /// Call this specifically on MouseInput::Pressed events with cursor position.
 pub fn try_start_scrollbar_drag(
     node: &mut WidgetNode,
@@ -407,7 +1182,10 @@ fn try_start_scrollbar_drag_offset(
     let abs_y = parent_y + layout.location.y;
 
     let child_y = abs_y - node.scroll_y;
-    for child in &mut node.children {
+    for (index, child) in node.children.iter_mut().enumerate() {
+        if !child_takes_input(node.widget.as_ref(), index) {
+            continue;
+        }
         if try_start_scrollbar_drag_offset(child, taffy, cx, cy, abs_x, child_y) {
             return true;
         }
@@ -428,7 +1206,7 @@ fn try_start_scrollbar_drag_offset(
     let track_h = container_h - SCROLLBAR_MARGIN * 2.0;
 
     // Check if click is in the scrollbar area
-    let in_scrollbar = cx >= track_x
+    let in_scrollbar = cx >= track_x - SCROLLBAR_GRAB_SLOP
         && cx <= track_x + SCROLLBAR_WIDTH + SCROLLBAR_MARGIN
         && cy >= abs_y
         && cy <= abs_y + container_h;
@@ -441,7 +1219,7 @@ fn try_start_scrollbar_drag_offset(
     let ratio = container_h / content_h;
     let thumb_h = (ratio * track_h).max(SCROLLBAR_MIN_THUMB);
     let scroll_ratio = if max_scroll > 0.0 { node.scroll_y / max_scroll } else { 0.0 };
-    let thumb_y = track_y + scroll_ratio * (track_h - thumb_h);
+    let thumb_y = track_y + scroll_ratio * (track_h - thumb_h).max(0.0);
 
     // Check if click is on the thumb
     if cy >= thumb_y && cy <= thumb_y + thumb_h {
@@ -450,9 +1228,9 @@ fn try_start_scrollbar_drag_offset(
         node.scrollbar_drag_start_y = cy;
         node.scrollbar_drag_start_scroll = node.scroll_y;
     } else {
-        // Click on track: jump to position
-        let click_ratio = (cy - track_y) / track_h;
-        node.scroll_y = (click_ratio * max_scroll).clamp(0.0, max_scroll);
+        // Click on track: one page toward the cursor
+        let page = if cy < thumb_y { -container_h } else { container_h };
+        node.scroll_y = (node.scroll_y + page).clamp(0.0, max_scroll);
     }
 
     true
@@ -466,6 +1244,21 @@ pub fn release_scrollbar_drag(node: &mut WidgetNode) {
     }
 }
 
+/// Calls `dismiss` on every widget except those along `consumed`, the path
+/// of the widget that took the press (its ancestors contain the press too).
+pub fn dismiss_popups(node: &mut WidgetNode, consumed: Option<&[usize]>, cursor: (f32, f32)) {
+    if consumed.is_none() {
+        node.widget.dismiss(cursor);
+    }
+    for (index, child) in node.children.iter_mut().enumerate() {
+        let child_path = match consumed {
+            Some([first, rest @ ..]) if *first == index => Some(rest),
+            _ => None,
+        };
+        dismiss_popups(child, child_path, cursor);
+    }
+}
+
 pub fn clear_active_widgets(node: &mut WidgetNode) {
     node.widget.clear_active();
     for child in &mut node.children {
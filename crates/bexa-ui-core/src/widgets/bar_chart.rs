@@ -1,16 +1,32 @@
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+
 use glyphon::Metrics;
 use glyphon::cosmic_text::Align;
 use taffy::prelude::*;
-use winit::event::{ElementState, MouseButton, WindowEvent};
+use winit::event::WindowEvent;
 
+use crate::animation::Tween;
 use crate::framework::{DrawContext, EventContext, Widget};
 use crate::signal::Signal;
+use crate::widgets::legend::Legend;
+
+/// Width reserved for value-axis labels beside the chart, and for value
+/// labels past the end of horizontal bars. Shared with `LineChart`.
+pub(crate) const AXIS_LABEL_W: f32 = 44.0;
+
+/// Gap between the side-by-side bars of a group.
+const GROUP_GAP: f32 = 2.0;
 
 /// A single bar entry.
 pub struct Bar {
     pub label: String,
+    /// The bar's value; for a multi-series bar, the total of `values`.
     pub value: f32,
     pub color: [f32; 4],
+    /// One value per series (see `BarChart::with_series`), or empty for a
+    /// plain bar of `value`.
+    pub values: Vec<f32>,
 }
 
 impl Bar {
@@ -19,10 +35,114 @@ impl Bar {
             label: label.into(),
             value,
             color,
+            values: Vec::new(),
         }
     }
+
+    /// A bar of one value per series, stacked or side by side as set by
+    /// `BarChart::with_mode`, in the series' colors. Values without a
+    /// series use `color`.
+    pub fn multi(label: impl Into<String>, values: Vec<f32>) -> Self {
+        Self {
+            label: label.into(),
+            value: values.iter().sum(),
+            color: [0.5, 0.55, 0.6, 1.0],
+            values,
+        }
+    }
+
+    /// Values drawn as segments: `values`, or `value` for a plain bar.
+    fn segment_values(&self) -> Vec<f32> {
+        if self.values.is_empty() { vec![self.value] } else { self.values.clone() }
+    }
+}
+
+/// How a `BarChart` draws bars that hold several series values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BarMode {
+    /// Segments on top of each other: the bar is their total, positive
+    /// values stacking up from zero and negative ones down.
+    #[default]
+    Stacked,
+    /// Side by side within the bar's slot, each from zero.
+    Grouped,
+}
+
+/// Part of a bar in value space: a share of its slot across the category
+/// axis (`offset`, `extent` as fractions) and a span `lo..hi` of values.
+#[derive(Clone, Copy)]
+struct Piece {
+    offset: f32,
+    extent: f32,
+    lo: f32,
+    hi: f32,
+}
+
+/// A bar as pieces: the filled ones with their colors, and those that get
+/// a value label with the value shown.
+struct BarPieces {
+    fills: Vec<(Piece, [f32; 4])>,
+    labels: Vec<(Piece, f32)>,
+}
+
+/// Slot span of `piece` within a slot at `start` of `length` pixels, with
+/// a gap between grouped pieces.
+fn piece_span(piece: &Piece, start: f32, length: f32) -> (f32, f32) {
+    let gap = if piece.extent < 1.0 { GROUP_GAP } else { 0.0 };
+    let len = (piece.extent * length - gap).max(1.0);
+    (start + piece.offset * length + gap / 2.0, len)
+}
+
+/// Default label format: whole numbers as-is, one decimal otherwise, and
+/// thousands as "k" with `k_decimals` decimals.
+pub(crate) fn format_compact(value: f32, k_decimals: usize) -> String {
+    if value.abs() >= 1000.0 {
+        format!("{:.*}k", k_decimals, value / 1000.0)
+    } else if value == value.floor() {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+/// A bar as currently drawn, animating towards its entry in `bars`.
+struct DisplayBar {
+    label: String,
+    value: f32,
+    color: [f32; 4],
+    /// Whether the bar was made with `Bar::multi`, so it takes series colors
+    multi: bool,
+    /// Target per segment: one for a plain bar, one per series otherwise
+    values: Vec<f32>,
+    heights: Vec<Tween>,
+    /// 0.0–1.0; scales the bar's slot width and opacity for enter/exit
+    presence: Tween,
+    removed: bool,
 }
 
+impl DisplayBar {
+    /// Starts the exit transition (no-op if already leaving).
+    fn retired(mut self) -> Self {
+        self.removed = true;
+        for height in &mut self.heights {
+            height.set_target(0.0);
+        }
+        self.presence.set_target(0.0);
+        self
+    }
+}
+
+/// Bar chart over a reactive list of bars, vertical unless
+/// `with_horizontal` is set.
+///
+/// Bars made with `Bar::multi` hold a value per series, stacked or grouped
+/// per `with_mode`; name and color the series with `with_series`, and show
+/// them with `legend`.
+///
+/// When `bars` changes, heights animate to the new values and bars are
+/// matched by label: new labels grow in, missing ones shrink and fade out
+/// while their neighbours close the gap. Frames are requested while a
+/// transition runs.
 pub struct BarChart {
     bars: Signal<Vec<Bar>>,
     metrics: Metrics,
@@ -30,17 +150,30 @@ pub struct BarChart {
     bar_gap: f32,
     bar_radius: f32,
     padding: f32,
-    // Colors
+    // Colors; `None` uses the theme's `border` for the axes and its text
+    // colors for labels and values
     bg: [f32; 4],
-    axis_color: [f32; 4],
-    label_color: [u8; 3],
-    value_color: [u8; 3],
+    axis_color: Option<[f32; 4]>,
+    label_color: Option<[u8; 3]>,
+    value_color: Option<[u8; 3]>,
     hover_opacity: f32,
+    horizontal: bool,
+    mode: BarMode,
+    /// Name and color of each series of multi-series bars
+    series: Vec<(String, [f32; 4])>,
     // State
     hover_index: Option<usize>,
     max_value: Option<f32>,
+    min_value: Option<f32>,
     show_grid: bool,
     grid_lines: usize,
+    transition: Duration,
+    formatter: Option<Box<dyn Fn(f32) -> String>>,
+    // Animation state, synced with `bars` during draw
+    display: RefCell<Vec<DisplayBar>>,
+    scale: Cell<Option<(Tween, Tween)>>,
+    // Cached span per display bar along the category axis (set during draw)
+    bar_spans: RefCell<Vec<(f32, f32)>>,
 }
 
 impl BarChart {
@@ -53,14 +186,23 @@ impl BarChart {
             bar_radius: 4.0,
             padding: 8.0,
             bg: [0.0, 0.0, 0.0, 0.0],
-            axis_color: [0.3, 0.35, 0.4, 1.0],
-            label_color: [160, 170, 180],
-            value_color: [220, 220, 230],
+            axis_color: None,
+            label_color: None,
+            value_color: None,
             hover_opacity: 0.3,
+            horizontal: false,
+            mode: BarMode::Stacked,
+            series: Vec::new(),
             hover_index: None,
             max_value: None,
+            min_value: None,
             show_grid: true,
             grid_lines: 4,
+            transition: Duration::from_millis(300),
+            formatter: None,
+            display: RefCell::new(Vec::new()),
+            scale: Cell::new(None),
+            bar_spans: RefCell::new(Vec::new()),
         }
     }
 
@@ -69,6 +211,35 @@ impl BarChart {
         self
     }
 
+    /// Lays the bars out left to right from a value axis along the bottom,
+    /// with the labels on the left: easier to read for ranked lists with
+    /// long names. The label column fits the longest label, up to 40% of
+    /// the width.
+    pub fn with_horizontal(mut self, horizontal: bool) -> Self {
+        self.horizontal = horizontal;
+        self
+    }
+
+    /// Stacks (the default) or groups the values of multi-series bars.
+    pub fn with_mode(mut self, mode: BarMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Adds a series: the next value of each `Bar::multi` is drawn in
+    /// `color`. Call once per series, in order.
+    pub fn with_series(mut self, name: impl Into<String>, color: [f32; 4]) -> Self {
+        self.series.push((name.into(), color));
+        self
+    }
+
+    /// A legend of the series' names and colors, to place near the chart.
+    pub fn legend(&self, metrics: Metrics) -> Legend {
+        self.series
+            .iter()
+            .fold(Legend::new(metrics), |legend, (name, color)| legend.with_entry(name.clone(), *color))
+    }
+
     pub fn with_bar_radius(mut self, radius: f32) -> Self {
         self.bar_radius = radius;
         self
@@ -79,12 +250,32 @@ impl BarChart {
         self
     }
 
+    /// Fixed bottom of the value axis; by default it is 0, or the lowest
+    /// value when there are negative bars.
+    pub fn with_min_value(mut self, min: f32) -> Self {
+        self.min_value = Some(min);
+        self
+    }
+
     pub fn with_grid(mut self, show: bool, lines: usize) -> Self {
         self.show_grid = show;
         self.grid_lines = lines;
         self
     }
 
+    /// Length of value/enter/exit transitions; `Duration::ZERO` snaps.
+    pub fn with_transition(mut self, duration: Duration) -> Self {
+        self.transition = duration;
+        self
+    }
+
+    /// Formats axis tick labels and on-bar values, e.g. `|v| format!("{v:.0}%")`.
+    /// Without one, thousands are shortened to "k".
+    pub fn with_value_formatter(mut self, format: impl Fn(f32) -> String + 'static) -> Self {
+        self.formatter = Some(Box::new(format));
+        self
+    }
+
     pub fn with_colors(
         mut self,
         bg: [f32; 4],
@@ -93,61 +284,237 @@ impl BarChart {
         value: [u8; 3],
     ) -> Self {
         self.bg = bg;
-        self.axis_color = axis;
-        self.label_color = label;
-        self.value_color = value;
+        self.axis_color = Some(axis);
+        self.label_color = Some(label);
+        self.value_color = Some(value);
         self
     }
 
-    fn effective_max(&self) -> f32 {
-        if let Some(m) = self.max_value {
-            return m;
+    /// Lowest and highest value a bar reaches: its stacked totals of
+    /// negative and positive values, or its extreme values when grouped.
+    fn value_extent(&self, values: &[f32]) -> (f32, f32) {
+        if self.mode == BarMode::Stacked {
+            let lo = values.iter().filter(|&&v| v < 0.0).sum::<f32>();
+            let hi = values.iter().filter(|&&v| v > 0.0).sum::<f32>();
+            (lo, hi)
+        } else {
+            values.iter().fold((0.0_f32, 0.0_f32), |(lo, hi), &v| (lo.min(v), hi.max(v)))
+        }
+    }
+
+    /// Value axis range; always includes zero so bars have a baseline.
+    fn effective_range(&self) -> (f32, f32) {
+        let (data_min, data_max) = self.bars.with(|bars| {
+            bars.iter().fold((0.0_f32, 0.0_f32), |(lo, hi), b| {
+                let (bar_lo, bar_hi) = self.value_extent(&b.segment_values());
+                (lo.min(bar_lo), hi.max(bar_hi))
+            })
+        });
+        let min = self.min_value.unwrap_or(data_min);
+        let mut max = self.max_value.unwrap_or(data_max);
+        if max <= min {
+            max = min + 1.0;
         }
+        (min, max)
+    }
+
+    /// Animated axis range, so rescaling doesn't jump either.
+    fn animated_range(&self) -> (f32, f32) {
+        let (min, max) = self.effective_range();
+        let (mut lo, mut hi) = self.scale.get().unwrap_or_else(|| {
+            (Tween::new(min, self.transition), Tween::new(max, self.transition))
+        });
+        lo.set_target(min);
+        hi.set_target(max);
+        self.scale.set(Some((lo, hi)));
+        (lo.value(), hi.value())
+    }
+
+    /// Retargets the display list at the current `bars`, keeping removed
+    /// bars in place until they have faded out.
+    fn sync_display(&self) {
+        let mut display = self.display.borrow_mut();
+        let first_sync = display.is_empty() && self.scale.get().is_none();
+
         self.bars.with(|bars| {
-            bars.iter().map(|b| b.value).fold(0.0_f32, f32::max).max(1.0)
-        })
+            let in_bars = |label: &str| bars.iter().any(|b| b.label == label);
+            let mut slots: Vec<Option<DisplayBar>> =
+                std::mem::take(&mut *display).into_iter().map(Some).collect();
+            let mut next = Vec::with_capacity(bars.len());
+            let mut cursor = 0;
+
+            for bar in bars {
+                let pos = slots
+                    .iter()
+                    .position(|s| s.as_ref().is_some_and(|d| d.label == bar.label));
+
+                // Removed bars that sat before this one keep their place
+                if let Some(pos) = pos {
+                    while cursor < pos {
+                        if let Some(gone) = slots[cursor].take_if(|d| !in_bars(&d.label)) {
+                            next.push(gone.retired());
+                        }
+                        cursor += 1;
+                    }
+                }
+
+                let values = bar.segment_values();
+                let mut item = pos.and_then(|p| slots[p].take()).unwrap_or_else(|| {
+                    // Bars present on the first frame start settled
+                    let presence = if first_sync { 1.0 } else { 0.0 };
+                    DisplayBar {
+                        label: bar.label.clone(),
+                        value: bar.value,
+                        color: bar.color,
+                        multi: false,
+                        values: Vec::new(),
+                        heights: Vec::new(),
+                        presence: Tween::new(presence, self.transition),
+                        removed: false,
+                    }
+                });
+                // Added segments grow from zero, except on the first frame
+                item.heights.truncate(values.len());
+                while item.heights.len() < values.len() {
+                    let start = if first_sync { values[item.heights.len()] } else { 0.0 };
+                    item.heights.push(Tween::new(start, self.transition));
+                }
+                for (height, &value) in item.heights.iter_mut().zip(&values) {
+                    height.set_target(value);
+                }
+                item.value = bar.value;
+                item.color = bar.color;
+                item.multi = !bar.values.is_empty();
+                item.values = values;
+                item.removed = false;
+                item.presence.set_target(1.0);
+                next.push(item);
+            }
+
+            // Removed bars from the end of the old list
+            for slot in slots.iter_mut().skip(cursor) {
+                if let Some(gone) = slot.take_if(|d| !in_bars(&d.label)) {
+                    next.push(gone.retired());
+                }
+            }
+
+            next.retain(|d| !d.removed || d.presence.is_running());
+            *display = next;
+        });
     }
-}
 
-impl Widget for BarChart {
-    fn style(&self) -> Style {
-        Style {
-            size: Size {
-                width: Dimension::Percent(1.0),
-                height: Dimension::Length(self.height),
-            },
-            flex_shrink: 0.0,
-            ..Default::default()
+    fn format_value(&self, value: f32, k_decimals: usize) -> String {
+        match &self.formatter {
+            Some(format) => format(value),
+            None => format_compact(value, k_decimals),
         }
     }
 
-    fn draw(&self, ctx: &mut DrawContext) {
+    /// Fill of a bar's `color`, brightened on hover and faded with its
+    /// presence.
+    fn bar_fill(&self, mut color: [f32; 4], is_hover: bool, weight: f32) -> [f32; 4] {
+        if is_hover {
+            color[0] = (color[0] + self.hover_opacity).min(1.0);
+            color[1] = (color[1] + self.hover_opacity).min(1.0);
+            color[2] = (color[2] + self.hover_opacity).min(1.0);
+        }
+        color[3] *= weight;
+        color
+    }
+
+    /// A bar's filled pieces, at their animated heights, and the pieces
+    /// that get a value label with the value (the target) they show: the
+    /// whole bar when stacked, each piece when grouped.
+    fn pieces(&self, bar: &DisplayBar) -> BarPieces {
+        let heights: Vec<f32> = bar.heights.iter().map(|h| h.value()).collect();
+        let color_of = |i: usize| {
+            let series_color = self.series.get(i).map(|&(_, color)| color);
+            if bar.multi { series_color.unwrap_or(bar.color) } else { bar.color }
+        };
+
+        if self.mode == BarMode::Grouped && heights.len() > 1 {
+            let extent = 1.0 / heights.len() as f32;
+            let pieces: Vec<Piece> = heights
+                .iter()
+                .enumerate()
+                .map(|(i, &v)| Piece { offset: i as f32 * extent, extent, lo: v.min(0.0), hi: v.max(0.0) })
+                .collect();
+            let fills = pieces.iter().enumerate().map(|(i, &p)| (p, color_of(i))).collect();
+            let labels = pieces.into_iter().zip(bar.values.iter().copied()).collect();
+            return BarPieces { fills, labels };
+        }
+
+        // Stacked: positive values build up from zero, negative ones down
+        let (mut up, mut down) = (0.0_f32, 0.0_f32);
+        let mut fills = Vec::with_capacity(heights.len());
+        for (i, &v) in heights.iter().enumerate() {
+            let (lo, hi) = if v < 0.0 {
+                down += v;
+                (down, down - v)
+            } else {
+                up += v;
+                (up - v, up)
+            };
+            fills.push((Piece { offset: 0.0, extent: 1.0, lo, hi }, color_of(i)));
+        }
+        let whole = Piece { offset: 0.0, extent: 1.0, lo: down, hi: up };
+        BarPieces { fills, labels: vec![(whole, bar.value)] }
+    }
+
+    /// Corner radius of filled pieces; stacked segments are square so they
+    /// meet cleanly.
+    fn piece_radius(&self, pieces: usize) -> f32 {
+        if self.mode == BarMode::Stacked && pieces > 1 { 0.0 } else { self.bar_radius }
+    }
+
+    /// Start and extent of each display bar along the category axis, from
+    /// `start` over `length`. Slots scale with presence so neighbours slide
+    /// over while a bar enters or leaves. Also cached for hover hit-tests.
+    fn layout_slots(&self, display: &[DisplayBar], start: f32, length: f32) -> Vec<(f32, f32)> {
+        let weights: Vec<f32> = display.iter().map(|d| d.presence.value()).collect();
+        let total_weight: f32 = weights.iter().sum();
+        let mut slots = Vec::with_capacity(display.len());
+        if total_weight > 0.0 {
+            let total_gaps = (total_weight - 1.0).max(0.0) * self.bar_gap;
+            let slot_len = ((length - total_gaps) / total_weight).max(4.0);
+            let mut pos = start;
+            for weight in weights {
+                slots.push((pos, slot_len * weight));
+                pos += slot_len * weight + self.bar_gap * weight;
+            }
+        }
+        *self.bar_spans.borrow_mut() = slots.clone();
+        slots
+    }
+
+    /// Bars growing up from the bottom, labels below.
+    fn draw_vertical(&self, ctx: &mut DrawContext, (min_val, max_val): (f32, f32)) {
+        let theme = ctx.theme;
+        let axis_color = self.axis_color.unwrap_or(theme.border);
+        let label_color = self.label_color.unwrap_or(theme.text_secondary);
+        let value_color = self.value_color.unwrap_or(theme.text_primary);
         let layout = ctx.layout;
         let x = layout.location.x;
         let y = layout.location.y;
         let w = layout.size.width;
         let h = layout.size.height;
 
-        // Background
-        if self.bg[3] > 0.0 {
-            ctx.renderer.fill_rect_rounded(
-                (x, y, w, h),
-                self.bg,
-                0.0,
-            );
-        }
-
         let label_area_h = self.metrics.line_height + 4.0;
         let value_area_h = self.metrics.line_height;
-        let y_label_w: f32 = 44.0; // reserved width for Y-axis labels
+        // Negative bars put their value labels below, so reserve room there
+        let below_h = if self.effective_range().0 < 0.0 { value_area_h } else { 0.0 };
         let chart_top = y + self.padding + value_area_h;
-        let chart_bottom = y + h - self.padding - label_area_h;
+        let chart_bottom = y + h - self.padding - label_area_h - below_h;
         let chart_h = (chart_bottom - chart_top).max(10.0);
-        let chart_left = x + self.padding + y_label_w;
+        let chart_left = x + self.padding + AXIS_LABEL_W;
         let chart_right = x + w - self.padding;
         let chart_w = (chart_right - chart_left).max(10.0);
 
-        let max_val = self.effective_max();
+        let span = (max_val - min_val).max(f32::EPSILON);
+        let value_y = |v: f32| {
+            (chart_bottom - (v - min_val) / span * chart_h).clamp(chart_top, chart_bottom)
+        };
+        let zero_y = value_y(0.0);
 
         // Grid lines
         if self.show_grid && self.grid_lines > 0 {
@@ -160,106 +527,277 @@ impl Widget for BarChart {
                 let gy = chart_bottom - frac * chart_h;
                 ctx.renderer.fill_rect_rounded(
                     (chart_left, gy, chart_w, 0.5),
-                    [self.axis_color[0], self.axis_color[1], self.axis_color[2], 0.3],
+                    [axis_color[0], axis_color[1], axis_color[2], 0.3],
                     0.0,
                 );
                 // Grid value label (drawn in the reserved Y-axis area)
-                let val = frac * max_val;
-                let val_str = if val >= 1000.0 {
-                    format!("{:.0}k", val / 1000.0)
-                } else if val == val.floor() {
-                    format!("{:.0}", val)
-                } else {
-                    format!("{:.1}", val)
-                };
+                let val_str = self.format_value(min_val + frac * span, 0);
                 ctx.renderer.draw_text(
                     &val_str,
                     (x + self.padding, gy - small_metrics.line_height * 0.5),
-                    self.label_color,
-                    (y_label_w - 4.0, small_metrics.line_height),
+                    label_color,
+                    (AXIS_LABEL_W - 4.0, small_metrics.line_height),
                     small_metrics,
                     Align::Right,
                 );
             }
         }
 
-        // Bottom axis
+        // Zero axis (the bottom edge unless there are negative values)
         ctx.renderer.fill_rect_rounded(
-            (chart_left, chart_bottom, chart_w, 1.0),
-            self.axis_color,
+            (chart_left, zero_y, chart_w, 1.0),
+            axis_color,
             0.0,
         );
 
-        // Bars
-        self.bars.with(|bars| {
-            let count = bars.len();
-            if count == 0 {
-                return;
+        let display = self.display.borrow();
+        let slots = self.layout_slots(&display, chart_left, chart_w);
+        for (i, (bar, &(bx, bar_w))) in display.iter().zip(&slots).enumerate() {
+            let weight = bar.presence.value();
+            let is_hover = self.hover_index == Some(i) && !bar.removed;
+            let BarPieces { fills, labels } = self.pieces(bar);
+            let radius = self.piece_radius(fills.len());
+
+            // Positive values grow up from zero, negative ones down
+            for (piece, color) in &fills {
+                let (px, pw) = piece_span(piece, bx, bar_w);
+                let top = value_y(piece.hi);
+                let bottom = value_y(piece.lo);
+                ctx.renderer.fill_rect_rounded(
+                    (px, top, pw, bottom - top),
+                    self.bar_fill(*color, is_hover, weight),
+                    radius,
+                );
             }
 
-            let total_gaps = (count.saturating_sub(1)) as f32 * self.bar_gap;
-            let bar_w = ((chart_w - total_gaps) / count as f32).max(4.0);
+            // Labels only once the bar is mostly in
+            if weight < 0.5 {
+                continue;
+            }
 
-            for (i, bar) in bars.iter().enumerate() {
-                let bx = chart_left + i as f32 * (bar_w + self.bar_gap);
-                let bar_h = (bar.value / max_val * chart_h).min(chart_h);
-                let by = chart_bottom - bar_h;
+            // Value outside the bar's end (the target, not the animated height)
+            for (piece, value) in &labels {
+                let (px, pw) = piece_span(piece, bx, bar_w);
+                let top = value_y(piece.hi);
+                let bottom = value_y(piece.lo);
+                if !is_hover && bottom - top <= value_area_h + 4.0 {
+                    continue;
+                }
+                let val_str = self.format_value(*value, 1);
+                let val_metrics = Metrics::new(
+                    self.metrics.font_size * 0.8,
+                    self.metrics.line_height * 0.8,
+                );
+                let label_y = if *value < 0.0 {
+                    bottom + 2.0
+                } else {
+                    top - val_metrics.line_height - 2.0
+                };
+                ctx.renderer.draw_text(
+                    &val_str,
+                    (px, label_y),
+                    value_color,
+                    (pw, val_metrics.line_height),
+                    val_metrics,
+                    Align::Center,
+                );
+            }
 
-                let is_hover = self.hover_index == Some(i);
+            // Label below axis
+            let label_metrics = Metrics::new(
+                self.metrics.font_size * 0.75,
+                self.metrics.line_height * 0.75,
+            );
+            ctx.renderer.draw_text(
+                &bar.label,
+                (bx, chart_bottom + below_h + 4.0),
+                label_color,
+                (bar_w, label_metrics.line_height),
+                label_metrics,
+                Align::Center,
+            );
+        }
+    }
 
-                // Bar rect
-                let mut color = bar.color;
-                if is_hover {
-                    // Brighten on hover
-                    color[0] = (color[0] + self.hover_opacity).min(1.0);
-                    color[1] = (color[1] + self.hover_opacity).min(1.0);
-                    color[2] = (color[2] + self.hover_opacity).min(1.0);
-                }
+    /// Bars growing right from the left, labels on the left and the value
+    /// axis along the bottom.
+    fn draw_horizontal(&self, ctx: &mut DrawContext, (min_val, max_val): (f32, f32)) {
+        let theme = ctx.theme;
+        let axis_color = self.axis_color.unwrap_or(theme.border);
+        let label_color = self.label_color.unwrap_or(theme.text_secondary);
+        let value_color = self.value_color.unwrap_or(theme.text_primary);
+        let layout = ctx.layout;
+        let x = layout.location.x;
+        let y = layout.location.y;
+        let w = layout.size.width;
+        let h = layout.size.height;
+
+        let small_metrics = Metrics::new(
+            self.metrics.font_size * 0.7,
+            self.metrics.line_height * 0.7,
+        );
+        let label_metrics = Metrics::new(
+            self.metrics.font_size * 0.75,
+            self.metrics.line_height * 0.75,
+        );
+        let val_metrics = Metrics::new(
+            self.metrics.font_size * 0.8,
+            self.metrics.line_height * 0.8,
+        );
+
+        let display = self.display.borrow();
+        // Label column sized to the longest label (estimated), up to 40%
+        let longest = display.iter().map(|d| d.label.chars().count()).max().unwrap_or(0);
+        let label_w = (longest as f32 * label_metrics.font_size * 0.6 + 8.0).min(w * 0.4);
+        // Negative bars put their value labels left of the bar
+        let left_w = if self.effective_range().0 < 0.0 { AXIS_LABEL_W } else { 0.0 };
+        let chart_top = y + self.padding;
+        let chart_bottom = y + h - self.padding - small_metrics.line_height - 4.0;
+        let chart_h = (chart_bottom - chart_top).max(10.0);
+        let chart_left = x + self.padding + label_w + left_w;
+        let chart_right = x + w - self.padding - AXIS_LABEL_W;
+        let chart_w = (chart_right - chart_left).max(10.0);
+
+        let span = (max_val - min_val).max(f32::EPSILON);
+        let value_x = |v: f32| {
+            (chart_left + (v - min_val) / span * chart_w).clamp(chart_left, chart_right)
+        };
+        let zero_x = value_x(0.0);
 
+        // Grid lines, value labels under the chart
+        if self.show_grid && self.grid_lines > 0 {
+            for i in 0..=self.grid_lines {
+                let frac = i as f32 / self.grid_lines as f32;
+                let gx = chart_left + frac * chart_w;
                 ctx.renderer.fill_rect_rounded(
-                    (bx, by, bar_w, bar_h),
-                    color,
-                    self.bar_radius,
+                    (gx, chart_top, 0.5, chart_h),
+                    [axis_color[0], axis_color[1], axis_color[2], 0.3],
+                    0.0,
+                );
+                let val_str = self.format_value(min_val + frac * span, 0);
+                ctx.renderer.draw_text(
+                    &val_str,
+                    (gx - AXIS_LABEL_W / 2.0, chart_bottom + 4.0),
+                    label_color,
+                    (AXIS_LABEL_W, small_metrics.line_height),
+                    small_metrics,
+                    Align::Center,
                 );
+            }
+        }
 
-                // Value on top of bar
-                if is_hover || bar_h > value_area_h + 4.0 {
-                    let val_str = if bar.value >= 1000.0 {
-                        format!("{:.1}k", bar.value / 1000.0)
-                    } else if bar.value == bar.value.floor() {
-                        format!("{:.0}", bar.value)
-                    } else {
-                        format!("{:.1}", bar.value)
-                    };
-                    let val_metrics = Metrics::new(
-                        self.metrics.font_size * 0.8,
-                        self.metrics.line_height * 0.8,
-                    );
-                    ctx.renderer.draw_text(
-                        &val_str,
-                        (bx, by - val_metrics.line_height - 2.0),
-                        self.value_color,
-                        (bar_w, val_metrics.line_height),
-                        val_metrics,
-                        Align::Center,
-                    );
-                }
+        // Zero axis (the left edge unless there are negative values)
+        ctx.renderer.fill_rect_rounded(
+            (zero_x, chart_top, 1.0, chart_h),
+            axis_color,
+            0.0,
+        );
 
-                // Label below axis
-                let label_metrics = Metrics::new(
-                    self.metrics.font_size * 0.75,
-                    self.metrics.line_height * 0.75,
+        let slots = self.layout_slots(&display, chart_top, chart_h);
+        for (i, (bar, &(by, bar_h))) in display.iter().zip(&slots).enumerate() {
+            let weight = bar.presence.value();
+            let is_hover = self.hover_index == Some(i) && !bar.removed;
+            let BarPieces { fills, labels } = self.pieces(bar);
+            let radius = self.piece_radius(fills.len());
+
+            // Positive values grow right from zero, negative ones left
+            for (piece, color) in &fills {
+                let (py, ph) = piece_span(piece, by, bar_h);
+                let left = value_x(piece.lo);
+                let right = value_x(piece.hi);
+                ctx.renderer.fill_rect_rounded(
+                    (left, py, right - left, ph),
+                    self.bar_fill(*color, is_hover, weight),
+                    radius,
                 );
+            }
+
+            // Labels only once the bar is mostly in
+            if weight < 0.5 {
+                continue;
+            }
+
+            // Value past the bar's end (the target, not the animated width);
+            // grouped bars too thin for theirs show them on hover
+            for (piece, value) in &labels {
+                let (py, ph) = piece_span(piece, by, bar_h);
+                if labels.len() > 1 && !is_hover && ph < val_metrics.line_height {
+                    continue;
+                }
+                let val_str = self.format_value(*value, 1);
+                let val_y = py + (ph - val_metrics.line_height) / 2.0;
+                let (val_x, val_align) = if *value < 0.0 {
+                    (value_x(piece.lo) - 4.0 - AXIS_LABEL_W, Align::Right)
+                } else {
+                    (value_x(piece.hi) + 4.0, Align::Left)
+                };
                 ctx.renderer.draw_text(
-                    &bar.label,
-                    (bx, chart_bottom + 4.0),
-                    self.label_color,
-                    (bar_w, label_metrics.line_height),
-                    label_metrics,
-                    Align::Center,
+                    &val_str,
+                    (val_x, val_y),
+                    value_color,
+                    (AXIS_LABEL_W, val_metrics.line_height),
+                    val_metrics,
+                    val_align,
                 );
             }
-        });
+
+            // Label left of the chart
+            ctx.renderer.draw_text(
+                &bar.label,
+                (x + self.padding, by + (bar_h - label_metrics.line_height) / 2.0),
+                label_color,
+                ((label_w - 8.0).max(0.0), label_metrics.line_height),
+                label_metrics,
+                Align::Right,
+            );
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        let scaling = self.scale.get().is_some_and(|(lo, hi)| lo.is_running() || hi.is_running());
+        scaling
+            || self
+                .display
+                .borrow()
+                .iter()
+                .any(|d| d.presence.is_running() || d.heights.iter().any(|h| h.is_running()))
+    }
+}
+
+impl Widget for BarChart {
+    fn style(&self) -> Style {
+        Style {
+            size: Size {
+                width: Dimension::Percent(1.0),
+                height: Dimension::Length(self.height),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawContext) {
+        let layout = ctx.layout;
+
+        // Background
+        if self.bg[3] > 0.0 {
+            ctx.renderer.fill_rect_rounded(
+                (layout.location.x, layout.location.y, layout.size.width, layout.size.height),
+                self.bg,
+                0.0,
+            );
+        }
+
+        self.sync_display();
+        let range = self.animated_range();
+        if self.is_animating() {
+            ctx.renderer.request_next_frame();
+        }
+        if self.horizontal {
+            self.draw_horizontal(ctx, range);
+        } else {
+            self.draw_vertical(ctx, range);
+        }
     }
 
     fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
@@ -274,26 +812,24 @@ impl Widget for BarChart {
                     && py >= layout.location.y
                     && py <= layout.location.y + layout.size.height;
 
-                let new_hover = if inside {
+                let new_hover = if inside && self.horizontal {
+                    // Bar slot under the cursor, gap included
+                    self.bar_spans
+                        .borrow()
+                        .iter()
+                        .position(|&(by, bh)| py >= by && py < by + bh + self.bar_gap)
+                } else if inside {
                     let label_area_h = self.metrics.line_height + 4.0;
                     let value_area_h = self.metrics.line_height;
-                    let y_label_w: f32 = 44.0;
-                    let chart_left = layout.location.x + self.padding + y_label_w;
-                    let chart_w = (layout.size.width - self.padding * 2.0 - y_label_w).max(10.0);
                     let chart_bottom = layout.location.y + layout.size.height - self.padding - label_area_h;
                     let chart_top = layout.location.y + self.padding + value_area_h;
 
                     if py >= chart_top && py <= chart_bottom + label_area_h {
-                        let count = self.bars.with(|b| b.len());
-                        if count > 0 {
-                            let total_gaps = (count.saturating_sub(1)) as f32 * self.bar_gap;
-                            let bar_w = ((chart_w - total_gaps) / count as f32).max(4.0);
-                            let rel_x = px - chart_left;
-                            let idx = (rel_x / (bar_w + self.bar_gap)) as usize;
-                            if idx < count { Some(idx) } else { None }
-                        } else {
-                            None
-                        }
+                        // Bar slot under the cursor, gap included
+                        self.bar_spans
+                            .borrow()
+                            .iter()
+                            .position(|&(bx, bw)| px >= bx && px < bx + bw + self.bar_gap)
                     } else {
                         None
                     }
@@ -1,26 +1,37 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
 use glyphon::Metrics;
 use glyphon::cosmic_text::Align;
 use taffy::prelude::*;
 use winit::event::{ElementState, MouseButton, WindowEvent};
 
 use crate::framework::{DrawContext, EventContext, Widget};
+use crate::reactive::on_frame;
+use crate::theme::Theme;
+
+// Shared with the frame task that fires hold-repeats
+type ClickHandler = Rc<RefCell<dyn FnMut()>>;
 
 pub struct Button {
     label: String,
     metrics: Metrics,
     padding: f32,
     border_radius: f32,
-    bg_color: [f32; 3],
-    hover_color: [f32; 3],
-    active_color: [f32; 3],
-    focus_color: [f32; 3],
+    /// Set by `with_colors`; `None` uses the theme's button colors
+    colors: Option<[[f32; 3]; 4]>,
     text_color: [u8; 3],
     hover_text_color: [u8; 3],
     active_text_color: [u8; 3],
     hover: bool,
     active: bool,
     focus: bool,
-    on_click: Option<Box<dyn FnMut()>>,
+    on_click: Option<ClickHandler>,
+    repeat: Option<(Duration, Duration)>,
+    // Bumped on every press and release; a repeat task only runs while the
+    // press that started it is current
+    press_id: Rc<Cell<u64>>,
 }
 
 impl Button {
@@ -30,10 +41,7 @@ impl Button {
             metrics,
             padding: 16.0,
             border_radius: 0.0,
-            bg_color: [0.20, 0.65, 0.85],
-            hover_color: [0.35, 0.75, 0.92],
-            active_color: [0.15, 0.55, 0.75],
-            focus_color: [0.18, 0.60, 0.82],
+            colors: None,
             text_color: [25, 25, 25],
             hover_text_color: [15, 15, 15],
             active_text_color: [250, 250, 250],
@@ -41,9 +49,26 @@ impl Button {
             active: false,
             focus: false,
             on_click: None,
+            repeat: None,
+            press_id: Rc::new(Cell::new(0)),
         }
     }
 
+    /// Keyboard-like hold delay, for use with `with_repeat`.
+    pub const REPEAT_DELAY: Duration = Duration::from_millis(400);
+    /// Keyboard-like repeat rate, for use with `with_repeat`.
+    pub const REPEAT_INTERVAL: Duration = Duration::from_millis(80);
+
+    /// While the mouse button is held on the button, fires `on_click` again
+    /// after `initial_delay` and then every `interval`, like a held key.
+    /// Releasing or moving off the button stops the repeat. Repeats fire on
+    /// the frame clock, at most once per frame.
+    pub fn with_repeat(mut self, initial_delay: Duration, interval: Duration) -> Self {
+        self.repeat = Some((initial_delay, interval));
+        self
+    }
+
+    /// Replaces the theme's button colors.
     pub fn with_colors(
         mut self,
         bg: [f32; 3],
@@ -51,10 +76,7 @@ impl Button {
         active: [f32; 3],
         focus: [f32; 3],
     ) -> Self {
-        self.bg_color = bg;
-        self.hover_color = hover;
-        self.active_color = active;
-        self.focus_color = focus;
+        self.colors = Some([bg, hover, active, focus]);
         self
     }
 
@@ -76,18 +98,24 @@ impl Button {
     }
 
     pub fn set_on_click(&mut self, handler: impl FnMut() + 'static) {
-        self.on_click = Some(Box::new(handler));
+        self.on_click = Some(Rc::new(RefCell::new(handler)));
     }
 
-    fn current_color(&self) -> [f32; 3] {
+    fn current_color(&self, theme: &Theme) -> [f32; 3] {
+        let [bg, hover, active, focus] = self.colors.unwrap_or([
+            theme.button,
+            theme.button_hover,
+            theme.button_active,
+            theme.button_focus,
+        ]);
         if self.active {
-            self.active_color
+            active
         } else if self.hover {
-            self.hover_color
+            hover
         } else if self.focus {
-            self.focus_color
+            focus
         } else {
-            self.bg_color
+            bg
         }
     }
 
@@ -101,18 +129,39 @@ impl Button {
         }
     }
 
-    fn hit_test(&self, layout: &Layout, x: f32, y: f32) -> bool {
-        x >= layout.location.x
-            && x <= layout.location.x + layout.size.width
-            && y >= layout.location.y
-            && y <= layout.location.y + layout.size.height
-    }
-
     fn click(&mut self) {
-        if let Some(handler) = self.on_click.as_mut() {
-            handler();
+        if let Some(handler) = &self.on_click {
+            (handler.borrow_mut())();
         }
     }
+
+    fn start_repeat(&mut self) {
+        let id = self.press_id.get() + 1;
+        self.press_id.set(id);
+        let (Some((delay, interval)), Some(handler)) = (self.repeat, self.on_click.clone()) else {
+            return;
+        };
+        let interval = interval.max(Duration::from_millis(1));
+        let press_id = self.press_id.clone();
+        let mut next = Instant::now() + delay;
+        on_frame(move |now| {
+            if press_id.get() != id {
+                return false;
+            }
+            if now >= next {
+                (handler.borrow_mut())();
+                // Skip missed beats after a stall instead of bursting
+                next = (next + interval).max(now);
+            }
+            // Keep frames coming while held, so beats land on time
+            crate::request_redraw();
+            true
+        });
+    }
+
+    fn stop_repeat(&mut self) {
+        self.press_id.set(self.press_id.get() + 1);
+    }
 }
 
 impl Widget for Button {
@@ -131,7 +180,7 @@ impl Widget for Button {
 
     fn draw(&self, ctx: &mut DrawContext) {
         let layout = ctx.layout;
-        let color = self.current_color();
+        let color = self.current_color(ctx.theme);
         ctx.renderer.fill_rect_rounded(
             (
                 layout.location.x,
@@ -160,26 +209,34 @@ impl Widget for Button {
         );
     }
 
+    fn on_mouse_enter(&mut self) {
+        self.hover = true;
+    }
+
+    fn on_mouse_leave(&mut self) {
+        self.hover = false;
+        self.stop_repeat();
+    }
+
     fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
-        let layout = ctx.layout;
         match ctx.event {
-            WindowEvent::CursorMoved { position, .. } => {
-                let over = self.hit_test(layout, position.x as f32, position.y as f32);
-                self.hover = over;
-                false // don't consume — let siblings update hover too
-            }
             WindowEvent::MouseInput {
                 state: ElementState::Pressed,
                 button: MouseButton::Left,
                 ..
+            } if self.hover => {
+                self.active = !self.active;
+                self.click();
+                self.start_repeat();
+                true
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button: MouseButton::Left,
+                ..
             } => {
-                if self.hover {
-                    self.active = !self.active;
-                    self.click();
-                    true
-                } else {
-                    false
-                }
+                self.stop_repeat();
+                false
             }
             _ => false,
         }
@@ -200,5 +257,6 @@ impl Widget for Button {
 
     fn clear_active(&mut self) {
         self.active = false;
+        self.stop_repeat();
     }
 }
@@ -0,0 +1,255 @@
+use std::cell::Cell;
+
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+
+use crate::framework::{DrawContext, EventContext, Widget};
+use crate::renderer::Transform;
+use crate::widgets::popover::AnchorRect;
+
+/// Drag distance (px) before a press turns into a pan instead of a click.
+const DRAG_THRESHOLD: f32 = 3.0;
+
+/// Floor for `with_zoom_limits`, so zooming out never reaches zero.
+const MIN_ZOOM: f32 = 0.01;
+
+/// Free-form drawing surface with built-in pan and zoom.
+///
+/// The paint callback draws in canvas coordinates: `(0, 0)` is the canvas's
+/// top-left corner at zero pan, and one unit is one pixel at zoom 1. Pan
+/// (left or middle drag) and zoom (Ctrl + wheel, anchored at the cursor) are
+/// applied as a renderer transform to everything the callback draws. Inside
+/// the callback, `ctx.layout` is the visible region in canvas coordinates,
+/// handy for culling.
+///
+/// Hit-testing goes the other way: `screen = canvas * zoom + origin + pan`.
+/// Click handlers receive the point already mapped to canvas coordinates,
+/// and `screen_to_canvas` exposes the same mapping.
+///
+/// ```ignore
+/// let canvas = Canvas::new(|ctx| {
+///     ctx.rounded_rect((40.0, 40.0, 120.0, 60.0), 6.0, [0.2, 0.5, 0.8, 1.0]);
+///     ctx.line((160.0, 70.0), (260.0, 140.0), 2.0, [0.9, 0.9, 0.9, 1.0]);
+/// })
+/// .with_on_click(|x, y| println!("clicked canvas at {x}, {y}"));
+/// ```
+pub struct Canvas {
+    painter: Box<dyn Fn(&mut DrawContext)>,
+    on_click: Option<Box<dyn FnMut(f32, f32)>>,
+    height: Option<f32>,
+    min_zoom: f32,
+    max_zoom: f32,
+    // Colors
+    background: Option<[f32; 4]>,
+    // State
+    pan: (f32, f32),
+    zoom: f32,
+    ctrl: bool,
+    press: Option<(f32, f32)>,
+    panning: bool,
+    /// Where the pan last moved to: the press point until a drag starts
+    pan_from: (f32, f32),
+    // Cached absolute rect (set during draw)
+    rect: Cell<AnchorRect>,
+}
+
+impl Canvas {
+    pub fn new(painter: impl Fn(&mut DrawContext) + 'static) -> Self {
+        Self {
+            painter: Box::new(painter),
+            on_click: None,
+            height: None,
+            min_zoom: 0.1,
+            max_zoom: 10.0,
+            background: None,
+            pan: (0.0, 0.0),
+            zoom: 1.0,
+            ctrl: false,
+            press: None,
+            panning: false,
+            pan_from: (0.0, 0.0),
+            rect: Cell::new((0.0, 0.0, 0.0, 0.0)),
+        }
+    }
+
+    /// Called with the canvas-space point of a left click (a press and
+    /// release without dragging).
+    pub fn with_on_click(mut self, handler: impl FnMut(f32, f32) + 'static) -> Self {
+        self.on_click = Some(Box::new(handler));
+        self
+    }
+
+    /// Fixed height; by default the canvas grows to fill its parent.
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    pub fn with_background(mut self, color: [f32; 4]) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Zoom range for Ctrl + wheel. The minimum is kept above zero since
+    /// hit-testing divides by the zoom.
+    pub fn with_zoom_limits(mut self, min: f32, max: f32) -> Self {
+        self.min_zoom = min.max(MIN_ZOOM);
+        self.max_zoom = max.max(self.min_zoom);
+        self.zoom = self.zoom.clamp(self.min_zoom, self.max_zoom);
+        self
+    }
+
+    /// Initial pan offset (screen pixels) and zoom factor.
+    pub fn with_view(mut self, pan: (f32, f32), zoom: f32) -> Self {
+        self.pan = pan;
+        self.zoom = zoom.clamp(self.min_zoom, self.max_zoom);
+        self
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    pub fn pan(&self) -> (f32, f32) {
+        self.pan
+    }
+
+    /// Canvas-to-screen transform for the current pan, zoom and position.
+    pub fn transform(&self) -> Transform {
+        let (x, y, _, _) = self.rect.get();
+        Transform::new((x + self.pan.0, y + self.pan.1), self.zoom)
+    }
+
+    pub fn screen_to_canvas(&self, point: (f32, f32)) -> (f32, f32) {
+        self.transform().invert(point)
+    }
+
+    fn contains(&self, x: f32, y: f32) -> bool {
+        let (rx, ry, rw, rh) = self.rect.get();
+        x >= rx && x <= rx + rw && y >= ry && y <= ry + rh
+    }
+
+    /// Zooms by `factor`, keeping the canvas point under `cursor` fixed.
+    fn zoom_at(&mut self, cursor: (f32, f32), factor: f32) {
+        let anchor = self.screen_to_canvas(cursor);
+        self.zoom = (self.zoom * factor).clamp(self.min_zoom, self.max_zoom);
+        let (x, y, _, _) = self.rect.get();
+        self.pan = (cursor.0 - x - anchor.0 * self.zoom, cursor.1 - y - anchor.1 * self.zoom);
+    }
+}
+
+impl Widget for Canvas {
+    fn style(&self) -> Style {
+        Style {
+            flex_grow: if self.height.is_some() { 0.0 } else { 1.0 },
+            size: Size {
+                width: Dimension::Percent(1.0),
+                height: match self.height {
+                    Some(h) => Dimension::Length(h),
+                    None => Dimension::Auto,
+                },
+            },
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawContext) {
+        let layout = ctx.layout;
+        let rect = (layout.location.x, layout.location.y, layout.size.width, layout.size.height);
+        self.rect.set(rect);
+
+        if let Some(bg) = self.background {
+            ctx.renderer.fill_rect_rounded(rect, bg, 0.0);
+        }
+
+        let transform = self.transform();
+        let (vx, vy) = transform.invert((rect.0, rect.1));
+        let mut visible = *layout;
+        visible.location = Point { x: vx, y: vy };
+        visible.size = Size {
+            width: rect.2 / self.zoom,
+            height: rect.3 / self.zoom,
+        };
+
+        ctx.renderer.push_clip(rect);
+        ctx.renderer.push_transform(transform);
+        let mut local = DrawContext {
+            renderer: &mut *ctx.renderer,
+            layout: &visible,
+            theme: ctx.theme,
+        };
+        (self.painter)(&mut local);
+        ctx.renderer.pop_transform();
+        ctx.renderer.pop_clip();
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        match ctx.event {
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.ctrl = modifiers.state().control_key();
+                false
+            }
+            WindowEvent::CursorMoved { .. } => {
+                let (px, py) = ctx.cursor;
+                if let Some((sx, sy)) = self.press {
+                    if !self.panning && ((px - sx).abs() > DRAG_THRESHOLD || (py - sy).abs() > DRAG_THRESHOLD) {
+                        self.panning = true;
+                    }
+                    // From the press point the first time, so the movement
+                    // made before crossing the threshold counts too
+                    if self.panning {
+                        let (fx, fy) = self.pan_from;
+                        self.pan = (self.pan.0 + px - fx, self.pan.1 + py - fy);
+                        self.pan_from = (px, py);
+                    }
+                }
+                false
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left | MouseButton::Middle,
+                ..
+            } => {
+                let (cx, cy) = ctx.cursor;
+                if !self.contains(cx, cy) {
+                    return false;
+                }
+                self.press = Some(ctx.cursor);
+                self.pan_from = ctx.cursor;
+                self.panning = false;
+                true
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button,
+                ..
+            } => {
+                let Some(_) = self.press.take() else {
+                    return false;
+                };
+                let was_panning = std::mem::take(&mut self.panning);
+                if !was_panning && *button == MouseButton::Left {
+                    let (x, y) = self.screen_to_canvas(ctx.cursor);
+                    if let Some(handler) = self.on_click.as_mut() {
+                        handler(x, y);
+                    }
+                }
+                true
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let (cx, cy) = ctx.cursor;
+                if !self.ctrl || !self.contains(cx, cy) {
+                    return false;
+                }
+                let steps = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => *y,
+                    MouseScrollDelta::PixelDelta(d) => d.y as f32 / 40.0,
+                };
+                self.zoom_at(ctx.cursor, 1.1_f32.powf(steps));
+                true
+            }
+            _ => false,
+        }
+    }
+}
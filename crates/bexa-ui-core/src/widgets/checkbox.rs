@@ -1,27 +1,27 @@
 use glyphon::Metrics;
 use glyphon::cosmic_text::Align;
 use taffy::prelude::*;
-use winit::event::{ElementState, KeyEvent, MouseButton, WindowEvent};
-use winit::keyboard::{Key, ModifiersState, NamedKey};
+use winit::event::{ElementState, MouseButton, WindowEvent};
 
 use crate::framework::{DrawContext, EventContext, Widget};
 use crate::icons;
 use crate::signal::{Signal, SetSignal};
+use crate::widgets::measured_label::MeasuredLabel;
 
 pub struct Checkbox {
-    label: String,
+    label: MeasuredLabel,
     checked: Signal<bool>,
     set_checked: SetSignal<bool>,
     metrics: Metrics,
     box_size: f32,
     gap: f32,
     border_radius: f32,
-    // Colors
-    box_bg: [f32; 4],
-    box_checked_bg: [f32; 4],
-    box_border: [f32; 4],
-    check_color: [u8; 3],
-    text_color: [u8; 3],
+    // Colors; `None` uses the theme's checkbox colors and `text_primary`
+    box_bg: Option<[f32; 4]>,
+    box_checked_bg: Option<[f32; 4]>,
+    box_border: Option<[f32; 4]>,
+    check_color: Option<[u8; 3]>,
+    text_color: Option<[u8; 3]>,
     // State
     hover: bool,
     focus: bool,
@@ -35,18 +35,18 @@ impl Checkbox {
         metrics: Metrics,
     ) -> Self {
         Self {
-            label: label.into(),
+            label: MeasuredLabel::new(label),
             checked,
             set_checked,
             metrics,
             box_size: 20.0,
             gap: 8.0,
             border_radius: 4.0,
-            box_bg: [0.16, 0.28, 0.38, 1.0],
-            box_checked_bg: [0.20, 0.65, 0.85, 1.0],
-            box_border: [0.4, 0.55, 0.7, 1.0],
-            check_color: [255, 255, 255],
-            text_color: [230, 230, 230],
+            box_bg: None,
+            box_checked_bg: None,
+            box_border: None,
+            check_color: None,
+            text_color: None,
             hover: false,
             focus: false,
         }
@@ -74,15 +74,15 @@ impl Checkbox {
         box_border: [f32; 4],
         check_color: [u8; 3],
     ) -> Self {
-        self.box_bg = box_bg;
-        self.box_checked_bg = box_checked_bg;
-        self.box_border = box_border;
-        self.check_color = check_color;
+        self.box_bg = Some(box_bg);
+        self.box_checked_bg = Some(box_checked_bg);
+        self.box_border = Some(box_border);
+        self.check_color = Some(check_color);
         self
     }
 
     pub fn with_text_color(mut self, color: [u8; 3]) -> Self {
-        self.text_color = color;
+        self.text_color = Some(color);
         self
     }
 
@@ -90,21 +90,16 @@ impl Checkbox {
         let current = self.checked.get();
         self.set_checked.set(!current);
     }
-
-    fn hit_test(&self, layout: &Layout, x: f32, y: f32) -> bool {
-        x >= layout.location.x
-            && x <= layout.location.x + layout.size.width
-            && y >= layout.location.y
-            && y <= layout.location.y + layout.size.height
-    }
 }
 
 impl Widget for Checkbox {
     fn style(&self) -> Style {
         let height = self.box_size.max(self.metrics.line_height) + 8.0;
+        // Sized to its content so packed rows don't clip the label
+        let width = 4.0 + self.box_size + self.gap + self.label.width(self.metrics) + 4.0;
         Style {
             size: Size {
-                width: Dimension::Auto,
+                width: Dimension::Length(width),
                 height: Dimension::Length(height),
             },
             flex_shrink: 0.0,
@@ -113,6 +108,12 @@ impl Widget for Checkbox {
     }
 
     fn draw(&self, ctx: &mut DrawContext) {
+        let theme = ctx.theme;
+        let box_bg = self.box_bg.unwrap_or(theme.checkbox_bg);
+        let box_checked_bg = self.box_checked_bg.unwrap_or(theme.checkbox_checked_bg);
+        let box_border = self.box_border.unwrap_or(theme.checkbox_border);
+        let check_color = self.check_color.unwrap_or(theme.checkbox_check);
+        let text_color = self.text_color.unwrap_or(theme.text_primary);
         let layout = ctx.layout;
         let is_checked = self.checked.get();
 
@@ -120,30 +121,30 @@ impl Widget for Checkbox {
         let box_x = layout.location.x + 4.0;
         let box_y = layout.location.y + (layout.size.height - self.box_size) / 2.0;
         let bg = if is_checked {
-            self.box_checked_bg
+            box_checked_bg
         } else if self.hover {
             // Brighten bg on hover
             [
-                (self.box_bg[0] + 0.06).min(1.0),
-                (self.box_bg[1] + 0.06).min(1.0),
-                (self.box_bg[2] + 0.06).min(1.0),
-                self.box_bg[3],
+                (box_bg[0] + 0.06).min(1.0),
+                (box_bg[1] + 0.06).min(1.0),
+                (box_bg[2] + 0.06).min(1.0),
+                box_bg[3],
             ]
         } else {
-            self.box_bg
+            box_bg
         };
         let border_w = if self.focus { 2.0 } else if self.hover { 1.5 } else { 1.0 };
         let border_c = if self.focus {
-            [0.3, 0.6, 0.9, 1.0]
+            ctx.theme.focus_ring
         } else if self.hover {
             [
-                (self.box_border[0] + 0.1).min(1.0),
-                (self.box_border[1] + 0.1).min(1.0),
-                (self.box_border[2] + 0.1).min(1.0),
-                self.box_border[3],
+                (box_border[0] + 0.1).min(1.0),
+                (box_border[1] + 0.1).min(1.0),
+                (box_border[2] + 0.1).min(1.0),
+                box_border[3],
             ]
         } else {
-            self.box_border
+            box_border
         };
         ctx.renderer.fill_rect_styled(
             (box_x, box_y, self.box_size, self.box_size),
@@ -162,7 +163,7 @@ impl Widget for Checkbox {
             ctx.renderer.draw_text_with_font(
                 icons::CHECK,
                 (icon_x, icon_y),
-                self.check_color,
+                check_color,
                 (icon_size, icon_size),
                 icon_metrics,
                 Align::Center,
@@ -174,46 +175,24 @@ impl Widget for Checkbox {
         let text_x = box_x + self.box_size + self.gap;
         let text_y = layout.location.y + (layout.size.height - self.metrics.line_height) / 2.0;
         let text_w = (layout.size.width - (text_x - layout.location.x)).max(0.0);
-        ctx.renderer.draw_text(
-            &self.label,
-            (text_x, text_y),
-            self.text_color,
-            (text_w, self.metrics.line_height),
-            self.metrics,
-            Align::Left,
-        );
+        self.label.draw(ctx.renderer, (text_x, text_y), text_w, text_color, self.metrics);
+    }
+
+    fn on_mouse_enter(&mut self) {
+        self.hover = true;
+    }
+
+    fn on_mouse_leave(&mut self) {
+        self.hover = false;
     }
 
     fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
-        let layout = ctx.layout;
         match ctx.event {
-            WindowEvent::CursorMoved { position, .. } => {
-                let over = self.hit_test(layout, position.x as f32, position.y as f32);
-                self.hover = over;
-                false // don't consume — let siblings update hover too
-            }
             WindowEvent::MouseInput {
                 state: ElementState::Pressed,
                 button: MouseButton::Left,
                 ..
-            } => {
-                if self.hover {
-                    self.toggle();
-                    true
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        }
-    }
-
-    fn handle_key_event(&mut self, event: &KeyEvent, _modifiers: ModifiersState) -> bool {
-        if event.state != ElementState::Pressed {
-            return false;
-        }
-        match &event.logical_key {
-            Key::Named(NamedKey::Space) | Key::Named(NamedKey::Enter) => {
+            } if self.hover => {
                 self.toggle();
                 true
             }
@@ -221,6 +200,10 @@ impl Widget for Checkbox {
         }
     }
 
+    fn update_measures(&mut self, measures: &[Vec<f32>]) {
+        self.label.update_measures(measures);
+    }
+
     fn is_focusable(&self) -> bool {
         true
     }
@@ -1,15 +1,33 @@
 use taffy::prelude::*;
 use taffy::Overflow;
+use winit::event::{ElementState, MouseButton, WindowEvent};
 
-use crate::framework::{DrawContext, Widget};
+use crate::framework::{is_left_press, DrawContext, EventContext, Widget};
 
 pub struct Container {
     style: Style,
     background: Option<[f32; 4]>,
+    hover_background: Option<[f32; 4]>,
+    active_background: Option<[f32; 4]>,
     border_radius: f32,
     border_width: f32,
     border_color: [f32; 4],
     scrollable: bool,
+    scrollbar_autohide: Option<bool>,
+    clip: bool,
+    focusable: bool,
+    selects_text: bool,
+    on_click: Option<Box<dyn FnMut()>>,
+    // State
+    focused: bool,
+    hover: bool,
+    pressed: bool,
+}
+
+impl Default for Container {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Container {
@@ -22,10 +40,20 @@ impl Container {
                 ..Default::default()
             },
             background: None,
+            hover_background: None,
+            active_background: None,
             border_radius: 0.0,
             border_width: 0.0,
             border_color: [0.0; 4],
             scrollable: false,
+            scrollbar_autohide: None,
+            clip: false,
+            focusable: false,
+            selects_text: false,
+            on_click: None,
+            focused: false,
+            hover: false,
+            pressed: false,
         }
     }
 
@@ -49,6 +77,27 @@ impl Container {
         self
     }
 
+    /// Background while the cursor is over the container or its children.
+    pub fn with_hover_background(mut self, color: [f32; 3]) -> Self {
+        self.hover_background = Some([color[0], color[1], color[2], 1.0]);
+        self
+    }
+
+    /// Background while the left button is held down after a press inside,
+    /// and the cursor is still over the container.
+    pub fn with_active_background(mut self, color: [f32; 3]) -> Self {
+        self.active_background = Some([color[0], color[1], color[2], 1.0]);
+        self
+    }
+
+    fn current_background(&self) -> Option<[f32; 4]> {
+        if !self.hover {
+            return self.background;
+        }
+        let active = self.active_background.filter(|_| self.pressed);
+        active.or(self.hover_background).or(self.background)
+    }
+
     pub fn with_border_radius(mut self, radius: f32) -> Self {
         self.border_radius = radius;
         self
@@ -76,6 +125,51 @@ impl Container {
         self
     }
 
+    /// Hides the scrollbar while idle and fades it in on scroll or when the
+    /// pointer nears it (`true`), or keeps it always visible (`false`).
+    /// Defaults to `Theme::scrollbar_autohide`.
+    pub fn with_scrollbar_autohide(mut self, autohide: bool) -> Self {
+        self.scrollbar_autohide = Some(autohide);
+        self
+    }
+
+    /// Crops children to the container's bounds, following the corner
+    /// radius from `with_border_radius` (e.g. a circular avatar or a fixed
+    /// media tile). Oversized children also stop stretching the container.
+    /// Only drawing is cropped; text is cut by the bounding rectangle, not
+    /// the rounded corners.
+    pub fn with_clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
+        if clip {
+            self.style.overflow.x = Overflow::Hidden;
+            self.style.overflow.y = Overflow::Hidden;
+        }
+        self
+    }
+
+    /// Lets Tab focus the container. A focused scroll container (see
+    /// `with_scroll`) scrolls with the arrow keys, PageUp/PageDown, Space and
+    /// Home/End, for keyboard-only navigation of long content.
+    pub fn with_focusable(mut self) -> Self {
+        self.focusable = true;
+        self
+    }
+
+    /// Makes the container a button: a press inside that no child took, or
+    /// Enter / Space while focused, calls `handler`. Clickable containers
+    /// are focusable; without a handler the container ignores the pointer.
+    pub fn with_on_click(mut self, handler: impl FnMut() + 'static) -> Self {
+        self.on_click = Some(Box::new(handler));
+        self
+    }
+
+    /// Lets the pointer select the text inside (labels, log lines, table
+    /// cells) and Ctrl+C copy it. See `Widget::selects_text`.
+    pub fn with_text_selection(mut self, selectable: bool) -> Self {
+        self.selects_text = selectable;
+        self
+    }
+
     pub fn with_gap(mut self, gap: f32) -> Self {
         self.style.gap = Size {
             width: LengthPercentage::Length(gap),
@@ -83,6 +177,29 @@ impl Container {
         };
         self
     }
+
+    /// Share of the parent's free space this container takes, relative to
+    /// its siblings. Defaults to 1; use 0 for a panel that keeps its basis
+    /// size while a growing sibling takes the rest.
+    pub fn with_flex_grow(mut self, grow: f32) -> Self {
+        self.style.flex_grow = grow;
+        self
+    }
+
+    /// How much this container gives up when its siblings overflow the
+    /// parent. Defaults to 1; 0 keeps it from shrinking below its basis.
+    pub fn with_flex_shrink(mut self, shrink: f32) -> Self {
+        self.style.flex_shrink = shrink;
+        self
+    }
+
+    /// Size along the parent's main axis before free space is shared out,
+    /// e.g. `Dimension::Length(240.0)` for a fixed sidebar. Defaults to
+    /// `Dimension::Auto` (the container's content or set size).
+    pub fn with_flex_basis(mut self, basis: Dimension) -> Self {
+        self.style.flex_basis = basis;
+        self
+    }
 }
 
 impl Widget for Container {
@@ -94,8 +211,20 @@ impl Widget for Container {
         self.scrollable
     }
 
+    fn scrollbar_autohide(&self) -> Option<bool> {
+        self.scrollbar_autohide
+    }
+
+    fn clip_children(&self) -> Option<f32> {
+        self.clip.then_some(self.border_radius)
+    }
+
+    fn selects_text(&self) -> bool {
+        self.selects_text
+    }
+
     fn draw(&self, ctx: &mut DrawContext) {
-        if let Some(color) = self.background {
+        if let Some(color) = self.current_background() {
             let layout = ctx.layout;
             ctx.renderer.fill_rect_styled(
                 (
@@ -111,4 +240,73 @@ impl Widget for Container {
             );
         }
     }
+
+    // After the children, so their backgrounds can't cover the ring
+    fn draw_over(&self, ctx: &mut DrawContext) {
+        if self.focused {
+            let layout = ctx.layout;
+            ctx.renderer.fill_rect_styled(
+                (
+                    layout.location.x,
+                    layout.location.y,
+                    layout.size.width,
+                    layout.size.height,
+                ),
+                [0.0; 4],
+                self.border_radius,
+                2.0,
+                ctx.theme.focus_ring,
+            );
+        }
+    }
+
+    fn on_mouse_enter(&mut self) {
+        self.hover = true;
+    }
+
+    fn on_mouse_leave(&mut self) {
+        self.hover = false;
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        if let WindowEvent::MouseInput {
+            state: ElementState::Released,
+            button: MouseButton::Left,
+            ..
+        } = ctx.event
+        {
+            self.pressed = false;
+            return false;
+        }
+        if !is_left_press(ctx.event) {
+            return false;
+        }
+        let layout = ctx.layout;
+        let (cx, cy) = ctx.cursor;
+        let inside = cx >= layout.location.x
+            && cx <= layout.location.x + layout.size.width
+            && cy >= layout.location.y
+            && cy <= layout.location.y + layout.size.height;
+        self.pressed = inside;
+        // Presses inside that no child took focus the container
+        if !inside || !self.is_focusable() {
+            return false;
+        }
+        self.activate();
+        true
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.focusable || self.on_click.is_some()
+    }
+
+    fn activate(&mut self) {
+        if let Some(handler) = self.on_click.as_mut() {
+            handler();
+        }
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
 }
@@ -0,0 +1,249 @@
+use std::cell::Cell;
+
+use glyphon::Metrics;
+use glyphon::cosmic_text::Align;
+use taffy::prelude::*;
+use winit::event::{ElementState, MouseButton, WindowEvent};
+
+use crate::framework::{DrawContext, EventContext, Widget};
+use crate::widgets::popover::{place_overlay, AnchorRect, PopoverAlign, PopoverSide};
+
+/// Popup list of actions opened by a right click on its children.
+///
+/// The menu wraps its trigger (its tree children) and opens at the cursor,
+/// flipped at the viewport edges. Clicking an item calls the select handler
+/// with the item's index and closes the menu; clicking elsewhere or Escape
+/// closes it without a choice. While it is open, the children get no
+/// pointer events.
+///
+/// ```ignore
+/// let menu = ContextMenu::new(metrics)
+///     .with_item("Restart")
+///     .with_item("Stop")
+///     .with_on_select(move |index| run_action(index));
+/// let node = WidgetNode::new(menu, vec![table_node]);
+/// ```
+pub struct ContextMenu {
+    items: Vec<String>,
+    on_select: Option<Box<dyn FnMut(usize)>>,
+    metrics: Metrics,
+    padding: f32,
+    item_padding: f32,
+    min_width: f32,
+    border_radius: f32,
+    // Colors
+    bg: [f32; 4],
+    border: [f32; 4],
+    hover_bg: [f32; 4],
+    text_color: [u8; 3],
+    // State
+    open_at: Option<(f32, f32)>,
+    hover_index: Option<usize>,
+    // Cached absolute rect (set during draw)
+    menu_rect: Cell<AnchorRect>,
+}
+
+impl ContextMenu {
+    pub fn new(metrics: Metrics) -> Self {
+        Self {
+            items: Vec::new(),
+            on_select: None,
+            metrics,
+            padding: 4.0,
+            item_padding: 6.0,
+            min_width: 140.0,
+            border_radius: 6.0,
+            bg: [0.12, 0.14, 0.20, 0.98],
+            border: [0.35, 0.40, 0.55, 1.0],
+            hover_bg: [1.0, 1.0, 1.0, 0.10],
+            text_color: [220, 220, 230],
+            open_at: None,
+            hover_index: None,
+            menu_rect: Cell::new((0.0, 0.0, 0.0, 0.0)),
+        }
+    }
+
+    /// Adds an item; items are indexed in the order they are added.
+    pub fn with_item(mut self, label: impl Into<String>) -> Self {
+        self.items.push(label.into());
+        self
+    }
+
+    /// Called with the index of the item clicked.
+    pub fn with_on_select(mut self, handler: impl FnMut(usize) + 'static) -> Self {
+        self.on_select = Some(Box::new(handler));
+        self
+    }
+
+    /// Narrowest the menu gets; it widens to fit longer labels.
+    pub fn with_min_width(mut self, width: f32) -> Self {
+        self.min_width = width;
+        self
+    }
+
+    pub fn with_border_radius(mut self, radius: f32) -> Self {
+        self.border_radius = radius;
+        self
+    }
+
+    pub fn with_colors(mut self, bg: [f32; 4], border: [f32; 4], hover_bg: [f32; 4], text_color: [u8; 3]) -> Self {
+        self.bg = bg;
+        self.border = border;
+        self.hover_bg = hover_bg;
+        self.text_color = text_color;
+        self
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open_at.is_some()
+    }
+
+    fn close(&mut self) {
+        self.open_at = None;
+        self.hover_index = None;
+    }
+
+    fn item_height(&self) -> f32 {
+        self.metrics.line_height + self.item_padding * 2.0
+    }
+
+    fn menu_size(&self) -> (f32, f32) {
+        // Estimate text width: ~0.6em per char
+        let longest = self.items.iter().map(|item| item.chars().count()).max().unwrap_or(0);
+        let text_w = longest as f32 * self.metrics.font_size * 0.6;
+        let w = (text_w + self.item_padding * 2.0 + self.padding * 2.0).max(self.min_width);
+        let h = self.items.len() as f32 * self.item_height() + self.padding * 2.0;
+        (w, h)
+    }
+
+    fn item_at(&self, x: f32, y: f32) -> Option<usize> {
+        let (mx, my, mw, mh) = self.menu_rect.get();
+        if !rect_contains((mx, my, mw, mh), x, y) {
+            return None;
+        }
+        let offset = y - my - self.padding;
+        if offset < 0.0 {
+            return None;
+        }
+        let index = (offset / self.item_height()) as usize;
+        (index < self.items.len()).then_some(index)
+    }
+}
+
+fn rect_contains(rect: AnchorRect, x: f32, y: f32) -> bool {
+    x >= rect.0 && x <= rect.0 + rect.2 && y >= rect.1 && y <= rect.1 + rect.3
+}
+
+impl Widget for ContextMenu {
+    fn style(&self) -> Style {
+        // Transparent wrapper around the trigger
+        Style {
+            flex_direction: FlexDirection::Column,
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn draw_over(&self, ctx: &mut DrawContext) {
+        let Some((ox, oy)) = self.open_at else {
+            return;
+        };
+
+        let (w, h) = self.menu_size();
+        let (mx, my, _) = place_overlay(
+            (ox, oy, 0.0, 0.0),
+            (w, h),
+            ctx.renderer.viewport_size(),
+            PopoverSide::Bottom,
+            PopoverAlign::Start,
+            0.0,
+        );
+        self.menu_rect.set((mx, my, w, h));
+
+        ctx.renderer.overlay_fill_rect_styled((mx, my, w, h), self.bg, self.border_radius, 1.0, self.border);
+
+        let item_h = self.item_height();
+        for (index, item) in self.items.iter().enumerate() {
+            let iy = my + self.padding + index as f32 * item_h;
+            if self.hover_index == Some(index) {
+                ctx.renderer.overlay_fill_rect_styled(
+                    (mx + self.padding, iy, w - self.padding * 2.0, item_h),
+                    self.hover_bg,
+                    (self.border_radius - self.padding).max(2.0),
+                    0.0,
+                    [0.0; 4],
+                );
+            }
+            ctx.renderer.overlay_draw_text(
+                item,
+                (mx + self.padding + self.item_padding, iy + self.item_padding),
+                self.text_color,
+                (w - (self.padding + self.item_padding) * 2.0, self.metrics.line_height),
+                self.metrics,
+                Align::Left,
+            );
+        }
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        let (cx, cy) = ctx.cursor;
+        match ctx.event {
+            WindowEvent::CursorMoved { .. } if self.is_open() => {
+                self.hover_index = self.item_at(cx, cy);
+                false
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Right,
+                ..
+            } => {
+                let layout = ctx.layout;
+                let own = (layout.location.x, layout.location.y, layout.size.width, layout.size.height);
+                if rect_contains(own, cx, cy) && !self.items.is_empty() {
+                    self.open_at = Some((cx, cy));
+                    self.hover_index = None;
+                    // Placed on the next draw
+                    self.menu_rect.set((0.0, 0.0, 0.0, 0.0));
+                    return true;
+                }
+                false
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } if self.is_open() => {
+                let inside = rect_contains(self.menu_rect.get(), cx, cy);
+                if let Some(index) = self.item_at(cx, cy) {
+                    self.close();
+                    if let Some(handler) = self.on_select.as_mut() {
+                        handler(index);
+                    }
+                    return true;
+                }
+                if !inside {
+                    self.close();
+                }
+                // Presses on the menu's padding don't fall through either
+                inside
+            }
+            _ => false,
+        }
+    }
+
+    fn clear_active(&mut self) {
+        self.close();
+    }
+
+    fn dismiss(&mut self, cursor: (f32, f32)) {
+        let (cx, cy) = cursor;
+        // A press under the menu may reach widgets below it in tree order
+        if !rect_contains(self.menu_rect.get(), cx, cy) {
+            self.close();
+        }
+    }
+
+    fn blocks_child_input(&self) -> bool {
+        self.is_open()
+    }
+}
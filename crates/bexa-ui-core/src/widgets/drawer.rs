@@ -0,0 +1,197 @@
+use std::cell::Cell;
+use std::time::Duration;
+
+use taffy::prelude::*;
+use taffy::Overflow;
+use winit::event::{ElementState, WindowEvent};
+
+use crate::animation::Tween;
+use crate::framework::{DrawContext, EventContext, Widget};
+use crate::signal::Signal;
+
+/// Collapsible sidebar for app shells.
+///
+/// The node's children (typically a navigation list) are always laid out at
+/// the open width; the drawer animates its own width between that and the
+/// rail width, clipping whatever doesn't fit. Put icons first in each row so
+/// they stay visible as an icons-only rail while the labels get clipped
+/// away. A rail width of 0 slides the drawer out entirely, and hides the
+/// children once it is closed.
+///
+/// The children are part of the window tree, so Tab, hover and scrolling
+/// reach them like any other widget.
+///
+/// ```
+/// use bexa_ui_core::{
+///     build_taffy, collect_focus_paths, create_signal, hovered_paths, is_path_interactive, sync_styles, ui,
+///     widget_rect, Button, Drawer, Flex, Metrics,
+/// };
+/// use taffy::prelude::*;
+///
+/// let (open, _set_open) = create_signal(false);
+/// let mut root = ui! {
+///     Flex::row(0.0) => {
+///         Drawer::new(open).with_open_width(220.0).with_rail_width(52.0) => {
+///             Button::new("Dashboard", Metrics::new(14.0, 20.0)),
+///         },
+///     }
+/// };
+/// let mut taffy = TaffyTree::new();
+/// let id = build_taffy(&mut root, &mut taffy);
+/// sync_styles(&mut root, &mut taffy, 800.0, 600.0, true);
+/// taffy.compute_layout(id, Size::MAX_CONTENT).unwrap();
+///
+/// // Collapsed to the rail, the content keeps the open width
+/// assert_eq!(widget_rect(&root, &taffy, &[0]).unwrap().2, 52.0);
+/// assert_eq!(widget_rect(&root, &taffy, &[0, 0]).unwrap().2, 220.0);
+///
+/// // The navigation button is in the window's focus order
+/// let mut focus = Vec::new();
+/// collect_focus_paths(&root, &mut Vec::new(), &mut focus);
+/// assert_eq!(focus, vec![vec![0, 0]]);
+/// assert!(is_path_interactive(&root, &[0, 0]));
+///
+/// // and is hovered on the rail, but not where it is clipped away
+/// assert_eq!(hovered_paths(&root, &taffy, (20.0, 10.0)), vec![vec![], vec![0], vec![0, 0]]);
+/// assert_eq!(hovered_paths(&root, &taffy, (150.0, 10.0)), vec![Vec::<usize>::new()]);
+/// ```
+pub struct Drawer {
+    open: Signal<bool>,
+    open_width: f32,
+    rail_width: f32,
+    // Colors
+    bg: [f32; 4],
+    border: [f32; 4],
+    // State
+    width: Cell<Tween>,
+    was_open: Cell<bool>,
+}
+
+impl Drawer {
+    pub fn new(open: Signal<bool>) -> Self {
+        let open_width = 240.0;
+        let rail_width = 56.0;
+        let is_open = open.get();
+        let start = if is_open { open_width } else { rail_width };
+
+        Self {
+            open,
+            open_width,
+            rail_width,
+            bg: [0.10, 0.12, 0.17, 1.0],
+            border: [0.20, 0.25, 0.35, 1.0],
+            width: Cell::new(Tween::new(start, Duration::from_millis(200))),
+            was_open: Cell::new(is_open),
+        }
+    }
+
+    pub fn with_open_width(mut self, width: f32) -> Self {
+        self.open_width = width;
+        self.reset_width();
+        self
+    }
+
+    /// Width when collapsed; 0 hides the drawer completely.
+    pub fn with_rail_width(mut self, width: f32) -> Self {
+        self.rail_width = width;
+        self.reset_width();
+        self
+    }
+
+    /// Length of the open/collapse animation.
+    pub fn with_duration(self, duration: Duration) -> Self {
+        let mut tween = self.width.get();
+        tween.set_duration(duration);
+        self.width.set(tween);
+        self
+    }
+
+    pub fn with_colors(mut self, bg: [f32; 4], border: [f32; 4]) -> Self {
+        self.bg = bg;
+        self.border = border;
+        self
+    }
+
+    fn target_width(&self, open: bool) -> f32 {
+        if open { self.open_width } else { self.rail_width }
+    }
+
+    fn reset_width(&mut self) {
+        let mut tween = self.width.get();
+        tween.jump_to(self.target_width(self.was_open.get()));
+        self.width.set(tween);
+    }
+
+    /// Current animated width; starts a transition when `open` changed.
+    fn current_width(&self) -> f32 {
+        let open = self.open.get();
+        let mut tween = self.width.get();
+        if open != self.was_open.get() {
+            self.was_open.set(open);
+            tween.set_target(self.target_width(open));
+            self.width.set(tween);
+        }
+        tween.value()
+    }
+}
+
+impl Widget for Drawer {
+    fn style(&self) -> Style {
+        // Height stretches with the parent row. One grid column at the open
+        // width keeps the children there while the drawer itself narrows
+        Style {
+            display: Display::Grid,
+            grid_template_columns: vec![length(self.open_width)],
+            size: Size {
+                width: Dimension::Length(self.current_width()),
+                height: Dimension::Auto,
+            },
+            overflow: taffy::Point {
+                x: Overflow::Hidden,
+                y: Overflow::Hidden,
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawContext) {
+        let layout = ctx.layout;
+        let (x, y, w, h) = (layout.location.x, layout.location.y, layout.size.width, layout.size.height);
+        if self.width.get().is_running() {
+            ctx.renderer.request_next_frame();
+        }
+        if w > 0.0 {
+            ctx.renderer.fill_rect_rounded((x, y, w, h), self.bg, 0.0);
+        }
+    }
+
+    fn draw_over(&self, ctx: &mut DrawContext) {
+        // The border stays on top of the clipped children
+        let layout = ctx.layout;
+        let (x, y, w, h) = (layout.location.x, layout.location.y, layout.size.width, layout.size.height);
+        if w > 0.0 {
+            ctx.renderer.fill_rect_rounded((x + w - 1.0, y, 1.0, h), self.border, 0.0);
+        }
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        // Presses on the drawer background don't fall through to the page
+        let layout = ctx.layout;
+        let (cx, cy) = ctx.cursor;
+        let inside = cx >= layout.location.x
+            && cx < layout.location.x + layout.size.width
+            && cy >= layout.location.y
+            && cy < layout.location.y + layout.size.height;
+        inside && matches!(ctx.event, WindowEvent::MouseInput { state: ElementState::Pressed, .. })
+    }
+
+    fn clip_children(&self) -> Option<f32> {
+        Some(0.0)
+    }
+
+    fn is_child_visible(&self, _index: usize) -> bool {
+        // Slid out entirely, nothing in the drawer can take focus
+        self.current_width() > 0.0
+    }
+}
@@ -45,6 +45,36 @@ impl Flex {
             },
         }
     }
+
+    /// Free space goes between items; the first and last touch the edges.
+    pub fn space_between(mut self) -> Self {
+        self.style.justify_content = Some(JustifyContent::SpaceBetween);
+        self
+    }
+
+    /// Free space goes around each item (half-size gaps at the edges).
+    pub fn space_around(mut self) -> Self {
+        self.style.justify_content = Some(JustifyContent::SpaceAround);
+        self
+    }
+
+    /// Share of the parent's free space, as `Container::with_flex_grow`.
+    pub fn with_flex_grow(mut self, grow: f32) -> Self {
+        self.style.flex_grow = grow;
+        self
+    }
+
+    /// Willingness to shrink on overflow, as `Container::with_flex_shrink`.
+    pub fn with_flex_shrink(mut self, shrink: f32) -> Self {
+        self.style.flex_shrink = shrink;
+        self
+    }
+
+    /// Main-axis size before growing, as `Container::with_flex_basis`.
+    pub fn with_flex_basis(mut self, basis: Dimension) -> Self {
+        self.style.flex_basis = basis;
+        self
+    }
 }
 
 impl Widget for Flex {
@@ -1,10 +1,25 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
 use taffy::prelude::*;
 
 use crate::framework::{DrawContext, Widget};
 use crate::renderer::ImageFit;
 
+/// Picture loaded from a file or from encoded bytes (PNG, JPEG, ...),
+/// decoded once by the render layer and shared by every `Image` showing it.
+/// An image that fails to load draws only its background.
+///
+/// ```ignore
+/// let logo = Image::from_bytes(include_bytes!("../assets/logo.png").as_slice())
+///     .with_size(64.0, 64.0)
+///     .with_fit(ImageFit::Contain);
+/// ```
 pub struct Image {
+    /// The file path, or a key derived from `bytes`.
     path: String,
+    bytes: Option<Arc<[u8]>>,
     style: Style,
     tint: [f32; 4],
     border_radius: f32,
@@ -16,6 +31,7 @@ impl Image {
     pub fn new(path: impl Into<String>) -> Self {
         Self {
             path: path.into(),
+            bytes: None,
             style: Style {
                 size: Size {
                     width: Dimension::Length(120.0),
@@ -31,6 +47,25 @@ impl Image {
         }
     }
 
+    /// Image read from the file at `path`; the same as `new`.
+    pub fn from_path(path: impl Into<String>) -> Self {
+        Self::new(path)
+    }
+
+    /// Image decoded from encoded file contents, e.g. `include_bytes!`.
+    /// Images built from the same bytes share one texture.
+    pub fn from_bytes(bytes: impl Into<Arc<[u8]>>) -> Self {
+        let bytes = bytes.into();
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        // NUL can't appear in a path, so the key never names a file
+        let key = format!("\0bytes:{:016x}", hasher.finish());
+        Self {
+            bytes: Some(bytes),
+            ..Self::new(key)
+        }
+    }
+
     pub fn with_size(mut self, width: f32, height: f32) -> Self {
         self.style.size = Size {
             width: Dimension::Length(width),
@@ -88,6 +123,9 @@ impl Widget for Image {
             ctx.renderer.fill_rect_styled(rect, color, self.border_radius, 0.0, [0.0; 4]);
         }
 
-        ctx.renderer.draw_image(&self.path, rect, self.tint, self.fit);
+        match &self.bytes {
+            Some(bytes) => ctx.renderer.draw_image_bytes(&self.path, bytes, rect, self.tint, self.fit),
+            None => ctx.renderer.draw_image(&self.path, rect, self.tint, self.fit),
+        }
     }
 }
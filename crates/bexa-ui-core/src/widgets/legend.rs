@@ -0,0 +1,261 @@
+use std::cell::{Cell, RefCell};
+
+use glyphon::Metrics;
+use glyphon::cosmic_text::Align;
+use taffy::prelude::*;
+use winit::event::{ElementState, MouseButton, WindowEvent};
+
+use crate::framework::{DrawContext, EventContext, Widget};
+use crate::signal::{SetSignal, Signal};
+
+struct LegendEntry {
+    label: String,
+    color: [f32; 4],
+}
+
+/// Color swatches with labels, laid out in a wrapping row under a chart.
+///
+/// With `with_toggle`, clicking an entry flips its flag in the visibility
+/// signal (indexed like the entries), so a multi-series chart bound to the
+/// same signal can hide and show series. Hidden entries draw dimmed with a
+/// hollow swatch.
+///
+/// ```ignore
+/// let (visible, set_visible) = create_signal(vec![true, true]);
+/// let legend = Legend::new(Metrics::new(12.0, 16.0))
+///     .with_entry("Requests", [0.25, 0.60, 0.85, 1.0])
+///     .with_entry("Errors", [0.85, 0.30, 0.28, 1.0])
+///     .with_toggle(visible, set_visible);
+/// ```
+pub struct Legend {
+    entries: Vec<LegendEntry>,
+    metrics: Metrics,
+    swatch_size: f32,
+    spacing: f32,
+    row_gap: f32,
+    visible: Option<Signal<Vec<bool>>>,
+    set_visible: Option<SetSignal<Vec<bool>>>,
+    // Colors
+    text_color: Option<[u8; 3]>,
+    hover_bg: Option<[f32; 4]>,
+    // State
+    hover: Option<usize>,
+    // Wrapped row count from the last draw; drives the height in `style`
+    rows: Cell<usize>,
+    // Cached absolute entry rects (set during draw)
+    entry_rects: RefCell<Vec<(f32, f32, f32, f32)>>,
+    // Label text command index per entry, for width feedback
+    text_indices: RefCell<Vec<usize>>,
+    text_widths: Vec<f32>,
+}
+
+impl Legend {
+    pub fn new(metrics: Metrics) -> Self {
+        Self {
+            entries: Vec::new(),
+            metrics,
+            swatch_size: 10.0,
+            spacing: 16.0,
+            row_gap: 4.0,
+            visible: None,
+            set_visible: None,
+            text_color: None,
+            hover_bg: None,
+            hover: None,
+            rows: Cell::new(1),
+            entry_rects: RefCell::new(Vec::new()),
+            text_indices: RefCell::new(Vec::new()),
+            text_widths: Vec::new(),
+        }
+    }
+
+    pub fn with_entry(mut self, label: impl Into<String>, color: [f32; 4]) -> Self {
+        self.entries.push(LegendEntry {
+            label: label.into(),
+            color,
+        });
+        self
+    }
+
+    /// Makes entries clickable, toggling `visible[i]` for entry `i`.
+    /// Missing flags count as visible.
+    pub fn with_toggle(mut self, visible: Signal<Vec<bool>>, set_visible: SetSignal<Vec<bool>>) -> Self {
+        self.visible = Some(visible);
+        self.set_visible = Some(set_visible);
+        self
+    }
+
+    /// Horizontal space between entries.
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn with_swatch_size(mut self, size: f32) -> Self {
+        self.swatch_size = size;
+        self
+    }
+
+    pub fn with_text_color(mut self, color: [u8; 3]) -> Self {
+        self.text_color = Some(color);
+        self
+    }
+
+    pub fn with_hover_bg(mut self, color: [f32; 4]) -> Self {
+        self.hover_bg = Some(color);
+        self
+    }
+
+    fn is_visible(&self, index: usize) -> bool {
+        match &self.visible {
+            Some(visible) => visible.with(|v| v.get(index).copied().unwrap_or(true)),
+            None => true,
+        }
+    }
+
+    /// Width of an entry: measured last frame, estimated before that.
+    fn entry_width(&self, index: usize, entry: &LegendEntry) -> f32 {
+        let text_w = match self.text_widths.get(index) {
+            Some(&w) if w > 0.0 => w,
+            _ => entry.label.chars().count() as f32 * self.metrics.font_size * 0.6,
+        };
+        self.swatch_size + 6.0 + text_w
+    }
+
+    fn entry_at(&self, x: f32, y: f32) -> Option<usize> {
+        self.entry_rects
+            .borrow()
+            .iter()
+            .position(|&(rx, ry, rw, rh)| x >= rx && x <= rx + rw && y >= ry && y <= ry + rh)
+    }
+}
+
+impl Widget for Legend {
+    fn style(&self) -> Style {
+        let rows = self.rows.get().max(1) as f32;
+        let height = rows * self.metrics.line_height + (rows - 1.0) * self.row_gap;
+        Style {
+            size: Size {
+                width: Dimension::Percent(1.0),
+                height: Dimension::Length(height),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawContext) {
+        let layout = ctx.layout;
+        let x = layout.location.x;
+        let y = layout.location.y;
+        let w = layout.size.width;
+
+        let text_color = self.text_color.unwrap_or(ctx.theme.text_secondary);
+        let hover_bg = self.hover_bg.unwrap_or(ctx.theme.hover);
+        let line_h = self.metrics.line_height;
+
+        let mut rects = Vec::with_capacity(self.entries.len());
+        let mut text_indices = Vec::with_capacity(self.entries.len());
+        let mut cursor_x = x;
+        let mut cursor_y = y;
+        let mut rows = 1;
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            let ew = self.entry_width(i, entry);
+            // Wrap onto a new row, unless this is already the row start
+            if cursor_x > x && cursor_x + ew > x + w {
+                cursor_x = x;
+                cursor_y += line_h + self.row_gap;
+                rows += 1;
+            }
+            rects.push((cursor_x, cursor_y, ew, line_h));
+
+            if self.hover == Some(i) && self.set_visible.is_some() {
+                ctx.renderer.fill_rect_rounded(
+                    (cursor_x - 4.0, cursor_y, ew + 8.0, line_h),
+                    hover_bg,
+                    4.0,
+                );
+            }
+
+            let shown = self.is_visible(i);
+            let swatch = (
+                cursor_x,
+                cursor_y + (line_h - self.swatch_size) / 2.0,
+                self.swatch_size,
+                self.swatch_size,
+            );
+            if shown {
+                ctx.renderer.fill_rect_rounded(swatch, entry.color, 2.0);
+            } else {
+                ctx.renderer.fill_rect_styled(swatch, [0.0; 4], 2.0, 1.5, entry.color);
+            }
+
+            let color = if shown {
+                text_color
+            } else {
+                [text_color[0] / 2, text_color[1] / 2, text_color[2] / 2]
+            };
+            let chars = entry.label.chars().count();
+            let idx = ctx.renderer.draw_text_measured(
+                &entry.label,
+                (cursor_x + self.swatch_size + 6.0, cursor_y),
+                color,
+                (w, line_h),
+                self.metrics,
+                Align::Left,
+                vec![chars],
+            );
+            text_indices.push(idx);
+
+            cursor_x += ew + self.spacing;
+        }
+
+        self.rows.set(rows);
+        *self.entry_rects.borrow_mut() = rects;
+        *self.text_indices.borrow_mut() = text_indices;
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        if self.set_visible.is_none() {
+            return false;
+        }
+        match ctx.event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.hover = self.entry_at(position.x as f32, position.y as f32);
+                false
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } => {
+                let (Some(idx), Some(set_visible)) = (self.hover, &self.set_visible) else {
+                    return false;
+                };
+                let count = self.entries.len();
+                set_visible.update(|flags| {
+                    if flags.len() < count {
+                        flags.resize(count, true);
+                    }
+                    flags[idx] = !flags[idx];
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn update_measures(&mut self, measures: &[Vec<f32>]) {
+        let indices = self.text_indices.borrow();
+        self.text_widths = indices
+            .iter()
+            .map(|&i| {
+                measures
+                    .get(i)
+                    .and_then(|edges| edges.first().copied())
+                    .unwrap_or(0.0)
+            })
+            .collect();
+    }
+}
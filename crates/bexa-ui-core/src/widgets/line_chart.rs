@@ -0,0 +1,387 @@
+use glyphon::Metrics;
+use glyphon::cosmic_text::Align;
+use taffy::prelude::*;
+use winit::event::WindowEvent;
+
+use crate::framework::{DrawContext, EventContext, Widget};
+use crate::signal::Signal;
+use crate::widgets::bar_chart::{format_compact, AXIS_LABEL_W};
+use crate::widgets::legend::Legend;
+use crate::widgets::popover::PopoverSide;
+use crate::widgets::tooltip::{draw_tooltip_bubble, TooltipStyle};
+
+/// How close (in pixels) the cursor must be to a point to pick it.
+const HOVER_RADIUS: f32 = 24.0;
+
+/// The data point nearest the cursor while drawing.
+struct Nearest {
+    distance_sq: f32,
+    point: (f32, f32),
+    screen: (f32, f32),
+    series: usize,
+}
+
+/// One line of a `LineChart`.
+struct LineSeries {
+    name: String,
+    points: Signal<Vec<(f32, f32)>>,
+    color: [f32; 4],
+}
+
+/// Line chart of one or more series of `(x, y)` points, e.g. metrics over
+/// time with `x` in seconds.
+///
+/// Points are joined in the order given, so keep them sorted by `x`. Lines
+/// are drawn as segments with round joints, so keep colors opaque:
+/// translucent lines show their joints. Both axes fit the data of the
+/// visible series unless pinned with `with_min_value` / `with_max_value`
+/// (y) or `with_x_range`. Hovering near a point draws a crosshair on it and
+/// a tooltip with its value.
+///
+/// ```ignore
+/// let (cpu, set_cpu) = create_signal(vec![(0.0, 12.0), (1.0, 18.5), (2.0, 15.2)]);
+/// let chart = LineChart::new(metrics, 220.0)
+///     .with_series("CPU", cpu, [0.25, 0.60, 0.90, 1.0])
+///     .with_value_formatter(|v| format!("{v:.0}%"));
+/// let legend = chart.legend(Metrics::new(12.0, 16.0));
+/// ```
+pub struct LineChart {
+    series: Vec<LineSeries>,
+    metrics: Metrics,
+    height: f32,
+    padding: f32,
+    line_width: f32,
+    point_radius: f32,
+    // Colors; `None` uses the theme's `border` for the axes and
+    // `text_secondary` for the labels
+    bg: [f32; 4],
+    axis_color: Option<[f32; 4]>,
+    label_color: Option<[u8; 3]>,
+    crosshair_color: [f32; 4],
+    // State
+    hover: Option<(f32, f32)>,
+    visible: Option<Signal<Vec<bool>>>,
+    min_value: Option<f32>,
+    max_value: Option<f32>,
+    x_range: Option<(f32, f32)>,
+    show_grid: bool,
+    grid_lines: usize,
+    formatter: Option<Box<dyn Fn(f32) -> String>>,
+    x_formatter: Option<Box<dyn Fn(f32) -> String>>,
+    tooltip_style: TooltipStyle,
+}
+
+impl LineChart {
+    pub fn new(metrics: Metrics, height: f32) -> Self {
+        Self {
+            series: Vec::new(),
+            metrics,
+            height,
+            padding: 8.0,
+            line_width: 2.0,
+            point_radius: 0.0,
+            bg: [0.0, 0.0, 0.0, 0.0],
+            axis_color: None,
+            label_color: None,
+            crosshair_color: [0.6, 0.65, 0.75, 0.6],
+            hover: None,
+            visible: None,
+            min_value: None,
+            max_value: None,
+            x_range: None,
+            show_grid: true,
+            grid_lines: 4,
+            formatter: None,
+            x_formatter: None,
+            tooltip_style: TooltipStyle::default(),
+        }
+    }
+
+    /// Adds a line through `points`, redrawn whenever they change.
+    pub fn with_series(
+        mut self,
+        name: impl Into<String>,
+        points: Signal<Vec<(f32, f32)>>,
+        color: [f32; 4],
+    ) -> Self {
+        self.series.push(LineSeries {
+            name: name.into(),
+            points,
+            color,
+        });
+        self
+    }
+
+    /// Hides the series whose flag is false, indexed like the series; bind
+    /// it to `Legend::with_toggle` to show and hide lines from the legend.
+    pub fn with_visible_series(mut self, visible: Signal<Vec<bool>>) -> Self {
+        self.visible = Some(visible);
+        self
+    }
+
+    /// A legend of the series' names and colors, to place near the chart.
+    pub fn legend(&self, metrics: Metrics) -> Legend {
+        self.series
+            .iter()
+            .fold(Legend::new(metrics), |legend, s| legend.with_entry(s.name.clone(), s.color))
+    }
+
+    pub fn with_line_width(mut self, width: f32) -> Self {
+        self.line_width = width;
+        self
+    }
+
+    /// Draws a dot of `radius` on every point (none by default).
+    pub fn with_point_radius(mut self, radius: f32) -> Self {
+        self.point_radius = radius;
+        self
+    }
+
+    pub fn with_min_value(mut self, min: f32) -> Self {
+        self.min_value = Some(min);
+        self
+    }
+
+    pub fn with_max_value(mut self, max: f32) -> Self {
+        self.max_value = Some(max);
+        self
+    }
+
+    /// Fixed x axis, e.g. the last 60 seconds; points outside are clipped.
+    pub fn with_x_range(mut self, min: f32, max: f32) -> Self {
+        self.x_range = Some((min, max));
+        self
+    }
+
+    pub fn with_grid(mut self, show: bool, lines: usize) -> Self {
+        self.show_grid = show;
+        self.grid_lines = lines;
+        self
+    }
+
+    /// Formats y tick labels and hovered values, e.g. `|v| format!("{v:.0}%")`.
+    /// Without one, thousands are shortened to "k".
+    pub fn with_value_formatter(mut self, format: impl Fn(f32) -> String + 'static) -> Self {
+        self.formatter = Some(Box::new(format));
+        self
+    }
+
+    /// Formats x tick labels and hovered positions, e.g. `|t| format!("{t:.0}s")`.
+    pub fn with_x_formatter(mut self, format: impl Fn(f32) -> String + 'static) -> Self {
+        self.x_formatter = Some(Box::new(format));
+        self
+    }
+
+    pub fn with_colors(
+        mut self,
+        bg: [f32; 4],
+        axis: [f32; 4],
+        label: [u8; 3],
+        crosshair: [f32; 4],
+    ) -> Self {
+        self.bg = bg;
+        self.axis_color = Some(axis);
+        self.label_color = Some(label);
+        self.crosshair_color = crosshair;
+        self
+    }
+
+    fn format_value(&self, value: f32, k_decimals: usize) -> String {
+        match &self.formatter {
+            Some(format) => format(value),
+            None => format_compact(value, k_decimals),
+        }
+    }
+
+    fn format_x(&self, x: f32) -> String {
+        match &self.x_formatter {
+            Some(format) => format(x),
+            None => format_compact(x, 1),
+        }
+    }
+
+    fn is_visible(&self, index: usize) -> bool {
+        self.visible
+            .as_ref()
+            .is_none_or(|v| v.with(|flags| flags.get(index).copied().unwrap_or(true)))
+    }
+
+    /// X and y ranges of the visible series, with any pinned ends.
+    fn ranges(&self) -> ((f32, f32), (f32, f32)) {
+        let mut x = (f32::MAX, f32::MIN);
+        let mut y = (f32::MAX, f32::MIN);
+        for (i, series) in self.series.iter().enumerate() {
+            if !self.is_visible(i) {
+                continue;
+            }
+            series.points.with(|points| {
+                for &(px, py) in points {
+                    x = (x.0.min(px), x.1.max(px));
+                    y = (y.0.min(py), y.1.max(py));
+                }
+            });
+        }
+        if x.0 > x.1 {
+            x = (0.0, 1.0);
+            y = (0.0, 1.0);
+        }
+        let x = self.x_range.unwrap_or(x);
+        let y = (self.min_value.unwrap_or(y.0), self.max_value.unwrap_or(y.1));
+        let widen = |(lo, hi): (f32, f32)| if hi > lo { (lo, hi) } else { (lo, lo + 1.0) };
+        (widen(x), widen(y))
+    }
+}
+
+impl Widget for LineChart {
+    fn style(&self) -> Style {
+        Style {
+            size: Size {
+                width: Dimension::Percent(1.0),
+                height: Dimension::Length(self.height),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawContext) {
+        let theme = ctx.theme;
+        let axis_color = self.axis_color.unwrap_or(theme.border);
+        let label_color = self.label_color.unwrap_or(theme.text_secondary);
+        let layout = ctx.layout;
+        let x = layout.location.x;
+        let y = layout.location.y;
+        let w = layout.size.width;
+        let h = layout.size.height;
+
+        // Background
+        if self.bg[3] > 0.0 {
+            ctx.renderer.fill_rect_rounded((x, y, w, h), self.bg, 0.0);
+        }
+
+        let small_metrics = Metrics::new(
+            self.metrics.font_size * 0.7,
+            self.metrics.line_height * 0.7,
+        );
+        let chart_top = y + self.padding + small_metrics.line_height * 0.5;
+        let chart_bottom = y + h - self.padding - small_metrics.line_height - 4.0;
+        let chart_h = (chart_bottom - chart_top).max(10.0);
+        let chart_left = x + self.padding + AXIS_LABEL_W;
+        // Room for the last x label to sit centered on the right edge
+        let chart_right = x + w - self.padding - AXIS_LABEL_W / 2.0;
+        let chart_w = (chart_right - chart_left).max(10.0);
+
+        let ((x_min, x_max), (y_min, y_max)) = self.ranges();
+        let to_screen = |(px, py): (f32, f32)| {
+            (
+                chart_left + (px - x_min) / (x_max - x_min) * chart_w,
+                chart_bottom - (py - y_min) / (y_max - y_min) * chart_h,
+            )
+        };
+
+        // Grid lines, y labels on the left and x labels underneath
+        if self.show_grid && self.grid_lines > 0 {
+            let grid_color = [axis_color[0], axis_color[1], axis_color[2], 0.3];
+            for i in 0..=self.grid_lines {
+                let frac = i as f32 / self.grid_lines as f32;
+                let gy = chart_bottom - frac * chart_h;
+                ctx.renderer.fill_rect_rounded((chart_left, gy, chart_w, 0.5), grid_color, 0.0);
+                let val_str = self.format_value(y_min + frac * (y_max - y_min), 0);
+                ctx.renderer.draw_text(
+                    &val_str,
+                    (x + self.padding, gy - small_metrics.line_height * 0.5),
+                    label_color,
+                    (AXIS_LABEL_W - 4.0, small_metrics.line_height),
+                    small_metrics,
+                    Align::Right,
+                );
+
+                let gx = chart_left + frac * chart_w;
+                ctx.renderer.fill_rect_rounded((gx, chart_top, 0.5, chart_h), grid_color, 0.0);
+                let x_str = self.format_x(x_min + frac * (x_max - x_min));
+                ctx.renderer.draw_text(
+                    &x_str,
+                    (gx - AXIS_LABEL_W / 2.0, chart_bottom + 4.0),
+                    label_color,
+                    (AXIS_LABEL_W, small_metrics.line_height),
+                    small_metrics,
+                    Align::Center,
+                );
+            }
+        }
+
+        // Bottom axis
+        ctx.renderer.fill_rect_rounded((chart_left, chart_bottom, chart_w, 1.0), axis_color, 0.0);
+
+        // Lines, clipped to the plot, tracking the point nearest the cursor
+        let mut nearest: Option<Nearest> = None;
+        ctx.renderer.push_clip_within((chart_left, chart_top - self.line_width, chart_w, chart_h + self.line_width * 2.0));
+        for (si, series) in self.series.iter().enumerate() {
+            if !self.is_visible(si) {
+                continue;
+            }
+            series.points.with(|points| {
+                let screen: Vec<(f32, f32)> = points.iter().map(|&p| to_screen(p)).collect();
+                for pair in screen.windows(2) {
+                    ctx.renderer.draw_line(pair[0], pair[1], self.line_width, series.color);
+                }
+                // Dots, or round joints so bends don't show notches
+                for (i, &(sx, sy)) in screen.iter().enumerate() {
+                    let interior = i > 0 && i + 1 < screen.len();
+                    let r = if self.point_radius > 0.0 {
+                        self.point_radius
+                    } else if interior {
+                        self.line_width / 2.0
+                    } else {
+                        continue;
+                    };
+                    ctx.renderer.fill_rect_rounded((sx - r, sy - r, r * 2.0, r * 2.0), series.color, r);
+                }
+
+                if let Some((cx, cy)) = self.hover {
+                    for (&point, &sp) in points.iter().zip(&screen) {
+                        let d = (sp.0 - cx).powi(2) + (sp.1 - cy).powi(2);
+                        if d <= HOVER_RADIUS * HOVER_RADIUS && nearest.as_ref().is_none_or(|n| d < n.distance_sq) {
+                            nearest = Some(Nearest { distance_sq: d, point, screen: sp, series: si });
+                        }
+                    }
+                }
+            });
+        }
+        ctx.renderer.pop_clip();
+
+        // Crosshair and value of the hovered point
+        if let Some(Nearest { point: (px, py), screen: (sx, sy), series: si, .. }) = nearest {
+            ctx.renderer.fill_rect_rounded((sx - 0.5, chart_top, 1.0, chart_h), self.crosshair_color, 0.0);
+            ctx.renderer.fill_rect_rounded((chart_left, sy - 0.5, chart_w, 1.0), self.crosshair_color, 0.0);
+            let r = self.point_radius.max(self.line_width) + 2.0;
+            let color = self.series[si].color;
+            ctx.renderer.fill_rect_styled((sx - r, sy - r, r * 2.0, r * 2.0), color, r, 2.0, [1.0, 1.0, 1.0, 0.9]);
+
+            let text = format!("{}: {}", self.series[si].name, self.format_value(py, 1));
+            let anchor = (sx - r, sy - r, r * 2.0, r * 2.0);
+            draw_tooltip_bubble(ctx.renderer, anchor, &text, &self.format_x(px), PopoverSide::Top, 6.0, &self.tooltip_style);
+        }
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        let layout = ctx.layout;
+        match ctx.event {
+            WindowEvent::CursorMoved { position, .. } => {
+                let px = position.x as f32;
+                let py = position.y as f32;
+                let inside = px >= layout.location.x
+                    && px <= layout.location.x + layout.size.width
+                    && py >= layout.location.y
+                    && py <= layout.location.y + layout.size.height;
+                self.hover = inside.then_some((px, py));
+                false // don't consume — let siblings update hover too
+            }
+            WindowEvent::CursorLeft { .. } => {
+                self.hover = None;
+                false
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use taffy::prelude::*;
+use winit::event::WindowEvent;
+
+use crate::framework::{DrawContext, EventContext, Widget};
+use crate::signal::Signal;
+use crate::tree::WidgetNode;
+use crate::widgets::spinner::Spinner;
+
+/// Dims a subtree and shows a spinner over it while a signal is true.
+///
+/// Wraps its children (usually a single panel). While loading, a scrim and
+/// a centered `Spinner` are drawn over the children's area, and the children
+/// get no clicks, wheel, or keyboard focus; presses on the covered area are
+/// swallowed. Unlike `Modal`, only this subtree is covered, so the rest of
+/// the window stays usable.
+///
+/// ```ignore
+/// let (loading, set_loading) = create_signal(true);
+/// let panel = loading_overlay(loading, servers_table);
+/// // later, when the data has arrived
+/// set_loading.set(false);
+/// ```
+pub struct LoadingOverlay {
+    loading: Signal<bool>,
+    spinner: Spinner,
+    grow: f32,
+    border_radius: f32,
+    // Colors
+    scrim: [f32; 4],
+}
+
+impl LoadingOverlay {
+    pub fn new(loading: Signal<bool>) -> Self {
+        Self {
+            loading,
+            spinner: Spinner::new().with_size(28.0),
+            grow: 0.0,
+            border_radius: 0.0,
+            scrim: [0.0, 0.0, 0.0, 0.45],
+        }
+    }
+
+    /// Replaces the default spinner, e.g. to change its size or color.
+    pub fn with_spinner(mut self, spinner: Spinner) -> Self {
+        self.spinner = spinner;
+        self
+    }
+
+    pub fn with_scrim(mut self, color: [f32; 4]) -> Self {
+        self.scrim = color;
+        self
+    }
+
+    /// Rounds the scrim's corners to match a rounded child.
+    pub fn with_border_radius(mut self, radius: f32) -> Self {
+        self.border_radius = radius;
+        self
+    }
+
+    /// Flex grow of the wrapper, for children that should fill their parent.
+    pub fn with_grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading.get()
+    }
+}
+
+impl Widget for LoadingOverlay {
+    fn style(&self) -> Style {
+        Style {
+            flex_direction: FlexDirection::Column,
+            flex_grow: self.grow,
+            size: Size {
+                width: Dimension::Percent(1.0),
+                height: Dimension::Auto,
+            },
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, _ctx: &mut DrawContext) {}
+
+    fn draw_over(&self, ctx: &mut DrawContext) {
+        if !self.is_loading() {
+            return;
+        }
+        let layout = ctx.layout;
+        let (x, y) = (layout.location.x, layout.location.y);
+        let (w, h) = (layout.size.width, layout.size.height);
+
+        ctx.renderer.fill_rect_rounded((x, y, w, h), self.scrim, self.border_radius);
+        self.spinner.paint(ctx.renderer, (x + w / 2.0, y + h / 2.0));
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        if !self.is_loading() {
+            return false;
+        }
+        let layout = ctx.layout;
+        let (cx, cy) = ctx.cursor;
+        let inside = cx >= layout.location.x
+            && cx <= layout.location.x + layout.size.width
+            && cy >= layout.location.y
+            && cy <= layout.location.y + layout.size.height;
+        // Keep pointer input from reaching whatever is behind the scrim
+        inside && matches!(ctx.event, WindowEvent::MouseInput { .. } | WindowEvent::MouseWheel { .. })
+    }
+
+    fn blocks_child_input(&self) -> bool {
+        self.is_loading()
+    }
+}
+
+/// Convenience: a `LoadingOverlay` node covering `child` while `loading`.
+pub fn loading_overlay(loading: Signal<bool>, child: WidgetNode) -> WidgetNode {
+    WidgetNode::new(LoadingOverlay::new(loading), vec![child])
+}
@@ -0,0 +1,64 @@
+use std::cell::Cell;
+
+use glyphon::Metrics;
+use glyphon::cosmic_text::Align;
+
+use crate::renderer::Renderer;
+
+/// Label text beside a control (checkbox, radio, toggle) that sizes the
+/// widget by its measured width.
+///
+/// The width comes from the text measures of the previous frame; before the
+/// first one it is estimated from the character count.
+pub(crate) struct MeasuredLabel {
+    text: String,
+    /// Text command index of the last draw, to find its measures
+    cmd: Cell<Option<usize>>,
+    width: f32,
+}
+
+impl MeasuredLabel {
+    pub(crate) fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            cmd: Cell::new(None),
+            width: 0.0,
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Label width: measured last frame, estimated before that.
+    pub(crate) fn width(&self, metrics: Metrics) -> f32 {
+        if self.width > 0.0 {
+            self.width
+        } else {
+            self.text.chars().count() as f32 * metrics.font_size * 0.6
+        }
+    }
+
+    /// Draws the text left-aligned at `pos`, in `avail_w` or the label
+    /// width if that is wider, and measures it for the next frame.
+    pub(crate) fn draw(&self, renderer: &mut Renderer, pos: (f32, f32), avail_w: f32, color: [u8; 3], metrics: Metrics) {
+        let idx = renderer.draw_text_measured(
+            &self.text,
+            pos,
+            color,
+            (avail_w.max(self.width(metrics)), metrics.line_height),
+            metrics,
+            Align::Left,
+            vec![self.text.chars().count()],
+        );
+        self.cmd.set(Some(idx));
+    }
+
+    /// Picks up the width measured for the last draw.
+    pub(crate) fn update_measures(&mut self, measures: &[Vec<f32>]) {
+        let measured = self.cmd.get().and_then(|idx| measures.get(idx)?.first().copied());
+        if let Some(width) = measured {
+            self.width = width;
+        }
+    }
+}
@@ -1,20 +1,33 @@
 mod bar_chart;
 mod button;
+mod canvas;
 mod checkbox;
 mod container;
+mod context_menu;
+mod drawer;
 mod flex;
 mod icon;
 mod image;
 mod label;
+mod legend;
+mod line_chart;
+mod loading_overlay;
+mod measured_label;
 mod modal;
+mod popover;
 mod radio;
+mod router;
 mod scroll_view;
 mod select;
 mod slider;
+mod spacer;
+mod spinner;
+mod status_bar;
 mod table;
 mod tabs;
 mod text_input;
 mod toggle;
+mod toolbar;
 mod tooltip;
 mod tree_view;
 
@@ -22,23 +35,35 @@ mod tree_view;
 #[allow(dead_code)]
 mod terminal;
 
-pub use bar_chart::{Bar, BarChart};
+pub use bar_chart::{Bar, BarChart, BarMode};
 pub use button::Button;
+pub use canvas::Canvas;
 pub use checkbox::Checkbox;
 pub use container::Container;
+pub use context_menu::ContextMenu;
+pub use drawer::Drawer;
 pub use flex::Flex;
 pub use icon::Icon;
 pub use image::Image;
 pub use label::Label;
+pub use legend::Legend;
+pub use line_chart::LineChart;
+pub use loading_overlay::{loading_overlay, LoadingOverlay};
 pub use modal::Modal;
-pub use radio::{RadioButton, radio_group};
+pub use popover::{place_overlay, popover, AnchorRect, Popover, PopoverAlign, PopoverSide};
+pub use radio::{RadioButton, RadioGroup, radio_group};
+pub use router::{router, Router};
 pub use scroll_view::ScrollView;
 pub use select::Select;
 pub use slider::Slider;
-pub use table::{Column, Table};
-pub use tabs::Tabs;
-pub use text_input::TextInput;
+pub use spacer::Spacer;
+pub use spinner::Spinner;
+pub use status_bar::{StatusBar, StatusSegment};
+pub use table::{CellContent, Column, Table};
+pub use tabs::{tab_view, Tabs};
+pub use text_input::{CaretStyle, TextInput};
 pub use toggle::Toggle;
+pub use toolbar::{Toolbar, ToolbarOrientation};
 pub use tooltip::{Tooltip, TooltipPosition};
 pub use tree_view::{TreeNode, TreeView};
 
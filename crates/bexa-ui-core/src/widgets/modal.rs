@@ -10,6 +10,26 @@ use crate::framework::{DrawContext, EventContext, Widget};
 use crate::icons;
 use crate::signal::{Signal, SetSignal};
 
+const PADDING: f32 = 20.0;
+
+/// Dialog centered over a dimmed backdrop, drawn in the overlay layer.
+///
+/// The panel shows the title and body lines, then lays out the node's
+/// children in a column below them, so a dialog can hold buttons or a form.
+/// Children are hidden while the modal is closed. Place the modal first
+/// among its siblings so its children see pointer events before the widgets
+/// behind it. While open, Tab cycles focus through the children only, and
+/// focus returns to where it was once the modal closes.
+///
+/// ```ignore
+/// let (open, set_open) = create_signal(false);
+/// let mut cancel = Button::new("Cancel", metrics);
+/// cancel.set_on_click(move || set_open.set(false));
+/// let dialog = WidgetNode::new(
+///     Modal::new(open, set_open).with_title("Discard changes?"),
+///     vec![WidgetNode::new(cancel, vec![])],
+/// );
+/// ```
 pub struct Modal {
     open: Signal<bool>,
     set_open: SetSignal<bool>,
@@ -27,9 +47,17 @@ pub struct Modal {
     close_color: [u8; 3],
     border_radius: f32,
     close_on_backdrop: bool,
+    gap: f32,
     // Cached viewport for rendering
     viewport_w: Cell<f32>,
     viewport_h: Cell<f32>,
+    // Panel placement: the parent's absolute origin and the panel height as
+    // last laid out, and the inset `style` asked for relative to the parent
+    origin: Cell<(f32, f32)>,
+    panel_h: Cell<f32>,
+    inset: Cell<(f32, f32)>,
+    // True while the panel is not where `style` will put it next frame
+    unsettled: Cell<bool>,
 }
 
 impl Modal {
@@ -50,8 +78,13 @@ impl Modal {
             close_color: [160, 165, 180],
             border_radius: 10.0,
             close_on_backdrop: true,
+            gap: 12.0,
             viewport_w: Cell::new(800.0),
             viewport_h: Cell::new(600.0),
+            origin: Cell::new((0.0, 0.0)),
+            panel_h: Cell::new(0.0),
+            inset: Cell::new((0.0, 0.0)),
+            unsettled: Cell::new(false),
         }
     }
 
@@ -84,35 +117,94 @@ impl Modal {
         self
     }
 
+    /// Whether a press on the backdrop, outside the panel, closes the modal.
+    /// Presses on the panel never do, except on its close X.
     pub fn with_close_on_backdrop(mut self, close: bool) -> Self {
         self.close_on_backdrop = close;
         self
     }
+
+    /// Space between the child widgets.
+    pub fn with_gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Height of the title and body lines above the children.
+    fn header_height(&self) -> f32 {
+        let title_h = if self.title.is_empty() { 0.0 } else { self.title_metrics.line_height + 16.0 };
+        title_h + self.body_lines.len() as f32 * (self.metrics.line_height + 4.0)
+    }
+
+    /// Rect of the close X in the top-right corner of a panel at `origin`.
+    fn close_button_rect(origin: (f32, f32), width: f32) -> (f32, f32, f32, f32) {
+        (origin.0 + width - PADDING - 12.0, origin.1 + PADDING * 0.5, 16.0, 20.0)
+    }
+
+    /// Absolute top-left of a panel `height` tall, centered in the viewport.
+    fn panel_origin(&self, height: f32) -> (f32, f32) {
+        let vw = self.viewport_w.get().max(800.0);
+        let vh = self.viewport_h.get().max(600.0);
+        ((vw - self.width) / 2.0, ((vh - height) / 2.0).max(0.0))
+    }
 }
 
 impl Widget for Modal {
     fn style(&self) -> Style {
-        // Modal takes no layout space — it renders entirely in the overlay layer
+        if !self.open.get() {
+            // Modal takes no layout space — it renders entirely in the overlay layer
+            return Style {
+                size: Size {
+                    width: Dimension::Length(0.0),
+                    height: Dimension::Length(0.0),
+                },
+                ..Default::default()
+            };
+        }
+
+        // Open, the node is the panel itself: taken out of the flow and
+        // placed at the viewport center, which is relative to the parent
+        // origin seen in the last draw
+        let (x, y) = self.panel_origin(self.panel_h.get());
+        let (ox, oy) = self.origin.get();
+        let inset = (x - ox, y - oy);
+        self.inset.set(inset);
         Style {
+            position: Position::Absolute,
+            inset: Rect {
+                left: LengthPercentageAuto::Length(inset.0),
+                top: LengthPercentageAuto::Length(inset.1),
+                right: LengthPercentageAuto::Auto,
+                bottom: LengthPercentageAuto::Auto,
+            },
             size: Size {
-                width: Dimension::Length(0.0),
-                height: Dimension::Length(0.0),
+                width: Dimension::Length(self.width),
+                height: Dimension::Auto,
+            },
+            flex_direction: FlexDirection::Column,
+            gap: Size {
+                width: LengthPercentage::Length(0.0),
+                height: LengthPercentage::Length(self.gap),
+            },
+            padding: Rect {
+                left: LengthPercentage::Length(PADDING),
+                right: LengthPercentage::Length(PADDING),
+                top: LengthPercentage::Length(PADDING + self.header_height()),
+                bottom: LengthPercentage::Length(PADDING),
             },
             ..Default::default()
         }
     }
 
     fn draw(&self, ctx: &mut DrawContext) {
-        // Cache the root layout dimensions as viewport estimate
-        // The layout.location gives us the widget's own position, but we need
-        // to draw over the entire viewport. We use a large enough backdrop.
+        // Cache the viewport for centering; until the renderer knows it the
+        // defaults stand in. We use a large enough backdrop either way.
         let layout = ctx.layout;
-        // Prefer renderer viewport size when available
         let (vw, vh) = ctx.renderer.viewport_size();
-        let est_w = if vw > 0.0 { vw } else { layout.location.x + layout.size.width };
-        let est_h = if vh > 0.0 { vh } else { layout.location.y + layout.size.height };
-        self.viewport_w.set(est_w);
-        self.viewport_h.set(est_h);
+        if vw > 0.0 && vh > 0.0 {
+            self.viewport_w.set(vw);
+            self.viewport_h.set(vh);
+        }
 
         if !self.open.get() {
             return;
@@ -131,19 +223,26 @@ impl Widget for Modal {
             [0.0; 4],
         );
 
-        // Calculate modal dimensions
-        let modal_w = self.width;
-        let title_h = if self.title.is_empty() { 0.0 } else { self.title_metrics.line_height + 16.0 };
-        let body_h = self.body_lines.len() as f32 * (self.metrics.line_height + 4.0);
-        let padding = 20.0;
-        let close_btn_h = 20.0;
-        let modal_h = title_h + body_h + padding * 2.0 + close_btn_h;
+        // The panel is this node's layout. When the viewport, the parent or
+        // the children's height moved it off center, the next layout puts it
+        // back; skip this frame rather than show it misplaced
+        let (mx, my) = (layout.location.x, layout.location.y);
+        let (modal_w, modal_h) = (layout.size.width, layout.size.height);
+        let (ix, iy) = self.inset.get();
+        self.origin.set((mx - ix, my - iy));
+        self.panel_h.set(modal_h);
+        let (tx, ty) = self.panel_origin(modal_h);
+        let unsettled = (tx - mx).abs() > 0.5 || (ty - my).abs() > 0.5;
+        self.unsettled.set(unsettled);
 
-        // Center in viewport (use cached values, with fallback)
-        let est_vw = self.viewport_w.get().max(800.0);
-        let est_vh = self.viewport_h.get().max(600.0);
-        let mx = (est_vw - modal_w) / 2.0;
-        let my = (est_vh - modal_h) / 2.0;
+        if unsettled {
+            // Children still draw until `draw_over`; clip them all away
+            ctx.renderer.begin_overlay();
+            ctx.renderer.push_clip((0.0, 0.0, 0.0, 0.0));
+            ctx.renderer.request_next_frame();
+            return;
+        }
+        let padding = PADDING;
 
         // Modal background
         ctx.renderer.overlay_fill_rect_styled(
@@ -155,14 +254,13 @@ impl Widget for Modal {
         );
 
         // Close X button (top-right)
-        let close_x = mx + modal_w - padding - 12.0;
-        let close_y = my + padding * 0.5;
+        let (close_x, close_y, close_w, close_h) = Self::close_button_rect((mx, my), modal_w);
         let close_metrics = Metrics::new(14.0, 20.0);
         ctx.renderer.overlay_draw_text_with_font(
             icons::CLOSE,
             (close_x, close_y),
             self.close_color,
-            (16.0, 20.0),
+            (close_w, close_h),
             close_metrics,
             Align::Center,
             icons::NERD_FONT_FAMILY,
@@ -203,6 +301,19 @@ impl Widget for Modal {
             );
             cy += self.metrics.line_height + 4.0;
         }
+
+        // Children draw on the panel, until `draw_over`
+        ctx.renderer.begin_overlay();
+    }
+
+    fn draw_over(&self, ctx: &mut DrawContext) {
+        if !self.open.get() {
+            return;
+        }
+        if self.unsettled.get() {
+            ctx.renderer.pop_clip();
+        }
+        ctx.renderer.end_overlay();
     }
 
     fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
@@ -210,21 +321,24 @@ impl Widget for Modal {
             return false;
         }
 
-        match ctx.event {
-            WindowEvent::MouseInput {
-                state: ElementState::Pressed,
-                button: MouseButton::Left,
-                ..
-            } => {
-                if self.close_on_backdrop {
-                    // Close on any click when modal is open
-                    // (A more precise check would test if click is outside the modal rect,
-                    //  but since the modal is overlay-rendered, any click effectively interacts with it)
-                    self.set_open.set(false);
-                    return true;
-                }
+        if let WindowEvent::MouseInput {
+            state: ElementState::Pressed,
+            button: MouseButton::Left,
+            ..
+        } = ctx.event
+        {
+            // The layout is the panel, as drawn; presses on it stay in the dialog
+            let layout = ctx.layout;
+            let (cx, cy) = ctx.cursor;
+            let on_panel = cx >= layout.location.x
+                && cx <= layout.location.x + layout.size.width
+                && cy >= layout.location.y
+                && cy <= layout.location.y + layout.size.height;
+            let (bx, by, bw, bh) = Self::close_button_rect((layout.location.x, layout.location.y), layout.size.width);
+            let on_close = cx >= bx && cx <= bx + bw && cy >= by && cy <= by + bh;
+            if on_close || (self.close_on_backdrop && !on_panel) {
+                self.set_open.set(false);
             }
-            _ => {}
         }
 
         // Consume all events when modal is open to prevent interaction with widgets below
@@ -237,17 +351,37 @@ impl Widget for Modal {
         }
 
         if event.state == ElementState::Pressed {
-            if let Key::Named(NamedKey::Escape) = &event.logical_key {
-                self.set_open.set(false);
-                return true;
+            match &event.logical_key {
+                Key::Named(NamedKey::Escape) => {
+                    self.set_open.set(false);
+                    return true;
+                }
+                // Moves focus on to the children
+                Key::Named(NamedKey::Tab) => return false,
+                _ => {}
             }
         }
 
-        // Consume all key events when modal is open
+        // Consume all other key events when modal is open
         true
     }
 
     fn is_focusable(&self) -> bool {
         true
     }
+
+    fn clear_active(&mut self) {
+        // Escape left unconsumed by a focused child closes the modal too
+        if self.open.get() {
+            self.set_open.set(false);
+        }
+    }
+
+    fn is_child_visible(&self, _index: usize) -> bool {
+        self.open.get()
+    }
+
+    fn traps_focus(&self) -> bool {
+        self.open.get()
+    }
 }
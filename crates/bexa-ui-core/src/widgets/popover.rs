@@ -0,0 +1,400 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use taffy::prelude::*;
+use winit::event::{ElementState, WindowEvent};
+
+use crate::framework::{DrawContext, EventContext, Widget};
+use crate::signal::{Signal, SetSignal};
+use crate::tree::WidgetNode;
+
+/// Rect `(x, y, width, height)` in absolute pixel coords.
+pub type AnchorRect = (f32, f32, f32, f32);
+
+/// Preferred side of the anchor an overlay opens on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PopoverSide {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl PopoverSide {
+    fn opposite(self) -> Self {
+        match self {
+            PopoverSide::Top => PopoverSide::Bottom,
+            PopoverSide::Bottom => PopoverSide::Top,
+            PopoverSide::Left => PopoverSide::Right,
+            PopoverSide::Right => PopoverSide::Left,
+        }
+    }
+}
+
+/// Alignment of the overlay along the anchor edge.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PopoverAlign {
+    Start,
+    Center,
+    End,
+}
+
+/// Computes where an overlay of `size` goes next to `anchor`.
+///
+/// Flips to the opposite side when the preferred one would overflow the
+/// viewport (and the opposite fits), then clamps along the cross axis so the
+/// overlay stays on screen. Returns `(x, y, side_used)`.
+/// A zero viewport (not known yet) disables flipping and clamping.
+///
+/// ```
+/// use bexa_ui_core::{place_overlay, PopoverAlign, PopoverSide};
+///
+/// let viewport = (800.0, 600.0);
+/// let size = (200.0, 100.0);
+/// let place = |anchor, side, align| place_overlay(anchor, size, viewport, side, align, 4.0);
+///
+/// // Too close to the bottom edge: flips above the anchor
+/// let near_bottom = (100.0, 550.0, 80.0, 30.0);
+/// assert_eq!(place(near_bottom, PopoverSide::Bottom, PopoverAlign::Start), (100.0, 446.0, PopoverSide::Top));
+///
+/// // And below it near the top edge
+/// let near_top = (100.0, 10.0, 80.0, 30.0);
+/// assert_eq!(place(near_top, PopoverSide::Top, PopoverAlign::Start), (100.0, 44.0, PopoverSide::Bottom));
+///
+/// // Clamped to the right and left edges along the cross axis
+/// let near_right = (750.0, 100.0, 40.0, 30.0);
+/// assert_eq!(place(near_right, PopoverSide::Bottom, PopoverAlign::Start), (600.0, 134.0, PopoverSide::Bottom));
+/// let near_left = (0.0, 100.0, 40.0, 30.0);
+/// assert_eq!(place(near_left, PopoverSide::Bottom, PopoverAlign::Center), (0.0, 134.0, PopoverSide::Bottom));
+///
+/// // Before the viewport is known, the preferred side is used as is
+/// assert_eq!(
+///     place_overlay(near_bottom, size, (0.0, 0.0), PopoverSide::Bottom, PopoverAlign::Center, 4.0),
+///     (40.0, 584.0, PopoverSide::Bottom)
+/// );
+/// ```
+pub fn place_overlay(
+    anchor: AnchorRect,
+    size: (f32, f32),
+    viewport: (f32, f32),
+    side: PopoverSide,
+    align: PopoverAlign,
+    gap: f32,
+) -> (f32, f32, PopoverSide) {
+    let (ax, ay, aw, ah) = anchor;
+    let (w, h) = size;
+    let (vw, vh) = viewport;
+    let known = vw > 0.0 && vh > 0.0;
+
+    let fits = |s: PopoverSide| match s {
+        PopoverSide::Top => ay - gap - h >= 0.0,
+        PopoverSide::Bottom => ay + ah + gap + h <= vh,
+        PopoverSide::Left => ax - gap - w >= 0.0,
+        PopoverSide::Right => ax + aw + gap + w <= vw,
+    };
+
+    let side = if !known || fits(side) || !fits(side.opposite()) {
+        side
+    } else {
+        side.opposite()
+    };
+
+    let aligned_x = match align {
+        PopoverAlign::Start => ax,
+        PopoverAlign::Center => ax + (aw - w) / 2.0,
+        PopoverAlign::End => ax + aw - w,
+    };
+    let aligned_y = match align {
+        PopoverAlign::Start => ay,
+        PopoverAlign::Center => ay + (ah - h) / 2.0,
+        PopoverAlign::End => ay + ah - h,
+    };
+
+    let (mut x, mut y) = match side {
+        PopoverSide::Top => (aligned_x, ay - gap - h),
+        PopoverSide::Bottom => (aligned_x, ay + ah + gap),
+        PopoverSide::Left => (ax - gap - w, aligned_y),
+        PopoverSide::Right => (ax + aw + gap, aligned_y),
+    };
+
+    if known {
+        match side {
+            PopoverSide::Top | PopoverSide::Bottom => x = x.min(vw - w).max(0.0),
+            PopoverSide::Left | PopoverSide::Right => y = y.min(vh - h).max(0.0),
+        }
+    }
+
+    (x, y, side)
+}
+
+/// Overlay panel anchored to a target, rendering arbitrary widget content.
+///
+/// Build the node with `popover`: it wraps the trigger and anchors to the
+/// trigger's rect, or to an explicit rect set with `with_anchor` (e.g. from
+/// `widget_rect`). The content sits in the same tree, in a panel shown
+/// while open, so focus, hover and scrolling reach it like the rest of the
+/// window; in focus order it comes right after the trigger.
+///
+/// ```
+/// use bexa_ui_core::{build_taffy, collect_focus_paths, create_signal, is_path_interactive, popover, Button, Metrics, Popover, WidgetNode};
+///
+/// let metrics = Metrics::new(14.0, 20.0);
+/// let (open, set_open) = create_signal(false);
+/// let mut root = popover(
+///     Popover::new(open, set_open.clone()),
+///     WidgetNode::new(Button::new("Filters", metrics), vec![]),
+///     WidgetNode::new(Button::new("Apply", metrics), vec![]),
+/// );
+/// let mut taffy = taffy::TaffyTree::new();
+/// build_taffy(&mut root, &mut taffy);
+///
+/// // Tab reaches the trigger, then the content once the popover is open
+/// let mut focus = Vec::new();
+/// collect_focus_paths(&root, &mut Vec::new(), &mut focus);
+/// assert_eq!(focus, vec![vec![0], vec![1, 0]]);
+/// assert!(!is_path_interactive(&root, &[1, 0]));
+/// set_open.set(true);
+/// assert!(is_path_interactive(&root, &[1, 0]));
+/// ```
+pub struct Popover {
+    open: Signal<bool>,
+    set_open: SetSignal<bool>,
+    anchor: Option<Signal<Option<AnchorRect>>>,
+    close_on_outside_click: bool,
+    panel: PanelStyle,
+    // Absolute rects, shared with the panel node (set during draw)
+    anchor_rect: Rc<Cell<AnchorRect>>,
+    popup_rect: Rc<Cell<AnchorRect>>,
+}
+
+/// Placement and look of the panel, set through the `Popover` builders.
+#[derive(Clone, Copy)]
+struct PanelStyle {
+    side: PopoverSide,
+    align: PopoverAlign,
+    gap: f32,
+    padding: f32,
+    width: Option<f32>,
+    border_radius: f32,
+    bg: [f32; 4],
+    border: [f32; 4],
+}
+
+impl Popover {
+    pub fn new(open: Signal<bool>, set_open: SetSignal<bool>) -> Self {
+        Self {
+            open,
+            set_open,
+            anchor: None,
+            close_on_outside_click: true,
+            panel: PanelStyle {
+                side: PopoverSide::Bottom,
+                align: PopoverAlign::Start,
+                gap: 4.0,
+                padding: 8.0,
+                width: None,
+                border_radius: 6.0,
+                bg: [0.12, 0.14, 0.20, 0.98],
+                border: [0.35, 0.40, 0.55, 1.0],
+            },
+            anchor_rect: Rc::new(Cell::new((0.0, 0.0, 0.0, 0.0))),
+            popup_rect: Rc::new(Cell::new((0.0, 0.0, 0.0, 0.0))),
+        }
+    }
+
+    pub fn with_placement(mut self, side: PopoverSide, align: PopoverAlign) -> Self {
+        self.panel.side = side;
+        self.panel.align = align;
+        self
+    }
+
+    /// Anchor to an explicit absolute rect instead of the popover's own layout.
+    /// `None` falls back to the own layout rect.
+    pub fn with_anchor(mut self, anchor: Signal<Option<AnchorRect>>) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    pub fn with_gap(mut self, gap: f32) -> Self {
+        self.panel.gap = gap;
+        self
+    }
+
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.panel.padding = padding;
+        self
+    }
+
+    /// Fixed panel width; by default the panel sizes to its content.
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.panel.width = Some(width);
+        self
+    }
+
+    pub fn with_border_radius(mut self, radius: f32) -> Self {
+        self.panel.border_radius = radius;
+        self
+    }
+
+    pub fn with_colors(mut self, bg: [f32; 4], border: [f32; 4]) -> Self {
+        self.panel.bg = bg;
+        self.panel.border = border;
+        self
+    }
+
+    pub fn with_close_on_outside_click(mut self, close: bool) -> Self {
+        self.close_on_outside_click = close;
+        self
+    }
+
+    fn rect_contains(rect: AnchorRect, x: f32, y: f32) -> bool {
+        x >= rect.0 && x <= rect.0 + rect.2 && y >= rect.1 && y <= rect.1 + rect.3
+    }
+}
+
+impl Widget for Popover {
+    fn style(&self) -> Style {
+        // Transparent wrapper around the trigger
+        Style {
+            flex_direction: FlexDirection::Column,
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawContext) {
+        let layout = ctx.layout;
+        let own = (layout.location.x, layout.location.y, layout.size.width, layout.size.height);
+        let anchor = self.anchor.as_ref().and_then(|a| a.get()).unwrap_or(own);
+        self.anchor_rect.set(anchor);
+    }
+
+    fn dismiss(&mut self, cursor: (f32, f32)) {
+        let (cx, cy) = cursor;
+        // A press under the popup may reach widgets below it in tree order
+        let inside = Self::rect_contains(self.popup_rect.get(), cx, cy)
+            || Self::rect_contains(self.anchor_rect.get(), cx, cy);
+        if self.close_on_outside_click && !inside && self.open.get() {
+            self.set_open.set(false);
+        }
+    }
+
+    fn is_child_visible(&self, index: usize) -> bool {
+        // The trigger, then the panel
+        index == 0 || self.open.get()
+    }
+}
+
+/// The content's panel: taken out of the flow and placed next to the anchor,
+/// relative to the `Popover` origin seen in the last draw, like `Modal`.
+struct PopoverPanel {
+    style: PanelStyle,
+    anchor_rect: Rc<Cell<AnchorRect>>,
+    popup_rect: Rc<Cell<AnchorRect>>,
+    viewport: Cell<(f32, f32)>,
+    // The parent's absolute origin and the panel size as last laid out, and
+    // the inset `style` asked for relative to the parent
+    origin: Cell<(f32, f32)>,
+    size: Cell<(f32, f32)>,
+    inset: Cell<(f32, f32)>,
+    // True while the panel is not where `style` will put it next frame
+    unsettled: Cell<bool>,
+}
+
+impl PopoverPanel {
+    /// Absolute top-left for a panel of `size` next to the current anchor.
+    fn placement(&self, size: (f32, f32)) -> (f32, f32) {
+        let style = &self.style;
+        let (x, y, _) = place_overlay(self.anchor_rect.get(), size, self.viewport.get(), style.side, style.align, style.gap);
+        (x, y)
+    }
+}
+
+impl Widget for PopoverPanel {
+    fn style(&self) -> Style {
+        let (x, y) = self.placement(self.size.get());
+        let (ox, oy) = self.origin.get();
+        let inset = (x - ox, y - oy);
+        self.inset.set(inset);
+        Style {
+            position: Position::Absolute,
+            inset: Rect {
+                left: LengthPercentageAuto::Length(inset.0),
+                top: LengthPercentageAuto::Length(inset.1),
+                right: LengthPercentageAuto::Auto,
+                bottom: LengthPercentageAuto::Auto,
+            },
+            size: Size {
+                width: self.style.width.map_or(Dimension::Auto, Dimension::Length),
+                height: Dimension::Auto,
+            },
+            flex_direction: FlexDirection::Column,
+            padding: Rect {
+                left: LengthPercentage::Length(self.style.padding),
+                right: LengthPercentage::Length(self.style.padding),
+                top: LengthPercentage::Length(self.style.padding),
+                bottom: LengthPercentage::Length(self.style.padding),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawContext) {
+        let layout = ctx.layout;
+        let (px, py) = (layout.location.x, layout.location.y);
+        let (w, h) = (layout.size.width, layout.size.height);
+        self.viewport.set(ctx.renderer.viewport_size());
+        let (ix, iy) = self.inset.get();
+        self.origin.set((px - ix, py - iy));
+        self.size.set((w, h));
+        self.popup_rect.set((px, py, w, h));
+
+        // When the anchor, the viewport or the content size moved the panel,
+        // the next layout puts it back; skip this frame rather than show it
+        // misplaced
+        let (tx, ty) = self.placement((w, h));
+        let unsettled = (tx - px).abs() > 0.5 || (ty - py).abs() > 0.5;
+        self.unsettled.set(unsettled);
+        if unsettled {
+            // Children still draw until `draw_over`; clip them all away
+            ctx.renderer.begin_overlay();
+            ctx.renderer.push_clip((0.0, 0.0, 0.0, 0.0));
+            ctx.renderer.request_next_frame();
+            return;
+        }
+
+        ctx.renderer.overlay_fill_rect_styled((px, py, w, h), self.style.bg, self.style.border_radius, 1.0, self.style.border);
+        // Children draw on the panel, until `draw_over`
+        ctx.renderer.begin_overlay();
+    }
+
+    fn draw_over(&self, ctx: &mut DrawContext) {
+        if self.unsettled.get() {
+            ctx.renderer.pop_clip();
+        }
+        ctx.renderer.end_overlay();
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        // Presses on the panel never fall through to widgets below
+        let (cx, cy) = ctx.cursor;
+        matches!(ctx.event, WindowEvent::MouseInput { state: ElementState::Pressed, .. })
+            && Popover::rect_contains(self.popup_rect.get(), cx, cy)
+    }
+}
+
+/// Convenience: a `Popover` node around `trigger`, showing `content` in its
+/// panel while open.
+pub fn popover(popover: Popover, trigger: WidgetNode, content: WidgetNode) -> WidgetNode {
+    let panel = PopoverPanel {
+        style: popover.panel,
+        anchor_rect: popover.anchor_rect.clone(),
+        popup_rect: popover.popup_rect.clone(),
+        viewport: Cell::new((0.0, 0.0)),
+        origin: Cell::new((0.0, 0.0)),
+        size: Cell::new((0.0, 0.0)),
+        inset: Cell::new((0.0, 0.0)),
+        unsettled: Cell::new(false),
+    };
+    WidgetNode::new(popover, vec![trigger, WidgetNode::new(panel, vec![content])])
+}
@@ -1,5 +1,4 @@
 use glyphon::Metrics;
-use glyphon::cosmic_text::Align;
 use taffy::prelude::*;
 use winit::event::{ElementState, KeyEvent, MouseButton, WindowEvent};
 use winit::keyboard::{Key, ModifiersState, NamedKey};
@@ -7,20 +6,21 @@ use winit::keyboard::{Key, ModifiersState, NamedKey};
 use crate::framework::{DrawContext, EventContext, Widget};
 use crate::signal::{Signal, SetSignal};
 use crate::WidgetNode;
+use crate::widgets::measured_label::MeasuredLabel;
 
 pub struct RadioButton {
-    label: String,
+    label: MeasuredLabel,
     index: usize,
     selected: Signal<usize>,
     set_selected: SetSignal<usize>,
     metrics: Metrics,
     circle_size: f32,
     gap: f32,
-    // Colors
-    circle_bg: [f32; 4],
-    circle_border: [f32; 4],
-    dot_color: [f32; 4],
-    text_color: [u8; 3],
+    // Colors; `None` uses the theme's radio colors and `text_primary`
+    circle_bg: Option<[f32; 4]>,
+    circle_border: Option<[f32; 4]>,
+    dot_color: Option<[f32; 4]>,
+    text_color: Option<[u8; 3]>,
     // State
     hover: bool,
     focus: bool,
@@ -35,17 +35,17 @@ impl RadioButton {
         metrics: Metrics,
     ) -> Self {
         Self {
-            label: label.into(),
+            label: MeasuredLabel::new(label),
             index,
             selected,
             set_selected,
             metrics,
             circle_size: 20.0,
             gap: 8.0,
-            circle_bg: [0.16, 0.28, 0.38, 1.0],
-            circle_border: [0.4, 0.55, 0.7, 1.0],
-            dot_color: [0.20, 0.65, 0.85, 1.0],
-            text_color: [230, 230, 230],
+            circle_bg: None,
+            circle_border: None,
+            dot_color: None,
+            text_color: None,
             hover: false,
             focus: false,
         }
@@ -67,14 +67,14 @@ impl RadioButton {
         circle_border: [f32; 4],
         dot_color: [f32; 4],
     ) -> Self {
-        self.circle_bg = circle_bg;
-        self.circle_border = circle_border;
-        self.dot_color = dot_color;
+        self.circle_bg = Some(circle_bg);
+        self.circle_border = Some(circle_border);
+        self.dot_color = Some(dot_color);
         self
     }
 
     pub fn with_text_color(mut self, color: [u8; 3]) -> Self {
-        self.text_color = color;
+        self.text_color = Some(color);
         self
     }
 
@@ -92,22 +92,14 @@ impl RadioButton {
             && y >= layout.location.y
             && y <= layout.location.y + layout.size.height
     }
-}
-
-impl Widget for RadioButton {
-    fn style(&self) -> Style {
-        let height = self.circle_size.max(self.metrics.line_height) + 8.0;
-        Style {
-            size: Size {
-                width: Dimension::Auto,
-                height: Dimension::Length(height),
-            },
-            flex_shrink: 0.0,
-            ..Default::default()
-        }
-    }
 
-    fn draw(&self, ctx: &mut DrawContext) {
+    /// Draws the radio, with the focus ring when `focus` is set.
+    fn draw_with_focus(&self, ctx: &mut DrawContext, focus: bool) {
+        let theme = ctx.theme;
+        let circle_bg = self.circle_bg.unwrap_or(theme.radio_bg);
+        let circle_border = self.circle_border.unwrap_or(theme.radio_border);
+        let dot_color = self.dot_color.unwrap_or(theme.radio_dot);
+        let text_color = self.text_color.unwrap_or(theme.text_primary);
         let layout = ctx.layout;
         let selected = self.is_selected();
 
@@ -115,28 +107,28 @@ impl Widget for RadioButton {
         let cx = layout.location.x + 4.0;
         let cy = layout.location.y + (layout.size.height - self.circle_size) / 2.0;
         let radius = self.circle_size / 2.0;
-        let border_w = if self.focus { 2.0 } else if self.hover { 1.5 } else { 1.0 };
-        let border_c = if self.focus {
-            [0.3, 0.6, 0.9, 1.0]
+        let border_w = if focus { 2.0 } else if self.hover { 1.5 } else { 1.0 };
+        let border_c = if focus {
+            ctx.theme.focus_ring
         } else if self.hover {
             [
-                (self.circle_border[0] + 0.1).min(1.0),
-                (self.circle_border[1] + 0.1).min(1.0),
-                (self.circle_border[2] + 0.1).min(1.0),
-                self.circle_border[3],
+                (circle_border[0] + 0.1).min(1.0),
+                (circle_border[1] + 0.1).min(1.0),
+                (circle_border[2] + 0.1).min(1.0),
+                circle_border[3],
             ]
         } else {
-            self.circle_border
+            circle_border
         };
         let circle_bg = if self.hover && !selected {
             [
-                (self.circle_bg[0] + 0.06).min(1.0),
-                (self.circle_bg[1] + 0.06).min(1.0),
-                (self.circle_bg[2] + 0.06).min(1.0),
-                self.circle_bg[3],
+                (circle_bg[0] + 0.06).min(1.0),
+                (circle_bg[1] + 0.06).min(1.0),
+                (circle_bg[2] + 0.06).min(1.0),
+                circle_bg[3],
             ]
         } else {
-            self.circle_bg
+            circle_bg
         };
         ctx.renderer.fill_rect_styled(
             (cx, cy, self.circle_size, self.circle_size),
@@ -153,7 +145,7 @@ impl Widget for RadioButton {
             let dot_y = cy + (self.circle_size - dot_size) / 2.0;
             ctx.renderer.fill_rect_rounded(
                 (dot_x, dot_y, dot_size, dot_size),
-                self.dot_color,
+                dot_color,
                 dot_size / 2.0,
             );
         }
@@ -162,14 +154,27 @@ impl Widget for RadioButton {
         let text_x = cx + self.circle_size + self.gap;
         let text_y = layout.location.y + (layout.size.height - self.metrics.line_height) / 2.0;
         let text_w = (layout.size.width - (text_x - layout.location.x)).max(0.0);
-        ctx.renderer.draw_text(
-            &self.label,
-            (text_x, text_y),
-            self.text_color,
-            (text_w, self.metrics.line_height),
-            self.metrics,
-            Align::Left,
-        );
+        self.label.draw(ctx.renderer, (text_x, text_y), text_w, text_color, self.metrics);
+    }
+}
+
+impl Widget for RadioButton {
+    fn style(&self) -> Style {
+        let height = self.circle_size.max(self.metrics.line_height) + 8.0;
+        // Sized to its content so packed rows don't clip the label
+        let width = 4.0 + self.circle_size + self.gap + self.label.width(self.metrics) + 4.0;
+        Style {
+            size: Size {
+                width: Dimension::Length(width),
+                height: Dimension::Length(height),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawContext) {
+        self.draw_with_focus(ctx, self.focus);
     }
 
     fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
@@ -184,31 +189,16 @@ impl Widget for RadioButton {
                 state: ElementState::Pressed,
                 button: MouseButton::Left,
                 ..
-            } => {
-                if self.hover && !self.is_selected() {
-                    self.select();
-                    true
-                } else {
-                    false
-                }
+            } if self.hover && !self.is_selected() => {
+                self.select();
+                true
             }
             _ => false,
         }
     }
 
-    fn handle_key_event(&mut self, event: &KeyEvent, _modifiers: ModifiersState) -> bool {
-        if event.state != ElementState::Pressed {
-            return false;
-        }
-        match &event.logical_key {
-            Key::Named(NamedKey::Space) | Key::Named(NamedKey::Enter) => {
-                if !self.is_selected() {
-                    self.select();
-                }
-                true
-            }
-            _ => false,
-        }
+    fn update_measures(&mut self, measures: &[Vec<f32>]) {
+        self.label.update_measures(measures);
     }
 
     fn is_focusable(&self) -> bool {
@@ -220,7 +210,9 @@ impl Widget for RadioButton {
     }
 
     fn activate(&mut self) {
-        self.select();
+        if !self.is_selected() {
+            self.select();
+        }
     }
 }
 
@@ -242,3 +234,213 @@ pub fn radio_group(
         })
         .collect()
 }
+
+/// A column of radio buttons behaving as one control, like native radios.
+///
+/// The group is a single tab stop: Tab focuses it as a whole, and the
+/// focus ring shows on the selected option (the first one while the
+/// selection is out of range). Arrow Down / Right select the next option and
+/// Arrow Up / Left the previous one, wrapping around; focus follows the
+/// selection since it stays on the group. Enter / Space select the focused
+/// option, and clicking an option selects it and focuses the group.
+///
+/// Use the plain `radio_group` instead when every radio should be its own
+/// tab stop, or when the options must sit in different containers.
+///
+/// ```ignore
+/// let (protocol, set_protocol) = create_signal(0usize);
+/// let group = RadioGroup::new(&["SSH", "FTP", "SFTP"], protocol, set_protocol, metrics);
+/// ```
+pub struct RadioGroup {
+    options: Vec<RadioButton>,
+    selected: Signal<usize>,
+    set_selected: SetSignal<usize>,
+    gap: f32,
+    // State
+    focus: bool,
+}
+
+impl RadioGroup {
+    pub fn new(
+        options: &[&str],
+        selected: Signal<usize>,
+        set_selected: SetSignal<usize>,
+        metrics: Metrics,
+    ) -> Self {
+        let options = options
+            .iter()
+            .enumerate()
+            .map(|(i, label)| RadioButton::new(*label, i, selected.clone(), set_selected.clone(), metrics))
+            .collect();
+        Self {
+            options,
+            selected,
+            set_selected,
+            gap: 6.0,
+            focus: false,
+        }
+    }
+
+    /// Vertical space between options.
+    pub fn with_gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn with_colors(
+        mut self,
+        circle_bg: [f32; 4],
+        circle_border: [f32; 4],
+        dot_color: [f32; 4],
+    ) -> Self {
+        self.options = self
+            .options
+            .into_iter()
+            .map(|radio| radio.with_colors(circle_bg, circle_border, dot_color))
+            .collect();
+        self
+    }
+
+    pub fn with_text_color(mut self, color: [u8; 3]) -> Self {
+        self.options = self.options.into_iter().map(|radio| radio.with_text_color(color)).collect();
+        self
+    }
+
+    /// Option showing focus: the selected one, or the first without a selection.
+    fn focused_option(&self) -> usize {
+        let selected = self.selected.get();
+        if selected < self.options.len() { selected } else { 0 }
+    }
+
+    fn select_step(&mut self, forward: bool) {
+        let count = self.options.len();
+        if count == 0 {
+            return;
+        }
+        let selected = self.selected.get();
+        let next = match (selected < count, forward) {
+            (true, true) => (selected + 1) % count,
+            (true, false) => (selected + count - 1) % count,
+            (false, true) => 0,
+            (false, false) => count - 1,
+        };
+        self.set_selected.set(next);
+    }
+
+    /// Layout of each option, stacked down from the group's own origin.
+    fn option_layouts(&self, layout: &Layout) -> Vec<Layout> {
+        let mut y = layout.location.y;
+        self.options
+            .iter()
+            .map(|radio| {
+                let size = fixed_size(&radio.style());
+                let mut option = *layout;
+                option.location.y = y;
+                option.size = size;
+                y += size.height + self.gap;
+                option
+            })
+            .collect()
+    }
+}
+
+/// Size of a widget whose style uses fixed lengths, as `RadioButton`'s does.
+fn fixed_size(style: &Style) -> Size<f32> {
+    let length = |dimension: Dimension| match dimension {
+        Dimension::Length(value) => value,
+        _ => 0.0,
+    };
+    Size {
+        width: length(style.size.width),
+        height: length(style.size.height),
+    }
+}
+
+impl Widget for RadioGroup {
+    fn style(&self) -> Style {
+        let sizes: Vec<Size<f32>> = self.options.iter().map(|radio| fixed_size(&radio.style())).collect();
+        let width = sizes.iter().map(|size| size.width).fold(0.0, f32::max);
+        let gaps = self.gap * sizes.len().saturating_sub(1) as f32;
+        let height = sizes.iter().map(|size| size.height).sum::<f32>() + gaps;
+        Style {
+            size: Size {
+                width: Dimension::Length(width),
+                height: Dimension::Length(height),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawContext) {
+        // Picked per frame, since the selection can also change from outside
+        let focused = self.focus.then(|| self.focused_option());
+        for (i, (radio, layout)) in self.options.iter().zip(self.option_layouts(ctx.layout)).enumerate() {
+            let mut option = DrawContext {
+                renderer: &mut *ctx.renderer,
+                layout: &layout,
+                theme: ctx.theme,
+            };
+            radio.draw_with_focus(&mut option, focused == Some(i));
+        }
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        let layouts = self.option_layouts(ctx.layout);
+        for (radio, layout) in self.options.iter_mut().zip(&layouts) {
+            let mut option = EventContext {
+                event: ctx.event,
+                layout,
+                cursor: ctx.cursor,
+                double_click: ctx.double_click,
+            };
+            // Every option sees cursor moves, to keep hover current
+            radio.handle_event(&mut option);
+        }
+        // A press on any option, even the selected one, focuses the group
+        matches!(
+            ctx.event,
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            }
+        ) && self.options.iter().any(|radio| radio.hover)
+    }
+
+    fn handle_key_event(&mut self, event: &KeyEvent, _modifiers: ModifiersState) -> bool {
+        // Enter / Space fall through to the window, which calls `activate`
+        match &event.logical_key {
+            Key::Named(NamedKey::ArrowDown | NamedKey::ArrowRight) => {
+                self.select_step(true);
+                true
+            }
+            Key::Named(NamedKey::ArrowUp | NamedKey::ArrowLeft) => {
+                self.select_step(false);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn update_measures(&mut self, measures: &[Vec<f32>]) {
+        for radio in &mut self.options {
+            radio.update_measures(measures);
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focus = focused;
+    }
+
+    fn activate(&mut self) {
+        let focused = self.focused_option();
+        if let Some(radio) = self.options.get_mut(focused) {
+            radio.activate();
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use taffy::prelude::*;
+
+use crate::framework::{DrawContext, Widget};
+use crate::signal::Signal;
+use crate::tree::WidgetNode;
+
+/// Shows one of several pages, picked by the key in a signal.
+///
+/// Each child is a page, matched to keys by position. Only the page whose
+/// key equals the signal's value is laid out, drawn and receives events and
+/// focus; with no match, nothing is shown. Inactive pages stay alive: their
+/// widgets keep scroll offsets, input text and other state, and reappear
+/// unchanged when their key comes back.
+///
+/// ```ignore
+/// #[derive(Clone, PartialEq)]
+/// enum Page { Home, Settings }
+///
+/// let (page, set_page) = create_signal(Page::Home);
+/// let pages = router(page, vec![
+///     (Page::Home, home_view()),
+///     (Page::Settings, settings_view()),
+/// ]);
+/// ```
+pub struct Router<K> {
+    active: Signal<K>,
+    keys: Vec<K>,
+}
+
+impl<K: PartialEq + 'static> Router<K> {
+    pub fn new(active: Signal<K>) -> Self {
+        Self {
+            active,
+            keys: Vec::new(),
+        }
+    }
+
+    /// Adds the key for the next child page.
+    pub fn with_route(mut self, key: K) -> Self {
+        self.keys.push(key);
+        self
+    }
+
+    /// Index of the page shown for the current key.
+    pub fn active_index(&self) -> Option<usize> {
+        self.active.with(|active| self.keys.iter().position(|key| key == active))
+    }
+}
+
+impl<K: PartialEq + 'static> Widget for Router<K> {
+    fn style(&self) -> Style {
+        Style {
+            flex_direction: FlexDirection::Column,
+            flex_grow: 1.0,
+            size: Size {
+                width: Dimension::Percent(1.0),
+                height: Dimension::Auto,
+            },
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, _ctx: &mut DrawContext) {}
+
+    fn is_child_visible(&self, index: usize) -> bool {
+        self.active_index() == Some(index)
+    }
+}
+
+/// Convenience: a `Router` node with one page per `(key, page)` pair.
+pub fn router<K: PartialEq + 'static>(active: Signal<K>, routes: Vec<(K, WidgetNode)>) -> WidgetNode {
+    let mut widget = Router::new(active);
+    let mut pages = Vec::with_capacity(routes.len());
+    for (key, page) in routes {
+        widget = widget.with_route(key);
+        pages.push(page);
+    }
+    WidgetNode::new(widget, pages)
+}
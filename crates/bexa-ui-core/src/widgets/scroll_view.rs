@@ -1,7 +1,7 @@
 use taffy::prelude::*;
 use taffy::Overflow;
 
-use crate::framework::{DrawContext, Widget};
+use crate::framework::{is_left_press, DrawContext, EventContext, Widget};
 
 pub struct ScrollView {
     style: Style,
@@ -9,6 +9,15 @@ pub struct ScrollView {
     border_radius: f32,
     border_width: f32,
     border_color: [f32; 4],
+    focusable: bool,
+    // State
+    focused: bool,
+}
+
+impl Default for ScrollView {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ScrollView {
@@ -28,6 +37,8 @@ impl ScrollView {
             border_radius: 0.0,
             border_width: 0.0,
             border_color: [0.0; 4],
+            focusable: false,
+            focused: false,
         }
     }
 
@@ -72,6 +83,13 @@ impl ScrollView {
         self
     }
 
+    /// Lets Tab focus the view, so it scrolls with the arrow keys,
+    /// PageUp/PageDown, Space and Home/End, for keyboard-only navigation.
+    pub fn with_focusable(mut self) -> Self {
+        self.focusable = true;
+        self
+    }
+
     pub fn with_gap(mut self, gap: f32) -> Self {
         self.style.gap = Size {
             width: LengthPercentage::Length(gap),
@@ -107,4 +125,44 @@ impl Widget for ScrollView {
             );
         }
     }
+
+    // After the children, so their backgrounds can't cover the ring
+    fn draw_over(&self, ctx: &mut DrawContext) {
+        if self.focused {
+            let layout = ctx.layout;
+            ctx.renderer.fill_rect_styled(
+                (
+                    layout.location.x,
+                    layout.location.y,
+                    layout.size.width,
+                    layout.size.height,
+                ),
+                [0.0; 4],
+                self.border_radius,
+                2.0,
+                ctx.theme.focus_ring,
+            );
+        }
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        // Presses inside that no child took focus the container
+        if !self.focusable || !is_left_press(ctx.event) {
+            return false;
+        }
+        let layout = ctx.layout;
+        let (cx, cy) = ctx.cursor;
+        cx >= layout.location.x
+            && cx <= layout.location.x + layout.size.width
+            && cy >= layout.location.y
+            && cy <= layout.location.y + layout.size.height
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.focusable
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
 }
@@ -1,4 +1,5 @@
 use std::cell::Cell;
+use std::time::{Duration, Instant};
 
 use glyphon::Metrics;
 use glyphon::cosmic_text::Align;
@@ -9,7 +10,19 @@ use winit::keyboard::{Key, ModifiersState, NamedKey};
 use crate::framework::{DrawContext, EventContext, Widget};
 use crate::icons;
 use crate::signal::{Signal, SetSignal};
-
+use crate::theme::opaque;
+use crate::widgets::popover::{place_overlay, PopoverAlign, PopoverSide};
+
+/// Pause after which type-ahead starts a new prefix instead of extending it.
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Dropdown picker over a fixed list of options.
+///
+/// When focused, typing a printable character opens the dropdown. By default
+/// the keystrokes jump to the first option starting with the typed prefix
+/// (repeating one letter cycles through its matches). With `with_searchable`
+/// they build a query instead that filters the list by substring; Backspace
+/// edits it and Enter picks the highlighted (or first) match.
 pub struct Select {
     options: Vec<String>,
     selected: Signal<usize>,
@@ -18,24 +31,31 @@ pub struct Select {
     padding: f32,
     border_radius: f32,
     item_height: Cell<f32>,
-    // Colors
-    bg: [f32; 4],
-    border: [f32; 4],
-    text_color: [u8; 3],
-    dropdown_bg: [f32; 4],
-    dropdown_border: [f32; 4],
-    hover_bg: [f32; 4],
+    searchable: bool,
+    // Colors; `None` uses the theme's `panel`, `border`, `text_primary` and
+    // `button` (the highlighted option)
+    bg: Option<[f32; 4]>,
+    border: Option<[f32; 4]>,
+    text_color: Option<[u8; 3]>,
+    dropdown_bg: Option<[f32; 4]>,
+    dropdown_border: Option<[f32; 4]>,
+    hover_bg: Option<[f32; 4]>,
     hover_text: [u8; 3],
     // State
     open: bool,
     hover: bool,
     hover_index: Option<usize>,
     focus: bool,
+    query: String,
+    typeahead: String,
+    last_typed: Option<Instant>,
     // Cached absolute position for overlay drawing (set in draw, used in handle_event)
     abs_x: Cell<f32>,
     abs_y: Cell<f32>,
     abs_w: Cell<f32>,
     abs_h: Cell<f32>,
+    dropdown_x: Cell<f32>,
+    dropdown_y: Cell<f32>,
 }
 
 impl Select {
@@ -53,21 +73,27 @@ impl Select {
             padding: 8.0,
             border_radius: 6.0,
             item_height: Cell::new(0.0),
-            bg: [0.16, 0.28, 0.38, 1.0],
-            border: [0.4, 0.55, 0.7, 1.0],
-            text_color: [230, 230, 230],
-            dropdown_bg: [0.14, 0.24, 0.34, 1.0],
-            dropdown_border: [0.4, 0.55, 0.7, 1.0],
-            hover_bg: [0.20, 0.65, 0.85, 1.0],
+            searchable: false,
+            bg: None,
+            border: None,
+            text_color: None,
+            dropdown_bg: None,
+            dropdown_border: None,
+            hover_bg: None,
             hover_text: [255, 255, 255],
             open: false,
             hover: false,
             hover_index: None,
             focus: false,
+            query: String::new(),
+            typeahead: String::new(),
+            last_typed: None,
             abs_x: Cell::new(0.0),
             abs_y: Cell::new(0.0),
             abs_w: Cell::new(0.0),
             abs_h: Cell::new(0.0),
+            dropdown_x: Cell::new(0.0),
+            dropdown_y: Cell::new(0.0),
         }
     }
 
@@ -87,9 +113,9 @@ impl Select {
         border: [f32; 4],
         text_color: [u8; 3],
     ) -> Self {
-        self.bg = bg;
-        self.border = border;
-        self.text_color = text_color;
+        self.bg = Some(bg);
+        self.border = Some(border);
+        self.text_color = Some(text_color);
         self
     }
 
@@ -100,23 +126,102 @@ impl Select {
         hover_bg: [f32; 4],
         hover_text: [u8; 3],
     ) -> Self {
-        self.dropdown_bg = dropdown_bg;
-        self.dropdown_border = dropdown_border;
-        self.hover_bg = hover_bg;
+        self.dropdown_bg = Some(dropdown_bg);
+        self.dropdown_border = Some(dropdown_border);
+        self.hover_bg = Some(hover_bg);
         self.hover_text = hover_text;
         self
     }
 
+    /// Typing while open filters the options instead of jumping between them.
+    pub fn with_searchable(mut self, searchable: bool) -> Self {
+        self.searchable = searchable;
+        self
+    }
+
     fn selected_text(&self) -> &str {
         let idx = self.selected.get();
         self.options.get(idx).map(|s| s.as_str()).unwrap_or("")
     }
 
-    fn hit_test(&self, layout: &Layout, x: f32, y: f32) -> bool {
-        x >= layout.location.x
-            && x <= layout.location.x + layout.size.width
-            && y >= layout.location.y
-            && y <= layout.location.y + layout.size.height
+    /// Indices of the options shown in the dropdown, in display order.
+    fn visible_options(&self) -> Vec<usize> {
+        if !self.searchable || self.query.is_empty() {
+            return (0..self.options.len()).collect();
+        }
+        let query = self.query.to_lowercase();
+        (0..self.options.len())
+            .filter(|&i| self.options[i].to_lowercase().contains(&query))
+            .collect()
+    }
+
+    fn open_dropdown(&mut self) {
+        self.open = true;
+        self.hover_index = Some(self.selected.get());
+        self.query.clear();
+    }
+
+    fn close_dropdown(&mut self) {
+        self.open = false;
+        self.hover_index = None;
+        self.query.clear();
+    }
+
+    /// Moves the highlight `step` rows through the visible options.
+    fn move_hover(&mut self, step: isize) {
+        let visible = self.visible_options();
+        if visible.is_empty() {
+            return;
+        }
+        let current = self
+            .hover_index
+            .and_then(|h| visible.iter().position(|&i| i == h));
+        let next = match current {
+            Some(pos) => (pos as isize + step).clamp(0, visible.len() as isize - 1) as usize,
+            None => 0,
+        };
+        self.hover_index = Some(visible[next]);
+    }
+
+    /// Handles a printable keystroke: filters when searchable, otherwise
+    /// jumps to the next option matching the typed prefix.
+    fn type_text(&mut self, text: &str) {
+        if !self.open {
+            self.open_dropdown();
+        }
+
+        if self.searchable {
+            self.query.push_str(text);
+            self.hover_index = self.visible_options().first().copied();
+            return;
+        }
+
+        let now = Instant::now();
+        let expired = self
+            .last_typed
+            .is_none_or(|t| now.duration_since(t) > TYPEAHEAD_TIMEOUT);
+        if expired {
+            self.typeahead.clear();
+        }
+        self.last_typed = Some(now);
+        self.typeahead.push_str(&text.to_lowercase());
+
+        // Repeating one letter cycles through its matches; anything else
+        // refines the prefix from the current option onwards
+        let count = self.options.len();
+        let current = self.hover_index.unwrap_or(self.selected.get());
+        let mut chars = self.typeahead.chars();
+        let first = chars.next();
+        let (needle, start) = match first {
+            Some(c) if chars.all(|other| other == c) => (c.to_string(), current + 1),
+            _ => (self.typeahead.clone(), current),
+        };
+        let found = (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|&i| self.options[i].to_lowercase().starts_with(&needle));
+        if let Some(idx) = found {
+            self.hover_index = Some(idx);
+        }
     }
 
     fn dropdown_item_at(&self, x: f32, y: f32) -> Option<usize> {
@@ -124,8 +229,8 @@ impl Select {
             return None;
         }
         let item_h = self.item_height.get();
-        let dropdown_x = self.abs_x.get();
-        let dropdown_y = self.abs_y.get() + self.abs_h.get();
+        let dropdown_x = self.dropdown_x.get();
+        let dropdown_y = self.dropdown_y.get();
         let dropdown_w = self.abs_w.get();
 
         if x < dropdown_x || x > dropdown_x + dropdown_w {
@@ -137,12 +242,8 @@ impl Select {
             return None;
         }
 
-        let idx = (rel_y / item_h) as usize;
-        if idx < self.options.len() {
-            Some(idx)
-        } else {
-            None
-        }
+        let row = (rel_y / item_h) as usize;
+        self.visible_options().get(row).copied()
     }
 }
 
@@ -165,6 +266,13 @@ impl Widget for Select {
         let y = layout.location.y;
         let w = layout.size.width;
         let h = layout.size.height;
+        let theme = ctx.theme;
+        let bg = self.bg.unwrap_or(opaque(theme.panel));
+        let border = self.border.unwrap_or(theme.border);
+        let text_color = self.text_color.unwrap_or(theme.text_primary);
+        let dropdown_bg = self.dropdown_bg.unwrap_or(opaque(theme.panel));
+        let dropdown_border = self.dropdown_border.unwrap_or(theme.border);
+        let hover_bg = self.hover_bg.unwrap_or(opaque(theme.button));
 
         // Cache absolute position for event handling
         self.abs_x.set(x);
@@ -176,26 +284,26 @@ impl Widget for Select {
         // Draw the select box
         let border_w = if self.focus { 2.0 } else if self.hover { 1.5 } else { 1.0 };
         let border_c = if self.focus {
-            [0.3, 0.6, 0.9, 1.0]
+            ctx.theme.focus_ring
         } else if self.hover {
             [
-                (self.border[0] + 0.1).min(1.0),
-                (self.border[1] + 0.1).min(1.0),
-                (self.border[2] + 0.1).min(1.0),
-                self.border[3],
+                (border[0] + 0.1).min(1.0),
+                (border[1] + 0.1).min(1.0),
+                (border[2] + 0.1).min(1.0),
+                border[3],
             ]
         } else {
-            self.border
+            border
         };
         let select_bg = if self.hover && !self.open {
             [
-                (self.bg[0] + 0.04).min(1.0),
-                (self.bg[1] + 0.04).min(1.0),
-                (self.bg[2] + 0.04).min(1.0),
-                self.bg[3],
+                (bg[0] + 0.04).min(1.0),
+                (bg[1] + 0.04).min(1.0),
+                (bg[2] + 0.04).min(1.0),
+                bg[3],
             ]
         } else {
-            self.bg
+            bg
         };
         ctx.renderer.fill_rect_styled(
             (x, y, w, h),
@@ -210,10 +318,13 @@ impl Widget for Select {
         let text_y = y + (h - self.metrics.line_height) / 2.0;
         let chevron_space = 24.0;
         let text_w = (w - self.padding * 2.0 - chevron_space).max(0.0);
+        // While searching the box shows the query being typed
+        let searching = self.open && self.searchable && !self.query.is_empty();
+        let shown = if searching { self.query.as_str() } else { self.selected_text() };
         ctx.renderer.draw_text(
-            self.selected_text(),
+            shown,
             (text_x, text_y),
-            self.text_color,
+            text_color,
             (text_w, self.metrics.line_height),
             self.metrics,
             Align::Left,
@@ -227,7 +338,7 @@ impl Widget for Select {
         ctx.renderer.draw_text_with_font(
             chevron,
             (icon_x, icon_y),
-            self.text_color,
+            text_color,
             (16.0, self.metrics.line_height),
             icon_metrics,
             Align::Center,
@@ -237,21 +348,45 @@ impl Widget for Select {
         // Draw dropdown overlay when open
         if self.open {
             let item_h = self.item_height.get();
-            let dropdown_h = item_h * self.options.len() as f32 + self.padding;
-            let dropdown_y = y + h;
+            let visible = self.visible_options();
+            let rows = visible.len().max(1);
+            let dropdown_h = item_h * rows as f32 + self.padding;
+            // Opens below, or above when there is no room below
+            let (x, dropdown_y, _) = place_overlay(
+                (x, y, w, h),
+                (w, dropdown_h),
+                ctx.renderer.viewport_size(),
+                PopoverSide::Bottom,
+                PopoverAlign::Start,
+                0.0,
+            );
+            self.dropdown_x.set(x);
+            self.dropdown_y.set(dropdown_y);
 
             // Dropdown background
             ctx.renderer.overlay_fill_rect_styled(
                 (x, dropdown_y, w, dropdown_h),
-                self.dropdown_bg,
+                dropdown_bg,
                 self.border_radius,
                 1.0,
-                self.dropdown_border,
+                dropdown_border,
             );
 
+            if visible.is_empty() {
+                ctx.renderer.overlay_draw_text(
+                    "No matches",
+                    (x + self.padding, dropdown_y + self.padding * 0.5 + (item_h - self.metrics.line_height) / 2.0),
+                    ctx.theme.text_secondary,
+                    (w - self.padding * 2.0, self.metrics.line_height),
+                    self.metrics,
+                    Align::Left,
+                );
+            }
+
             // Dropdown items
-            for (i, option) in self.options.iter().enumerate() {
-                let iy = dropdown_y + self.padding * 0.5 + i as f32 * item_h;
+            for (row, &i) in visible.iter().enumerate() {
+                let option = &self.options[i];
+                let iy = dropdown_y + self.padding * 0.5 + row as f32 * item_h;
                 let is_hover = self.hover_index == Some(i);
                 let is_selected = self.selected.get() == i;
 
@@ -259,7 +394,7 @@ impl Widget for Select {
                 if is_hover {
                     ctx.renderer.overlay_fill_rect_styled(
                         (x + 2.0, iy, w - 4.0, item_h),
-                        self.hover_bg,
+                        hover_bg,
                         4.0,
                         0.0,
                         [0.0; 4],
@@ -271,7 +406,7 @@ impl Widget for Select {
                 } else if is_selected {
                     [180, 220, 255]
                 } else {
-                    self.text_color
+                    text_color
                 };
 
                 ctx.renderer.overlay_draw_text(
@@ -286,19 +421,20 @@ impl Widget for Select {
         }
     }
 
-    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
-        let layout = ctx.layout;
+    fn on_mouse_enter(&mut self) {
+        self.hover = true;
+    }
 
+    fn on_mouse_leave(&mut self) {
+        self.hover = false;
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
         match ctx.event {
             WindowEvent::CursorMoved { position, .. } => {
-                let px = position.x as f32;
-                let py = position.y as f32;
-                let over = self.hit_test(layout, px, py);
-                self.hover = over;
-
                 // Track hover over dropdown items
                 if self.open {
-                    self.hover_index = self.dropdown_item_at(px, py);
+                    self.hover_index = self.dropdown_item_at(position.x as f32, position.y as f32);
                 }
                 false // don't consume — let siblings update hover too
             }
@@ -309,17 +445,14 @@ impl Widget for Select {
             } => {
                 if self.open {
                     // Check if clicking on dropdown item
-                    if let Some(idx) = self.hover_index {
+                    // Clicking outside or on the select box just closes
+                    if let Some(idx) = self.dropdown_item_at(ctx.cursor.0, ctx.cursor.1) {
                         self.set_selected.set(idx);
-                        self.open = false;
-                        self.hover_index = None;
-                    } else {
-                        // Clicked outside or on the select box → close
-                        self.open = false;
                     }
+                    self.close_dropdown();
                     true
                 } else if self.hover {
-                    self.open = true;
+                    self.open_dropdown();
                     true
                 } else {
                     false
@@ -329,36 +462,37 @@ impl Widget for Select {
         }
     }
 
-    fn handle_key_event(&mut self, event: &KeyEvent, _modifiers: ModifiersState) -> bool {
+    fn handle_key_event(&mut self, event: &KeyEvent, modifiers: ModifiersState) -> bool {
         if event.state != ElementState::Pressed {
             return false;
         }
+        // Printable characters open the dropdown and search; a space while
+        // searching is part of the query rather than a toggle
+        let chord = modifiers.control_key() || modifiers.alt_key() || modifiers.super_key();
+        let typed = match &event.logical_key {
+            Key::Character(c) if !chord && !c.chars().any(char::is_control) => Some(c.as_str()),
+            Key::Named(NamedKey::Space) if self.open && self.searchable => Some(" "),
+            _ => None,
+        };
+        if let Some(text) = typed {
+            self.type_text(text);
+            return true;
+        }
+
+        // Enter / Space fall through to the window, which calls `activate`
         match &event.logical_key {
-            Key::Named(NamedKey::Space) | Key::Named(NamedKey::Enter) => {
-                if self.open {
-                    // Select hovered item or close
-                    if let Some(idx) = self.hover_index {
-                        self.set_selected.set(idx);
-                    }
-                    self.open = false;
-                } else {
-                    self.open = true;
-                }
+            Key::Named(NamedKey::Backspace) if self.open && self.searchable => {
+                self.query.pop();
+                self.hover_index = self.visible_options().first().copied();
                 true
             }
-            Key::Named(NamedKey::Escape) => {
-                if self.open {
-                    self.open = false;
-                    true
-                } else {
-                    false
-                }
+            Key::Named(NamedKey::Escape) if self.open => {
+                self.close_dropdown();
+                true
             }
             Key::Named(NamedKey::ArrowDown) => {
                 if self.open {
-                    let current = self.hover_index.unwrap_or(self.selected.get());
-                    let next = (current + 1).min(self.options.len().saturating_sub(1));
-                    self.hover_index = Some(next);
+                    self.move_hover(1);
                 } else {
                     // Move selection down
                     let current = self.selected.get();
@@ -369,9 +503,7 @@ impl Widget for Select {
             }
             Key::Named(NamedKey::ArrowUp) => {
                 if self.open {
-                    let current = self.hover_index.unwrap_or(self.selected.get());
-                    let next = current.saturating_sub(1);
-                    self.hover_index = Some(next);
+                    self.move_hover(-1);
                 } else {
                     let current = self.selected.get();
                     let next = current.saturating_sub(1);
@@ -390,11 +522,29 @@ impl Widget for Select {
     fn set_focus(&mut self, focused: bool) {
         self.focus = focused;
         if !focused {
-            self.open = false;
+            self.close_dropdown();
         }
     }
 
+    /// Opens the dropdown, or picks the highlighted option (the first match
+    /// when nothing is highlighted) and closes it.
     fn activate(&mut self) {
-        self.open = !self.open;
+        if self.open {
+            let pick = self
+                .hover_index
+                .or_else(|| self.visible_options().first().copied());
+            if let Some(idx) = pick {
+                self.set_selected.set(idx);
+            }
+            self.close_dropdown();
+        } else {
+            self.open_dropdown();
+        }
+    }
+
+    fn dismiss(&mut self, _cursor: (f32, f32)) {
+        if self.open {
+            self.close_dropdown();
+        }
     }
 }
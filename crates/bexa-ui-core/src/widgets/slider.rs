@@ -1,10 +1,15 @@
 use glyphon::Metrics;
+use glyphon::cosmic_text::Align;
 use taffy::prelude::*;
 use winit::event::{ElementState, KeyEvent, MouseButton, WindowEvent};
 use winit::keyboard::{Key, ModifiersState, NamedKey};
 
 use crate::framework::{DrawContext, EventContext, Widget};
 use crate::signal::{Signal, SetSignal};
+use crate::theme::{opaque, tint};
+
+/// Space between the value label and the knob.
+const LABEL_GAP: f32 = 4.0;
 
 pub struct Slider {
     value: Signal<f32>,
@@ -16,17 +21,19 @@ pub struct Slider {
     track_height: f32,
     knob_radius: f32,
     padding: f32,
-    // Colors
-    track_bg: [f32; 4],
-    track_fill: [f32; 4],
+    // Colors; `None` uses the theme's `button` for the fill, `border`,
+    // `text_secondary` for the label and a shade of `panel` for the track
+    track_bg: Option<[f32; 4]>,
+    track_fill: Option<[f32; 4]>,
     knob_color: [f32; 4],
-    border_color: [f32; 4],
+    border_color: Option<[f32; 4]>,
+    label_color: Option<[u8; 3]>,
+    /// Formats the value drawn above the knob, from `with_value_label`
+    value_label: Option<Box<dyn Fn(f32) -> String>>,
     // State
     hover: bool,
     dragging: bool,
     focus: bool,
-    last_mouse_x: f32,
-    last_mouse_y: f32,
 }
 
 impl Slider {
@@ -41,15 +48,15 @@ impl Slider {
             track_height: 6.0,
             knob_radius: 8.0,
             padding: 8.0,
-            track_bg: [0.16, 0.22, 0.30, 1.0],
-            track_fill: [0.20, 0.65, 0.85, 1.0],
+            track_bg: None,
+            track_fill: None,
             knob_color: [0.92, 0.92, 0.95, 1.0],
-            border_color: [0.35, 0.45, 0.60, 1.0],
+            border_color: None,
+            label_color: None,
+            value_label: None,
             hover: false,
             dragging: false,
             focus: false,
-            last_mouse_x: 0.0,
-            last_mouse_y: 0.0,
         }
     }
 
@@ -82,28 +89,61 @@ impl Slider {
         knob: [f32; 4],
         border: [f32; 4],
     ) -> Self {
-        self.track_bg = track_bg;
-        self.track_fill = track_fill;
+        self.track_bg = Some(track_bg);
+        self.track_fill = Some(track_fill);
         self.knob_color = knob;
-        self.border_color = border;
+        self.border_color = Some(border);
+        self
+    }
+
+    /// Draws the value above the knob, formatted by `format`, e.g.
+    /// `|v| format!("{v:.0}%")`. It follows the knob while dragging and
+    /// stays inside the widget at either end; the slider grows by a line
+    /// to fit it.
+    pub fn with_value_label(mut self, format: impl Fn(f32) -> String + 'static) -> Self {
+        self.value_label = Some(Box::new(format));
         self
     }
 
-    fn hit_test(&self, layout: &Layout, x: f32, y: f32) -> bool {
-        x >= layout.location.x
-            && x <= layout.location.x + layout.size.width
-            && y >= layout.location.y
-            && y <= layout.location.y + layout.size.height
+    pub fn with_label_color(mut self, color: [u8; 3]) -> Self {
+        self.label_color = Some(color);
+        self
+    }
+
+    /// Height reserved above the track for the value label.
+    fn label_h(&self) -> f32 {
+        if self.value_label.is_some() {
+            self.metrics.line_height + LABEL_GAP
+        } else {
+            0.0
+        }
     }
 
     fn track_bounds(&self, layout: &Layout) -> (f32, f32, f32) {
         let track_x = layout.location.x + self.padding;
         let track_w = (layout.size.width - self.padding * 2.0).max(1.0);
-        let track_y = layout.location.y + (layout.size.height - self.track_height) / 2.0;
+        // Centered below the value label, if any
+        let label_h = self.label_h();
+        let track_y =
+            layout.location.y + label_h + (layout.size.height - label_h - self.track_height) / 2.0;
         (track_x, track_y, track_w)
     }
 
+    /// Position of `value` along the track, 0.0–1.0. A collapsed range or a
+    /// NaN value sits at the start instead of poisoning the geometry.
+    fn fraction(&self, value: f32) -> f32 {
+        let span = self.max - self.min;
+        if span <= f32::EPSILON || !span.is_finite() {
+            return 0.0;
+        }
+        let t = (value - self.min) / span;
+        if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) }
+    }
+
     fn clamp_value(&self, value: f32) -> f32 {
+        if value.is_nan() {
+            return self.min;
+        }
         let mut v = value.clamp(self.min, self.max);
         if self.step > 0.0 {
             let steps = ((v - self.min) / self.step).round();
@@ -134,7 +174,7 @@ impl Widget for Slider {
         Style {
             size: Size {
                 width: Dimension::Percent(1.0),
-                height: Dimension::Length(height.max(self.metrics.line_height + 4.0)),
+                height: Dimension::Length(height.max(self.metrics.line_height + 4.0) + self.label_h()),
             },
             flex_shrink: 0.0,
             ..Default::default()
@@ -142,39 +182,44 @@ impl Widget for Slider {
     }
 
     fn draw(&self, ctx: &mut DrawContext) {
+        let theme = ctx.theme;
+        let track_bg = self.track_bg.unwrap_or(tint(theme.panel, theme.text_primary, 0.12));
+        let track_fill = self.track_fill.unwrap_or(opaque(theme.button));
+        let border_color = self.border_color.unwrap_or(theme.border);
+        let label_color = self.label_color.unwrap_or(theme.text_secondary);
         let layout = ctx.layout;
         let (track_x, track_y, track_w) = self.track_bounds(layout);
         let track_radius = self.track_height / 2.0;
 
         let border_w = if self.focus { 2.0 } else if self.hover { 1.5 } else { 1.0 };
         let border_c = if self.focus {
-            [0.3, 0.6, 0.9, 1.0]
+            ctx.theme.focus_ring
         } else if self.hover {
             [
-                (self.border_color[0] + 0.08).min(1.0),
-                (self.border_color[1] + 0.08).min(1.0),
-                (self.border_color[2] + 0.08).min(1.0),
-                self.border_color[3],
+                (border_color[0] + 0.08).min(1.0),
+                (border_color[1] + 0.08).min(1.0),
+                (border_color[2] + 0.08).min(1.0),
+                border_color[3],
             ]
         } else {
-            self.border_color
+            border_color
         };
 
         // Track background
         ctx.renderer.fill_rect_styled(
             (track_x, track_y, track_w, self.track_height),
-            self.track_bg,
+            track_bg,
             track_radius,
             border_w,
             border_c,
         );
 
         // Filled portion
-        let t = (self.value.get() - self.min) / (self.max - self.min);
-        let fill_w = (track_w * t.clamp(0.0, 1.0)).max(0.0);
+        let t = self.fraction(self.value.get());
+        let fill_w = track_w * t;
         ctx.renderer.fill_rect_rounded(
             (track_x, track_y, fill_w, self.track_height),
-            self.track_fill,
+            track_fill,
             track_radius,
         );
 
@@ -187,19 +232,42 @@ impl Widget for Slider {
             self.knob_color,
             self.knob_radius,
         );
+
+        // Value label, centered over the knob but kept inside the widget
+        if let Some(format) = &self.value_label {
+            let text = format(self.value.get());
+            // Estimate text width: ~0.6em per char, plus one for slack
+            let label_w = ((text.chars().count() as f32 + 1.0) * self.metrics.font_size * 0.6)
+                .min(layout.size.width);
+            let min_x = layout.location.x;
+            let max_x = layout.location.x + layout.size.width - label_w;
+            let label_x = (track_x + fill_w - label_w / 2.0).clamp(min_x, max_x.max(min_x));
+            let label_y = knob_y - LABEL_GAP - self.metrics.line_height;
+            ctx.renderer.draw_text(
+                &text,
+                (label_x, label_y),
+                label_color,
+                (label_w, self.metrics.line_height),
+                self.metrics,
+                Align::Center,
+            );
+        }
+    }
+
+    fn on_mouse_enter(&mut self) {
+        self.hover = true;
+    }
+
+    fn on_mouse_leave(&mut self) {
+        self.hover = false;
     }
 
     fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
         let layout = ctx.layout;
         match ctx.event {
             WindowEvent::CursorMoved { position, .. } => {
-                let px = position.x as f32;
-                let py = position.y as f32;
-                self.last_mouse_x = px;
-                self.last_mouse_y = py;
-                self.hover = self.hit_test(layout, px, py);
                 if self.dragging {
-                    self.set_value_from_x(layout, px);
+                    self.set_value_from_x(layout, position.x as f32);
                 }
                 false
             }
@@ -207,15 +275,10 @@ impl Widget for Slider {
                 state: ElementState::Pressed,
                 button: MouseButton::Left,
                 ..
-            } => {
-                self.hover = self.hit_test(layout, self.last_mouse_x, self.last_mouse_y);
-                if self.hover {
-                    self.dragging = true;
-                    self.set_value_from_x(layout, self.last_mouse_x);
-                    true
-                } else {
-                    false
-                }
+            } if self.hover => {
+                self.dragging = true;
+                self.set_value_from_x(layout, ctx.cursor.0);
+                true
             }
             WindowEvent::MouseInput {
                 state: ElementState::Released,
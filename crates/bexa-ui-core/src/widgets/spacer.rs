@@ -0,0 +1,53 @@
+use taffy::prelude::*;
+
+use crate::framework::Widget;
+
+/// Empty node that takes up the free space along its parent's main axis.
+///
+/// Pushes the items after it to the far edge of a row or column:
+///
+/// ```ignore
+/// ui! {
+///     Flex::row(8.0) => {
+///         title_label,
+///         Spacer::new(),
+///         save_button,
+///     }
+/// }
+/// ```
+pub struct Spacer {
+    grow: f32,
+}
+
+impl Default for Spacer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Spacer {
+    pub fn new() -> Self {
+        Self { grow: 1.0 }
+    }
+
+    /// Share of the free space relative to other growing siblings.
+    pub fn with_grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+}
+
+impl Widget for Spacer {
+    fn style(&self) -> Style {
+        Style {
+            flex_grow: self.grow,
+            flex_shrink: 1.0,
+            flex_basis: Dimension::Length(0.0),
+            min_size: Size {
+                width: Dimension::Length(0.0),
+                height: Dimension::Length(0.0),
+            },
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::f32::consts::TAU;
+use std::time::{Duration, Instant};
+
+use taffy::prelude::*;
+
+use crate::framework::{DrawContext, Widget};
+use crate::renderer::Renderer;
+
+/// Number of dots around the ring.
+const DOTS: usize = 8;
+
+/// Indeterminate progress indicator: a ring of dots whose brightness
+/// rotates around it.
+///
+/// The animation runs off the wall clock, so it keeps turning as long as the
+/// window redraws. `paint` draws the same ring anywhere, for widgets that
+/// show a spinner without a layout node of its own (see `LoadingOverlay`).
+///
+/// ```ignore
+/// let spinner = Spinner::new().with_size(32.0).with_color([0.9, 0.9, 0.95, 1.0]);
+/// ```
+pub struct Spinner {
+    size: f32,
+    period: Duration,
+    // Colors
+    color: Option<[f32; 4]>,
+    // State
+    start: Instant,
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Spinner {
+    pub fn new() -> Self {
+        Self {
+            size: 24.0,
+            period: Duration::from_millis(900),
+            color: None,
+            start: Instant::now(),
+        }
+    }
+
+    /// Diameter of the ring.
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Time for one full turn.
+    pub fn with_period(mut self, period: Duration) -> Self {
+        self.period = period;
+        self
+    }
+
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    /// Draws the ring centered on `center`, at the current animation phase.
+    pub fn paint(&self, renderer: &mut Renderer, center: (f32, f32)) {
+        let color = self.color.unwrap_or(renderer.theme().info);
+        let period = self.period.as_secs_f32().max(0.001);
+        let phase = (self.start.elapsed().as_secs_f32() / period).fract();
+        renderer.request_next_frame();
+        // Step one dot at a time, like most platform spinners
+        let lead = (phase * DOTS as f32) as usize;
+
+        let dot = self.size * 0.18;
+        let radius = (self.size - dot) / 2.0;
+        for i in 0..DOTS {
+            let angle = i as f32 / DOTS as f32 * TAU - TAU / 4.0;
+            let (cx, cy) = (center.0 + radius * angle.cos(), center.1 + radius * angle.sin());
+            // Brightest at the lead dot, fading along the trail behind it
+            let age = (lead + DOTS - i) % DOTS;
+            let alpha = 1.0 - age as f32 / DOTS as f32 * 0.85;
+            fill_dot(renderer, (cx, cy), dot, [color[0], color[1], color[2], color[3] * alpha]);
+        }
+    }
+}
+
+fn fill_dot(renderer: &mut Renderer, center: (f32, f32), diameter: f32, color: [f32; 4]) {
+    renderer.fill_rect_rounded(
+        (center.0 - diameter / 2.0, center.1 - diameter / 2.0, diameter, diameter),
+        color,
+        diameter / 2.0,
+    );
+}
+
+impl Widget for Spinner {
+    fn style(&self) -> Style {
+        Style {
+            size: Size {
+                width: Dimension::Length(self.size),
+                height: Dimension::Length(self.size),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawContext) {
+        let layout = ctx.layout;
+        let center = (
+            layout.location.x + layout.size.width / 2.0,
+            layout.location.y + layout.size.height / 2.0,
+        );
+        self.paint(ctx.renderer, center);
+    }
+}
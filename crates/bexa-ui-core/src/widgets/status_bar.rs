@@ -0,0 +1,332 @@
+use std::cell::RefCell;
+
+use glyphon::Metrics;
+use glyphon::cosmic_text::Align;
+use taffy::prelude::*;
+use winit::event::{ElementState, MouseButton, WindowEvent};
+
+use crate::framework::{DrawContext, EventContext, Widget};
+use crate::icons::NERD_FONT_FAMILY;
+use crate::signal::{IntoSignal, Signal};
+
+/// One entry of a `StatusBar`: optional icon plus a reactive label.
+pub struct StatusSegment {
+    icon: Option<&'static str>,
+    text: Signal<String>,
+    color: Option<[u8; 3]>,
+    icon_color: Option<[u8; 3]>,
+    on_click: Option<Box<dyn FnMut()>>,
+}
+
+impl StatusSegment {
+    pub fn new(text: impl IntoSignal<String>) -> Self {
+        Self {
+            icon: None,
+            text: text.into_signal(),
+            color: None,
+            icon_color: None,
+            on_click: None,
+        }
+    }
+
+    pub fn with_icon(mut self, icon: &'static str) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Text color; defaults to the bar's text color.
+    pub fn with_color(mut self, color: [u8; 3]) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Icon color; defaults to the segment's text color.
+    pub fn with_icon_color(mut self, color: [u8; 3]) -> Self {
+        self.icon_color = Some(color);
+        self
+    }
+
+    /// Makes the segment clickable (gets a hover highlight).
+    pub fn with_on_click(mut self, handler: impl FnMut() + 'static) -> Self {
+        self.on_click = Some(Box::new(handler));
+        self
+    }
+}
+
+/// Bottom bar with left- and right-aligned segments.
+///
+/// Labels are signals, so segments update as the app state changes. In a
+/// column layout the bar pushes itself to the bottom of its parent.
+///
+/// ```ignore
+/// let bar = StatusBar::new(Metrics::new(12.0, 16.0))
+///     .with_left(StatusSegment::new(status).with_icon(icons::CHECK_CIRCLE))
+///     .with_right(StatusSegment::new("UTF-8"))
+///     .with_right(StatusSegment::new(cursor_pos).with_on_click(|| goto_line()));
+/// ```
+pub struct StatusBar {
+    left: Vec<StatusSegment>,
+    right: Vec<StatusSegment>,
+    metrics: Metrics,
+    padding: f32,
+    spacing: f32,
+    // Colors (`None` follows the theme)
+    bg: Option<[f32; 4]>,
+    border_color: Option<[f32; 4]>,
+    text_color: Option<[u8; 3]>,
+    hover_bg: Option<[f32; 4]>,
+    // State
+    hover: Option<usize>,
+    // Cached absolute segment rects, left segments first (set during draw)
+    segment_rects: RefCell<Vec<(f32, f32, f32, f32)>>,
+    // Text command index per segment, for width feedback
+    text_indices: RefCell<Vec<usize>>,
+    text_widths: Vec<f32>,
+}
+
+impl StatusBar {
+    pub fn new(metrics: Metrics) -> Self {
+        Self {
+            left: Vec::new(),
+            right: Vec::new(),
+            metrics,
+            padding: 6.0,
+            spacing: 8.0,
+            bg: None,
+            border_color: None,
+            text_color: None,
+            hover_bg: None,
+            hover: None,
+            segment_rects: RefCell::new(Vec::new()),
+            text_indices: RefCell::new(Vec::new()),
+            text_widths: Vec::new(),
+        }
+    }
+
+    pub fn with_left(mut self, segment: StatusSegment) -> Self {
+        self.left.push(segment);
+        self
+    }
+
+    pub fn with_right(mut self, segment: StatusSegment) -> Self {
+        self.right.push(segment);
+        self
+    }
+
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Horizontal space between segments (a divider sits in the middle).
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn with_colors(mut self, bg: [f32; 4], border: [f32; 4], hover_bg: [f32; 4]) -> Self {
+        self.bg = Some(bg);
+        self.border_color = Some(border);
+        self.hover_bg = Some(hover_bg);
+        self
+    }
+
+    pub fn with_text_color(mut self, color: [u8; 3]) -> Self {
+        self.text_color = Some(color);
+        self
+    }
+
+    fn segments(&self) -> impl Iterator<Item = &StatusSegment> {
+        self.left.iter().chain(self.right.iter())
+    }
+
+    fn segment_mut(&mut self, index: usize) -> Option<&mut StatusSegment> {
+        let left_len = self.left.len();
+        if index < left_len {
+            self.left.get_mut(index)
+        } else {
+            self.right.get_mut(index - left_len)
+        }
+    }
+
+    fn icon_width(&self) -> f32 {
+        self.metrics.line_height
+    }
+
+    /// Width of segment content: measured last frame, estimated before that.
+    fn segment_width(&self, index: usize, segment: &StatusSegment) -> f32 {
+        let text_w = match self.text_widths.get(index) {
+            Some(&w) if w > 0.0 => w,
+            _ => segment
+                .text
+                .with(|t| t.chars().count() as f32 * self.metrics.font_size * 0.6),
+        };
+        let icon_w = if segment.icon.is_some() {
+            self.icon_width() + 4.0
+        } else {
+            0.0
+        };
+        icon_w + text_w
+    }
+
+    fn segment_at(&self, x: f32, y: f32) -> Option<usize> {
+        let rects = self.segment_rects.borrow();
+        self.segments()
+            .zip(rects.iter())
+            .position(|(segment, &(rx, ry, rw, rh))| {
+                segment.on_click.is_some() && x >= rx && x <= rx + rw && y >= ry && y <= ry + rh
+            })
+    }
+}
+
+impl Widget for StatusBar {
+    fn style(&self) -> Style {
+        let height = self.metrics.line_height + self.padding * 2.0;
+        Style {
+            size: Size {
+                width: Dimension::Percent(1.0),
+                height: Dimension::Length(height),
+            },
+            // Auto top margin pins the bar to the bottom of a column
+            margin: Rect {
+                left: LengthPercentageAuto::Length(0.0),
+                right: LengthPercentageAuto::Length(0.0),
+                top: LengthPercentageAuto::Auto,
+                bottom: LengthPercentageAuto::Length(0.0),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawContext) {
+        let layout = ctx.layout;
+        let x = layout.location.x;
+        let y = layout.location.y;
+        let w = layout.size.width;
+        let h = layout.size.height;
+
+        let [pr, pg, pb] = ctx.theme.panel;
+        let bg = self.bg.unwrap_or([pr, pg, pb, 1.0]);
+        let border = self.border_color.unwrap_or(ctx.theme.border);
+        let hover_bg = self.hover_bg.unwrap_or(ctx.theme.hover);
+        let text_color = self.text_color.unwrap_or(ctx.theme.text_secondary);
+
+        ctx.renderer.fill_rect_rounded((x, y, w, h), bg, 0.0);
+        ctx.renderer.fill_rect_rounded((x, y, w, 1.0), border, 0.0);
+
+        // Lay out left segments from the left edge, right ones from the right edge
+        let count = self.left.len() + self.right.len();
+        let mut rects = vec![(0.0, 0.0, 0.0, 0.0); count];
+        let mut cursor = x + self.padding;
+        for (i, segment) in self.left.iter().enumerate() {
+            let sw = self.segment_width(i, segment);
+            rects[i] = (cursor, y, sw, h);
+            cursor += sw + self.spacing;
+        }
+        let mut cursor = x + w - self.padding;
+        let left_len = self.left.len();
+        for (j, segment) in self.right.iter().enumerate().rev() {
+            let i = left_len + j;
+            let sw = self.segment_width(i, segment);
+            cursor -= sw;
+            rects[i] = (cursor, y, sw, h);
+            cursor -= self.spacing;
+        }
+
+        let mut text_indices = Vec::with_capacity(count);
+        let text_y = y + self.padding;
+        for (i, segment) in self.segments().enumerate() {
+            let (sx, _, sw, _) = rects[i];
+
+            if self.hover == Some(i) {
+                let pad = self.spacing / 2.0;
+                ctx.renderer.fill_rect_rounded(
+                    (sx - pad, y + 2.0, sw + pad * 2.0, h - 4.0),
+                    hover_bg,
+                    3.0,
+                );
+            }
+
+            // Divider between neighbours on the same side
+            let has_next = i + 1 < count && i + 1 != left_len;
+            if has_next {
+                let div_x = sx + sw + self.spacing / 2.0;
+                ctx.renderer.fill_rect_rounded(
+                    (div_x, y + self.padding, 1.0, h - self.padding * 2.0),
+                    border,
+                    0.0,
+                );
+            }
+
+            let color = segment.color.unwrap_or(text_color);
+            let mut text_x = sx;
+            if let Some(icon) = segment.icon {
+                ctx.renderer.draw_text_with_font(
+                    icon,
+                    (sx, text_y),
+                    segment.icon_color.unwrap_or(color),
+                    (self.icon_width(), self.metrics.line_height),
+                    self.metrics,
+                    Align::Center,
+                    NERD_FONT_FAMILY,
+                );
+                text_x += self.icon_width() + 4.0;
+            }
+
+            let idx = segment.text.with(|text| {
+                let chars = text.chars().count();
+                ctx.renderer.draw_text_measured(
+                    text,
+                    (text_x, text_y),
+                    color,
+                    (w, self.metrics.line_height),
+                    self.metrics,
+                    Align::Left,
+                    vec![chars],
+                )
+            });
+            text_indices.push(idx);
+        }
+
+        *self.segment_rects.borrow_mut() = rects;
+        *self.text_indices.borrow_mut() = text_indices;
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        match ctx.event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.hover = self.segment_at(position.x as f32, position.y as f32);
+                false
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } => {
+                let Some(idx) = self.hover else {
+                    return false;
+                };
+                if let Some(handler) = self.segment_mut(idx).and_then(|s| s.on_click.as_mut()) {
+                    handler();
+                    return true;
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn update_measures(&mut self, measures: &[Vec<f32>]) {
+        let indices = self.text_indices.borrow();
+        self.text_widths = indices
+            .iter()
+            .map(|&i| {
+                measures
+                    .get(i)
+                    .and_then(|edges| edges.first().copied())
+                    .unwrap_or(0.0)
+            })
+            .collect();
+    }
+}
@@ -1,17 +1,99 @@
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use arboard::Clipboard;
 use glyphon::Metrics;
 use glyphon::cosmic_text::Align;
 use taffy::prelude::*;
-use winit::event::{ElementState, KeyEvent, MouseButton, WindowEvent};
+use winit::event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta, WindowEvent};
 use winit::keyboard::{Key, ModifiersState, NamedKey};
 
 use crate::framework::{DrawContext, EventContext, Widget};
+use crate::icons;
 use crate::signal::{Signal, SetSignal};
+use crate::theme::{opaque, tint};
+use crate::widgets::popover::{AnchorRect, PopoverSide};
+use crate::widgets::tooltip::{draw_tooltip_bubble, TooltipStyle};
+
+/// Bounds width for cell text, so it stays on one line and is clipped
+/// at the cell edge instead of wrapping.
+const UNWRAPPED_WIDTH: f32 = 10_000.0;
+
+/// Width kept for the sort chevron at the right of a sorted header.
+const SORT_ICON_W: f32 = 14.0;
+
+/// How far from a column's right edge a header press starts a resize.
+const RESIZE_GRAB: f32 = 4.0;
+/// Narrowest a column can be dragged, whatever its `min_width`.
+const MIN_RESIZE_W: f32 = 24.0;
+
+/// What a column renderer draws in a cell.
+///
+/// Cells keep the table's fixed row height whatever their content: anything
+/// taller is clipped, and everything is clipped to the column width. Copying
+/// (Ctrl+C) always uses the raw cell string, not the rendered content.
+pub enum CellContent {
+    /// Plain text in the row's text color.
+    Text(String),
+    /// Text in a fixed color, kept on selected rows too.
+    Colored(String, [u8; 3]),
+    /// Pill-shaped label, sized to its measured text width.
+    Badge {
+        text: String,
+        bg: [f32; 4],
+        color: [u8; 3],
+    },
+    /// Display-only widget drawn into the cell's padded box (full row
+    /// height). Its own style size is ignored and it receives no events.
+    Widget(Box<dyn Widget>),
+}
+
+type CellRenderer = Box<dyn Fn(&str) -> CellContent>;
+
+/// Picks a cell's text color from its (row, column) and string.
+type CellStyle = Box<dyn Fn(usize, usize, &str) -> Option<[u8; 3]>>;
+
+/// (row, column) of a cell.
+type CellId = (usize, usize);
+
+/// Horizontal scroll state for one frame: the first `frozen` columns stay
+/// put, the rest shift left by `offset` and are clipped to the right of the
+/// frozen block.
+struct HScroll {
+    offset: f32,
+    frozen: usize,
+    frozen_w: f32,
+    width: f32,
+}
+
+impl HScroll {
+    /// On-screen x of a column relative to the table's left edge.
+    fn col_x(&self, ci: usize, cx: f32) -> f32 {
+        if ci < self.frozen { cx } else { cx - self.offset }
+    }
+
+    /// Clip for column `ci`'s layer, spanning `top..top + height`.
+    fn layer(&self, ci: usize, x: f32, top: f32, height: f32) -> AnchorRect {
+        if ci < self.frozen {
+            (x, top, self.frozen_w, height)
+        } else {
+            (x + self.frozen_w, top, (self.width - self.frozen_w).max(0.0), height)
+        }
+    }
+}
 
 /// Column definition for a Table.
 pub struct Column {
     pub header: String,
     /// Relative flex weight (e.g. 1.0, 2.0, 3.0).
     pub flex: f32,
+    /// Horizontal alignment of the header and of text and badge cells.
+    pub align: Align,
+    /// Narrowest width in pixels, whatever the flex share.
+    pub min_width: f32,
+    renderer: Option<CellRenderer>,
 }
 
 impl Column {
@@ -19,34 +101,123 @@ impl Column {
         Self {
             header: header.into(),
             flex,
+            align: Align::Left,
+            min_width: 0.0,
+            renderer: None,
         }
     }
+
+    /// Keeps the column at least `width` wide. When the minimums add up to
+    /// more than the table's width, the table scrolls horizontally with
+    /// Shift + wheel (or a horizontal wheel / touchpad swipe).
+    pub fn with_min_width(mut self, width: f32) -> Self {
+        self.min_width = width;
+        self
+    }
+
+    /// Aligns the column's header and cells, e.g. `Align::Right` for numbers.
+    /// Text too wide for the cell stays left-aligned so its start is visible.
+    pub fn with_align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Custom cell rendering: maps each cell string to what gets drawn.
+    ///
+    /// ```ignore
+    /// Column::new("Status", 1.2).with_renderer(|status| match status {
+    ///     "Running" => CellContent::Badge { text: status.into(), bg: GREEN, color: WHITE },
+    ///     _ => CellContent::Text(status.into()),
+    /// })
+    /// ```
+    pub fn with_renderer(mut self, renderer: impl Fn(&str) -> CellContent + 'static) -> Self {
+        self.renderer = Some(Box::new(renderer));
+        self
+    }
 }
 
+/// Header clicks sort the rows by that column: ascending, then descending,
+/// then back to the order of `rows`. Sorting only changes the display
+/// order; `rows` is left as is, and `selected_row` / `with_selected_rows`
+/// keep indexing into it, so a selection follows its row. A column whose
+/// cells all start with a number sorts numerically, others by text. A
+/// trailing unit is ignored (`"12.4%"`, `"30 ms"`), except byte sizes, which
+/// are scaled so `"2.1 GB"` sorts above `"256 MB"`.
 pub struct Table {
     columns: Vec<Column>,
     rows: Signal<Vec<Vec<String>>>,
     selected_row: Signal<Option<usize>>,
     set_selected_row: SetSignal<Option<usize>>,
+    /// Optional multi-row selection; when non-empty it takes precedence for copy.
+    selected_rows: Option<Signal<Vec<usize>>>,
+    on_row_double_click: Option<Box<dyn FnMut(usize)>>,
     metrics: Metrics,
     row_height: f32,
     header_height: f32,
     padding: f32,
-    // Colors
-    header_bg: [f32; 4],
-    header_text: [u8; 3],
-    row_bg: [f32; 4],
-    row_alt_bg: [f32; 4],
-    row_hover_bg: [f32; 4],
-    row_selected_bg: [f32; 4],
-    text_color: [u8; 3],
+    sticky_header: bool,
+    frozen_columns: usize,
+    striped: bool,
+    row_lines: bool,
+    column_lines: bool,
+    resizable: bool,
+    // Colors; `None` derives them from the theme: rows on `panel`, shaded
+    // toward the text for stripes and the header, `hover` and `selection`
+    // behind hovered and selected rows, `text_secondary` for the header text
+    header_bg: Option<[f32; 4]>,
+    header_text: Option<[u8; 3]>,
+    row_bg: Option<[f32; 4]>,
+    row_alt_bg: Option<[f32; 4]>,
+    row_selected_bg: Option<[f32; 4]>,
+    text_color: Option<[u8; 3]>,
     selected_text: [u8; 3],
-    border_color: [f32; 4],
+    /// Gridlines and header border; `None` uses the theme's `border`
+    border_color: Option<[f32; 4]>,
+    cell_style: Option<CellStyle>,
     // State
+    hover: bool,
     hover_row: Option<usize>,
+    /// Focused cell column within the selected row (moved with Left/Right)
+    focus_col: Option<usize>,
     focus: bool,
+    /// Sorted column and whether ascending; `None` keeps the order of `rows`
+    sort: Option<(usize, bool)>,
+    /// Widths set by dragging a header separator, in place of the flex share
+    widths: HashMap<usize, f32>,
+    /// Column being resized, with the cursor x and its width when the drag began
+    resizing: Option<(usize, f32, f32)>,
+    /// Column whose resize handle is under the cursor
+    hover_handle: Option<usize>,
+    /// Column of the last press on a resize handle, to spot a double-click
+    last_handle_press: Option<usize>,
+    /// Display position the arrow keys moved the selection to, for
+    /// `take_scroll_target`
+    reveal_pos: Option<usize>,
     // layout cache
     max_visible: usize,
+    /// Display order of `rows`, for drawing, hit-testing and the arrow keys
+    order: RefCell<Vec<usize>>,
+    /// Hash of `sort` and the rows `order` was built for; it is only
+    /// rebuilt (re-parsed and re-sorted) once either changes
+    order_key: Cell<Option<u64>>,
+    /// Absolute top of the (possibly pinned) header, set during draw
+    header_y: Cell<f32>,
+    // Badge text widths, measured on the previous frame
+    badge_widths: HashMap<String, f32>,
+    badge_indices: RefCell<Vec<(String, usize)>>,
+    // Text command index and available width per (row, column) cell
+    text_indices: RefCell<Vec<(CellId, usize, f32)>>,
+    /// Cells whose text didn't fit last frame
+    truncated: HashSet<CellId>,
+    /// Measured cell text widths from the previous frame, for alignment
+    text_widths: HashMap<CellId, f32>,
+    hover_col: Option<usize>,
+    tooltip_style: TooltipStyle,
+    /// Horizontal scroll offset of the unfrozen columns
+    scroll_x: f32,
+    shift: bool,
+    /// Largest `scroll_x` for the widths of the last draw
+    max_scroll_x: Cell<f32>,
 }
 
 impl Table {
@@ -62,22 +233,51 @@ impl Table {
             rows,
             selected_row,
             set_selected_row,
+            selected_rows: None,
+            on_row_double_click: None,
             metrics,
             row_height: 32.0,
             header_height: 36.0,
             padding: 8.0,
-            header_bg: [0.14, 0.16, 0.22, 1.0],
-            header_text: [180, 190, 220],
-            row_bg: [0.10, 0.12, 0.16, 1.0],
-            row_alt_bg: [0.12, 0.14, 0.19, 1.0],
-            row_hover_bg: [0.18, 0.22, 0.30, 1.0],
-            row_selected_bg: [0.20, 0.45, 0.70, 1.0],
-            text_color: [210, 210, 220],
+            sticky_header: true,
+            frozen_columns: 0,
+            striped: true,
+            row_lines: true,
+            column_lines: false,
+            resizable: false,
+            header_bg: None,
+            header_text: None,
+            row_bg: None,
+            row_alt_bg: None,
+            row_selected_bg: None,
+            text_color: None,
             selected_text: [255, 255, 255],
-            border_color: [0.25, 0.28, 0.35, 1.0],
+            border_color: None,
+            cell_style: None,
+            hover: false,
             hover_row: None,
+            focus_col: None,
             focus: false,
+            sort: None,
+            widths: HashMap::new(),
+            resizing: None,
+            hover_handle: None,
+            last_handle_press: None,
+            reveal_pos: None,
             max_visible: 100,
+            order: RefCell::new(Vec::new()),
+            order_key: Cell::new(None),
+            header_y: Cell::new(0.0),
+            badge_widths: HashMap::new(),
+            badge_indices: RefCell::new(Vec::new()),
+            text_indices: RefCell::new(Vec::new()),
+            truncated: HashSet::new(),
+            text_widths: HashMap::new(),
+            hover_col: None,
+            tooltip_style: TooltipStyle::default(),
+            scroll_x: 0.0,
+            shift: false,
+            max_scroll_x: Cell::new(0.0),
         }
     }
 
@@ -91,11 +291,47 @@ impl Table {
         self
     }
 
+    /// Keeps the header pinned to the top of an enclosing scroll view while
+    /// the rows scroll beneath it (on by default).
+    pub fn with_sticky_header(mut self, sticky: bool) -> Self {
+        self.sticky_header = sticky;
+        self
+    }
+
+    /// Caps the rows shown (100 by default); the table is sized to fit them.
+    /// Only rows inside the enclosing scroll viewport (or the window) are
+    /// drawn, so large caps stay cheap.
     pub fn with_max_visible(mut self, n: usize) -> Self {
         self.max_visible = n;
         self
     }
 
+    /// Pins the first `n` columns (e.g. the row name) while the others
+    /// scroll horizontally; see `Column::with_min_width`. A divider marks the
+    /// boundary, with a shadow once the columns behind it are scrolled.
+    ///
+    /// The sticky header follows the same split: frozen header cells stay
+    /// put and the rest scroll with their columns. Row hover and selection
+    /// backgrounds span both parts; the focused-cell outline is clipped to
+    /// its part.
+    pub fn with_frozen_columns(mut self, n: usize) -> Self {
+        self.frozen_columns = n;
+        self
+    }
+
+    /// Rows included when copying with Ctrl+C (one tab-separated line each).
+    pub fn with_selected_rows(mut self, rows: Signal<Vec<usize>>) -> Self {
+        self.selected_rows = Some(rows);
+        self
+    }
+
+    /// Called with the index in `rows` of a double-clicked row (e.g. to open
+    /// it). The row ends up selected.
+    pub fn with_on_row_double_click(mut self, handler: impl FnMut(usize) + 'static) -> Self {
+        self.on_row_double_click = Some(Box::new(handler));
+        self
+    }
+
     pub fn with_colors(
         mut self,
         header_bg: [f32; 4],
@@ -104,11 +340,40 @@ impl Table {
         selected_bg: [f32; 4],
         border: [f32; 4],
     ) -> Self {
-        self.header_bg = header_bg;
-        self.row_bg = row_bg;
-        self.row_alt_bg = row_alt;
-        self.row_selected_bg = selected_bg;
-        self.border_color = border;
+        self.header_bg = Some(header_bg);
+        self.row_bg = Some(row_bg);
+        self.row_alt_bg = Some(row_alt);
+        self.row_selected_bg = Some(selected_bg);
+        self.border_color = Some(border);
+        self
+    }
+
+    /// Alternates the background of even and odd rows (on by default).
+    pub fn with_striping(mut self, striped: bool) -> Self {
+        self.striped = striped;
+        self
+    }
+
+    /// Horizontal separator under each row (on by default).
+    pub fn with_row_lines(mut self, lines: bool) -> Self {
+        self.row_lines = lines;
+        self
+    }
+
+    /// Vertical lines between columns, through the header and rows (off by
+    /// default). With row lines too, this gives a full grid.
+    pub fn with_column_lines(mut self, lines: bool) -> Self {
+        self.column_lines = lines;
+        self
+    }
+
+    /// Lets columns be resized by dragging the separator at their right edge
+    /// in the header (off by default). A dragged width replaces the column's
+    /// flex share, leaving the others as they were; the table scrolls
+    /// horizontally once the columns are wider than it. Double-click a
+    /// separator to give the column back its flex width.
+    pub fn with_resizable_columns(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
         self
     }
 
@@ -118,12 +383,32 @@ impl Table {
         row: [u8; 3],
         selected: [u8; 3],
     ) -> Self {
-        self.header_text = header;
-        self.text_color = row;
+        self.header_text = Some(header);
+        self.text_color = Some(row);
         self.selected_text = selected;
         self
     }
 
+    /// Colors cell text per cell: `style(row, column, text)` returns the
+    /// color, or `None` for the usual row / selected-row color. `row` indexes
+    /// `rows`, whatever the sort. The color is kept on selected rows, and
+    /// applies to text cells and `CellContent::Text` from a column renderer.
+    ///
+    /// ```ignore
+    /// table.with_cell_style(|_, col, text| match (col, text) {
+    ///     (1, "Running") => Some([80, 200, 120]),
+    ///     (1, "Stopped") => Some([230, 90, 90]),
+    ///     _ => None,
+    /// })
+    /// ```
+    pub fn with_cell_style(
+        mut self,
+        style: impl Fn(usize, usize, &str) -> Option<[u8; 3]> + 'static,
+    ) -> Self {
+        self.cell_style = Some(Box::new(style));
+        self
+    }
+
     fn total_flex(&self) -> f32 {
         self.columns.iter().map(|c| c.flex).sum::<f32>().max(1.0)
     }
@@ -132,30 +417,460 @@ impl Table {
         let total_flex = self.total_flex();
         let mut result = Vec::with_capacity(self.columns.len());
         let mut cx = 0.0;
-        for col in &self.columns {
-            let w = (col.flex / total_flex) * total_w;
+        for (ci, col) in self.columns.iter().enumerate() {
+            let flex_w = (col.flex / total_flex) * total_w;
+            let w = self.widths.get(&ci).copied().unwrap_or(flex_w).max(col.min_width);
             result.push((cx, w));
             cx += w;
         }
         result
     }
 
+    /// Indices into `rows` in display order, sorted per `sort`.
+    fn display_order(&self) -> Ref<'_, Vec<usize>> {
+        self.refresh_order();
+        self.order.borrow()
+    }
+
+    /// Rebuilds `order` if the sort or the rows changed since it was built.
+    /// Hashing the row count and the sorted column is much cheaper than
+    /// parsing and sorting them again every frame.
+    fn refresh_order(&self) {
+        let key = self.rows.with(|rows| {
+            let mut hasher = DefaultHasher::new();
+            self.sort.hash(&mut hasher);
+            rows.len().hash(&mut hasher);
+            if let Some((col, _)) = self.sort {
+                for row in rows {
+                    row.get(col).hash(&mut hasher);
+                }
+            }
+            hasher.finish()
+        });
+        if self.order_key.get() != Some(key) {
+            *self.order.borrow_mut() = self.sorted_order();
+            self.order_key.set(Some(key));
+        }
+    }
+
+    /// `order` built from scratch.
+    fn sorted_order(&self) -> Vec<usize> {
+        self.rows.with(|rows| {
+            let mut order: Vec<usize> = (0..rows.len()).collect();
+            let Some((col, ascending)) = self.sort else {
+                return order;
+            };
+            let cell = |ri: usize| rows[ri].get(col).map_or("", |s| s.as_str());
+            let directed = |ord: std::cmp::Ordering| if ascending { ord } else { ord.reverse() };
+            let numbers: Option<Vec<f64>> = order.iter().map(|&ri| sort_number(cell(ri))).collect();
+            match numbers {
+                Some(numbers) => order.sort_by(|&a, &b| directed(numbers[a].total_cmp(&numbers[b]))),
+                None => order.sort_by(|&a, &b| directed(cell(a).cmp(cell(b)))),
+            }
+            order
+        })
+    }
+
+    /// Indices into `rows` in the order they're shown.
+    pub fn row_order(&self) -> Vec<usize> {
+        self.display_order().clone()
+    }
+
+    /// Header click on `column`: ascending, then descending, then unsorted.
+    ///
+    /// ```
+    /// use bexa_ui_core::{create_signal, Column, Metrics, Table};
+    ///
+    /// let rows = vec![
+    ///     vec!["256 MB".to_string(), "12.4%".to_string(), "10".to_string()],
+    ///     vec!["2.1 GB".to_string(), "9%".to_string(), "9".to_string()],
+    ///     vec!["900 KB".to_string(), "13%".to_string(), "n/a".to_string()],
+    /// ];
+    /// let columns = vec![Column::new("Size", 1.0), Column::new("CPU", 1.0), Column::new("Threads", 1.0)];
+    /// let (rows, _) = create_signal(rows);
+    /// let (selected, set_selected) = create_signal(None);
+    /// let mut table = Table::new(columns, rows, selected, set_selected, Metrics::new(14.0, 20.0));
+    ///
+    /// // Byte sizes compare scaled: 900 KB, 256 MB, 2.1 GB
+    /// table.toggle_sort(0);
+    /// assert_eq!(table.row_order(), vec![2, 0, 1]);
+    /// table.toggle_sort(0);
+    /// assert_eq!(table.row_order(), vec![1, 0, 2]);
+    /// table.toggle_sort(0);
+    /// assert_eq!(table.row_order(), vec![0, 1, 2]);
+    ///
+    /// // "12.4%" sorts as 12.4, between 9 and 13 (by text it would come first)
+    /// table.toggle_sort(1);
+    /// assert_eq!(table.row_order(), vec![1, 0, 2]);
+    ///
+    /// // One cell without a number: the whole column sorts by text, "10"
+    /// // before "9"
+    /// table.toggle_sort(2);
+    /// assert_eq!(table.row_order(), vec![0, 1, 2]);
+    /// table.toggle_sort(2);
+    /// assert_eq!(table.row_order(), vec![2, 1, 0]);
+    /// ```
+    pub fn toggle_sort(&mut self, column: usize) {
+        self.sort = match self.sort {
+            Some((col, true)) if col == column => Some((column, false)),
+            Some((col, false)) if col == column => None,
+            _ => Some((column, true)),
+        };
+        self.refresh_order();
+    }
+
+    /// Text copied by Ctrl+C: the focused cell, or the selected row(s) as
+    /// tab-separated lines.
+    ///
+    /// ```
+    /// use bexa_ui_core::{create_signal, Column, Metrics, Table};
+    /// use winit::keyboard::{Key, ModifiersState, NamedKey};
+    ///
+    /// let rows = vec![
+    ///     vec!["ada".to_string(), "admin".to_string()],
+    ///     vec!["bob".to_string(), "viewer".to_string()],
+    ///     vec!["cy".to_string(), "editor".to_string()],
+    /// ];
+    /// let (rows, _) = create_signal(rows);
+    /// let (selected, set_selected) = create_signal(Some(1));
+    /// let (multi, set_multi) = create_signal(Vec::new());
+    /// let columns = vec![Column::new("Name", 1.0), Column::new("Role", 1.0)];
+    /// let mut table = Table::new(columns, rows, selected, set_selected, Metrics::new(14.0, 20.0))
+    ///     .with_selected_rows(multi);
+    ///
+    /// // The whole row, then the focused cell once Right moved into the row
+    /// assert_eq!(table.copy_text().as_deref(), Some("bob\tviewer"));
+    /// table.handle_key(&Key::Named(NamedKey::ArrowRight), ModifiersState::empty());
+    /// table.handle_key(&Key::Named(NamedKey::ArrowRight), ModifiersState::empty());
+    /// assert_eq!(table.copy_text().as_deref(), Some("viewer"));
+    ///
+    /// // Several selected rows copy as lines, in row order
+    /// set_multi.set(vec![2, 0]);
+    /// assert_eq!(table.copy_text().as_deref(), Some("ada\tadmin\ncy\teditor"));
+    /// ```
+    pub fn copy_text(&self) -> Option<String> {
+        let multi = self
+            .selected_rows
+            .as_ref()
+            .map(|s| s.get())
+            .filter(|v| !v.is_empty());
+        let selected = self.selected_row.get();
+
+        self.rows.with(|rows| {
+            if multi.is_none()
+                && let (Some(ri), Some(ci)) = (selected, self.focus_col)
+            {
+                return rows.get(ri).and_then(|r| r.get(ci)).cloned();
+            }
+
+            let mut indices = multi.unwrap_or_else(|| selected.into_iter().collect());
+            indices.sort_unstable();
+            indices.dedup();
+            let lines: Vec<String> = indices
+                .iter()
+                .filter_map(|&i| rows.get(i))
+                .map(|r| r.join("\t"))
+                .collect();
+            if lines.is_empty() { None } else { Some(lines.join("\n")) }
+        })
+    }
+
+    fn copy_to_clipboard(&self) -> bool {
+        let Some(text) = self.copy_text() else {
+            return false;
+        };
+        if let Ok(mut cb) = Clipboard::new() {
+            let _ = cb.set_text(text);
+        }
+        true
+    }
+
+    /// Handles a press of `key`, as `Widget::handle_key_event` does:
+    /// Up/Down/Home/End move the selection, Left/Right the focused cell, and
+    /// Ctrl+C copies (see `copy_text`).
+    pub fn handle_key(&mut self, key: &Key, modifiers: ModifiersState) -> bool {
+        let count = self.rows.with(|r| r.len());
+        if count == 0 {
+            return false;
+        }
+        if modifiers.control_key()
+            && let Key::Character(c) = key
+            && c.as_str() == "c"
+        {
+            return self.copy_to_clipboard();
+        }
+        match key {
+            Key::Named(NamedKey::ArrowRight) => {
+                let last = self.columns.len().saturating_sub(1);
+                self.focus_col = Some(self.focus_col.map_or(0, |c| (c + 1).min(last)));
+                true
+            }
+            Key::Named(NamedKey::ArrowLeft) => {
+                if let Some(c) = self.focus_col {
+                    self.focus_col = Some(c.saturating_sub(1));
+                    true
+                } else {
+                    false
+                }
+            }
+            Key::Named(NamedKey::Escape) => {
+                // Leave cell mode, back to whole-row selection
+                self.focus_col.take().is_some()
+            }
+            Key::Named(NamedKey::ArrowDown) => {
+                let (next, ri) = {
+                    let order = self.display_order();
+                    let current = self.selected_row.get().and_then(|ri| order.iter().position(|&r| r == ri)).unwrap_or(0);
+                    let next = (current + 1).min(count - 1);
+                    (next, order[next])
+                };
+                self.set_selected_row.set(Some(ri));
+                self.reveal_pos = Some(next);
+                true
+            }
+            Key::Named(NamedKey::ArrowUp) => {
+                let (next, ri) = {
+                    let order = self.display_order();
+                    let current = self.selected_row.get().and_then(|ri| order.iter().position(|&r| r == ri)).unwrap_or(0);
+                    let next = current.saturating_sub(1);
+                    (next, order[next])
+                };
+                self.set_selected_row.set(Some(ri));
+                self.reveal_pos = Some(next);
+                true
+            }
+            Key::Named(NamedKey::Home) => {
+                self.set_selected_row.set(self.display_order().first().copied());
+                self.reveal_pos = Some(0);
+                true
+            }
+            Key::Named(NamedKey::End) => {
+                self.set_selected_row.set(self.display_order().last().copied());
+                self.reveal_pos = Some(count - 1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Horizontal scroll layout for a table `width` wide.
+    fn h_scroll(&self, col_info: &[(f32, f32)], width: f32) -> HScroll {
+        let frozen = self.frozen_columns.min(col_info.len());
+        let frozen_w = col_info[..frozen].iter().map(|&(_, cw)| cw).sum::<f32>().min(width);
+        let content_w = col_info.last().map_or(0.0, |&(cx, cw)| cx + cw);
+        let max_scroll = (content_w - width).max(0.0);
+        HScroll {
+            offset: self.scroll_x.min(max_scroll),
+            frozen,
+            frozen_w,
+            width,
+        }
+    }
+
+    /// Column under `rel_x`, measured from the table's left edge.
+    fn col_at(&self, col_info: &[(f32, f32)], hs: &HScroll, rel_x: f32) -> Option<usize> {
+        col_info.iter().enumerate().position(|(ci, &(cx, cw))| {
+            let left = hs.col_x(ci, cx);
+            // Scrolled columns hidden behind the frozen block don't count
+            let visible_left = if ci < hs.frozen { left } else { left.max(hs.frozen_w) };
+            rel_x >= visible_left && rel_x < left + cw
+        })
+    }
+
+    /// Column whose right edge is within grab distance of `rel_x`.
+    fn resize_handle_at(&self, col_info: &[(f32, f32)], hs: &HScroll, rel_x: f32) -> Option<usize> {
+        col_info.iter().enumerate().position(|(ci, &(cx, cw))| {
+            let right = hs.col_x(ci, cx) + cw;
+            let hidden = ci >= hs.frozen && right <= hs.frozen_w;
+            !hidden && (rel_x - right).abs() <= RESIZE_GRAB
+        })
+    }
+
+    /// Whether `(px, py)` is over the (possibly pinned) header.
+    fn in_header(&self, layout: &Layout, (px, py): (f32, f32)) -> bool {
+        let header_y = self.header_y.get();
+        px >= layout.location.x
+            && px <= layout.location.x + layout.size.width
+            && py >= header_y
+            && py < header_y + self.header_height
+    }
+
+    fn align_of(&self, column: usize) -> Align {
+        self.columns.get(column).map_or(Align::Left, |c| c.align)
+    }
+
+    /// Left offset placing content of `content_w` in a cell of `cell_w`.
+    /// Unwrapped text is laid out from its left edge, so alignment is done
+    /// here with the width measured last frame.
+    fn align_offset(align: Align, content_w: f32, cell_w: f32) -> f32 {
+        let free = (cell_w - content_w).max(0.0);
+        match align {
+            Align::Right | Align::End => free,
+            Align::Center => free / 2.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Draws cell text on one line, clipped to the cell, and records its
+    /// measured width so truncated cells can show the full text on hover.
+    fn draw_cell_text(
+        &self,
+        ctx: &mut DrawContext,
+        cell_id: CellId,
+        text: &str,
+        cell: AnchorRect,
+        color: [u8; 3],
+    ) {
+        let (cx, cy, cw, ch) = cell;
+        let text_y = cy + (ch - self.metrics.line_height) / 2.0;
+        let text_w = self
+            .text_widths
+            .get(&cell_id)
+            .copied()
+            .unwrap_or(text.chars().count() as f32 * self.metrics.font_size * 0.6);
+        let text_x = cx + Self::align_offset(self.align_of(cell_id.1), text_w, cw);
+        ctx.renderer.push_clip_within(cell);
+        let idx = ctx.renderer.draw_text_measured(
+            text,
+            (text_x, text_y),
+            color,
+            (UNWRAPPED_WIDTH, self.metrics.line_height),
+            self.metrics,
+            Align::Left,
+            vec![text.chars().count()],
+        );
+        ctx.renderer.pop_clip();
+        self.text_indices.borrow_mut().push((cell_id, idx, cw));
+    }
+
+    /// Draws one cell's content into its padded box.
+    fn draw_cell(
+        &self,
+        ctx: &mut DrawContext,
+        cell_id: CellId,
+        text: &str,
+        cell: AnchorRect,
+        color: [u8; 3],
+    ) {
+        let (cx, cy, cw, ch) = cell;
+        let content = match self.columns.get(cell_id.1).and_then(|c| c.renderer.as_ref()) {
+            Some(renderer) => renderer(text),
+            None => CellContent::Text(text.to_string()),
+        };
+
+        match content {
+            CellContent::Text(text) => self.draw_cell_text(ctx, cell_id, &text, cell, color),
+            CellContent::Colored(text, color) => self.draw_cell_text(ctx, cell_id, &text, cell, color),
+            CellContent::Badge { text, bg, color } => {
+                let badge_metrics = Metrics::new(self.metrics.font_size * 0.85, self.metrics.line_height * 0.85);
+                let pad_x = 8.0;
+                let text_w = self
+                    .badge_widths
+                    .get(&text)
+                    .copied()
+                    .unwrap_or(text.chars().count() as f32 * badge_metrics.font_size * 0.6);
+                let bw = (text_w + pad_x * 2.0).min(cw);
+                let bh = (badge_metrics.line_height + 4.0).min(ch);
+                let bx = cx + Self::align_offset(self.align_of(cell_id.1), bw, cw);
+                let by = cy + (ch - bh) / 2.0;
+                ctx.renderer.push_clip_within(cell);
+                ctx.renderer.fill_rect_rounded((bx, by, bw, bh), bg, bh / 2.0);
+                let chars = text.chars().count();
+                let idx = ctx.renderer.draw_text_measured(
+                    &text,
+                    (bx + pad_x, by + (bh - badge_metrics.line_height) / 2.0),
+                    color,
+                    ((bw - pad_x * 2.0).max(0.0), badge_metrics.line_height),
+                    badge_metrics,
+                    Align::Left,
+                    vec![chars],
+                );
+                ctx.renderer.pop_clip();
+                self.badge_indices.borrow_mut().push((text, idx));
+            }
+            CellContent::Widget(widget) => {
+                let mut layout = *ctx.layout;
+                layout.location = taffy::geometry::Point { x: cx, y: cy };
+                layout.size = Size { width: cw, height: ch };
+                ctx.renderer.push_clip_within(cell);
+                widget.draw(&mut DrawContext {
+                    renderer: &mut *ctx.renderer,
+                    layout: &layout,
+                    theme: ctx.theme,
+                });
+                ctx.renderer.pop_clip();
+            }
+        }
+    }
+
+    /// Column lines (if enabled) and the freeze divider, from `top` down
+    /// `height`.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_grid(
+        &self,
+        ctx: &mut DrawContext,
+        col_info: &[(f32, f32)],
+        hs: &HScroll,
+        x: f32,
+        top: f32,
+        height: f32,
+        color: [f32; 4],
+    ) {
+        if self.column_lines {
+            for (ci, &(cx, _)) in col_info.iter().enumerate().skip(1) {
+                let lx = hs.col_x(ci, cx);
+                if ci >= hs.frozen && (lx <= hs.frozen_w || lx >= hs.width) {
+                    continue;
+                }
+                ctx.renderer.fill_rect_rounded((x + lx - 0.5, top, 0.5, height), color, 0.0);
+            }
+        }
+        if hs.frozen > 0 && self.max_scroll_x.get() > 0.0 {
+            let bx = x + hs.frozen_w;
+            if hs.offset > 0.0 {
+                ctx.renderer.fill_rect_rounded((bx, top, 6.0, height), [0.0, 0.0, 0.0, 0.18], 0.0);
+                ctx.renderer.fill_rect_rounded((bx, top, 3.0, height), [0.0, 0.0, 0.0, 0.18], 0.0);
+            }
+            ctx.renderer.fill_rect_rounded((bx - 1.0, top, 1.0, height), color, 0.0);
+        }
+    }
+
+    /// Row of `rows` under `y`, if any.
     fn row_at(&self, layout: &Layout, y: f32) -> Option<usize> {
         let ly = layout.location.y;
         let data_y = ly + self.header_height;
-        if y < data_y {
+        // Rows scrolled under a pinned header aren't hoverable
+        let header_y = self.header_y.get();
+        if y < data_y || (y >= header_y && y < header_y + self.header_height) {
             return None;
         }
-        let idx = ((y - data_y) / self.row_height) as usize;
-        let count = self.rows.with(|r| r.len());
-        if idx < count.min(self.max_visible) {
-            Some(idx)
+        let pos = ((y - data_y) / self.row_height) as usize;
+        if pos < self.max_visible {
+            self.order.borrow().get(pos).copied()
         } else {
             None
         }
     }
 }
 
+/// Numeric sort key of a cell: its leading number, scaled by a byte-size
+/// unit (`KB`, `MiB`, ...) after it; other suffixes are ignored.
+fn sort_number(cell: &str) -> Option<f64> {
+    let cell = cell.trim();
+    let end = cell
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+')))
+        .unwrap_or(cell.len());
+    let number: f64 = cell[..end].parse().ok()?;
+    let scale = match cell[end..].trim().to_ascii_uppercase().as_str() {
+        "K" | "KB" | "KIB" => 1024f64,
+        "M" | "MB" | "MIB" => 1024f64.powi(2),
+        "G" | "GB" | "GIB" => 1024f64.powi(3),
+        "T" | "TB" | "TIB" => 1024f64.powi(4),
+        _ => 1.0,
+    };
+    Some(number * scale)
+}
+
 impl Widget for Table {
     fn style(&self) -> Style {
         let row_count = self.rows.with(|r| r.len().min(self.max_visible));
@@ -177,56 +892,71 @@ impl Widget for Table {
         let w = layout.size.width;
         let selected = self.selected_row.get();
         let col_info = self.col_x_width(w);
+        let hs = self.h_scroll(&col_info, w);
+        self.max_scroll_x
+            .set(col_info.last().map_or(0.0, |&(cx, cw)| cx + cw - w).max(0.0));
+        let theme = ctx.theme;
+        let border_color = self.border_color.unwrap_or(theme.border);
+        let row_bg = self.row_bg.unwrap_or(opaque(theme.panel));
+        let row_alt_bg = self.row_alt_bg.unwrap_or(tint(theme.panel, theme.text_primary, 0.03));
+        let row_hover_bg = theme.hover;
+        let row_selected_bg = self.row_selected_bg.unwrap_or(theme.selection);
+        let header_bg = self.header_bg.unwrap_or(tint(theme.panel, theme.text_primary, 0.08));
+        let header_text = self.header_text.unwrap_or(theme.text_secondary);
+        let text_color = self.text_color.unwrap_or(theme.text_primary);
+        self.badge_indices.borrow_mut().clear();
+        self.text_indices.borrow_mut().clear();
 
-        // Header background
-        ctx.renderer.fill_rect_rounded(
-            (x, y, w, self.header_height),
-            self.header_bg,
-            0.0,
-        );
+        let row_count = self.rows.with(|r| r.len().min(self.max_visible));
+        let total_h = self.header_height + row_count as f32 * self.row_height;
 
-        // Header text
-        let header_metrics = Metrics::new(
-            self.metrics.font_size * 0.85,
-            self.metrics.line_height,
-        );
-        for (i, col) in self.columns.iter().enumerate() {
-            let (cx, cw) = col_info[i];
-            let text_y = y + (self.header_height - header_metrics.line_height) / 2.0;
-            ctx.renderer.draw_text(
-                &col.header.to_uppercase(),
-                (x + cx + self.padding, text_y),
-                self.header_text,
-                ((cw - self.padding * 2.0).max(0.0), header_metrics.line_height),
-                header_metrics,
-                Align::Left,
-            );
-        }
+        // Visible band: the enclosing clip (a scroll viewport) if any, else
+        // down to the bottom of the window
+        let clip = ctx.renderer.current_clip();
+        let window_bottom = match ctx.renderer.viewport_size().1 {
+            h if h > 0.0 => h,
+            _ => f32::MAX,
+        };
+        let (view_top, view_bottom) = clip.map_or((f32::MIN, window_bottom), |(_, cy, _, ch)| (cy, cy + ch));
 
-        // Header bottom border
-        ctx.renderer.fill_rect_rounded(
-            (x, y + self.header_height - 1.0, w, 1.0),
-            self.border_color,
-            0.0,
-        );
+        // A sticky header rides the viewport top until the table scrolls out
+        let header_y = if self.sticky_header {
+            y.max(view_top).min(y + total_h - self.header_height)
+        } else {
+            y
+        };
+        self.header_y.set(header_y);
 
-        // Data rows
+        // Rows are clipped below the header so their text can't bleed over it
+        let rows_top = view_top.max(header_y + self.header_height);
+        let rows_clip = match clip {
+            Some((cx, _, cw, _)) => (cx, rows_top, cw, (view_bottom - rows_top).max(0.0)),
+            None => (x, rows_top, w, (y + total_h - rows_top).max(0.0)),
+        };
+
+        // Data rows: only the range intersecting the visible band, found
+        // from the fixed row height rather than by walking every row
+        let order = self.display_order();
+        let data_y = y + self.header_height;
+        let first = ((rows_top - data_y) / self.row_height).floor().max(0.0) as usize;
+        let last = (((view_bottom - data_y) / self.row_height).ceil().max(0.0) as usize).min(row_count);
+        ctx.renderer.push_clip(rows_clip);
         self.rows.with(|rows| {
-            let count = rows.len().min(self.max_visible);
-            for ri in 0..count {
-                let ry = y + self.header_height + ri as f32 * self.row_height;
+            for (pos, &ri) in order.iter().enumerate().take(last).skip(first) {
+                let row = &rows[ri];
+                let ry = data_y + pos as f32 * self.row_height;
                 let is_selected = selected == Some(ri);
                 let is_hover = self.hover_row == Some(ri);
 
                 // Row background
                 let row_bg = if is_selected {
-                    self.row_selected_bg
+                    row_selected_bg
                 } else if is_hover {
-                    self.row_hover_bg
-                } else if ri % 2 == 0 {
-                    self.row_bg
+                    row_hover_bg
+                } else if pos % 2 == 0 || !self.striped {
+                    row_bg
                 } else {
-                    self.row_alt_bg
+                    row_alt_bg
                 };
 
                 ctx.renderer.fill_rect_rounded(
@@ -239,45 +969,152 @@ impl Widget for Table {
                 let tc = if is_selected {
                     self.selected_text
                 } else {
-                    self.text_color
+                    text_color
                 };
 
-                let row = &rows[ri];
-                for (ci, (cx, cw)) in col_info.iter().enumerate() {
+                for (ci, &(cx, cw)) in col_info.iter().enumerate() {
                     let cell_text = row.get(ci).map(|s| s.as_str()).unwrap_or("");
-                    let text_y = ry + (self.row_height - self.metrics.line_height) / 2.0;
-                    ctx.renderer.draw_text(
-                        cell_text,
-                        (x + cx + self.padding, text_y),
-                        tc,
-                        ((cw - self.padding * 2.0).max(0.0), self.metrics.line_height),
-                        self.metrics,
-                        Align::Left,
+                    let color = self.cell_style.as_ref().and_then(|style| style(ri, ci, cell_text)).unwrap_or(tc);
+                    let cell_x = x + hs.col_x(ci, cx);
+                    let cell = (cell_x + self.padding, ry, (cw - self.padding * 2.0).max(0.0), self.row_height);
+                    ctx.renderer.push_clip_within(hs.layer(ci, x, y, total_h));
+                    self.draw_cell(ctx, (ri, ci), cell_text, cell, color);
+                    ctx.renderer.pop_clip();
+                }
+
+                // Focused cell outline
+                if is_selected
+                    && self.focus
+                    && let Some((ci, &(cx, cw))) = self.focus_col.and_then(|ci| Some((ci, col_info.get(ci)?)))
+                {
+                    ctx.renderer.push_clip_within(hs.layer(ci, x, y, total_h));
+                    ctx.renderer.fill_rect_styled(
+                        (x + hs.col_x(ci, cx) + 1.0, ry + 1.0, cw - 2.0, self.row_height - 2.0),
+                        [0.0, 0.0, 0.0, 0.0],
+                        2.0,
+                        1.5,
+                        [0.85, 0.90, 1.0, 1.0],
                     );
+                    ctx.renderer.pop_clip();
                 }
 
                 // Row separator
-                ctx.renderer.fill_rect_rounded(
-                    (x, ry + self.row_height - 0.5, w, 0.5),
-                    self.border_color,
-                    0.0,
-                );
+                if self.row_lines {
+                    ctx.renderer.fill_rect_rounded(
+                        (x, ry + self.row_height - 0.5, w, 0.5),
+                        border_color,
+                        0.0,
+                    );
+                }
             }
         });
+        self.draw_grid(ctx, &col_info, &hs, x, data_y, total_h - self.header_height, border_color);
+        ctx.renderer.pop_clip();
+
+        // Header background
+        ctx.renderer.fill_rect_rounded(
+            (x, header_y, w, self.header_height),
+            header_bg,
+            0.0,
+        );
+        self.draw_grid(ctx, &col_info, &hs, x, header_y, self.header_height, border_color);
+
+        // Resize handle being hovered or dragged
+        if let Some(ci) = self.resizing.map(|(ci, _, _)| ci).or(self.hover_handle)
+            && let Some(&(cx, cw)) = col_info.get(ci)
+        {
+            let right = x + hs.col_x(ci, cx) + cw;
+            ctx.renderer.push_clip_within(hs.layer(ci, x, y, total_h));
+            ctx.renderer.fill_rect_rounded(
+                (right - 1.0, header_y, 2.0, self.header_height),
+                ctx.theme.focus_ring,
+                0.0,
+            );
+            ctx.renderer.pop_clip();
+        }
+
+        // Header text
+        let header_metrics = Metrics::new(
+            self.metrics.font_size * 0.85,
+            self.metrics.line_height,
+        );
+        for (i, col) in self.columns.iter().enumerate() {
+            let (cx, cw) = col_info[i];
+            let text_y = header_y + (self.header_height - header_metrics.line_height) / 2.0;
+            let sorted = self.sort.filter(|&(col, _)| col == i);
+            let icon_w = if sorted.is_some() { SORT_ICON_W } else { 0.0 };
+            let text_w = (cw - self.padding * 2.0 - icon_w).max(0.0);
+            let text_x = x + hs.col_x(i, cx) + self.padding;
+            ctx.renderer.push_clip_within(hs.layer(i, x, y, total_h));
+            ctx.renderer.draw_text(
+                &col.header.to_uppercase(),
+                (text_x, text_y),
+                header_text,
+                (text_w, header_metrics.line_height),
+                header_metrics,
+                col.align,
+            );
+            if let Some((_, ascending)) = sorted {
+                let chevron = if ascending { icons::CHEVRON_UP } else { icons::CHEVRON_DOWN };
+                ctx.renderer.draw_text_with_font(
+                    chevron,
+                    (text_x + text_w, text_y),
+                    header_text,
+                    (icon_w, header_metrics.line_height),
+                    Metrics::new(header_metrics.font_size * 0.8, header_metrics.line_height),
+                    Align::Center,
+                    icons::NERD_FONT_FAMILY,
+                );
+            }
+            ctx.renderer.pop_clip();
+        }
+
+        // Header bottom border
+        ctx.renderer.fill_rect_rounded(
+            (x, header_y + self.header_height - 1.0, w, 1.0),
+            border_color,
+            0.0,
+        );
+
+        // Full text of a hovered truncated cell
+        if let (Some(ri), Some(ci)) = (self.hover_row, self.hover_col) {
+            let pos = self.order.borrow().iter().position(|&r| r == ri);
+            if let Some(pos) = pos.filter(|_| self.truncated.contains(&(ri, ci))) {
+                let (cx, cw) = col_info[ci];
+                let ry = y + self.header_height + pos as f32 * self.row_height;
+                let anchor = (x + hs.col_x(ci, cx) + self.padding, ry, (cw - self.padding * 2.0).max(0.0), self.row_height);
+                self.rows.with(|rows| {
+                    if let Some(text) = rows.get(ri).and_then(|r| r.get(ci)) {
+                        let style = &self.tooltip_style;
+                        draw_tooltip_bubble(ctx.renderer, anchor, text, "", PopoverSide::Bottom, 4.0, style);
+                    }
+                });
+            }
+        }
 
         // Focus ring
         if self.focus {
-            let total_h = self.header_height + self.rows.with(|r| r.len().min(self.max_visible)) as f32 * self.row_height;
             ctx.renderer.fill_rect_styled(
                 (x, y, w, total_h),
                 [0.0, 0.0, 0.0, 0.0],
                 0.0,
                 2.0,
-                [0.3, 0.6, 0.9, 1.0],
+                ctx.theme.focus_ring,
             );
         }
     }
 
+    fn on_mouse_enter(&mut self) {
+        self.hover = true;
+    }
+
+    fn on_mouse_leave(&mut self) {
+        self.hover = false;
+        self.hover_row = None;
+        self.hover_col = None;
+        self.hover_handle = None;
+    }
+
     fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
         let layout = ctx.layout;
 
@@ -285,28 +1122,95 @@ impl Widget for Table {
             WindowEvent::CursorMoved { position, .. } => {
                 let px = position.x as f32;
                 let py = position.y as f32;
-                let inside = px >= layout.location.x
-                    && px <= layout.location.x + layout.size.width
-                    && py >= layout.location.y
-                    && py <= layout.location.y + layout.size.height;
 
-                let new_hover = if inside {
+                if let Some((ci, start_x, start_w)) = self.resizing {
+                    let min_w = self.columns[ci].min_width.max(MIN_RESIZE_W);
+                    self.widths.insert(ci, (start_w + px - start_x).max(min_w));
+                }
+                self.hover_handle = if self.resizable && self.in_header(layout, (px, py)) {
+                    let col_info = self.col_x_width(layout.size.width);
+                    let hs = self.h_scroll(&col_info, layout.size.width);
+                    self.resize_handle_at(&col_info, &hs, px - layout.location.x)
+                } else {
+                    None
+                };
+
+                let new_hover = if self.hover {
                     self.row_at(layout, py)
                 } else {
                     None
                 };
 
                 self.hover_row = new_hover;
+                self.hover_col = new_hover.and_then(|_| {
+                    let col_info = self.col_x_width(layout.size.width);
+                    let hs = self.h_scroll(&col_info, layout.size.width);
+                    self.col_at(&col_info, &hs, px - layout.location.x)
+                });
                 false // don't consume — let siblings update hover too
             }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.shift = modifiers.state().shift_key();
+                false
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let max_scroll = self.max_scroll_x.get();
+                let (px, py) = ctx.cursor;
+                let inside = px >= layout.location.x
+                    && px <= layout.location.x + layout.size.width
+                    && py >= layout.location.y
+                    && py <= layout.location.y + layout.size.height;
+                if max_scroll <= 0.0 || !inside {
+                    return false;
+                }
+                // Shift turns the vertical wheel sideways
+                let (dx, dy) = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => (x * 40.0, y * 40.0),
+                    MouseScrollDelta::PixelDelta(d) => (d.x as f32, d.y as f32),
+                };
+                let dx = if self.shift { dy } else { dx };
+                if dx == 0.0 {
+                    return false;
+                }
+                self.scroll_x = (self.scroll_x.min(max_scroll) - dx).clamp(0.0, max_scroll);
+                true
+            }
             WindowEvent::MouseInput {
                 state: ElementState::Pressed,
                 button: MouseButton::Left,
                 ..
             } => {
+                if self.in_header(layout, ctx.cursor) {
+                    let rel_x = ctx.cursor.0 - layout.location.x;
+                    let col_info = self.col_x_width(layout.size.width);
+                    let hs = self.h_scroll(&col_info, layout.size.width);
+                    let handle = self.resize_handle_at(&col_info, &hs, rel_x).filter(|_| self.resizable);
+                    if let Some(ci) = handle {
+                        if ctx.double_click && self.last_handle_press == Some(ci) {
+                            self.widths.remove(&ci);
+                            self.last_handle_press = None;
+                        } else {
+                            self.resizing = Some((ci, ctx.cursor.0, col_info[ci].1));
+                            self.last_handle_press = Some(ci);
+                        }
+                        return true;
+                    }
+                    if let Some(ci) = self.col_at(&col_info, &hs, rel_x) {
+                        self.toggle_sort(ci);
+                        return true;
+                    }
+                }
                 if let Some(idx) = self.hover_row {
                     let current = self.selected_row.get();
-                    if current == Some(idx) {
+                    if ctx.double_click {
+                        // The first press may have toggled the row off
+                        if current != Some(idx) {
+                            self.set_selected_row.set(Some(idx));
+                        }
+                        if let Some(handler) = self.on_row_double_click.as_mut() {
+                            handler(idx);
+                        }
+                    } else if current == Some(idx) {
                         self.set_selected_row.set(None);
                     } else {
                         self.set_selected_row.set(Some(idx));
@@ -316,41 +1220,20 @@ impl Widget for Table {
                     false
                 }
             }
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button: MouseButton::Left,
+                ..
+            } => self.resizing.take().is_some(),
             _ => false,
         }
     }
 
-    fn handle_key_event(&mut self, event: &KeyEvent, _modifiers: ModifiersState) -> bool {
+    fn handle_key_event(&mut self, event: &KeyEvent, modifiers: ModifiersState) -> bool {
         if event.state != ElementState::Pressed {
             return false;
         }
-        let count = self.rows.with(|r| r.len());
-        if count == 0 {
-            return false;
-        }
-        match &event.logical_key {
-            Key::Named(NamedKey::ArrowDown) => {
-                let current = self.selected_row.get().unwrap_or(0);
-                let next = (current + 1).min(count - 1);
-                self.set_selected_row.set(Some(next));
-                true
-            }
-            Key::Named(NamedKey::ArrowUp) => {
-                let current = self.selected_row.get().unwrap_or(0);
-                let next = current.saturating_sub(1);
-                self.set_selected_row.set(Some(next));
-                true
-            }
-            Key::Named(NamedKey::Home) => {
-                self.set_selected_row.set(Some(0));
-                true
-            }
-            Key::Named(NamedKey::End) => {
-                self.set_selected_row.set(Some(count - 1));
-                true
-            }
-            _ => false,
-        }
+        self.handle_key(&event.logical_key, modifiers)
     }
 
     fn is_focusable(&self) -> bool {
@@ -360,4 +1243,40 @@ impl Widget for Table {
     fn set_focus(&mut self, focused: bool) {
         self.focus = focused;
     }
+
+    fn take_scroll_target(&mut self) -> Option<(f32, f32, f32, f32)> {
+        let pos = self.reveal_pos.take()?.min(self.max_visible.saturating_sub(1));
+        let top = self.header_height + pos as f32 * self.row_height;
+        // A pinned header covers the top of the scroll view, so the row
+        // must clear it too
+        Some(if self.sticky_header {
+            (0.0, top - self.header_height, 0.0, self.row_height + self.header_height)
+        } else {
+            (0.0, top, 0.0, self.row_height)
+        })
+    }
+
+    fn update_measures(&mut self, measures: &[Vec<f32>]) {
+        for (text, idx) in self.badge_indices.borrow().iter() {
+            if let Some(&w) = measures.get(*idx).and_then(|edges| edges.first()) {
+                self.badge_widths.insert(text.clone(), w);
+            }
+        }
+        self.text_widths = self
+            .text_indices
+            .borrow()
+            .iter()
+            .filter_map(|(cell, idx, _)| {
+                let width = measures.get(*idx).and_then(|edges| edges.first())?;
+                Some((*cell, *width))
+            })
+            .collect();
+        self.truncated = self
+            .text_indices
+            .borrow()
+            .iter()
+            .filter(|(cell, _, available)| self.text_widths.get(cell).is_some_and(|&w| w > available + 0.5))
+            .map(|(cell, _, _)| *cell)
+            .collect();
+    }
 }
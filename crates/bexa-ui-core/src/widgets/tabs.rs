@@ -6,6 +6,10 @@ use winit::keyboard::{Key, ModifiersState, NamedKey};
 
 use crate::framework::{DrawContext, EventContext, Widget};
 use crate::signal::{Signal, SetSignal};
+use crate::theme::{opaque, tint};
+use crate::tree::WidgetNode;
+use crate::widgets::container::Container;
+use crate::widgets::router::router;
 
 pub struct Tabs {
     labels: Vec<String>,
@@ -15,14 +19,15 @@ pub struct Tabs {
     tab_height: f32,
     padding: f32,
     border_radius: f32,
-    // Colors
-    bg: [f32; 4],
-    active_bg: [f32; 4],
-    hover_bg: [f32; 4],
-    border_color: [f32; 4],
-    text_color: [u8; 3],
-    active_text_color: [u8; 3],
-    indicator_color: [f32; 4],
+    // Colors; `None` uses the theme's `panel` (shaded for the active tab),
+    // `hover`, `border`, text colors and `button` for the indicator
+    bg: Option<[f32; 4]>,
+    active_bg: Option<[f32; 4]>,
+    hover_bg: Option<[f32; 4]>,
+    border_color: Option<[f32; 4]>,
+    text_color: Option<[u8; 3]>,
+    active_text_color: Option<[u8; 3]>,
+    indicator_color: Option<[f32; 4]>,
     // State
     hover: bool,
     hover_index: Option<usize>,
@@ -44,13 +49,13 @@ impl Tabs {
             tab_height: 40.0,
             padding: 16.0,
             border_radius: 0.0,
-            bg: [0.12, 0.14, 0.18, 1.0],
-            active_bg: [0.18, 0.20, 0.26, 1.0],
-            hover_bg: [0.15, 0.17, 0.22, 1.0],
-            border_color: [0.3, 0.3, 0.4, 1.0],
-            text_color: [160, 160, 180],
-            active_text_color: [230, 230, 250],
-            indicator_color: [0.20, 0.65, 0.85, 1.0],
+            bg: None,
+            active_bg: None,
+            hover_bg: None,
+            border_color: None,
+            text_color: None,
+            active_text_color: None,
+            indicator_color: None,
             hover: false,
             hover_index: None,
             focus: false,
@@ -79,16 +84,16 @@ impl Tabs {
         border: [f32; 4],
         indicator: [f32; 4],
     ) -> Self {
-        self.bg = bg;
-        self.active_bg = active_bg;
-        self.border_color = border;
-        self.indicator_color = indicator;
+        self.bg = Some(bg);
+        self.active_bg = Some(active_bg);
+        self.border_color = Some(border);
+        self.indicator_color = Some(indicator);
         self
     }
 
     pub fn with_text_colors(mut self, normal: [u8; 3], active: [u8; 3]) -> Self {
-        self.text_color = normal;
-        self.active_text_color = active;
+        self.text_color = Some(normal);
+        self.active_text_color = Some(active);
         self
     }
 
@@ -131,6 +136,14 @@ impl Widget for Tabs {
     }
 
     fn draw(&self, ctx: &mut DrawContext) {
+        let theme = ctx.theme;
+        let bg = self.bg.unwrap_or(opaque(theme.panel));
+        let active_bg = self.active_bg.unwrap_or(tint(theme.panel, theme.text_primary, 0.08));
+        let hover_bg = self.hover_bg.unwrap_or(theme.hover);
+        let border_color = self.border_color.unwrap_or(theme.border);
+        let text_color = self.text_color.unwrap_or(theme.text_secondary);
+        let active_text_color = self.active_text_color.unwrap_or(theme.text_primary);
+        let indicator_color = self.indicator_color.unwrap_or(opaque(theme.button));
         let layout = ctx.layout;
         let x = layout.location.x;
         let y = layout.location.y;
@@ -142,7 +155,7 @@ impl Widget for Tabs {
         // Draw tab bar background
         ctx.renderer.fill_rect_styled(
             (x, y, w, h),
-            self.bg,
+            bg,
             self.border_radius,
             0.0,
             [0.0; 4],
@@ -151,7 +164,7 @@ impl Widget for Tabs {
         // Bottom border
         ctx.renderer.fill_rect_rounded(
             (x, y + h - 1.0, w, 1.0),
-            self.border_color,
+            border_color,
             0.0,
         );
 
@@ -163,9 +176,9 @@ impl Widget for Tabs {
 
             // Tab background
             let tab_bg = if is_active {
-                self.active_bg
+                active_bg
             } else if is_hover {
-                self.hover_bg
+                hover_bg
             } else {
                 [0.0, 0.0, 0.0, 0.0] // transparent
             };
@@ -180,9 +193,9 @@ impl Widget for Tabs {
 
             // Tab text
             let tc = if is_active {
-                self.active_text_color
+                active_text_color
             } else {
-                self.text_color
+                text_color
             };
             let text_y = y + (h - self.metrics.line_height) / 2.0;
             ctx.renderer.draw_text(
@@ -199,7 +212,7 @@ impl Widget for Tabs {
                 let indicator_h = 3.0;
                 ctx.renderer.fill_rect_rounded(
                     (tx + 4.0, y + h - indicator_h, tw - 8.0, indicator_h),
-                    self.indicator_color,
+                    indicator_color,
                     1.5,
                 );
             }
@@ -212,24 +225,30 @@ impl Widget for Tabs {
                 [0.0, 0.0, 0.0, 0.0],
                 self.border_radius,
                 2.0,
-                [0.3, 0.6, 0.9, 1.0],
+                ctx.theme.focus_ring,
             );
         }
     }
 
+    fn on_mouse_enter(&mut self) {
+        self.hover = true;
+    }
+
+    fn on_mouse_leave(&mut self) {
+        self.hover = false;
+        self.hover_index = None;
+    }
+
     fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
         let layout = ctx.layout;
 
         match ctx.event {
             WindowEvent::CursorMoved { position, .. } => {
-                let px = position.x as f32;
-                let py = position.y as f32;
-                let over = px >= layout.location.x
-                    && px <= layout.location.x + layout.size.width
-                    && py >= layout.location.y
-                    && py <= layout.location.y + self.tab_height;
-                self.hover = over;
-                self.hover_index = self.tab_at(layout, px, py);
+                self.hover_index = if self.hover {
+                    self.tab_at(layout, position.x as f32, position.y as f32)
+                } else {
+                    None
+                };
                 false
             }
             WindowEvent::MouseInput {
@@ -280,3 +299,15 @@ impl Widget for Tabs {
         self.focus = focused;
     }
 }
+
+/// Convenience: a `Tabs` bar above one content panel per tab, showing the
+/// panel for the active index. Panels sit in a `Router` keyed by tab index,
+/// so each keeps its state (scroll, input text) while another tab is shown.
+pub fn tab_view(tabs: Tabs, panels: Vec<WidgetNode>) -> WidgetNode {
+    let active = tabs.active.clone();
+    let routes = panels.into_iter().enumerate().collect();
+    WidgetNode::new(
+        Container::new(),
+        vec![WidgetNode::new(tabs, vec![]), router(active, routes)],
+    )
+}
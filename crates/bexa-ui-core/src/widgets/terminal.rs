@@ -1,13 +1,23 @@
+use std::collections::VecDeque;
 use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
 use std::sync::{Arc, Mutex};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use arboard::Clipboard;
+use portable_pty::{ChildKiller, MasterPty, PtySize, SlavePty};
 
 use glyphon::cosmic_text::Align;
 use glyphon::Metrics;
 use taffy::prelude::*;
-use winit::event::{ElementState, KeyEvent, MouseButton, WindowEvent};
+use winit::event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta, WindowEvent};
 use winit::keyboard::{Key, ModifiersState, NamedKey};
 
 use crate::framework::{DrawContext, EventContext, Widget};
+use crate::renderer::Renderer;
+use crate::shortcut::KeyCombo;
 
 // ── Terminal cell ────────────────────────────────────────────────────────
 
@@ -32,6 +42,10 @@ impl Default for TermCell {
 
 // ── Terminal grid (VTE performer) ────────────────────────────────────────
 
+/// Lines of history a terminal keeps unless `Terminal::with_scrollback`
+/// says otherwise.
+const DEFAULT_SCROLLBACK: usize = 1000;
+
 pub struct TermGrid {
     pub cells: Vec<Vec<TermCell>>,
     pub rows: usize,
@@ -45,6 +59,12 @@ pub struct TermGrid {
     // Scroll region
     scroll_top: usize,
     scroll_bottom: usize,
+    /// Lines scrolled off the top of the screen, oldest first
+    pub scrollback: VecDeque<Vec<TermCell>>,
+    pub scrollback_limit: usize,
+    /// How many lines back into `scrollback` the view is scrolled; 0 shows
+    /// the live screen
+    pub view_offset: usize,
     // PTY writer for responding to DSR queries
     pty_writer: Option<Arc<Mutex<Box<dyn Write + Send>>>>,
 }
@@ -63,11 +83,25 @@ impl TermGrid {
             current_bold: false,
             scroll_top: 0,
             scroll_bottom: rows.saturating_sub(1),
+            scrollback: VecDeque::new(),
+            scrollback_limit: DEFAULT_SCROLLBACK,
+            view_offset: 0,
             pty_writer: None,
         }
     }
 
     pub fn resize(&mut self, rows: usize, cols: usize) {
+        // Shrinking keeps the cursor's line on screen; the lines above it
+        // that no longer fit go to the scrollback
+        if self.cursor_row >= rows {
+            let excess = self.cursor_row + 1 - rows;
+            let lines: Vec<_> = self.cells.drain(..excess).collect();
+            for line in lines {
+                self.push_scrollback(line);
+            }
+            self.cursor_row -= excess;
+        }
+        self.view_offset = self.view_offset.min(self.scrollback.len());
         self.rows = rows;
         self.cols = cols;
         self.cells.resize(rows, vec![TermCell::default(); cols]);
@@ -82,10 +116,56 @@ impl TermGrid {
 
     fn scroll_up(&mut self) {
         if self.scroll_top < self.scroll_bottom && self.scroll_bottom < self.rows {
-            self.cells.remove(self.scroll_top);
+            let line = self.cells.remove(self.scroll_top);
             self.cells
                 .insert(self.scroll_bottom, vec![TermCell::default(); self.cols]);
+            // Only lines leaving the top of the screen are history; a region
+            // further down (e.g. a pager's) scrolls them away for good
+            if self.scroll_top == 0 {
+                self.push_scrollback(line);
+            }
+        }
+    }
+
+    fn push_scrollback(&mut self, line: Vec<TermCell>) {
+        if self.scrollback_limit == 0 {
+            return;
         }
+        if self.scrollback.len() == self.scrollback_limit {
+            self.scrollback.pop_front();
+        }
+        self.scrollback.push_back(line);
+    }
+
+    /// The line drawn at screen row `row` while scrolled back by
+    /// `view_offset`: history above the live screen's rows. History from
+    /// before a resize is cut to the current width.
+    pub fn visible_line(&self, row: usize) -> Option<&[TermCell]> {
+        self.line(self.line_at_row(row))
+    }
+
+    /// Line `index` counting from the oldest in the scrollback on through
+    /// the screen, cut to the current width.
+    pub fn line(&self, index: usize) -> Option<&[TermCell]> {
+        let line = match index.checked_sub(self.scrollback.len()) {
+            Some(screen_row) => self.cells.get(screen_row),
+            None => self.scrollback.get(index),
+        }?;
+        Some(&line[..line.len().min(self.cols)])
+    }
+
+    /// Index (as for `line`) of the line at screen row `row`.
+    pub fn line_at_row(&self, row: usize) -> usize {
+        self.scrollback.len() - self.view_offset + row
+    }
+
+    /// Scrolls the view `lines` into history (negative back toward the live
+    /// screen). Returns whether it moved.
+    pub fn scroll_view(&mut self, lines: isize) -> bool {
+        let offset = self.view_offset.saturating_add_signed(lines).min(self.scrollback.len());
+        let moved = offset != self.view_offset;
+        self.view_offset = offset;
+        moved
     }
 
     fn newline(&mut self) {
@@ -120,7 +200,7 @@ impl TermGrid {
                     self.cells[self.cursor_row][col] = TermCell::default();
                 }
             }
-            2 | 3 => {
+            2 => {
                 // Erase entire screen
                 for row in &mut self.cells {
                     for cell in row.iter_mut() {
@@ -128,6 +208,11 @@ impl TermGrid {
                     }
                 }
             }
+            3 => {
+                // Erase saved lines (`clear` sends this after 2)
+                self.scrollback.clear();
+                self.view_offset = 0;
+            }
             _ => {}
         }
     }
@@ -156,11 +241,8 @@ impl TermGrid {
 
     fn apply_sgr(&mut self, params: &vte::Params) {
         let mut iter = params.iter();
-        loop {
-            let param = match iter.next() {
-                Some(slice) => slice[0],
-                None => break,
-            };
+        while let Some(slice) = iter.next() {
+            let param = slice[0];
             match param {
                 0 => {
                     self.current_fg = [204, 204, 204];
@@ -200,22 +282,20 @@ impl TermGrid {
                 97 => self.current_fg = [255, 255, 255],
                 // 256-color: 38;5;N
                 38 => {
-                    if let Some(five) = iter.next() {
-                        if five[0] == 5 {
-                            if let Some(n) = iter.next() {
-                                self.current_fg = ansi_256_to_rgb(n[0]);
-                            }
-                        }
+                    if let Some(five) = iter.next()
+                        && five[0] == 5
+                        && let Some(n) = iter.next()
+                    {
+                        self.current_fg = ansi_256_to_rgb(n[0]);
                     }
                 }
                 // 256-color bg: 48;5;N
                 48 => {
-                    if let Some(five) = iter.next() {
-                        if five[0] == 5 {
-                            if let Some(n) = iter.next() {
-                                self.current_bg = Some(ansi_256_to_rgb(n[0]));
-                            }
-                        }
+                    if let Some(five) = iter.next()
+                        && five[0] == 5
+                        && let Some(n) = iter.next()
+                    {
+                        self.current_bg = Some(ansi_256_to_rgb(n[0]));
                     }
                 }
                 _ => {}
@@ -320,7 +400,7 @@ impl vte::Perform for TermGrid {
             }
             'm' => {
                 // SGR
-                if params.len() == 0 {
+                if params.is_empty() {
                     // Reset
                     self.current_fg = [204, 204, 204];
                     self.current_bg = None;
@@ -387,11 +467,11 @@ impl vte::Perform for TermGrid {
                 if mode == 6 {
                     // CPR: respond with cursor position (1-based)
                     let response = format!("\x1b[{};{}R", self.cursor_row + 1, self.cursor_col + 1);
-                    if let Some(ref writer) = self.pty_writer {
-                        if let Ok(mut w) = writer.lock() {
-                            let _ = w.write_all(response.as_bytes());
-                            let _ = w.flush();
-                        }
+                    if let Some(ref writer) = self.pty_writer
+                        && let Ok(mut w) = writer.lock()
+                    {
+                        let _ = w.write_all(response.as_bytes());
+                        let _ = w.flush();
                     }
                 }
             }
@@ -410,21 +490,18 @@ impl vte::Perform for TermGrid {
     }
 
     fn esc_dispatch(&mut self, _intermediates: &[u8], _ignore: bool, byte: u8) {
-        match byte {
-            b'M' => {
-                // Reverse index (scroll down)
-                if self.cursor_row == self.scroll_top {
-                    // Insert line at top
-                    if self.scroll_bottom < self.cells.len() {
-                        self.cells.remove(self.scroll_bottom);
-                    }
-                    self.cells
-                        .insert(self.scroll_top, vec![TermCell::default(); self.cols]);
-                } else {
-                    self.cursor_row = self.cursor_row.saturating_sub(1);
+        if byte == b'M' {
+            // Reverse index (scroll down)
+            if self.cursor_row == self.scroll_top {
+                // Insert line at top
+                if self.scroll_bottom < self.cells.len() {
+                    self.cells.remove(self.scroll_bottom);
                 }
+                self.cells
+                    .insert(self.scroll_top, vec![TermCell::default(); self.cols]);
+            } else {
+                self.cursor_row = self.cursor_row.saturating_sub(1);
             }
-            _ => {}
         }
     }
 
@@ -475,13 +552,60 @@ fn ansi_256_to_rgb(n: u16) -> [u8; 3] {
 
 // ── Terminal Widget ──────────────────────────────────────────────────────
 
+/// How long dropping a `Terminal` waits for its reader thread to reap the
+/// shell before leaving the thread to finish on its own.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Lines of history one wheel notch scrolls.
+const WHEEL_LINES: f32 = 3.0;
+
+/// A position in the terminal's text: `(line, column)`.
+type GridPoint = (usize, usize);
+
+/// How long the laid-out size must hold before the grid and PTY follow it,
+/// so dragging a window edge doesn't make the shell redraw at every step.
+const RESIZE_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// The shell's process and our ends of its PTY, released by `shutdown`.
+struct PtySession {
+    killer: Box<dyn ChildKiller + Send + Sync>,
+    master: Box<dyn MasterPty + Send>,
+    // Kept open until shutdown: dropping it on Windows (ConPTY) kills the PTY
+    slave: Box<dyn SlavePty + Send>,
+    /// Tells the reader thread to stop feeding the grid and kill the shell
+    stopping: Arc<AtomicBool>,
+    /// Signalled by the reader thread once the shell has been reaped
+    reader_done: Receiver<()>,
+}
+
+/// A shell in a PTY, drawn as a character grid.
+///
+/// Dropping the widget (e.g. when its window closes) shuts the shell down:
+/// 1. the reader thread is told to stop, so no more output reaches the grid,
+/// 2. the shell gets SIGHUP (terminated outright on Windows),
+/// 3. our ends of the PTY are closed, which ends the reader's blocking read,
+/// 4. the reader kills the shell if it is still alive (SIGKILL after a short
+///    grace period) and reaps it,
+/// 5. the drop waits up to half a second for that, then leaves the thread
+///    to finish detached rather than stall the UI.
 pub struct Terminal {
     grid: Arc<Mutex<TermGrid>>,
     pty_writer: Option<Arc<Mutex<Box<dyn Write + Send>>>>,
+    session: Option<PtySession>,
     metrics: Metrics,
     focus: bool,
     font_family: String,
     bg_color: [f32; 4],
+    // State
+    wheel_lines: f32,
+    /// Text selected by dragging, as `(line, column)` points (lines as for
+    /// `TermGrid::line`), anchor first; columns are between cells
+    selection: Cell<Option<(GridPoint, GridPoint)>>,
+    selecting: bool,
+    // Grid size `(rows, cols)` the layout asks for and since when; the first
+    // layout applies at once
+    pending_size: Cell<Option<((usize, usize), Instant)>>,
+    sized: Cell<bool>,
 }
 
 impl Terminal {
@@ -495,10 +619,16 @@ impl Terminal {
         let mut terminal = Self {
             grid,
             pty_writer: None,
+            session: None,
             metrics,
             focus: false,
             font_family: "Consolas".to_string(),
             bg_color: [0.07, 0.07, 0.10, 1.0],
+            wheel_lines: 0.0,
+            selection: Cell::new(None),
+            selecting: false,
+            pending_size: Cell::new(None),
+            sized: Cell::new(false),
         };
 
         terminal.spawn_pty(initial_rows, initial_cols, grid_clone);
@@ -515,8 +645,22 @@ impl Terminal {
         self
     }
 
+    /// Keeps up to `lines` lines scrolled off the top, for the mouse wheel
+    /// to scroll back through (1000 by default; 0 keeps none).
+    pub fn with_scrollback(self, lines: usize) -> Self {
+        {
+            let mut grid = self.grid.lock().unwrap();
+            grid.scrollback_limit = lines;
+            // The shell may have printed already
+            let excess = grid.scrollback.len().saturating_sub(lines);
+            grid.scrollback.drain(..excess);
+            grid.view_offset = grid.view_offset.min(grid.scrollback.len());
+        }
+        self
+    }
+
     fn spawn_pty(&mut self, rows: usize, cols: usize, grid: Arc<Mutex<TermGrid>>) {
-        use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
+        use portable_pty::{CommandBuilder, NativePtySystem, PtySystem};
 
         let pty_system = NativePtySystem::default();
         let pair = pty_system
@@ -542,11 +686,12 @@ impl Terminal {
 
         // Reader thread: reads PTY output and feeds VTE parser
         let reader = pair.master.try_clone_reader().expect("clone reader");
-        // Keep slave alive — dropping it on Windows (ConPTY) kills the PTY immediately
-        let slave = pair.slave;
+        let killer = child.clone_killer();
+        let stopping = Arc::new(AtomicBool::new(false));
+        let thread_stopping = stopping.clone();
+        let (done_tx, reader_done) = mpsc::channel();
         std::thread::spawn(move || {
             use std::io::Read;
-            let _slave = slave; // prevent drop until thread ends
             let mut reader = reader;
             let mut parser = vte::Parser::new();
             let mut buf = [0u8; 4096];
@@ -555,28 +700,206 @@ impl Terminal {
                 match reader.read(&mut buf) {
                     Ok(0) => break,
                     Ok(n) => {
+                        if thread_stopping.load(Ordering::Acquire) {
+                            break;
+                        }
                         let mut g = grid.lock().unwrap();
                         parser.advance(&mut *g, &buf[..n]);
+                        // New output shows on the live screen
+                        g.view_offset = 0;
+                        drop(g);
+                        // The grid isn't a signal; wake the loop to show the output
+                        crate::request_redraw();
                     }
                     Err(_) => break,
                 }
             }
 
-            // Child process exited
+            // A shell that ignored SIGHUP is killed; one that exited is just reaped
+            if thread_stopping.load(Ordering::Acquire) {
+                let _ = child.kill();
+            }
             let _ = child.wait();
+            let _ = done_tx.send(());
         });
+
+        self.session = Some(PtySession {
+            killer,
+            master: pair.master,
+            slave: pair.slave,
+            stopping,
+            reader_done,
+        });
+    }
+
+    /// Stops the shell and its reader thread; see the type docs for the
+    /// sequence.
+    fn shutdown(&mut self) {
+        let Some(mut session) = self.session.take() else {
+            return;
+        };
+        session.stopping.store(true, Ordering::Release);
+        let _ = session.killer.kill();
+
+        // Close every handle on our side of the PTY so the reader's read fails
+        self.pty_writer = None;
+        if let Ok(mut grid) = self.grid.lock() {
+            grid.pty_writer = None;
+        }
+        drop(session.slave);
+        drop(session.master);
+
+        let _ = session.reader_done.recv_timeout(SHUTDOWN_TIMEOUT);
+    }
+
+    /// The point between cells nearest `cursor`, in a terminal drawn at
+    /// `origin`.
+    fn point_at(&self, origin: (f32, f32), cursor: (f32, f32)) -> GridPoint {
+        let grid = self.grid.lock().unwrap();
+        let char_w = self.metrics.font_size * 0.6;
+        let row = ((cursor.1 - origin.1) / self.metrics.line_height).floor().max(0.0) as usize;
+        let col = ((cursor.0 - origin.0) / char_w).round().max(0.0) as usize;
+        let row = row.min(grid.rows.saturating_sub(1));
+        (grid.line_at_row(row), col.min(grid.cols))
+    }
+
+    /// The selection's start and end, in reading order, if it isn't empty.
+    fn ordered_selection(&self) -> Option<(GridPoint, GridPoint)> {
+        let (anchor, focus) = self.selection.get()?;
+        (anchor != focus).then(|| (anchor.min(focus), anchor.max(focus)))
+    }
+
+    /// The selected text, one line per row with trailing blanks dropped.
+    fn selected_text(&self) -> Option<String> {
+        let (start, end) = self.ordered_selection()?;
+        let grid = self.grid.lock().unwrap();
+        let lines: Vec<String> = (start.0..=end.0)
+            .map(|index| {
+                let line = grid.line(index).unwrap_or(&[]);
+                let from = if index == start.0 { start.1 } else { 0 }.min(line.len());
+                let to = if index == end.0 { end.1 } else { line.len() }.min(line.len());
+                let text: String = line[from..to.max(from)].iter().map(|cell| cell.ch).collect();
+                text.trim_end().to_string()
+            })
+            .collect();
+        Some(lines.join("\n"))
+    }
+
+    fn copy_selection(&self) {
+        if let Some(text) = self.selected_text()
+            && let Ok(mut cb) = Clipboard::new()
+        {
+            let _ = cb.set_text(text);
+        }
+    }
+
+    fn paste(&self) {
+        let Some(text) = Clipboard::new().ok().and_then(|mut cb| cb.get_text().ok()) else {
+            return;
+        };
+        // The shell reads Enter as a carriage return
+        let text = text.replace("\r\n", "\r").replace('\n', "\r");
+        self.write_to_pty(text.as_bytes());
+    }
+
+    /// Resizes the grid and the PTY to `size` once the layout has settled
+    /// on it; the shell gets SIGWINCH and redraws.
+    fn fit_grid(&self, size: (usize, usize), renderer: &mut Renderer) {
+        let current = {
+            let grid = self.grid.lock().unwrap();
+            (grid.rows, grid.cols)
+        };
+        if size == current {
+            self.pending_size.set(None);
+            return;
+        }
+
+        let now = Instant::now();
+        let since = match self.pending_size.get() {
+            Some((pending, since)) if pending == size => since,
+            _ => now,
+        };
+        if self.sized.get() && now < since + RESIZE_DEBOUNCE {
+            self.pending_size.set(Some((size, since)));
+            renderer.request_redraw_at(since + RESIZE_DEBOUNCE);
+            return;
+        }
+
+        self.pending_size.set(None);
+        self.sized.set(true);
+        let (rows, cols) = size;
+        self.grid.lock().unwrap().resize(rows, cols);
+        if let Some(session) = &self.session {
+            let _ = session.master.resize(PtySize {
+                rows: rows as u16,
+                cols: cols as u16,
+                pixel_width: 0,
+                pixel_height: 0,
+            });
+        }
+    }
+
+    /// Bytes a key press sends to the shell, or `None` for keys the
+    /// terminal doesn't handle. Tab is one of them: a focused terminal sends
+    /// it to the PTY (for completion) instead of moving focus.
+    ///
+    /// ```
+    /// use bexa_ui_core::Terminal;
+    /// use winit::keyboard::{Key, ModifiersState, NamedKey};
+    ///
+    /// let tab = Key::Named(NamedKey::Tab);
+    /// assert_eq!(Terminal::key_bytes(&tab, ModifiersState::empty()), Some(b"\t".to_vec()));
+    /// assert_eq!(Terminal::key_bytes(&Key::Character("c".into()), ModifiersState::CONTROL), Some(vec![3]));
+    /// ```
+    pub fn key_bytes(key: &Key, modifiers: ModifiersState) -> Option<Vec<u8>> {
+        // Ctrl+letter, e.g. Ctrl+C is 0x03
+        if modifiers.control_key()
+            && let Key::Character(c) = key
+            && let Some(ch) = c.chars().next().filter(char::is_ascii_alphabetic)
+        {
+            return Some(vec![(ch.to_ascii_lowercase() as u8) - b'a' + 1]);
+        }
+
+        let bytes: &[u8] = match key {
+            Key::Named(NamedKey::Enter) => b"\r",
+            Key::Named(NamedKey::Space) => b" ",
+            Key::Named(NamedKey::Backspace) => b"\x7f",
+            Key::Named(NamedKey::Tab) => b"\t",
+            Key::Named(NamedKey::Escape) => b"\x1b",
+            Key::Named(NamedKey::ArrowUp) => b"\x1b[A",
+            Key::Named(NamedKey::ArrowDown) => b"\x1b[B",
+            Key::Named(NamedKey::ArrowRight) => b"\x1b[C",
+            Key::Named(NamedKey::ArrowLeft) => b"\x1b[D",
+            Key::Named(NamedKey::Home) => b"\x1b[H",
+            Key::Named(NamedKey::End) => b"\x1b[F",
+            Key::Named(NamedKey::Delete) => b"\x1b[3~",
+            Key::Named(NamedKey::PageUp) => b"\x1b[5~",
+            Key::Named(NamedKey::PageDown) => b"\x1b[6~",
+            Key::Character(c) => c.as_bytes(),
+            _ => return None,
+        };
+        Some(bytes.to_vec())
     }
 
     fn write_to_pty(&self, data: &[u8]) {
-        if let Some(ref writer) = self.pty_writer {
-            if let Ok(mut w) = writer.lock() {
-                let _ = w.write_all(data);
-                let _ = w.flush();
-            }
+        // Typing jumps back from history to the prompt, and drops the selection
+        self.grid.lock().unwrap().view_offset = 0;
+        self.selection.set(None);
+        if let Some(ref writer) = self.pty_writer
+            && let Ok(mut w) = writer.lock()
+        {
+            let _ = w.write_all(data);
+            let _ = w.flush();
         }
     }
 }
 
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
 impl Widget for Terminal {
     fn style(&self) -> Style {
         Style {
@@ -604,12 +927,22 @@ impl Widget for Terminal {
             [0.0; 4],
         );
 
-        let grid = self.grid.lock().unwrap();
         let char_w = self.metrics.font_size * 0.6;
         let line_h = self.metrics.line_height;
+        // A pane collapsed below one cell keeps the last grid
+        if w >= char_w && h >= line_h {
+            let size = ((h / line_h).floor() as usize, (w / char_w).floor() as usize);
+            self.fit_grid(size, ctx.renderer);
+        }
+
+        let grid = self.grid.lock().unwrap();
+        let selection = self.ordered_selection();
 
         // Draw cells row by row — each character placed at its exact grid position
-        for (row_idx, row) in grid.cells.iter().enumerate() {
+        for row_idx in 0..grid.rows {
+            let Some(row) = grid.visible_line(row_idx) else {
+                continue;
+            };
             let cy = y + row_idx as f32 * line_h;
             if cy + line_h < y || cy > y + h {
                 continue;
@@ -638,6 +971,24 @@ impl Widget for Terminal {
                 }
             }
 
+            // Selection highlight, under the text like the cell backgrounds
+            if let Some((start, end)) = selection {
+                let line = grid.line_at_row(row_idx);
+                if (start.0..=end.0).contains(&line) {
+                    let from = if line == start.0 { start.1 } else { 0 };
+                    let to = if line == end.0 { end.1 } else { grid.cols };
+                    if to > from {
+                        ctx.renderer.fill_rect_styled(
+                            (x + from as f32 * char_w, cy, (to - from) as f32 * char_w, line_h),
+                            ctx.theme.selection,
+                            0.0,
+                            0.0,
+                            [0.0; 4],
+                        );
+                    }
+                }
+            }
+
             // Second pass: draw each non-space character individually at its grid position
             for (col_idx, cell) in row.iter().enumerate() {
                 if cell.ch == ' ' {
@@ -658,10 +1009,11 @@ impl Widget for Terminal {
             }
         }
 
-        // Draw cursor
-        if self.focus && grid.cursor_row < grid.rows && grid.cursor_col < grid.cols {
+        // Draw cursor, which moves down with the screen while scrolled back
+        let cursor_row = grid.cursor_row + grid.view_offset;
+        if self.focus && cursor_row < grid.rows && grid.cursor_col < grid.cols {
             let cursor_x = x + grid.cursor_col as f32 * char_w;
-            let cursor_y = y + grid.cursor_row as f32 * line_h;
+            let cursor_y = y + cursor_row as f32 * line_h;
             ctx.renderer.fill_rect_styled(
                 (cursor_x, cursor_y, char_w, line_h),
                 [0.8, 0.8, 0.8, 0.6],
@@ -677,100 +1029,86 @@ impl Widget for Terminal {
             return false;
         }
 
-        // Ctrl+key combinations
-        if modifiers.control_key() {
-            match &event.logical_key {
-                Key::Character(c) => {
-                    let ch = c.chars().next().unwrap_or('\0');
-                    if ch >= 'a' && ch <= 'z' {
-                        let ctrl_byte = (ch as u8) - b'a' + 1;
-                        self.write_to_pty(&[ctrl_byte]);
-                        return true;
-                    }
-                    if ch >= 'A' && ch <= 'Z' {
-                        let ctrl_byte = (ch as u8) - b'A' + 1;
-                        self.write_to_pty(&[ctrl_byte]);
-                        return true;
-                    }
-                }
-                _ => {}
-            }
+        // Ctrl+C belongs to the shell, so copy and paste take Shift too
+        let clipboard_mods = ModifiersState::CONTROL | ModifiersState::SHIFT;
+        if KeyCombo::char("c", clipboard_mods).matches(event, modifiers) {
+            self.copy_selection();
+            return true;
+        }
+        if KeyCombo::char("v", clipboard_mods).matches(event, modifiers) {
+            self.paste();
+            return true;
         }
 
-        match &event.logical_key {
-            Key::Named(NamedKey::Enter) => {
-                self.write_to_pty(b"\r");
-                true
-            }
-            Key::Named(NamedKey::Backspace) => {
-                self.write_to_pty(b"\x7f");
-                true
-            }
-            Key::Named(NamedKey::Tab) => {
-                self.write_to_pty(b"\t");
-                true
-            }
-            Key::Named(NamedKey::Escape) => {
-                self.write_to_pty(b"\x1b");
-                true
-            }
-            Key::Named(NamedKey::ArrowUp) => {
-                self.write_to_pty(b"\x1b[A");
-                true
-            }
-            Key::Named(NamedKey::ArrowDown) => {
-                self.write_to_pty(b"\x1b[B");
-                true
-            }
-            Key::Named(NamedKey::ArrowRight) => {
-                self.write_to_pty(b"\x1b[C");
-                true
-            }
-            Key::Named(NamedKey::ArrowLeft) => {
-                self.write_to_pty(b"\x1b[D");
+        match Self::key_bytes(&event.logical_key, modifiers) {
+            Some(bytes) => {
+                self.write_to_pty(&bytes);
                 true
             }
-            Key::Named(NamedKey::Home) => {
-                self.write_to_pty(b"\x1b[H");
-                true
-            }
-            Key::Named(NamedKey::End) => {
-                self.write_to_pty(b"\x1b[F");
-                true
+            None => false,
+        }
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        let layout = ctx.layout;
+        let origin = (layout.location.x, layout.location.y);
+        let (cx, cy) = ctx.cursor;
+        let inside = cx >= origin.0
+            && cx <= origin.0 + layout.size.width
+            && cy >= origin.1
+            && cy <= origin.1 + layout.size.height;
+
+        match ctx.event {
+            WindowEvent::MouseWheel { delta, .. } if inside => {
+                self.wheel_lines += match delta {
+                    MouseScrollDelta::LineDelta(_, y) => y * WHEEL_LINES,
+                    MouseScrollDelta::PixelDelta(d) => d.y as f32 / self.metrics.line_height,
+                };
+                // Trackpads scroll a few pixels at a time; keep the partial line
+                let lines = self.wheel_lines.trunc();
+                self.wheel_lines -= lines;
+                // Up (positive) goes back into history; at either end the wheel
+                // is left for an enclosing scroll view
+                self.grid.lock().unwrap().scroll_view(lines as isize)
             }
-            Key::Named(NamedKey::Delete) => {
-                self.write_to_pty(b"\x1b[3~");
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } if inside => {
+                // Click anywhere on the terminal area to focus it; dragging selects
+                let point = self.point_at(origin, ctx.cursor);
+                self.selection.set(Some((point, point)));
+                self.selecting = true;
                 true
             }
-            Key::Named(NamedKey::PageUp) => {
-                self.write_to_pty(b"\x1b[5~");
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Middle,
+                ..
+            } if inside => {
+                // Middle click pastes, as in X11 terminals
+                self.paste();
                 true
             }
-            Key::Named(NamedKey::PageDown) => {
-                self.write_to_pty(b"\x1b[6~");
-                true
+            WindowEvent::CursorMoved { .. } if self.selecting => {
+                if let Some((anchor, _)) = self.selection.get() {
+                    self.selection.set(Some((anchor, self.point_at(origin, ctx.cursor))));
+                }
+                false
             }
-            Key::Character(c) => {
-                self.write_to_pty(c.as_bytes());
-                true
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button: MouseButton::Left,
+                ..
+            } => {
+                self.selecting = false;
+                false
             }
             _ => false,
         }
     }
 
-    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
-        if let WindowEvent::MouseInput {
-            state: ElementState::Pressed,
-            button: MouseButton::Left,
-            ..
-        } = ctx.event
-        {
-            // Click anywhere on the terminal area to focus it
-            return true;
-        }
-        false
-    }
-
     fn is_focusable(&self) -> bool {
         true
     }
@@ -1,5 +1,6 @@
+use std::borrow::Cow;
 use std::cell::Cell;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use arboard::Clipboard;
 use glyphon::Metrics;
@@ -9,36 +10,90 @@ use winit::event::{ElementState, MouseButton, WindowEvent, KeyEvent};
 use winit::keyboard::{Key, ModifiersState, NamedKey};
 
 use crate::framework::{DrawContext, EventContext, Widget};
-use crate::signal::SetSignal;
+use crate::icons::{self, NERD_FONT_FAMILY};
+use crate::signal::{SetSignal, Signal};
+use crate::theme::opaque;
+
+/// Caret width; the horizontal scroll keeps all of it inside the field.
+const CARET_W: f32 = 1.5;
+
+/// Caret blink period; the caret shows for the first half.
+const BLINK_PERIOD_MS: u128 = 1060;
+
+/// Shape and blinking of the `TextInput` caret.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CaretStyle {
+    /// Thin bar before the character at the cursor, blinking.
+    #[default]
+    Blink,
+    /// The same bar, always shown.
+    Solid,
+    /// Steady box covering the character at the cursor (one average
+    /// character wide at the end of the text), for terminal-like inputs.
+    Block,
+    /// Steady line under the character at the cursor, sized like `Block`.
+    Underline,
+}
+
+/// Space between the leading icon or clear button and the text.
+const ICON_GAP: f32 = 6.0;
+
+/// Layout width for the text buffer, wide enough that a single line
+/// never wraps. The field's clip crops what is outside the view.
+const UNWRAPPED_W: f32 = 1.0e6;
 
 pub struct TextInput {
     text: String,
     cursor_pos: usize,
     selection: Option<(usize, usize)>,
     on_change: Option<SetSignal<String>>,
+    /// External source of the text, from `with_value`
+    value: Option<Signal<String>>,
+    /// What `value` held when last adopted or written by an edit; anything
+    /// else in it is an external write
+    value_seen: String,
     metrics: Metrics,
-    text_color: [u8; 3],
+    /// `None` uses the theme's `text_primary`
+    text_color: Option<[u8; 3]>,
     placeholder_color: [u8; 3],
     placeholder: String,
-    background: [f32; 4],
-    focus_border_color: [f32; 4],
+    /// `None` uses the theme's `background`, to set the field off its panel
+    background: Option<[f32; 4]>,
     border_radius: f32,
     padding: f32,
+    /// When true, Tab inserts a tab character instead of moving focus
+    tab_inserts: bool,
+    /// Enter inserts a line break and the field grows with the lines
+    multiline: bool,
+    /// Rows shown before a multiline field scrolls instead of growing
+    max_rows: Option<usize>,
+    /// Drawn in place of each character, for passwords
+    mask: Option<char>,
+    caret_style: CaretStyle,
+    /// Nerd Font glyph drawn before the text, e.g. `icons::SEARCH`
+    leading_icon: Option<&'static str>,
+    clear_button: bool,
+    clear_hovered: bool,
     focused: bool,
     last_input_time: Instant,
-    /// Cached pixel width of text before cursor, updated by render layer
+    /// Cached pixel width of the caret's line before the cursor, updated by
+    /// render layer
     pub(crate) cursor_pixel_x: f32,
-    /// Cached pixel positions for selection highlight
-    selection_lo_px: f32,
-    selection_hi_px: f32,
-    /// Pixel x-positions of each character edge (0..=char_count), for click-to-position
-    char_edges: Vec<f32>,
+    /// Pixel x-positions of each character edge of each line
+    /// (0..=char_count), for click-to-position and selection highlight
+    line_edges: Vec<Vec<f32>>,
+    /// Scroll of the text, keeping the caret in view
+    scroll_x: f32,
+    scroll_y: f32,
+    /// Size of the visible text region from the last draw
+    view_w: Cell<f32>,
+    view_h: Cell<f32>,
     /// Whether mouse is currently dragging a selection
     mouse_dragging: bool,
-    /// Last known cursor position (window coords)
-    last_mouse_x: f32,
-    last_mouse_y: f32,
-    /// Index of the text command emitted during draw (for measure feedback)
+    /// Shift held, so a click extends the selection instead of moving the cursor
+    shift: bool,
+    /// Index of the text command of the first line emitted during draw (for
+    /// measure feedback); the other lines follow it
     text_cmd_index: Cell<Option<usize>>,
 }
 
@@ -49,27 +104,67 @@ impl TextInput {
             cursor_pos: 0,
             selection: None,
             on_change: Some(on_change),
+            value: None,
+            value_seen: String::new(),
             metrics: Metrics::new(16.0, 22.0),
-            text_color: [230, 230, 230],
+            text_color: None,
             placeholder_color: [120, 120, 140],
             placeholder: String::new(),
-            background: [0.12, 0.16, 0.22, 1.0],
-            focus_border_color: [0.3, 0.6, 0.9, 1.0],
+            background: None,
             border_radius: 6.0,
             padding: 10.0,
+            tab_inserts: false,
+            multiline: false,
+            max_rows: None,
+            mask: None,
+            caret_style: CaretStyle::default(),
+            leading_icon: None,
+            clear_button: false,
+            clear_hovered: false,
             focused: false,
             last_input_time: Instant::now(),
             cursor_pixel_x: 0.0,
-            selection_lo_px: 0.0,
-            selection_hi_px: 0.0,
-            char_edges: Vec::new(),
+            line_edges: Vec::new(),
+            scroll_x: 0.0,
+            scroll_y: 0.0,
+            view_w: Cell::new(0.0),
+            view_h: Cell::new(0.0),
             mouse_dragging: false,
-            last_mouse_x: 0.0,
-            last_mouse_y: 0.0,
+            shift: false,
             text_cmd_index: Cell::new(None),
         }
     }
 
+    /// A field two-way bound to one signal: edits write to it through `set`,
+    /// and writes to it from elsewhere show up in the field. Shorthand for
+    /// `TextInput::new(set).with_value(value)`.
+    ///
+    /// ```
+    /// use bexa_ui_core::{create_signal, TextInput};
+    /// use winit::keyboard::{Key, ModifiersState, NamedKey};
+    ///
+    /// let (query, set_query) = create_signal(String::from("hello"));
+    /// let mut search = TextInput::bound(query.clone(), set_query.clone());
+    /// assert_eq!((search.text(), search.cursor()), ("hello", 5));
+    ///
+    /// // A write from elsewhere (e.g. a "Clear" button) shows up on the next
+    /// // event, with the cursor kept inside the new text
+    /// set_query.set(String::from("hey"));
+    /// let shift = Key::Named(NamedKey::Shift);
+    /// search.handle_key(&shift, ModifiersState::empty());
+    /// assert_eq!((search.text(), search.cursor()), ("hey", 3));
+    ///
+    /// // Edits write through to the signal without coming back as a write
+    /// search.handle_key(&Key::Named(NamedKey::Home), ModifiersState::empty());
+    /// search.handle_key(&Key::Character("O".into()), ModifiersState::SHIFT);
+    /// assert_eq!(query.get(), "Ohey");
+    /// search.handle_key(&shift, ModifiersState::empty());
+    /// assert_eq!((search.text(), search.cursor()), ("Ohey", 1));
+    /// ```
+    pub fn bound(value: Signal<String>, set: SetSignal<String>) -> Self {
+        Self::new(set).with_value(value)
+    }
+
     pub fn with_placeholder(mut self, text: impl Into<String>) -> Self {
         self.placeholder = text.into();
         self
@@ -81,12 +176,12 @@ impl TextInput {
     }
 
     pub fn with_text_color(mut self, color: [u8; 3]) -> Self {
-        self.text_color = color;
+        self.text_color = Some(color);
         self
     }
 
     pub fn with_background(mut self, color: [f32; 4]) -> Self {
-        self.background = color;
+        self.background = Some(color);
         self
     }
 
@@ -100,16 +195,118 @@ impl TextInput {
         self
     }
 
+    /// Make Tab insert a `\t` (e.g. for code editors). Shift+Tab still moves focus
+    /// backwards so the field never traps keyboard users.
+    ///
+    /// ```
+    /// use bexa_ui_core::{create_signal, TextInput};
+    /// use winit::keyboard::{Key, ModifiersState, NamedKey};
+    ///
+    /// let tab = Key::Named(NamedKey::Tab);
+    /// let (_value, set_value) = create_signal(String::new());
+    ///
+    /// // A plain field leaves Tab to the window, which moves focus
+    /// let mut field = TextInput::new(set_value.clone());
+    /// assert!(!field.handle_key(&tab, ModifiersState::empty()));
+    /// assert_eq!(field.text(), "");
+    ///
+    /// // With tab_inserts, Tab types a tab and Shift+Tab still moves focus
+    /// let mut editor = TextInput::new(set_value).with_tab_inserts(true);
+    /// assert!(editor.handle_key(&tab, ModifiersState::empty()));
+    /// assert_eq!(editor.text(), "\t");
+    /// assert!(!editor.handle_key(&tab, ModifiersState::SHIFT));
+    /// ```
+    pub fn with_tab_inserts(mut self, inserts: bool) -> Self {
+        self.tab_inserts = inserts;
+        self
+    }
+
+    /// Makes Enter insert a line break, Up/Down move between lines and Home/End
+    /// go to the ends of the line (Ctrl+Home/End to the ends of the text).
+    /// The field grows with the line count; see `with_max_rows`. Lines don't
+    /// wrap: long ones scroll sideways, as in a single-line field.
+    pub fn with_multiline(mut self, multiline: bool) -> Self {
+        self.multiline = multiline;
+        self
+    }
+
+    /// Caps the height of a multiline field at `rows` lines; more lines
+    /// scroll to keep the caret in view.
+    pub fn with_max_rows(mut self, rows: usize) -> Self {
+        self.max_rows = Some(rows.max(1));
+        self
+    }
+
+    /// Shows every character as `mask` (usually `'•'`), for passwords.
+    /// `text()` and `on_change` still get the real text; copy and cut do
+    /// nothing, so the secret can't leave through the clipboard.
+    pub fn with_mask(mut self, mask: char) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    pub fn with_caret_style(mut self, style: CaretStyle) -> Self {
+        self.caret_style = style;
+        self
+    }
+
+    /// Draws a Nerd Font glyph (see `icons`) before the text, in the
+    /// placeholder color, e.g. `icons::SEARCH` for a search field.
+    pub fn with_leading_icon(mut self, glyph: &'static str) -> Self {
+        self.leading_icon = Some(glyph);
+        self
+    }
+
+    /// Shows an "x" at the right end while the field has text; clicking it
+    /// empties the field and reports the empty text to `on_change`.
+    pub fn with_clear_button(mut self, clear_button: bool) -> Self {
+        self.clear_button = clear_button;
+        self
+    }
+
     pub fn with_initial_value(mut self, text: impl Into<String>) -> Self {
         self.text = text.into();
         self.cursor_pos = self.text.len();
         self
     }
 
-    fn notify_change(&self) {
+    /// Keeps the text in step with `value`: writes to it from app code
+    /// (clearing a search box, loading a record) replace the text, clamp the
+    /// cursor and drop the selection. User edits still go to `on_change`;
+    /// external writes don't, so binding both ends of the same signal can't
+    /// loop. `bound` does this with the reader of the signal given to `new`,
+    /// for a two-way binding.
+    pub fn with_value(mut self, value: Signal<String>) -> Self {
+        self.value_seen = value.get();
+        self.text = self.value_seen.clone();
+        self.cursor_pos = self.text.chars().count();
+        self.value = Some(value);
+        self
+    }
+
+    /// Adopts an external write to `value`. Returns whether the text changed.
+    fn sync_value(&mut self) -> bool {
+        let Some(value) = &self.value else {
+            return false;
+        };
+        if value.with(|current| *current == self.value_seen) {
+            return false;
+        }
+        self.value_seen = value.get();
+        self.text = self.value_seen.clone();
+        self.cursor_pos = self.cursor_pos.min(self.text.chars().count());
+        self.selection = None;
+        true
+    }
+
+    fn notify_change(&mut self) {
         if let Some(ref sig) = self.on_change {
             sig.set(self.text.clone());
         }
+        // Our own edit, not one to adopt back, even if `value` is another signal
+        if self.value.is_some() {
+            self.value_seen = self.text.clone();
+        }
     }
 
     fn insert_text(&mut self, s: &str) {
@@ -172,15 +369,7 @@ impl TextInput {
             self.cursor_pos = (self.cursor_pos + delta as usize).min(char_count);
         }
 
-        if shift {
-            match self.selection {
-                None => self.selection = Some((old_pos, self.cursor_pos)),
-                Some((anchor, _)) => self.selection = Some((anchor, self.cursor_pos)),
-            }
-        } else {
-            self.selection = None;
-        }
-
+        self.extend_or_clear_selection(old_pos, shift);
         self.last_input_time = Instant::now();
     }
 
@@ -199,23 +388,30 @@ impl TextInput {
     }
 
     fn copy_selection(&self) {
-        if let Some(text) = self.selected_text() {
-            if let Ok(mut cb) = Clipboard::new() {
-                let _ = cb.set_text(text);
-            }
+        if self.mask.is_some() {
+            return;
+        }
+        if let Some(text) = self.selected_text()
+            && let Ok(mut cb) = Clipboard::new()
+        {
+            let _ = cb.set_text(text);
         }
     }
 
     fn cut_selection(&mut self) {
+        if self.mask.is_some() {
+            return;
+        }
         self.copy_selection();
         self.delete_selection();
     }
 
     fn paste(&mut self) {
-        if let Ok(mut cb) = Clipboard::new() {
-            if let Ok(text) = cb.get_text() {
-                self.insert_text(&text);
-            }
+        if let Ok(mut cb) = Clipboard::new()
+            && let Ok(text) = cb.get_text()
+        {
+            let text = if self.multiline { text.replace("\r\n", "\n") } else { text };
+            self.insert_text(&text);
         }
     }
 
@@ -232,8 +428,127 @@ impl TextInput {
     }
 
     fn cursor_visible(&self) -> bool {
+        if self.caret_style != CaretStyle::Blink {
+            return true;
+        }
         let elapsed = self.last_input_time.elapsed().as_millis();
-        (elapsed % 1060) < 530
+        (elapsed % BLINK_PERIOD_MS) < BLINK_PERIOD_MS / 2
+    }
+
+    /// When a blinking caret next shows or hides.
+    fn next_blink(&self) -> Instant {
+        let half = BLINK_PERIOD_MS / 2;
+        let elapsed = self.last_input_time.elapsed().as_millis();
+        let next = (elapsed / half + 1) * half;
+        self.last_input_time + Duration::from_millis(next as u64)
+    }
+
+    /// Width of the character at the cursor, for block and underline carets.
+    fn cursor_char_width(&self) -> f32 {
+        let (row, col) = self.row_col(self.cursor_pos);
+        match self.edge(row, col + 1) {
+            Some(next) => (next - self.cursor_pixel_x).max(1.0),
+            None => self.metrics.font_size * 0.6,
+        }
+    }
+
+    /// The text as drawn: masked characters match the real ones one to one,
+    /// so measured edges line up with cursor positions.
+    fn display_text(&self) -> Cow<'_, str> {
+        match self.mask {
+            Some(mask) => self.text.chars().map(|c| if c == '\n' { c } else { mask }).collect(),
+            None => Cow::Borrowed(&self.text),
+        }
+    }
+
+    /// First char and char count of each line; the whole text is one line
+    /// unless multiline.
+    fn line_spans(&self) -> Vec<(usize, usize)> {
+        if !self.multiline {
+            return vec![(0, self.text.chars().count())];
+        }
+        let mut start = 0;
+        self.text
+            .split('\n')
+            .map(|line| {
+                let len = line.chars().count();
+                let span = (start, len);
+                start += len + 1;
+                span
+            })
+            .collect()
+    }
+
+    /// Line and column of char position `pos`.
+    fn row_col(&self, pos: usize) -> (usize, usize) {
+        let spans = self.line_spans();
+        let row = spans.iter().rposition(|&(start, _)| start <= pos).unwrap_or(0);
+        let (start, len) = spans[row];
+        (row, (pos - start).min(len))
+    }
+
+    /// Measured x of the edge before column `col` of line `row`.
+    fn edge(&self, row: usize, col: usize) -> Option<f32> {
+        self.line_edges.get(row)?.get(col).copied()
+    }
+
+    /// The char position on line `row` whose edge is closest to `rel_x`.
+    fn pos_in_row(&self, row: usize, rel_x: f32) -> usize {
+        let spans = self.line_spans();
+        let Some(&(start, len)) = spans.get(row) else {
+            return self.text.chars().count();
+        };
+        let Some(edges) = self.line_edges.get(row).filter(|edges| !edges.is_empty()) else {
+            return start;
+        };
+        // Find the edge closest to rel_x
+        let mut best = 0;
+        let mut best_dist = f32::MAX;
+        for (i, &edge) in edges.iter().enumerate() {
+            let dist = (edge - rel_x).abs();
+            if dist < best_dist {
+                best_dist = dist;
+                best = i;
+            }
+        }
+        start + best.min(len)
+    }
+
+    /// Moves the cursor a line up or down, keeping its x; past the first or
+    /// last line it goes to the start or end of the text.
+    fn move_vertical(&mut self, down: bool, shift: bool) {
+        let old_pos = self.cursor_pos;
+        let (row, _) = self.row_col(old_pos);
+        let rows = self.line_spans().len();
+        self.cursor_pos = if !down && row == 0 {
+            0
+        } else if down && row + 1 >= rows {
+            self.text.chars().count()
+        } else {
+            let target = if down { row + 1 } else { row - 1 };
+            self.pos_in_row(target, self.cursor_pixel_x)
+        };
+        self.extend_or_clear_selection(old_pos, shift);
+        self.last_input_time = Instant::now();
+    }
+
+    /// Moves the cursor to `pos`, selecting from the anchor with Shift.
+    fn jump_to(&mut self, pos: usize, shift: bool) {
+        let old_pos = self.cursor_pos;
+        self.cursor_pos = pos;
+        self.extend_or_clear_selection(old_pos, shift);
+        self.last_input_time = Instant::now();
+    }
+
+    fn extend_or_clear_selection(&mut self, old_pos: usize, shift: bool) {
+        if shift {
+            match self.selection {
+                None => self.selection = Some((old_pos, self.cursor_pos)),
+                Some((anchor, _)) => self.selection = Some((anchor, self.cursor_pos)),
+            }
+        } else {
+            self.selection = None;
+        }
     }
 
     /// Returns the text substring before the cursor position.
@@ -257,24 +572,106 @@ impl TextInput {
         self.focused
     }
 
-    /// Given an absolute x pixel position, find the closest char position using glyph edges.
-    fn char_pos_from_x(&self, layout: &Layout, x: f32) -> usize {
-        let text_x = layout.location.x + self.padding;
-        let rel_x = x - text_x;
-        if self.char_edges.is_empty() {
-            return 0;
+    /// Given an absolute pixel position, find the closest char position using
+    /// glyph edges: the line under `y`, then the edge closest to `x` on it.
+    fn char_pos_at(&self, layout: &Layout, x: f32, y: f32) -> usize {
+        let (text_x, text_y, ..) = self.text_rect(layout);
+        let rel_x = x - text_x + self.scroll_x;
+        let rows = self.line_spans().len();
+        let row = ((y - text_y + self.scroll_y) / self.metrics.line_height).max(0.0) as usize;
+        self.pos_in_row(row.min(rows - 1), rel_x)
+    }
+
+    /// Scrolls just enough to bring the caret into view, and back as far as
+    /// the text allows when it gets shorter.
+    fn scroll_to_caret(&mut self) {
+        let view_w = self.view_w.get();
+        let content_w = self
+            .line_edges
+            .iter()
+            .filter_map(|edges| edges.last().copied())
+            .fold(0.0, f32::max);
+        let caret = self.cursor_pixel_x;
+        if caret + CARET_W - self.scroll_x > view_w {
+            self.scroll_x = caret + CARET_W - view_w;
         }
-        // Find the edge closest to rel_x
-        let mut best = 0;
-        let mut best_dist = f32::MAX;
-        for (i, &edge) in self.char_edges.iter().enumerate() {
-            let dist = (edge - rel_x).abs();
-            if dist < best_dist {
-                best_dist = dist;
-                best = i;
-            }
+        if caret < self.scroll_x {
+            self.scroll_x = caret;
+        }
+        let max_scroll = (content_w + CARET_W - view_w).max(0.0);
+        self.scroll_x = self.scroll_x.clamp(0.0, max_scroll);
+
+        let view_h = self.view_h.get();
+        let line_h = self.metrics.line_height;
+        let caret_top = self.row_col(self.cursor_pos).0 as f32 * line_h;
+        if caret_top + line_h - self.scroll_y > view_h {
+            self.scroll_y = caret_top + line_h - view_h;
+        }
+        if caret_top < self.scroll_y {
+            self.scroll_y = caret_top;
         }
-        best
+        let content_h = self.line_spans().len() as f32 * line_h;
+        self.scroll_y = self.scroll_y.clamp(0.0, (content_h - view_h).max(0.0));
+    }
+
+    /// Lines the field is tall enough to show.
+    fn visible_rows(&self) -> usize {
+        if !self.multiline {
+            return 1;
+        }
+        let rows = self.line_spans().len();
+        self.max_rows.map_or(rows, |max| rows.min(max))
+    }
+
+    /// Top of the leading icon and clear button: centered in a single-line
+    /// field, on the first line of a multiline one.
+    fn icon_y(&self, layout: &Layout) -> f32 {
+        if self.multiline {
+            layout.location.y + self.padding
+        } else {
+            layout.location.y + (layout.size.height - self.icon_slot_w()) * 0.5
+        }
+    }
+
+    /// Width of the leading icon or clear button slot: one line square.
+    fn icon_slot_w(&self) -> f32 {
+        self.metrics.line_height
+    }
+
+    /// Region the text scrolls in: inside the padding, after the leading
+    /// icon and before the clear button.
+    fn text_rect(&self, layout: &Layout) -> (f32, f32, f32, f32) {
+        let mut x = layout.location.x + self.padding;
+        let y = layout.location.y + self.padding;
+        let mut w = layout.size.width - self.padding * 2.0;
+        let h = (layout.size.height - self.padding * 2.0).max(0.0);
+        if self.leading_icon.is_some() {
+            x += self.icon_slot_w() + ICON_GAP;
+            w -= self.icon_slot_w() + ICON_GAP;
+        }
+        // Reserved even while hidden, so the text doesn't shift on the first key
+        if self.clear_button {
+            w -= self.icon_slot_w() + ICON_GAP;
+        }
+        (x, y, w.max(0.0), h)
+    }
+
+    /// The clear button, while it is shown.
+    fn clear_button_rect(&self, layout: &Layout) -> Option<(f32, f32, f32, f32)> {
+        if !self.clear_button || self.text.is_empty() {
+            return None;
+        }
+        let slot = self.icon_slot_w();
+        let x = layout.location.x + layout.size.width - self.padding - slot;
+        Some((x, self.icon_y(layout), slot, slot))
+    }
+
+    fn clear(&mut self) {
+        self.text.clear();
+        self.cursor_pos = 0;
+        self.selection = None;
+        self.last_input_time = Instant::now();
+        self.notify_change();
     }
 
     fn hit_test(&self, layout: &Layout, x: f32, y: f32) -> bool {
@@ -283,11 +680,114 @@ impl TextInput {
             && y >= layout.location.y
             && y <= layout.location.y + layout.size.height
     }
+
+    /// Handles a press of `key`, as `Widget::handle_key_event` does. Returns
+    /// false for keys the field leaves to the window, like Tab without
+    /// `with_tab_inserts`, which moves focus.
+    pub fn handle_key(&mut self, key: &Key, modifiers: ModifiersState) -> bool {
+        self.sync_value();
+        let ctrl = modifiers.control_key();
+        let shift = modifiers.shift_key();
+
+        if ctrl {
+            match key {
+                Key::Character(c) if c.as_str() == "a" => {
+                    self.select_all();
+                    return true;
+                }
+                Key::Character(c) if c.as_str() == "c" => {
+                    self.copy_selection();
+                    return true;
+                }
+                Key::Character(c) if c.as_str() == "v" => {
+                    self.paste();
+                    return true;
+                }
+                Key::Character(c) if c.as_str() == "x" => {
+                    self.cut_selection();
+                    return true;
+                }
+                _ => {}
+            }
+        }
+
+        match key {
+            Key::Character(c) => {
+                if !ctrl {
+                    self.insert_text(c.as_str());
+                    return true;
+                }
+                false
+            }
+            // winit reports Space as a named key, not a character
+            Key::Named(NamedKey::Space) => {
+                if !ctrl {
+                    self.insert_text(" ");
+                    return true;
+                }
+                false
+            }
+            Key::Named(NamedKey::Backspace) => {
+                self.delete_back();
+                true
+            }
+            Key::Named(NamedKey::Delete) => {
+                self.delete_forward();
+                true
+            }
+            Key::Named(NamedKey::ArrowLeft) => {
+                self.move_cursor(-1, shift);
+                true
+            }
+            Key::Named(NamedKey::ArrowRight) => {
+                self.move_cursor(1, shift);
+                true
+            }
+            Key::Named(NamedKey::ArrowUp) if self.multiline => {
+                self.move_vertical(false, shift);
+                true
+            }
+            Key::Named(NamedKey::ArrowDown) if self.multiline => {
+                self.move_vertical(true, shift);
+                true
+            }
+            Key::Named(NamedKey::Home) => {
+                // The line's start, or the text's with Ctrl (single-line: both)
+                let start = if ctrl { 0 } else { self.line_spans()[self.row_col(self.cursor_pos).0].0 };
+                self.jump_to(start, shift);
+                true
+            }
+            Key::Named(NamedKey::End) => {
+                let end = if ctrl {
+                    self.text.chars().count()
+                } else {
+                    let (start, len) = self.line_spans()[self.row_col(self.cursor_pos).0];
+                    start + len
+                };
+                self.jump_to(end, shift);
+                true
+            }
+            Key::Named(NamedKey::Tab) => {
+                // Unconsumed Tab moves focus (see `Widget::handle_key_event`)
+                if self.tab_inserts && !shift && !ctrl {
+                    self.insert_text("\t");
+                    return true;
+                }
+                false
+            }
+            Key::Named(NamedKey::Enter) if self.multiline && !ctrl => {
+                self.insert_text("\n");
+                true
+            }
+            Key::Named(NamedKey::Enter) => false,
+            _ => false,
+        }
+    }
 }
 
 impl Widget for TextInput {
     fn style(&self) -> Style {
-        let height = self.metrics.line_height + self.padding * 2.0;
+        let height = self.visible_rows() as f32 * self.metrics.line_height + self.padding * 2.0;
         Style {
             size: Size {
                 width: Dimension::Percent(1.0),
@@ -308,33 +808,82 @@ impl Widget for TextInput {
         // Background
         let border_w = if self.focused { 1.5 } else { 0.0 };
         let border_c = if self.focused {
-            self.focus_border_color
+            ctx.theme.focus_ring
         } else {
             [0.0; 4]
         };
+        let text_color = self.text_color.unwrap_or(ctx.theme.text_primary);
         ctx.renderer.fill_rect_styled(
             (x, y, w, h),
-            self.background,
+            self.background.unwrap_or(opaque(ctx.theme.background)),
             self.border_radius,
             border_w,
             border_c,
         );
 
-        let text_x = x + self.padding;
-        let text_y = y + self.padding;
-        let text_w = (w - self.padding * 2.0).max(0.0);
-        let text_h = (h - self.padding * 2.0).max(0.0);
+        let (text_x, text_y, text_w, text_h) = self.text_rect(layout);
+        self.view_w.set(text_w);
+        self.view_h.set(text_h);
 
-        // Selection highlight
+        let icon_metrics = Metrics::new(self.metrics.font_size, self.icon_slot_w());
+        if let Some(glyph) = self.leading_icon {
+            ctx.renderer.draw_text_with_font(
+                glyph,
+                (x + self.padding, self.icon_y(layout)),
+                self.placeholder_color,
+                (self.icon_slot_w(), self.icon_slot_w()),
+                icon_metrics,
+                Align::Center,
+                NERD_FONT_FAMILY,
+            );
+        }
+        if let Some((bx, by, bw, bh)) = self.clear_button_rect(layout) {
+            let color = if self.clear_hovered { text_color } else { self.placeholder_color };
+            ctx.renderer.draw_text_with_font(
+                icons::CLOSE,
+                (bx, by),
+                color,
+                (bw, bh),
+                Metrics::new(self.metrics.font_size * 0.8, bh),
+                Align::Center,
+                NERD_FONT_FAMILY,
+            );
+        }
+
+        // Text, selection and caret scroll together inside the text region
+        ctx.renderer.push_clip_within((text_x, text_y, text_w, text_h));
+        let scrolled_x = text_x - self.scroll_x;
+        let line_h = self.metrics.line_height;
+        // Top and height of line `row`; a single line fills the text region
+        let row_rect = |row: usize| {
+            if self.multiline {
+                (text_y - self.scroll_y + row as f32 * line_h, line_h)
+            } else {
+                (text_y, text_h)
+            }
+        };
+
+        // Selection highlight, one run per line
         if let Some((start, end)) = self.selection {
             let (lo, hi) = if start < end { (start, end) } else { (end, start) };
-            if lo != hi {
-                let sel_x0 = text_x + self.selection_lo_px;
-                let sel_x1 = text_x + self.selection_hi_px;
-                let sel_w = (sel_x1 - sel_x0).max(0.0);
+            let [sel_r, sel_g, sel_b, _] = ctx.theme.selection;
+            for (row, &(line_start, len)) in self.line_spans().iter().enumerate() {
+                let line_end = line_start + len;
+                if lo == hi || hi < line_start || lo > line_end {
+                    continue;
+                }
+                let from = lo.max(line_start) - line_start;
+                let to = hi.min(line_end) - line_start;
+                let sel_x0 = scrolled_x + self.edge(row, from).unwrap_or(0.0);
+                let mut sel_x1 = scrolled_x + self.edge(row, to).unwrap_or(0.0);
+                // A selected line break shows as a sliver past the line end
+                if hi > line_end {
+                    sel_x1 += self.metrics.font_size * 0.3;
+                }
+                let (row_y, row_h) = row_rect(row);
                 ctx.renderer.fill_rect_rounded(
-                    (sel_x0, text_y, sel_w, text_h),
-                    [0.2, 0.4, 0.7, 0.5],
+                    (sel_x0, row_y, (sel_x1 - sel_x0).max(0.0), row_h),
+                    [sel_r, sel_g, sel_b, 0.5],
                     2.0,
                 );
             }
@@ -351,15 +900,33 @@ impl Widget for TextInput {
                 self.metrics,
                 Align::Left,
             );
+        } else if self.multiline {
+            // One command per line, each measured like a single-line field
+            let mut first = None;
+            for (row, line) in self.display_text().split('\n').enumerate() {
+                let (row_y, row_h) = row_rect(row);
+                let measure: Vec<usize> = (0..=line.chars().count()).collect();
+                let idx = ctx.renderer.draw_text_measured(
+                    line,
+                    (scrolled_x, row_y),
+                    text_color,
+                    (UNWRAPPED_W, row_h),
+                    self.metrics,
+                    Align::Left,
+                    measure,
+                );
+                first.get_or_insert(idx);
+            }
+            self.text_cmd_index.set(first);
         } else {
             // Measure all char edges [0, 1, 2, ..., char_count] for mouse positioning
             let char_count = self.text.chars().count();
             let measure: Vec<usize> = (0..=char_count).collect();
             let idx = ctx.renderer.draw_text_measured(
-                &self.text,
-                (text_x, text_y),
-                self.text_color,
-                (text_w, text_h),
+                &self.display_text(),
+                (scrolled_x, text_y),
+                text_color,
+                (UNWRAPPED_W, text_h),
                 self.metrics,
                 Align::Left,
                 measure,
@@ -368,19 +935,45 @@ impl Widget for TextInput {
         }
 
         // Cursor (caret) — positioned using real pixel width from render layer
+        if self.focused && self.caret_style == CaretStyle::Blink {
+            ctx.renderer.request_redraw_at(self.next_blink());
+        }
         if self.focused && self.cursor_visible() {
-            let cursor_x = text_x + self.cursor_pixel_x;
+            let cursor_x = scrolled_x + self.cursor_pixel_x;
             let cursor_h = self.metrics.font_size;
-            let cursor_y = text_y + (text_h - cursor_h) * 0.5;
-            ctx.renderer.fill_rect_rounded(
-                (cursor_x, cursor_y, 1.5, cursor_h),
-                [0.4, 0.7, 1.0, 1.0],
-                0.0,
-            );
+            let (row_y, row_h) = row_rect(self.row_col(self.cursor_pos).0);
+            let cursor_y = row_y + (row_h - cursor_h) * 0.5;
+            let caret_color = [0.4, 0.7, 1.0, 1.0];
+            match self.caret_style {
+                CaretStyle::Blink | CaretStyle::Solid => {
+                    ctx.renderer.fill_rect_rounded(
+                        (cursor_x, cursor_y, CARET_W, cursor_h),
+                        caret_color,
+                        0.0,
+                    );
+                }
+                CaretStyle::Block => {
+                    // Translucent, since text draws after quads and stays readable on top
+                    ctx.renderer.fill_rect_rounded(
+                        (cursor_x, cursor_y, self.cursor_char_width(), cursor_h),
+                        [caret_color[0], caret_color[1], caret_color[2], 0.55],
+                        1.0,
+                    );
+                }
+                CaretStyle::Underline => {
+                    ctx.renderer.fill_rect_rounded(
+                        (cursor_x, cursor_y + cursor_h - 2.0, self.cursor_char_width(), 2.0),
+                        caret_color,
+                        0.0,
+                    );
+                }
+            }
         }
+        ctx.renderer.pop_clip();
     }
 
     fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        self.sync_value();
         let layout = ctx.layout;
         match ctx.event {
             WindowEvent::MouseInput {
@@ -388,10 +981,26 @@ impl Widget for TextInput {
                 button: MouseButton::Left,
                 ..
             } => {
-                if self.hit_test(layout, self.last_mouse_x, self.last_mouse_y) {
-                    let pos = self.char_pos_from_x(layout, self.last_mouse_x);
+                let (cx, cy) = ctx.cursor;
+                if let Some(rect) = self.clear_button_rect(layout)
+                    && in_rect(rect, cx, cy)
+                {
+                    self.clear();
+                    return true;
+                }
+                if self.hit_test(layout, cx, cy) {
+                    let pos = self.char_pos_at(layout, cx, cy);
+                    if self.shift && self.focused {
+                        // Extend from the existing anchor, or from the cursor
+                        let anchor = match self.selection {
+                            Some((anchor, _)) => anchor,
+                            None => self.cursor_pos,
+                        };
+                        self.selection = (anchor != pos).then_some((anchor, pos));
+                    } else {
+                        self.selection = None;
+                    }
                     self.cursor_pos = pos;
-                    self.selection = None;
                     self.mouse_dragging = true;
                     self.last_input_time = Instant::now();
                     true
@@ -407,11 +1016,16 @@ impl Widget for TextInput {
                 self.mouse_dragging = false;
                 false
             }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.shift = modifiers.state().shift_key();
+                false
+            }
             WindowEvent::CursorMoved { position, .. } => {
-                self.last_mouse_x = position.x as f32;
-                self.last_mouse_y = position.y as f32;
+                self.clear_hovered = self
+                    .clear_button_rect(layout)
+                    .is_some_and(|rect| in_rect(rect, position.x as f32, position.y as f32));
                 if self.mouse_dragging && self.focused {
-                    let pos = self.char_pos_from_x(layout, position.x as f32);
+                    let pos = self.char_pos_at(layout, position.x as f32, position.y as f32);
                     if pos != self.cursor_pos {
                         let anchor = match self.selection {
                             Some((anchor, _)) => anchor,
@@ -433,106 +1047,30 @@ impl Widget for TextInput {
     }
 
     fn handle_key_event(&mut self, event: &KeyEvent, modifiers: ModifiersState) -> bool {
-        let ctrl = modifiers.control_key();
-        let shift = modifiers.shift_key();
-
-        if ctrl {
-            match &event.logical_key {
-                Key::Character(c) if c.as_str() == "a" => {
-                    self.select_all();
-                    return true;
-                }
-                Key::Character(c) if c.as_str() == "c" => {
-                    self.copy_selection();
-                    return true;
-                }
-                Key::Character(c) if c.as_str() == "v" => {
-                    self.paste();
-                    return true;
-                }
-                Key::Character(c) if c.as_str() == "x" => {
-                    self.cut_selection();
-                    return true;
-                }
-                _ => {}
-            }
-        }
-
-        match &event.logical_key {
-            Key::Character(c) => {
-                if !ctrl {
-                    self.insert_text(c.as_str());
-                    return true;
-                }
-                false
-            }
-            Key::Named(NamedKey::Backspace) => {
-                self.delete_back();
-                true
-            }
-            Key::Named(NamedKey::Delete) => {
-                self.delete_forward();
-                true
-            }
-            Key::Named(NamedKey::ArrowLeft) => {
-                self.move_cursor(-1, shift);
-                true
-            }
-            Key::Named(NamedKey::ArrowRight) => {
-                self.move_cursor(1, shift);
-                true
-            }
-            Key::Named(NamedKey::Home) => {
-                let old = self.cursor_pos;
-                self.cursor_pos = 0;
-                if shift {
-                    match self.selection {
-                        None => self.selection = Some((old, 0)),
-                        Some((anchor, _)) => self.selection = Some((anchor, 0)),
-                    }
-                } else {
-                    self.selection = None;
-                }
-                self.last_input_time = Instant::now();
-                true
-            }
-            Key::Named(NamedKey::End) => {
-                let old = self.cursor_pos;
-                let end = self.text.chars().count();
-                self.cursor_pos = end;
-                if shift {
-                    match self.selection {
-                        None => self.selection = Some((old, end)),
-                        Some((anchor, _)) => self.selection = Some((anchor, end)),
-                    }
-                } else {
-                    self.selection = None;
-                }
-                self.last_input_time = Instant::now();
-                true
-            }
-            Key::Named(NamedKey::Tab) => false,
-            Key::Named(NamedKey::Enter) => false,
-            _ => false,
-        }
+        self.handle_key(&event.logical_key, modifiers)
     }
 
     fn update_measures(&mut self, measures: &[Vec<f32>]) {
-        if let Some(idx) = self.text_cmd_index.get() {
-            if let Some(edges) = measures.get(idx) {
-                self.char_edges = edges.clone();
-                // cursor_pixel_x = edge at cursor_pos
-                if let Some(&w) = edges.get(self.cursor_pos) {
-                    self.cursor_pixel_x = w;
-                }
-                // selection highlight positions
-                if let Some((start, end)) = self.selection {
-                    let (lo, hi) = if start < end { (start, end) } else { (end, start) };
-                    self.selection_lo_px = edges.get(lo).copied().unwrap_or(0.0);
-                    self.selection_hi_px = edges.get(hi).copied().unwrap_or(0.0);
-                }
+        // Measures of the old text; the next frame draws and measures the new one
+        if self.sync_value() {
+            return;
+        }
+        if let Some(first) = self.text_cmd_index.get() {
+            let rows = self.line_spans().len();
+            self.line_edges = (first..first + rows)
+                .map(|idx| measures.get(idx).cloned().unwrap_or_default())
+                .collect();
+            // cursor_pixel_x = edge at cursor_pos on its line
+            let (row, col) = self.row_col(self.cursor_pos);
+            if let Some(w) = self.edge(row, col) {
+                self.cursor_pixel_x = w;
             }
+        } else {
+            // Placeholder shown: nothing to scroll
+            self.line_edges.clear();
+            self.cursor_pixel_x = 0.0;
         }
+        self.scroll_to_caret();
     }
 
     fn is_focusable(&self) -> bool {
@@ -546,3 +1084,7 @@ impl Widget for TextInput {
         }
     }
 }
+
+fn in_rect((x, y, w, h): (f32, f32, f32, f32), px: f32, py: f32) -> bool {
+    px >= x && px <= x + w && py >= y && py <= y + h
+}
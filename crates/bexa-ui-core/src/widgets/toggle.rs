@@ -1,14 +1,13 @@
 use glyphon::Metrics;
-use glyphon::cosmic_text::Align;
 use taffy::prelude::*;
-use winit::event::{ElementState, KeyEvent, MouseButton, WindowEvent};
-use winit::keyboard::{Key, ModifiersState, NamedKey};
+use winit::event::{ElementState, MouseButton, WindowEvent};
 
 use crate::framework::{DrawContext, EventContext, Widget};
 use crate::signal::{Signal, SetSignal};
+use crate::widgets::measured_label::MeasuredLabel;
 
 pub struct Toggle {
-    label: String,
+    label: MeasuredLabel,
     checked: Signal<bool>,
     set_checked: SetSignal<bool>,
     metrics: Metrics,
@@ -16,12 +15,13 @@ pub struct Toggle {
     track_height: f32,
     gap: f32,
     padding: f32,
-    // Colors
-    track_off: [f32; 4],
-    track_on: [f32; 4],
+    // Colors; `None` uses the theme's checkbox colors, `success` when on
+    // and `text_primary` for the label
+    track_off: Option<[f32; 4]>,
+    track_on: Option<[f32; 4]>,
     knob_color: [f32; 4],
-    border_color: [f32; 4],
-    text_color: [u8; 3],
+    border_color: Option<[f32; 4]>,
+    text_color: Option<[u8; 3]>,
     // State
     hover: bool,
     focus: bool,
@@ -35,7 +35,7 @@ impl Toggle {
         metrics: Metrics,
     ) -> Self {
         Self {
-            label: label.into(),
+            label: MeasuredLabel::new(label),
             checked,
             set_checked,
             metrics,
@@ -43,11 +43,11 @@ impl Toggle {
             track_height: 22.0,
             gap: 10.0,
             padding: 4.0,
-            track_off: [0.16, 0.28, 0.38, 1.0],
-            track_on: [0.20, 0.65, 0.45, 1.0],
+            track_off: None,
+            track_on: None,
             knob_color: [0.95, 0.95, 0.97, 1.0],
-            border_color: [0.40, 0.55, 0.70, 1.0],
-            text_color: [230, 230, 230],
+            border_color: None,
+            text_color: None,
             hover: false,
             focus: false,
         }
@@ -77,21 +77,14 @@ impl Toggle {
         border: [f32; 4],
         text: [u8; 3],
     ) -> Self {
-        self.track_off = track_off;
-        self.track_on = track_on;
+        self.track_off = Some(track_off);
+        self.track_on = Some(track_on);
         self.knob_color = knob;
-        self.border_color = border;
-        self.text_color = text;
+        self.border_color = Some(border);
+        self.text_color = Some(text);
         self
     }
 
-    fn hit_test(&self, layout: &Layout, x: f32, y: f32) -> bool {
-        x >= layout.location.x
-            && x <= layout.location.x + layout.size.width
-            && y >= layout.location.y
-            && y <= layout.location.y + layout.size.height
-    }
-
     fn toggle(&self) {
         let current = self.checked.get();
         self.set_checked.set(!current);
@@ -101,9 +94,12 @@ impl Toggle {
 impl Widget for Toggle {
     fn style(&self) -> Style {
         let height = self.track_height.max(self.metrics.line_height) + self.padding * 2.0;
+        // Sized to its content so packed rows don't clip the label
+        let label_w = if self.label.is_empty() { 0.0 } else { self.gap + self.label.width(self.metrics) };
+        let width = self.padding * 2.0 + self.track_width + label_w;
         Style {
             size: Size {
-                width: Dimension::Auto,
+                width: Dimension::Length(width),
                 height: Dimension::Length(height),
             },
             flex_shrink: 0.0,
@@ -112,26 +108,31 @@ impl Widget for Toggle {
     }
 
     fn draw(&self, ctx: &mut DrawContext) {
+        let theme = ctx.theme;
+        let track_off = self.track_off.unwrap_or(theme.checkbox_bg);
+        let track_on = self.track_on.unwrap_or(theme.success);
+        let border_color = self.border_color.unwrap_or(theme.checkbox_border);
+        let text_color = self.text_color.unwrap_or(theme.text_primary);
         let layout = ctx.layout;
         let checked = self.checked.get();
 
         let track_x = layout.location.x + self.padding;
         let track_y = layout.location.y + (layout.size.height - self.track_height) / 2.0;
         let track_radius = self.track_height / 2.0;
-        let track_color = if checked { self.track_on } else { self.track_off };
+        let track_color = if checked { track_on } else { track_off };
 
         let border_w = if self.focus { 2.0 } else if self.hover { 1.5 } else { 1.0 };
         let border_c = if self.focus {
-            [0.3, 0.6, 0.9, 1.0]
+            ctx.theme.focus_ring
         } else if self.hover {
             [
-                (self.border_color[0] + 0.1).min(1.0),
-                (self.border_color[1] + 0.1).min(1.0),
-                (self.border_color[2] + 0.1).min(1.0),
-                self.border_color[3],
+                (border_color[0] + 0.1).min(1.0),
+                (border_color[1] + 0.1).min(1.0),
+                (border_color[2] + 0.1).min(1.0),
+                border_color[3],
             ]
         } else {
-            self.border_color
+            border_color
         };
 
         ctx.renderer.fill_rect_styled(
@@ -159,46 +160,25 @@ impl Widget for Toggle {
             let text_x = track_x + self.track_width + self.gap;
             let text_y = layout.location.y + (layout.size.height - self.metrics.line_height) / 2.0;
             let text_w = (layout.size.width - (text_x - layout.location.x)).max(0.0);
-            ctx.renderer.draw_text(
-                &self.label,
-                (text_x, text_y),
-                self.text_color,
-                (text_w, self.metrics.line_height),
-                self.metrics,
-                Align::Left,
-            );
+            self.label.draw(ctx.renderer, (text_x, text_y), text_w, text_color, self.metrics);
         }
     }
 
+    fn on_mouse_enter(&mut self) {
+        self.hover = true;
+    }
+
+    fn on_mouse_leave(&mut self) {
+        self.hover = false;
+    }
+
     fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
-        let layout = ctx.layout;
         match ctx.event {
-            WindowEvent::CursorMoved { position, .. } => {
-                self.hover = self.hit_test(layout, position.x as f32, position.y as f32);
-                false
-            }
             WindowEvent::MouseInput {
                 state: ElementState::Pressed,
                 button: MouseButton::Left,
                 ..
-            } => {
-                if self.hover {
-                    self.toggle();
-                    true
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        }
-    }
-
-    fn handle_key_event(&mut self, event: &KeyEvent, _modifiers: ModifiersState) -> bool {
-        if event.state != ElementState::Pressed {
-            return false;
-        }
-        match &event.logical_key {
-            Key::Named(NamedKey::Space) | Key::Named(NamedKey::Enter) => {
+            } if self.hover => {
                 self.toggle();
                 true
             }
@@ -206,6 +186,10 @@ impl Widget for Toggle {
         }
     }
 
+    fn update_measures(&mut self, measures: &[Vec<f32>]) {
+        self.label.update_measures(measures);
+    }
+
     fn is_focusable(&self) -> bool {
         true
     }
@@ -0,0 +1,435 @@
+use glyphon::Metrics;
+use glyphon::cosmic_text::Align;
+use taffy::prelude::*;
+use winit::event::{ElementState, KeyEvent, MouseButton, WindowEvent};
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+
+use crate::framework::{DrawContext, EventContext, Widget};
+use crate::icons::NERD_FONT_FAMILY;
+use crate::shortcut::KeyCombo;
+use crate::widgets::popover::PopoverSide;
+use crate::widgets::tooltip::{draw_tooltip_bubble, TooltipStyle};
+
+/// Direction the toolbar buttons are laid out in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToolbarOrientation {
+    /// A row of buttons (top bars).
+    Horizontal,
+    /// A column of buttons (side rails).
+    Vertical,
+}
+
+enum ToolbarItem {
+    Button {
+        icon: &'static str,
+        tooltip: String,
+        /// Shortcut hint shown in the tooltip, already formatted
+        shortcut: String,
+        action: Box<dyn FnMut()>,
+    },
+    Separator,
+}
+
+/// Row (or column) of compact icon-only buttons with hover tooltips.
+///
+/// ```ignore
+/// let toolbar = Toolbar::new()
+///     .with_item(icons::FOLDER_OPEN, "Open", || open_file())
+///     .with_item(icons::SAVE, "Save", || save_file())
+///     .with_shortcut(&KeyCombo::primary("s"))
+///     .with_separator()
+///     .with_item(icons::COG, "Settings", || show_settings());
+/// ```
+pub struct Toolbar {
+    items: Vec<ToolbarItem>,
+    orientation: ToolbarOrientation,
+    size: f32,
+    padding: f32,
+    spacing: f32,
+    border_radius: f32,
+    // Colors
+    bg: [f32; 4],
+    hover_bg: [f32; 4],
+    pressed_bg: [f32; 4],
+    icon_color: [u8; 3],
+    separator_color: [f32; 4],
+    tooltip_style: TooltipStyle,
+    // State
+    hover_index: Option<usize>,
+    pressed_index: Option<usize>,
+    focus: bool,
+    focus_index: Option<usize>,
+}
+
+const SEPARATOR_SPAN: f32 = 9.0;
+
+impl Default for Toolbar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Toolbar {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            orientation: ToolbarOrientation::Horizontal,
+            size: 32.0,
+            padding: 4.0,
+            spacing: 2.0,
+            border_radius: 4.0,
+            bg: [0.0, 0.0, 0.0, 0.0],
+            hover_bg: [1.0, 1.0, 1.0, 0.10],
+            pressed_bg: [1.0, 1.0, 1.0, 0.18],
+            icon_color: [210, 215, 225],
+            separator_color: [0.35, 0.38, 0.48, 1.0],
+            tooltip_style: TooltipStyle::default(),
+            hover_index: None,
+            pressed_index: None,
+            focus: false,
+            focus_index: None,
+        }
+    }
+
+    /// Adds an icon button; `tooltip` shows on hover (empty for none).
+    pub fn with_item(
+        mut self,
+        icon: &'static str,
+        tooltip: impl Into<String>,
+        action: impl FnMut() + 'static,
+    ) -> Self {
+        self.items.push(ToolbarItem::Button {
+            icon,
+            tooltip: tooltip.into(),
+            shortcut: String::new(),
+            action: Box::new(action),
+        });
+        self
+    }
+
+    /// Shows `combo` in the tooltip of the button added last, e.g. Ctrl+S
+    /// next to "Save". This is only a hint; the combo doesn't trigger the
+    /// button by itself, bind it with `App::on_shortcut`.
+    pub fn with_shortcut(mut self, combo: &KeyCombo) -> Self {
+        if let Some(ToolbarItem::Button { shortcut, .. }) = self.items.last_mut() {
+            *shortcut = combo.display();
+        }
+        self
+    }
+
+    /// Adds a divider line between groups of buttons.
+    pub fn with_separator(mut self) -> Self {
+        self.items.push(ToolbarItem::Separator);
+        self
+    }
+
+    pub fn with_orientation(mut self, orientation: ToolbarOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Side length of each square button.
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn with_border_radius(mut self, radius: f32) -> Self {
+        self.border_radius = radius;
+        self
+    }
+
+    pub fn with_colors(
+        mut self,
+        bg: [f32; 4],
+        hover_bg: [f32; 4],
+        pressed_bg: [f32; 4],
+        separator: [f32; 4],
+    ) -> Self {
+        self.bg = bg;
+        self.hover_bg = hover_bg;
+        self.pressed_bg = pressed_bg;
+        self.separator_color = separator;
+        self
+    }
+
+    pub fn with_icon_color(mut self, color: [u8; 3]) -> Self {
+        self.icon_color = color;
+        self
+    }
+
+    pub fn with_tooltip_colors(mut self, bg: [f32; 4], border: [f32; 4], text_color: [u8; 3]) -> Self {
+        self.tooltip_style.bg = bg;
+        self.tooltip_style.border = border;
+        self.tooltip_style.text_color = text_color;
+        self
+    }
+
+    fn item_span(&self, item: &ToolbarItem) -> f32 {
+        match item {
+            ToolbarItem::Button { .. } => self.size,
+            ToolbarItem::Separator => SEPARATOR_SPAN,
+        }
+    }
+
+    fn main_length(&self) -> f32 {
+        let spans: f32 = self.items.iter().map(|item| self.item_span(item)).sum();
+        let gaps = self.items.len().saturating_sub(1) as f32 * self.spacing;
+        spans + gaps + self.padding * 2.0
+    }
+
+    /// Absolute rect of every item, in order.
+    fn item_rects(&self, layout: &Layout) -> Vec<(f32, f32, f32, f32)> {
+        let x = layout.location.x;
+        let y = layout.location.y;
+        let cross = self.size;
+        let mut offset = self.padding;
+        self.items
+            .iter()
+            .map(|item| {
+                let span = self.item_span(item);
+                let rect = match self.orientation {
+                    ToolbarOrientation::Horizontal => (x + offset, y + self.padding, span, cross),
+                    ToolbarOrientation::Vertical => (x + self.padding, y + offset, cross, span),
+                };
+                offset += span + self.spacing;
+                rect
+            })
+            .collect()
+    }
+
+    fn button_at(&self, layout: &Layout, px: f32, py: f32) -> Option<usize> {
+        self.item_rects(layout)
+            .iter()
+            .zip(&self.items)
+            .position(|(&(x, y, w, h), item)| {
+                matches!(item, ToolbarItem::Button { .. })
+                    && px >= x
+                    && px <= x + w
+                    && py >= y
+                    && py <= y + h
+            })
+    }
+
+    fn button_indices(&self) -> Vec<usize> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| matches!(item, ToolbarItem::Button { .. }))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn move_focus(&mut self, forward: bool) -> bool {
+        let buttons = self.button_indices();
+        if buttons.is_empty() {
+            return false;
+        }
+        let pos = self
+            .focus_index
+            .and_then(|idx| buttons.iter().position(|&b| b == idx));
+        let next = match (pos, forward) {
+            (None, _) => 0,
+            (Some(p), true) => (p + 1) % buttons.len(),
+            (Some(p), false) => (p + buttons.len() - 1) % buttons.len(),
+        };
+        self.focus_index = Some(buttons[next]);
+        true
+    }
+
+    fn trigger(&mut self, index: usize) {
+        if let Some(ToolbarItem::Button { action, .. }) = self.items.get_mut(index) {
+            action();
+        }
+    }
+
+    fn draw_tooltip(&self, ctx: &mut DrawContext, anchor: (f32, f32, f32, f32), text: &str, hint: &str) {
+        let side = match self.orientation {
+            ToolbarOrientation::Horizontal => PopoverSide::Bottom,
+            ToolbarOrientation::Vertical => PopoverSide::Right,
+        };
+        draw_tooltip_bubble(ctx.renderer, anchor, text, hint, side, 6.0, &self.tooltip_style);
+    }
+}
+
+impl Widget for Toolbar {
+    fn style(&self) -> Style {
+        let main = self.main_length();
+        let cross = self.size + self.padding * 2.0;
+        let (width, height) = match self.orientation {
+            ToolbarOrientation::Horizontal => (main, cross),
+            ToolbarOrientation::Vertical => (cross, main),
+        };
+        Style {
+            size: Size {
+                width: Dimension::Length(width),
+                height: Dimension::Length(height),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawContext) {
+        let layout = ctx.layout;
+        let x = layout.location.x;
+        let y = layout.location.y;
+        let w = layout.size.width;
+        let h = layout.size.height;
+
+        if self.bg[3] > 0.0 {
+            ctx.renderer.fill_rect_rounded((x, y, w, h), self.bg, self.border_radius);
+        }
+
+        let icon_size = (self.size * 0.55).round();
+        let icon_metrics = Metrics::new(icon_size, icon_size * 1.2);
+        let rects = self.item_rects(layout);
+
+        for (i, (item, &(ix, iy, iw, ih))) in self.items.iter().zip(&rects).enumerate() {
+            match item {
+                ToolbarItem::Button { icon, .. } => {
+                    let item_bg = if self.pressed_index == Some(i) {
+                        self.pressed_bg
+                    } else if self.hover_index == Some(i) {
+                        self.hover_bg
+                    } else {
+                        [0.0; 4]
+                    };
+                    if item_bg[3] > 0.0 {
+                        ctx.renderer.fill_rect_rounded((ix, iy, iw, ih), item_bg, self.border_radius);
+                    }
+
+                    let text_top = iy + (ih - icon_metrics.line_height) / 2.0;
+                    ctx.renderer.draw_text_with_font(
+                        icon,
+                        (ix, text_top),
+                        self.icon_color,
+                        (iw, icon_metrics.line_height),
+                        icon_metrics,
+                        Align::Center,
+                        NERD_FONT_FAMILY,
+                    );
+
+                    if self.focus && self.focus_index == Some(i) {
+                        ctx.renderer.fill_rect_styled(
+                            (ix, iy, iw, ih),
+                            [0.0; 4],
+                            self.border_radius,
+                            2.0,
+                            ctx.theme.focus_ring,
+                        );
+                    }
+                }
+                ToolbarItem::Separator => {
+                    let inset = self.size * 0.2;
+                    let line = match self.orientation {
+                        ToolbarOrientation::Horizontal => {
+                            (ix + (iw - 1.0) / 2.0, iy + inset, 1.0, ih - inset * 2.0)
+                        }
+                        ToolbarOrientation::Vertical => {
+                            (ix + inset, iy + (ih - 1.0) / 2.0, iw - inset * 2.0, 1.0)
+                        }
+                    };
+                    ctx.renderer.fill_rect_rounded(line, self.separator_color, 0.0);
+                }
+            }
+        }
+
+        // Tooltip for the hovered button, or the focused one when using the keyboard
+        let tip_index = self
+            .hover_index
+            .or(if self.focus { self.focus_index } else { None });
+        if let Some(i) = tip_index
+            && let Some(ToolbarItem::Button { tooltip, shortcut, .. }) = self.items.get(i)
+            && !tooltip.is_empty()
+        {
+            self.draw_tooltip(ctx, rects[i], tooltip, shortcut);
+        }
+    }
+
+    fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
+        let layout = ctx.layout;
+        match ctx.event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.hover_index = self.button_at(layout, position.x as f32, position.y as f32);
+                if self.hover_index.is_none() {
+                    self.pressed_index = None;
+                }
+                false
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } => {
+                if let Some(idx) = self.hover_index {
+                    self.pressed_index = Some(idx);
+                    true
+                } else {
+                    false
+                }
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button: MouseButton::Left,
+                ..
+            } => {
+                // Fire on release over the same button, like a regular click
+                let pressed = self.pressed_index.take();
+                match pressed {
+                    Some(idx) if self.hover_index == Some(idx) => {
+                        self.trigger(idx);
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn handle_key_event(&mut self, event: &KeyEvent, _modifiers: ModifiersState) -> bool {
+        if event.state != ElementState::Pressed {
+            return false;
+        }
+        let horizontal = self.orientation == ToolbarOrientation::Horizontal;
+        match &event.logical_key {
+            Key::Named(NamedKey::ArrowRight) if horizontal => self.move_focus(true),
+            Key::Named(NamedKey::ArrowLeft) if horizontal => self.move_focus(false),
+            Key::Named(NamedKey::ArrowDown) if !horizontal => self.move_focus(true),
+            Key::Named(NamedKey::ArrowUp) if !horizontal => self.move_focus(false),
+            _ => false,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.button_indices().is_empty()
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focus = focused;
+        if focused && self.focus_index.is_none() {
+            self.focus_index = self.button_indices().first().copied();
+        }
+    }
+
+    fn activate(&mut self) {
+        if let Some(idx) = self.focus_index {
+            self.trigger(idx);
+        }
+    }
+
+    fn clear_active(&mut self) {
+        self.pressed_index = None;
+    }
+}
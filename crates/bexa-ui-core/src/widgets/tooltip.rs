@@ -1,4 +1,5 @@
 use std::cell::Cell;
+use std::time::{Duration, Instant};
 
 use glyphon::Metrics;
 use glyphon::cosmic_text::Align;
@@ -6,6 +7,9 @@ use taffy::prelude::*;
 use winit::event::WindowEvent;
 
 use crate::framework::{DrawContext, EventContext, Widget};
+use crate::renderer::Renderer;
+use crate::shortcut::KeyCombo;
+use crate::widgets::popover::{place_overlay, AnchorRect, PopoverAlign, PopoverSide};
 
 /// Position of the tooltip relative to its trigger area.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -14,17 +18,120 @@ pub enum TooltipPosition {
     Bottom,
 }
 
+/// Look of a tooltip bubble, shared by every widget that shows hover tips.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TooltipStyle {
+    pub metrics: Metrics,
+    pub padding: f32,
+    pub bg: [f32; 4],
+    pub border: [f32; 4],
+    pub text_color: [u8; 3],
+    /// Shortcut hints, dimmer than the text
+    pub hint_color: [u8; 3],
+    pub border_radius: f32,
+}
+
+impl Default for TooltipStyle {
+    fn default() -> Self {
+        Self {
+            metrics: Metrics::new(12.0, 16.0),
+            padding: 6.0,
+            bg: [0.12, 0.14, 0.20, 0.95],
+            border: [0.35, 0.40, 0.55, 1.0],
+            text_color: [220, 220, 230],
+            hint_color: [140, 145, 165],
+            border_radius: 4.0,
+        }
+    }
+}
+
+impl TooltipStyle {
+    /// The style at `opacity` (0 to 1) of a fade-in. Text has no alpha of
+    /// its own, so it blends from the bubble color instead.
+    pub fn faded(&self, opacity: f32) -> Self {
+        let fade = |[r, g, b, a]: [f32; 4]| [r, g, b, a * opacity];
+        let blend = |color: [u8; 3]| {
+            let mix = |from: f32, to: u8| (from * 255.0 + (to as f32 - from * 255.0) * opacity).round() as u8;
+            [mix(self.bg[0], color[0]), mix(self.bg[1], color[1]), mix(self.bg[2], color[2])]
+        };
+        Self {
+            bg: fade(self.bg),
+            border: fade(self.border),
+            text_color: blend(self.text_color),
+            hint_color: blend(self.hint_color),
+            ..*self
+        }
+    }
+}
+
+/// Space between a tooltip's text and its shortcut hint.
+const HINT_GAP: f32 = 16.0;
+
+/// Draws a one-line tooltip bubble on the overlay layer next to `anchor`,
+/// centered on it and flipped at the viewport edges. A non-empty `hint`
+/// (a shortcut like "Ctrl+S") is drawn dimmed at the right end.
+pub(crate) fn draw_tooltip_bubble(
+    renderer: &mut Renderer,
+    anchor: AnchorRect,
+    text: &str,
+    hint: &str,
+    side: PopoverSide,
+    gap: f32,
+    style: &TooltipStyle,
+) {
+    let metrics = style.metrics;
+    let pad = style.padding;
+    // Estimate text width: ~0.6em per char, clamped
+    let text_w = (text.chars().count() as f32 * metrics.font_size * 0.6).clamp(40.0, 480.0);
+    let hint_w = hint.chars().count() as f32 * metrics.font_size * 0.6;
+    let hint_span = if hint.is_empty() { 0.0 } else { HINT_GAP + hint_w };
+    let tip_w = text_w + hint_span + pad * 2.0;
+    let tip_h = metrics.line_height + pad * 2.0;
+    let (tip_x, tip_y, _) = place_overlay(
+        anchor,
+        (tip_w, tip_h),
+        renderer.viewport_size(),
+        side,
+        PopoverAlign::Center,
+        gap,
+    );
+
+    renderer.overlay_fill_rect_styled(
+        (tip_x, tip_y, tip_w, tip_h),
+        style.bg,
+        style.border_radius,
+        1.0,
+        style.border,
+    );
+    renderer.overlay_draw_text(
+        text,
+        (tip_x + pad, tip_y + pad),
+        style.text_color,
+        (text_w, metrics.line_height),
+        metrics,
+        if hint.is_empty() { Align::Center } else { Align::Left },
+    );
+    if !hint.is_empty() {
+        renderer.overlay_draw_text(
+            hint,
+            (tip_x + pad + text_w + HINT_GAP, tip_y + pad),
+            style.hint_color,
+            (hint_w, metrics.line_height),
+            metrics,
+            Align::Right,
+        );
+    }
+}
+
 pub struct Tooltip {
     text: String,
+    shortcut: String,
     position: TooltipPosition,
-    metrics: Metrics,
-    padding: f32,
-    bg: [f32; 4],
-    border: [f32; 4],
-    text_color: [u8; 3],
-    border_radius: f32,
+    style: TooltipStyle,
+    delay: Duration,
+    fade: Duration,
     // State
-    hover: bool,
+    hovered_since: Option<Instant>,
     // Cached absolute position (set during draw)
     abs_x: Cell<f32>,
     abs_y: Cell<f32>,
@@ -36,14 +143,12 @@ impl Tooltip {
     pub fn new(text: impl Into<String>) -> Self {
         Self {
             text: text.into(),
+            shortcut: String::new(),
             position: TooltipPosition::Top,
-            metrics: Metrics::new(12.0, 16.0),
-            padding: 6.0,
-            bg: [0.12, 0.14, 0.20, 0.95],
-            border: [0.35, 0.40, 0.55, 1.0],
-            text_color: [220, 220, 230],
-            border_radius: 4.0,
-            hover: false,
+            style: TooltipStyle::default(),
+            delay: Duration::ZERO,
+            fade: Duration::ZERO,
+            hovered_since: None,
             abs_x: Cell::new(0.0),
             abs_y: Cell::new(0.0),
             abs_w: Cell::new(0.0),
@@ -56,15 +161,35 @@ impl Tooltip {
         self
     }
 
+    /// Shows the shortcut for the wrapped action (e.g. a button's
+    /// accelerator) dimmed after the text.
+    pub fn with_shortcut(mut self, combo: &KeyCombo) -> Self {
+        self.shortcut = combo.display();
+        self
+    }
+
+    /// Shows the tooltip only once the cursor has rested on the trigger
+    /// this long, so sweeping across several triggers doesn't flash theirs.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Fades the tooltip in over `fade` once it shows.
+    pub fn with_fade(mut self, fade: Duration) -> Self {
+        self.fade = fade;
+        self
+    }
+
     pub fn with_metrics(mut self, metrics: Metrics) -> Self {
-        self.metrics = metrics;
+        self.style.metrics = metrics;
         self
     }
 
     pub fn with_colors(mut self, bg: [f32; 4], border: [f32; 4], text_color: [u8; 3]) -> Self {
-        self.bg = bg;
-        self.border = border;
-        self.text_color = text_color;
+        self.style.bg = bg;
+        self.style.border = border;
+        self.style.text_color = text_color;
         self
     }
 }
@@ -94,57 +219,52 @@ impl Widget for Tooltip {
         self.abs_w.set(w);
         self.abs_h.set(h);
 
-        // Draw tooltip overlay when hovering
-        if self.hover && !self.text.is_empty() {
-            let tip_h = self.metrics.line_height + self.padding * 2.0;
-            // Estimate text width: ~7px per char at 12px font, clamped
-            let estimated_w = (self.text.len() as f32 * self.metrics.font_size * 0.6)
-                .max(60.0)
-                .min(300.0);
-            let tip_w = estimated_w + self.padding * 2.0;
-
-            // Center horizontally over the trigger
-            let tip_x = x + (w - tip_w) / 2.0;
-            let tip_y = match self.position {
-                TooltipPosition::Top => y - tip_h - 4.0,
-                TooltipPosition::Bottom => y + h + 4.0,
-            };
-
-            // Background
-            ctx.renderer.overlay_fill_rect_styled(
-                (tip_x, tip_y, tip_w, tip_h),
-                self.bg,
-                self.border_radius,
-                1.0,
-                self.border,
-            );
-
-            // Text
-            ctx.renderer.overlay_draw_text(
-                &self.text,
-                (tip_x + self.padding, tip_y + self.padding),
-                self.text_color,
-                (estimated_w, self.metrics.line_height),
-                self.metrics,
-                Align::Center,
-            );
+        // Draw tooltip overlay once the cursor has hovered for the delay
+        let Some(since) = self.hovered_since else {
+            return;
+        };
+        if self.text.is_empty() {
+            return;
+        }
+        let shown_at = since + self.delay;
+        let now = Instant::now();
+        if now < shown_at {
+            ctx.renderer.request_redraw_at(shown_at);
+            return;
+        }
+        let opacity = if self.fade.is_zero() {
+            1.0
+        } else {
+            (now.duration_since(shown_at).as_secs_f32() / self.fade.as_secs_f32()).min(1.0)
+        };
+        if opacity < 1.0 {
+            ctx.renderer.request_next_frame();
         }
+
+        let side = match self.position {
+            TooltipPosition::Top => PopoverSide::Top,
+            TooltipPosition::Bottom => PopoverSide::Bottom,
+        };
+        let anchor = (x, y, w, h);
+        let style = self.style.faded(opacity);
+        draw_tooltip_bubble(ctx.renderer, anchor, &self.text, &self.shortcut, side, 4.0, &style);
     }
 
     fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
-        match ctx.event {
-            WindowEvent::CursorMoved { position, .. } => {
-                let px = position.x as f32;
-                let py = position.y as f32;
-                let x = self.abs_x.get();
-                let y = self.abs_y.get();
-                let w = self.abs_w.get();
-                let h = self.abs_h.get();
-
-                let inside = px >= x && px <= x + w && py >= y && py <= y + h;
-                self.hover = inside;
+        if let WindowEvent::CursorMoved { position, .. } = ctx.event {
+            let px = position.x as f32;
+            let py = position.y as f32;
+            let x = self.abs_x.get();
+            let y = self.abs_y.get();
+            let w = self.abs_w.get();
+            let h = self.abs_h.get();
+
+            let inside = px >= x && px <= x + w && py >= y && py <= y + h;
+            if !inside {
+                self.hovered_since = None;
+            } else if self.hovered_since.is_none() {
+                self.hovered_since = Some(Instant::now());
             }
-            _ => {}
         }
         false
     }
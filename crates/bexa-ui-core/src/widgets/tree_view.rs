@@ -1,3 +1,6 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+
 use glyphon::Metrics;
 use glyphon::cosmic_text::Align;
 use taffy::prelude::*;
@@ -6,6 +9,8 @@ use winit::keyboard::{Key, ModifiersState, NamedKey};
 
 use crate::framework::{DrawContext, EventContext, Widget};
 use crate::icons;
+use crate::widgets::popover::PopoverSide;
+use crate::widgets::tooltip::{draw_tooltip_bubble, TooltipStyle};
 
 /// A node in the tree structure.
 pub struct TreeNode {
@@ -55,18 +60,25 @@ pub struct TreeView {
     row_height: f32,
     indent: f32,
     padding: f32,
-    // Colors
+    // Colors; `None` uses the theme's `text_primary`, `hover` and `selection`
     bg: [f32; 4],
-    text_color: [u8; 3],
+    text_color: Option<[u8; 3]>,
     icon_color: [u8; 3],
-    hover_bg: [f32; 4],
-    selected_bg: [f32; 4],
+    hover_bg: Option<[f32; 4]>,
+    selected_bg: Option<[f32; 4]>,
     selected_text: [u8; 3],
     connector_color: [f32; 4],
     // State
     hover_flat_idx: Option<usize>,
     selected_flat_idx: Option<usize>,
     focus: bool,
+    /// Row the arrow keys moved to, for `take_scroll_target`
+    reveal_row: Option<usize>,
+    tooltip_style: TooltipStyle,
+    // Label text command index and available width per row (set during draw)
+    label_indices: RefCell<Vec<(usize, usize, f32)>>,
+    /// Rows whose label didn't fit last frame
+    truncated: HashSet<usize>,
 }
 
 impl TreeView {
@@ -78,15 +90,19 @@ impl TreeView {
             indent: 20.0,
             padding: 6.0,
             bg: [0.0, 0.0, 0.0, 0.0],
-            text_color: [210, 210, 220],
+            text_color: None,
             icon_color: [140, 170, 220],
-            hover_bg: [0.18, 0.22, 0.30, 1.0],
-            selected_bg: [0.20, 0.45, 0.70, 1.0],
+            hover_bg: None,
+            selected_bg: None,
             selected_text: [255, 255, 255],
             connector_color: [0.3, 0.35, 0.4, 0.6],
             hover_flat_idx: None,
             selected_flat_idx: None,
             focus: false,
+            reveal_row: None,
+            tooltip_style: TooltipStyle::default(),
+            label_indices: RefCell::new(Vec::new()),
+            truncated: HashSet::new(),
         }
     }
 
@@ -107,10 +123,10 @@ impl TreeView {
         hover_bg: [f32; 4],
         selected_bg: [f32; 4],
     ) -> Self {
-        self.text_color = text;
+        self.text_color = Some(text);
         self.icon_color = icon;
-        self.hover_bg = hover_bg;
-        self.selected_bg = selected_bg;
+        self.hover_bg = Some(hover_bg);
+        self.selected_bg = Some(selected_bg);
         self
     }
 
@@ -152,10 +168,11 @@ impl TreeView {
                     return Some(node as *mut TreeNode);
                 }
                 *idx += 1;
-                if node.is_branch() && node.expanded {
-                    if let Some(found) = find(&mut node.children, target, idx) {
-                        return Some(found);
-                    }
+                if node.is_branch()
+                    && node.expanded
+                    && let Some(found) = find(&mut node.children, target, idx)
+                {
+                    return Some(found);
                 }
             }
             None
@@ -167,10 +184,10 @@ impl TreeView {
     }
 
     fn toggle(&mut self, flat_idx: usize) {
-        if let Some(node) = self.node_at_mut(flat_idx) {
-            if node.is_branch() {
-                node.expanded = !node.expanded;
-            }
+        if let Some(node) = self.node_at_mut(flat_idx)
+            && node.is_branch()
+        {
+            node.expanded = !node.expanded;
         }
     }
 }
@@ -189,6 +206,10 @@ impl Widget for TreeView {
     }
 
     fn draw(&self, ctx: &mut DrawContext) {
+        let theme = ctx.theme;
+        let text_color = self.text_color.unwrap_or(theme.text_primary);
+        let hover_bg = self.hover_bg.unwrap_or(theme.hover);
+        let selected_bg = self.selected_bg.unwrap_or(theme.selection);
         let layout = ctx.layout;
         let x = layout.location.x;
         let y = layout.location.y;
@@ -208,6 +229,10 @@ impl Widget for TreeView {
             self.metrics.line_height,
         );
 
+        let mut label_indices = self.label_indices.borrow_mut();
+        label_indices.clear();
+        let mut hover_tip = None;
+
         self.walk_visible(|flat_idx, depth, node| {
             let ry = y + flat_idx as f32 * self.row_height;
             let indent_x = x + depth as f32 * self.indent;
@@ -219,13 +244,13 @@ impl Widget for TreeView {
             if is_selected {
                 ctx.renderer.fill_rect_rounded(
                     (x, ry, w, self.row_height),
-                    self.selected_bg,
+                    selected_bg,
                     0.0,
                 );
             } else if is_hover {
                 ctx.renderer.fill_rect_rounded(
                     (x, ry, w, self.row_height),
-                    self.hover_bg,
+                    hover_bg,
                     0.0,
                 );
             }
@@ -280,20 +305,35 @@ impl Widget for TreeView {
             let tc = if is_selected {
                 self.selected_text
             } else {
-                self.text_color
+                text_color
             };
             let text_y = ry + (self.row_height - self.metrics.line_height) / 2.0;
             let remaining = (w - (text_x - x)).max(0.0);
-            ctx.renderer.draw_text(
+            // One line, clipped at the edge; measured to detect truncation
+            let label_rect = (text_x, ry, remaining, self.row_height);
+            ctx.renderer.push_clip_within(label_rect);
+            let idx = ctx.renderer.draw_text_measured(
                 &node.label,
                 (text_x, text_y),
                 tc,
-                (remaining, self.metrics.line_height),
+                (10_000.0, self.metrics.line_height),
                 self.metrics,
                 Align::Left,
+                vec![node.label.chars().count()],
             );
+            ctx.renderer.pop_clip();
+            label_indices.push((flat_idx, idx, remaining));
+
+            if is_hover && self.truncated.contains(&flat_idx) {
+                hover_tip = Some((label_rect, node.label.clone()));
+            }
         });
 
+        // Full text of a hovered truncated label
+        if let Some((anchor, label)) = hover_tip {
+            draw_tooltip_bubble(ctx.renderer, anchor, &label, "", PopoverSide::Bottom, 4.0, &self.tooltip_style);
+        }
+
         // Focus ring
         if self.focus {
             let total_h = self.visible_count() as f32 * self.row_height;
@@ -302,11 +342,15 @@ impl Widget for TreeView {
                 [0.0, 0.0, 0.0, 0.0],
                 0.0,
                 2.0,
-                [0.3, 0.6, 0.9, 1.0],
+                ctx.theme.focus_ring,
             );
         }
     }
 
+    fn on_mouse_leave(&mut self) {
+        self.hover_flat_idx = None;
+    }
+
     fn handle_event(&mut self, ctx: &mut EventContext) -> bool {
         let layout = ctx.layout;
 
@@ -360,33 +404,35 @@ impl Widget for TreeView {
                 let current = self.selected_flat_idx.unwrap_or(0);
                 let next = (current + 1).min(count - 1);
                 self.selected_flat_idx = Some(next);
+                self.reveal_row = Some(next);
                 true
             }
             Key::Named(NamedKey::ArrowUp) => {
                 let current = self.selected_flat_idx.unwrap_or(0);
                 let next = current.saturating_sub(1);
                 self.selected_flat_idx = Some(next);
+                self.reveal_row = Some(next);
                 true
             }
             Key::Named(NamedKey::ArrowRight) => {
-                if let Some(idx) = self.selected_flat_idx {
-                    if let Some(node) = self.node_at_mut(idx) {
-                        if node.is_branch() && !node.expanded {
-                            node.expanded = true;
-                            return true;
-                        }
-                    }
+                if let Some(idx) = self.selected_flat_idx
+                    && let Some(node) = self.node_at_mut(idx)
+                    && node.is_branch()
+                    && !node.expanded
+                {
+                    node.expanded = true;
+                    return true;
                 }
                 false
             }
             Key::Named(NamedKey::ArrowLeft) => {
-                if let Some(idx) = self.selected_flat_idx {
-                    if let Some(node) = self.node_at_mut(idx) {
-                        if node.is_branch() && node.expanded {
-                            node.expanded = false;
-                            return true;
-                        }
-                    }
+                if let Some(idx) = self.selected_flat_idx
+                    && let Some(node) = self.node_at_mut(idx)
+                    && node.is_branch()
+                    && node.expanded
+                {
+                    node.expanded = false;
+                    return true;
                 }
                 false
             }
@@ -409,4 +455,24 @@ impl Widget for TreeView {
     fn set_focus(&mut self, focused: bool) {
         self.focus = focused;
     }
+
+    fn take_scroll_target(&mut self) -> Option<(f32, f32, f32, f32)> {
+        let row = self.reveal_row.take()?;
+        Some((0.0, row as f32 * self.row_height, 0.0, self.row_height))
+    }
+
+    fn update_measures(&mut self, measures: &[Vec<f32>]) {
+        self.truncated = self
+            .label_indices
+            .borrow()
+            .iter()
+            .filter(|(_, idx, available)| {
+                measures
+                    .get(*idx)
+                    .and_then(|edges| edges.first())
+                    .is_some_and(|&w| w > available + 0.5)
+            })
+            .map(|(row, _, _)| *row)
+            .collect();
+    }
 }
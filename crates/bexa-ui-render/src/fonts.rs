@@ -0,0 +1,82 @@
+//! Extra fonts and the fallback chain used to find glyphs a text's own
+//! font lacks, such as emoji.
+
+use std::sync::Arc;
+
+use glyphon::cosmic_text::{Fallback, PlatformFallback};
+use glyphon::{fontdb, FontSystem};
+use unicode_script::Script;
+
+/// Fallback lists with the families of fonts added through
+/// `App::with_font_data` ahead of the platform's own lists.
+///
+/// For each character its font lacks, a text tries in order:
+/// 1. the platform's fonts for the character's script (e.g. Noto Sans
+///    Arabic for Arabic on Linux),
+/// 2. the added fonts, in the order they were added,
+/// 3. the platform's common fallbacks, which end with its color emoji
+///    font: Noto Color Emoji on Linux, Apple Color Emoji on macOS, Segoe UI
+///    Emoji on Windows,
+/// 4. any other installed font that has the glyph.
+///
+/// Emoji have no script of their own, so step 2 is where an added emoji
+/// font takes over from the system's, or fills in where there is none.
+struct BexaFallback {
+    common: Vec<&'static str>,
+    platform: PlatformFallback,
+}
+
+impl Fallback for BexaFallback {
+    fn common_fallback(&self) -> &[&'static str] {
+        &self.common
+    }
+
+    fn forbidden_fallback(&self) -> &[&'static str] {
+        self.platform.forbidden_fallback()
+    }
+
+    fn script_fallback(&self, script: Script, locale: &str) -> &[&'static str] {
+        self.platform.script_fallback(script, locale)
+    }
+}
+
+/// Loads `fonts` (TTF, OTF or TTC bytes) into `font_system` and rebuilds it
+/// so their families join the fallback chain after `families`, the ones
+/// added by earlier calls. Returns the updated family list.
+pub(crate) fn add_fonts(
+    font_system: &mut FontSystem,
+    fonts: Vec<Vec<u8>>,
+    mut families: Vec<&'static str>,
+) -> Vec<&'static str> {
+    // The chain is fixed when a FontSystem is built, so swap in a new one
+    let placeholder = FontSystem::new_with_locale_and_db(String::new(), fontdb::Database::new());
+    let (locale, mut db) = std::mem::replace(font_system, placeholder).into_locale_and_db();
+
+    for data in fonts {
+        let ids = db.load_font_source(fontdb::Source::Binary(Arc::new(data)));
+        for id in ids {
+            let Some(face) = db.face(id) else {
+                continue;
+            };
+            for (name, _) in &face.families {
+                if !families.contains(&name.as_str()) {
+                    // Fallback names must be 'static; fonts are added once per app
+                    families.push(Box::leak(name.clone().into_boxed_str()));
+                }
+            }
+        }
+    }
+
+    let platform = PlatformFallback;
+    let common = families
+        .iter()
+        .copied()
+        .chain(platform.common_fallback().iter().copied())
+        .collect();
+    *font_system = FontSystem::new_with_locale_and_db_and_fallback(
+        locale,
+        db,
+        BexaFallback { common, platform },
+    );
+    families
+}
@@ -0,0 +1,187 @@
+//! GPU timestamp profiling of a window's render passes, for
+//! `App::with_gpu_profiling`.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+/// Timestamps per frame: a begin and end for each measured pass group.
+const QUERY_COUNT: u32 = 6;
+/// How often averaged timings are reported.
+const REPORT_PERIOD: Duration = Duration::from_secs(1);
+
+/// Average GPU time per frame of each group of render passes, over about a
+/// second of frames.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GpuTimings {
+    /// Quads and images, drawn under the text.
+    pub quads: Duration,
+    /// Main text.
+    pub text: Duration,
+    /// Overlay quads and text: popups, tooltips, modals.
+    pub overlay: Duration,
+    /// Frames the averages cover. Results are read back without stalling,
+    /// so only some frames are sampled.
+    pub frames: u32,
+}
+
+/// Measured pass groups, whose timestamps sit at `2 * group` and
+/// `2 * group + 1` in the query set.
+#[derive(Clone, Copy)]
+pub(crate) enum PassGroup {
+    Quads = 0,
+    Text = 1,
+    Overlay = 2,
+}
+
+impl PassGroup {
+    pub(crate) fn begin(self) -> u32 {
+        self as u32 * 2
+    }
+
+    pub(crate) fn end(self) -> u32 {
+        self as u32 * 2 + 1
+    }
+}
+
+pub(crate) struct GpuProfiler {
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    /// Nanoseconds per timestamp tick
+    period_ns: f64,
+    // Readback of the last sampled frame; no new frame is sampled until it lands
+    in_flight: bool,
+    map_sender: Sender<bool>,
+    map_done: Receiver<bool>,
+    // Totals since the last report, in nanoseconds
+    totals: [f64; 3],
+    frames: u32,
+    period_start: Instant,
+}
+
+impl GpuProfiler {
+    /// `None` unless the device was created with `Features::TIMESTAMP_QUERY`.
+    pub(crate) fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Option<Self> {
+        if !device.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+            return None;
+        }
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("Pass Timestamps"),
+            ty: wgpu::QueryType::Timestamp,
+            count: QUERY_COUNT,
+        });
+        let size = QUERY_COUNT as u64 * wgpu::QUERY_SIZE as u64;
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Timestamp Resolve Buffer"),
+            size,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Timestamp Readback Buffer"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let (map_sender, map_done) = mpsc::channel();
+        Some(Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            period_ns: queue.get_timestamp_period() as f64,
+            in_flight: false,
+            map_sender,
+            map_done,
+            totals: [0.0; 3],
+            frames: 0,
+            period_start: Instant::now(),
+        })
+    }
+
+    /// Whether this frame is sampled; skipped while the last sample is
+    /// still being read back.
+    pub(crate) fn is_sampling(&self) -> bool {
+        !self.in_flight
+    }
+
+    /// Timestamp writes for a render pass; the begin or end may belong to
+    /// a group that spans several passes.
+    pub(crate) fn timestamp_writes(
+        &self,
+        begin: Option<u32>,
+        end: Option<u32>,
+    ) -> wgpu::RenderPassTimestampWrites<'_> {
+        wgpu::RenderPassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: begin,
+            end_of_pass_write_index: end,
+        }
+    }
+
+    /// Copies the frame's timestamps where they can be mapped. Call after
+    /// the passes and before submitting `encoder`.
+    pub(crate) fn resolve(&self, encoder: &mut wgpu::CommandEncoder) {
+        encoder.resolve_query_set(&self.query_set, 0..QUERY_COUNT, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &self.readback_buffer,
+            0,
+            self.resolve_buffer.size(),
+        );
+    }
+
+    /// Starts reading back the sampled frame. Call right after the submit.
+    pub(crate) fn start_readback(&mut self) {
+        let sender = self.map_sender.clone();
+        self.readback_buffer
+            .map_async(wgpu::MapMode::Read, .., move |result| {
+                let _ = sender.send(result.is_ok());
+            });
+        self.in_flight = true;
+    }
+
+    /// Collects a finished readback without blocking, and returns the
+    /// averages once per report period.
+    pub(crate) fn poll(&mut self, device: &wgpu::Device) -> Option<GpuTimings> {
+        if self.in_flight {
+            let _ = device.poll(wgpu::PollType::Poll);
+            if let Ok(mapped) = self.map_done.try_recv() {
+                if mapped {
+                    self.accumulate();
+                    self.readback_buffer.unmap();
+                }
+                self.in_flight = false;
+            }
+        }
+
+        if self.period_start.elapsed() < REPORT_PERIOD || self.frames == 0 {
+            return None;
+        }
+        let frames = self.frames;
+        let average = |total: f64| Duration::from_nanos((total / frames as f64) as u64);
+        let timings = GpuTimings {
+            quads: average(self.totals[PassGroup::Quads as usize]),
+            text: average(self.totals[PassGroup::Text as usize]),
+            overlay: average(self.totals[PassGroup::Overlay as usize]),
+            frames,
+        };
+        self.totals = [0.0; 3];
+        self.frames = 0;
+        self.period_start = Instant::now();
+        Some(timings)
+    }
+
+    fn accumulate(&mut self) {
+        let ticks: Vec<u64> = {
+            let view = self.readback_buffer.get_mapped_range(..);
+            view.chunks_exact(8)
+                .map(|bytes| u64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+                .collect()
+        };
+        for (total, pair) in self.totals.iter_mut().zip(ticks.chunks_exact(2)) {
+            *total += pair[1].saturating_sub(pair[0]) as f64 * self.period_ns;
+        }
+        self.frames += 1;
+    }
+}
@@ -0,0 +1,154 @@
+//! Textures of decoded images, shared by every window of the app and kept
+//! under a byte budget, for `Image` and `DrawContext::image`.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+
+/// Budget of a new cache: room for about sixty 1024x1024 images.
+pub const DEFAULT_IMAGE_CACHE_BUDGET: u64 = 256 * 1024 * 1024;
+
+thread_local! {
+    static STATS: Cell<ImageCacheStats> = const { Cell::new(ImageCacheStats {
+        count: 0,
+        bytes: 0,
+        budget: DEFAULT_IMAGE_CACHE_BUDGET,
+    }) };
+}
+
+/// How much the image cache holds, from `image_cache_stats`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ImageCacheStats {
+    /// Textures uploaded.
+    pub count: usize,
+    /// GPU memory of those textures.
+    pub bytes: u64,
+    /// Bytes the cache evicts down to; see `App::with_image_cache_budget`.
+    pub budget: u64,
+}
+
+/// The image cache of the app (or `BexaSurface`) running on this thread,
+/// for debugging overlays and logs.
+pub fn image_cache_stats() -> ImageCacheStats {
+    STATS.with(Cell::get)
+}
+
+pub(crate) struct GpuImage {
+    pub(crate) _texture: wgpu::Texture,
+    pub(crate) _view: wgpu::TextureView,
+    pub(crate) bind_group: wgpu::BindGroup,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+struct Entry {
+    image: GpuImage,
+    bytes: u64,
+    /// Frame that last drew the image
+    last_used: u64,
+}
+
+/// Uploaded images by path (or `Image::from_bytes` key). When an upload
+/// would go over the budget, the least recently drawn images are dropped
+/// first; images drawn in the current frame are kept even then, so one
+/// frame can go over the budget.
+pub(crate) struct ImageCache {
+    entries: HashMap<String, Entry>,
+    budget: u64,
+    bytes: u64,
+    frame: u64,
+    /// Whether `image_cache_stats` reports this cache; off for the
+    /// throwaway caches of offscreen exports.
+    reported: bool,
+}
+
+impl ImageCache {
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            budget: DEFAULT_IMAGE_CACHE_BUDGET,
+            bytes: 0,
+            frame: 0,
+            reported: true,
+        }
+    }
+
+    pub(crate) fn unreported(mut self) -> Self {
+        self.reported = false;
+        self
+    }
+
+    pub(crate) fn set_budget(&mut self, budget: u64) {
+        self.budget = budget;
+        self.evict(0);
+        self.report();
+    }
+
+    /// Starts a window's frame; the images it draws are kept until the next.
+    pub(crate) fn begin_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Marks the image drawn this frame. Returns false when it isn't loaded.
+    pub(crate) fn touch(&mut self, key: &str) -> bool {
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                entry.last_used = self.frame;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<&GpuImage> {
+        self.entries.get(key).map(|entry| &entry.image)
+    }
+
+    pub(crate) fn insert(&mut self, key: String, image: GpuImage) {
+        // RGBA8, no mipmaps
+        let bytes = image.width as u64 * image.height as u64 * 4;
+        self.evict(bytes);
+        if let Some(old) = self.entries.insert(
+            key,
+            Entry {
+                image,
+                bytes,
+                last_used: self.frame,
+            },
+        ) {
+            self.bytes -= old.bytes;
+        }
+        self.bytes += bytes;
+        self.report();
+    }
+
+    /// Drops the least recently drawn images until `incoming` more bytes
+    /// fit the budget, or only images of this frame are left.
+    fn evict(&mut self, incoming: u64) {
+        while self.bytes + incoming > self.budget {
+            let oldest = self
+                .entries
+                .iter()
+                .filter(|(_, entry)| entry.last_used < self.frame)
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            let Some(key) = oldest else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&key) {
+                self.bytes -= entry.bytes;
+            }
+        }
+    }
+
+    fn report(&self) {
+        if self.reported {
+            STATS.with(|stats| {
+                stats.set(ImageCacheStats {
+                    count: self.entries.len(),
+                    bytes: self.bytes,
+                    budget: self.budget,
+                })
+            });
+        }
+    }
+}
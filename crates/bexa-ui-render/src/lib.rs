@@ -2,23 +2,41 @@
 // SPDX-License-Identifier: GPL-3.0-or-later OR Commercial
 // See LICENSE and LICENSE-COMMERCIAL for details.
 
+mod fonts;
+mod gpu_profiler;
+mod image_cache;
+#[cfg(feature = "theme-reload")]
+mod theme_file;
+
+pub use gpu_profiler::GpuTimings;
+pub use image_cache::{image_cache_stats, ImageCacheStats, DEFAULT_IMAGE_CACHE_BUDGET};
+
+// Re-exported so hosts embedding a `BexaSurface` build against the same versions
+pub use wgpu;
+pub use winit;
+
 use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
 use std::sync::Arc;
 
 use bexa_ui_core::{
-    build_taffy, clear_active_widgets, collect_focus_paths, dispatch_event, dispatch_scroll,
-    draw_widgets, handle_scrollbar_event, release_scrollbar_drag, sync_styles,
-    try_start_scrollbar_drag, update_widget_measures, widget_mut_at_path, ImageFit, Renderer, Theme,
-    WidgetNode, WindowRequest, WindowRequests,
+    build_taffy, clear_active_widgets, ClickTracker, collect_focus_paths, dismiss_popups, dispatch_event, focus_trap_path, mark_windows_for_redraw,
+    dispatch_scroll, draw_widgets, is_left_press, is_press, handle_scrollbar_event, hovered_paths, is_path_interactive,
+    notify_focus_transition, notify_pointer_transitions, release_scrollbar_drag, scroll_by_key, scroll_rect_into_view,
+    scroll_to, sync_styles, try_start_scrollbar_drag, update_widget_measures, widget_mut_at_path, GlyphExtent, ImageFit, KeyCombo, QuadCommand,
+    PresentMode, RedrawSchedule, Renderer, RoundedClip, TextSelection, Theme, UiSpawner, WidgetNode, WindowRequest, WindowRequests,
 };
 use bytemuck::{Pod, Zeroable};
+use glyphon::cosmic_text::CacheKeyFlags;
 use glyphon::{
     Attrs, Buffer, Cache, Color, Family, FontSystem, Metrics, Resolution, Shaping, SwashCache,
     TextArea, TextAtlas, TextBounds, TextRenderer, Viewport, Weight,
 };
 use image::GenericImageView;
 use taffy::prelude::*;
-use wgpu::util::DeviceExt;
+use gpu_profiler::{GpuProfiler, PassGroup};
+use image_cache::{GpuImage, ImageCache};
 use winit::event::{ElementState, Event, MouseButton, MouseScrollDelta, WindowEvent};
 use winit::event_loop::EventLoop;
 use winit::keyboard::{Key, ModifiersState, NamedKey};
@@ -35,6 +53,10 @@ struct Vertex {
     border_radius: f32,
     border_width: f32,
     border_color: [f32; 4],
+    // Rounded clip mask in physical pixels; radius 0 disables it
+    clip_center: [f32; 2],
+    clip_half: [f32; 2],
+    clip_radius: f32,
 }
 
 impl Vertex {
@@ -84,11 +106,34 @@ impl Vertex {
                     shader_location: 7,
                     format: wgpu::VertexFormat::Float32x4,
                 },
+                wgpu::VertexAttribute {
+                    offset: size_of::<[f32; 18]>() as u64,
+                    shader_location: 8,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: size_of::<[f32; 20]>() as u64,
+                    shader_location: 9,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: size_of::<[f32; 22]>() as u64,
+                    shader_location: 10,
+                    format: wgpu::VertexFormat::Float32,
+                },
             ],
         }
     }
 }
 
+/// Event loop wake-up interval while frame tasks (debounce, live data) are
+/// registered and no window needs a redraw, so they keep advancing without
+/// a busy loop.
+const IDLE_TICK: std::time::Duration = std::time::Duration::from_millis(100);
+/// Shortest time between relayouts for a new window size. A drag-resize
+/// sends many sizes per frame; in between, frames reuse the last layout.
+const RESIZE_LAYOUT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(16);
+
 const SHADER_SRC: &str = r#"
 struct VertexOut {
     @builtin(position) position: vec4<f32>,
@@ -99,6 +144,9 @@ struct VertexOut {
     @location(4) border_radius: f32,
     @location(5) border_width: f32,
     @location(6) border_color: vec4<f32>,
+    @location(7) clip_center: vec2<f32>,
+    @location(8) clip_half: vec2<f32>,
+    @location(9) clip_radius: f32,
 };
 
 @vertex
@@ -111,6 +159,9 @@ fn vs_main(
     @location(5) border_radius: f32,
     @location(6) border_width: f32,
     @location(7) border_color: vec4<f32>,
+    @location(8) clip_center: vec2<f32>,
+    @location(9) clip_half: vec2<f32>,
+    @location(10) clip_radius: f32,
 ) -> VertexOut {
     var out: VertexOut;
     out.position = vec4<f32>(position, 0.0, 1.0);
@@ -121,6 +172,9 @@ fn vs_main(
     out.border_radius = border_radius;
     out.border_width = border_width;
     out.border_color = border_color;
+    out.clip_center = clip_center;
+    out.clip_half = clip_half;
+    out.clip_radius = clip_radius;
     return out;
 }
 
@@ -130,12 +184,20 @@ fn sdf_rounded_rect(p: vec2<f32>, half_size: vec2<f32>, radius: f32) -> f32 {
     return length(max(q, vec2<f32>(0.0, 0.0))) + min(max(q.x, q.y), 0.0) - r;
 }
 
+fn clip_alpha(frag: vec2<f32>, center: vec2<f32>, half_size: vec2<f32>, radius: f32) -> f32 {
+    if radius <= 0.0 {
+        return 1.0;
+    }
+    return 1.0 - smoothstep(-1.0, 0.0, sdf_rounded_rect(frag - center, half_size, radius));
+}
+
 @fragment
 fn fs_main(in: VertexOut) -> @location(0) vec4<f32> {
     let p = in.uv * in.rect_half;
     let dist = sdf_rounded_rect(p, in.rect_half, in.border_radius);
     let aa = 1.0;
-    let fill_alpha = 1.0 - smoothstep(-aa, 0.0, dist);
+    let fill_alpha = (1.0 - smoothstep(-aa, 0.0, dist))
+        * clip_alpha(in.position.xy, in.clip_center, in.clip_half, in.clip_radius);
 
     if fill_alpha < 0.001 {
         discard;
@@ -159,6 +221,9 @@ struct VertexOut {
     @builtin(position) position: vec4<f32>,
     @location(0) uv: vec2<f32>,
     @location(1) color: vec4<f32>,
+    @location(2) clip_center: vec2<f32>,
+    @location(3) clip_half: vec2<f32>,
+    @location(4) clip_radius: f32,
 };
 
 @group(0) @binding(0) var image_tex: texture_2d<f32>;
@@ -169,21 +234,217 @@ fn vs_main(
     @location(0) position: vec2<f32>,
     @location(1) uv: vec2<f32>,
     @location(2) color: vec4<f32>,
+    @location(8) clip_center: vec2<f32>,
+    @location(9) clip_half: vec2<f32>,
+    @location(10) clip_radius: f32,
 ) -> VertexOut {
     var out: VertexOut;
     out.position = vec4<f32>(position, 0.0, 1.0);
     out.uv = uv;
     out.color = color;
+    out.clip_center = clip_center;
+    out.clip_half = clip_half;
+    out.clip_radius = clip_radius;
     return out;
 }
 
+fn sdf_rounded_rect(p: vec2<f32>, half_size: vec2<f32>, radius: f32) -> f32 {
+    let r = min(radius, min(half_size.x, half_size.y));
+    let q = abs(p) - half_size + vec2<f32>(r, r);
+    return length(max(q, vec2<f32>(0.0, 0.0))) + min(max(q.x, q.y), 0.0) - r;
+}
+
+fn clip_alpha(frag: vec2<f32>, center: vec2<f32>, half_size: vec2<f32>, radius: f32) -> f32 {
+    if radius <= 0.0 {
+        return 1.0;
+    }
+    return 1.0 - smoothstep(-1.0, 0.0, sdf_rounded_rect(frag - center, half_size, radius));
+}
+
 @fragment
 fn fs_main(in: VertexOut) -> @location(0) vec4<f32> {
-    let tex = textureSample(image_tex, image_sampler, in.uv);
-    return tex * in.color;
+    let tex = textureSample(image_tex, image_sampler, in.uv) * in.color;
+    let alpha = clip_alpha(in.position.xy, in.clip_center, in.clip_half, in.clip_radius);
+    return vec4<f32>(tex.rgb, tex.a * alpha);
 }
 "#;
 
+/// Maps quad and image commands from logical to physical pixels in place.
+/// Text is scaled later, in `build_text_areas`.
+fn scale_commands(renderer: &mut Renderer, scale: f32) {
+    let rect = |(x, y, w, h): (f32, f32, f32, f32)| (x * scale, y * scale, w * scale, h * scale);
+    for cmd in renderer
+        .quad_commands
+        .iter_mut()
+        .chain(renderer.overlay_quad_commands.iter_mut())
+    {
+        cmd.rect = rect(cmd.rect);
+        cmd.border_radius *= scale;
+        cmd.border_width *= scale;
+        cmd.clip = cmd.clip.map(rect);
+        cmd.rounded_clip = cmd.rounded_clip.map(|(r, radius)| (rect(r), radius * scale));
+    }
+    for cmd in &mut renderer.image_commands {
+        cmd.rect = rect(cmd.rect);
+        cmd.clip = cmd.clip.map(rect);
+        cmd.rounded_clip = cmd.rounded_clip.map(|(r, radius)| (rect(r), radius * scale));
+    }
+}
+
+/// Center, half extents and radius of a rounded clip, as vertex fields.
+fn rounded_clip_fields(clip: Option<RoundedClip>) -> ([f32; 2], [f32; 2], f32) {
+    match clip {
+        Some(((x, y, w, h), radius)) => ([x + w * 0.5, y + h * 0.5], [w * 0.5, h * 0.5], radius),
+        None => ([0.0; 2], [0.0; 2], 0.0),
+    }
+}
+
+/// Appends the four corners of a quad, rotated about its center, in the
+/// order `QUAD_INDICES` expects.
+/// UVs stay in the quad's local frame, so the SDF shader needs no rotation.
+fn push_quad_vertices(vertices: &mut Vec<Vertex>, cmd: &QuadCommand, viewport: (f32, f32)) {
+    let (vw, vh) = viewport;
+    let (x, y, w, h) = cmd.rect;
+    let cx = x + w * 0.5;
+    let cy = y + h * 0.5;
+    let hx = w * 0.5;
+    let hy = h * 0.5;
+    let (sin, cos) = cmd.rotation.sin_cos();
+    let (clip_center, clip_half, clip_radius) = rounded_clip_fields(cmd.rounded_clip);
+
+    let make_vertex = |u: f32, v: f32| {
+        let (lx, ly) = (u * hx, v * hy);
+        let px = cx + lx * cos - ly * sin;
+        let py = cy + lx * sin + ly * cos;
+        // v = -1 is the top edge
+        let color = match cmd.corner_colors {
+            Some([top_left, top_right, bottom_right, bottom_left]) => match (u < 0.0, v < 0.0) {
+                (true, true) => top_left,
+                (false, true) => top_right,
+                (false, false) => bottom_right,
+                (true, false) => bottom_left,
+            },
+            None => cmd.color,
+        };
+        Vertex {
+            position: [(px / vw) * 2.0 - 1.0, 1.0 - (py / vh) * 2.0],
+            uv: [u, v],
+            color,
+            rect_center: [cx, cy],
+            rect_half: [hx, hy],
+            border_radius: cmd.border_radius,
+            border_width: cmd.border_width,
+            border_color: cmd.border_color,
+            clip_center,
+            clip_half,
+            clip_radius,
+        }
+    };
+
+    vertices.push(make_vertex(-1.0, 1.0));
+    vertices.push(make_vertex(1.0, 1.0));
+    vertices.push(make_vertex(1.0, -1.0));
+    vertices.push(make_vertex(-1.0, -1.0));
+}
+
+/// The two triangles of a quad, over its corners bottom-left, bottom-right,
+/// top-right, top-left.
+const QUAD_INDICES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
+/// Indices drawn for `vertices`, six per four-vertex quad.
+fn index_count(vertices: &[Vertex]) -> u32 {
+    (vertices.len() / 4 * QUAD_INDICES.len()) as u32
+}
+
+/// Initial size of each per-frame vertex buffer; room for about a hundred quads.
+const INITIAL_VERTEX_BUFFER_SIZE: u64 = 64 * 1024;
+
+/// A vertex or index buffer kept across frames. Each frame's data is
+/// written into it; it is only reallocated, 1.5x larger or to fit, when the
+/// data outgrows it.
+struct StreamBuffer {
+    buffer: wgpu::Buffer,
+    label: &'static str,
+    usage: wgpu::BufferUsages,
+}
+
+impl StreamBuffer {
+    fn new(device: &wgpu::Device, label: &'static str, usage: wgpu::BufferUsages) -> Self {
+        let usage = usage | wgpu::BufferUsages::COPY_DST;
+        Self {
+            buffer: Self::allocate(device, label, usage, INITIAL_VERTEX_BUFFER_SIZE),
+            label,
+            usage,
+        }
+    }
+
+    fn allocate(
+        device: &wgpu::Device,
+        label: &'static str,
+        usage: wgpu::BufferUsages,
+        size: u64,
+    ) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size,
+            usage,
+            mapped_at_creation: false,
+        })
+    }
+
+    fn upload(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        let needed = data.len() as u64;
+        if needed > self.buffer.size() {
+            let size = (self.buffer.size() * 3 / 2)
+                .max(needed)
+                .next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
+            self.buffer = Self::allocate(device, self.label, self.usage, size);
+        }
+        queue.write_buffer(&self.buffer, 0, data);
+    }
+
+    fn slice(&self) -> wgpu::BufferSlice<'_> {
+        self.buffer.slice(..)
+    }
+}
+
+/// `QUAD_INDICES` repeated for every quad of the largest vertex buffer so
+/// far. Quads sit at the same offsets in every buffer, so the main, overlay
+/// and image passes all draw with it.
+struct QuadIndexBuffer {
+    buffer: StreamBuffer,
+    quads: usize,
+}
+
+impl QuadIndexBuffer {
+    fn new(device: &wgpu::Device) -> Self {
+        Self {
+            buffer: StreamBuffer::new(device, "Quad Index Buffer", wgpu::BufferUsages::INDEX),
+            quads: 0,
+        }
+    }
+
+    /// Makes room for the quads of `vertices`; written only when it grows.
+    fn reserve(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, vertices: &[Vertex]) {
+        let quads = vertices.len() / 4;
+        if quads <= self.quads {
+            return;
+        }
+        let indices: Vec<u32> = (0..quads as u32)
+            .flat_map(|quad| QUAD_INDICES.map(|index| quad * 4 + index))
+            .collect();
+        self.buffer.upload(device, queue, bytemuck::cast_slice(&indices));
+        self.quads = quads;
+    }
+
+    fn slice(&self) -> wgpu::BufferSlice<'_> {
+        self.buffer.slice()
+    }
+}
+
 struct DrawBatch {
     start: u32,
     count: u32,
@@ -197,64 +458,131 @@ struct ImageBatch {
     key: String,
 }
 
-struct GpuImage {
-    _texture: wgpu::Texture,
-    _view: wgpu::TextureView,
-    bind_group: wgpu::BindGroup,
-    width: u32,
-    height: u32,
-}
-
 // ── Shared GPU resources (one per application) ──────────────────────────
 
 struct SharedGpu {
-    instance: wgpu::Instance,
     device: wgpu::Device,
     queue: wgpu::Queue,
     render_pipeline: wgpu::RenderPipeline,
     image_pipeline: wgpu::RenderPipeline,
     image_bind_group_layout: wgpu::BindGroupLayout,
     image_sampler: wgpu::Sampler,
-    images: HashMap<String, GpuImage>,
+    images: ImageCache,
     font_system: FontSystem,
     swash_cache: SwashCache,
     text_atlas: TextAtlas,
     surface_format: wgpu::TextureFormat,
+    text_hinting: TextHinting,
+    /// Default for new windows' surfaces
+    present_mode: PresentMode,
+    /// New frame renderers time their passes; only set on devices created
+    /// with `Features::TIMESTAMP_QUERY`
+    gpu_profiling: bool,
+    /// Families of the fonts added with `with_font_data`, in fallback order
+    font_families: Vec<&'static str>,
+}
+
+impl SharedGpu {
+    fn add_fonts(&mut self, fonts: Vec<Vec<u8>>) {
+        if fonts.is_empty() {
+            return;
+        }
+        let families = std::mem::take(&mut self.font_families);
+        self.font_families = fonts::add_fonts(&mut self.font_system, fonts, families);
+    }
+}
+
+/// Creates window surfaces. Only `App` has one; a host embedding BexaUI
+/// through `BexaSurface` owns its surfaces itself.
+struct Display {
+    instance: wgpu::Instance,
+    adapter: wgpu::Adapter,
+}
+
+/// Swapchain of a window that BexaUI presents to itself.
+struct SurfaceTarget {
+    surface: wgpu::Surface<'static>,
+    config: wgpu::SurfaceConfiguration,
+}
+
+impl SurfaceTarget {
+    fn new(
+        display: &Display,
+        window: &Arc<Window>,
+        gpu: &SharedGpu,
+        clear_color: Option<[f32; 4]>,
+        present_mode: Option<PresentMode>,
+    ) -> Self {
+        let size = window.inner_size();
+        let surface = display.instance
+            .create_surface(window.clone())
+            .expect("create surface");
+
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: gpu.surface_format,
+            width: size.width.max(1),
+            height: size.height.max(1),
+            present_mode: pick_present_mode(&surface, display, present_mode.unwrap_or(gpu.present_mode)),
+            alpha_mode: pick_alpha_mode(&surface, display, clear_color),
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        surface.configure(&gpu.device, &config);
+        Self { surface, config }
+    }
 }
 
 // ── Per-window state ────────────────────────────────────────────────────
 
 struct WindowState {
     window: Arc<Window>,
-    surface: wgpu::Surface<'static>,
-    config: wgpu::SurfaceConfiguration,
+    /// `None` when embedded: frames go to views the host passes in
+    target: Option<SurfaceTarget>,
     size: winit::dpi::PhysicalSize<u32>,
     // Rendering
-    vertex_buffer: wgpu::Buffer,
-    vertex_count: u32,
-    overlay_vertex_buffer: wgpu::Buffer,
-    overlay_vertex_count: u32,
-    draw_batches: Vec<DrawBatch>,
-    overlay_draw_batches: Vec<DrawBatch>,
-    image_vertex_buffer: wgpu::Buffer,
-    image_vertex_count: u32,
-    image_batches: Vec<ImageBatch>,
-    text_renderer: TextRenderer,
-    overlay_text_renderer: TextRenderer,
-    text_viewport: Viewport,
-    text_buffers: Vec<Buffer>,
-    overlay_text_buffers: Vec<Buffer>,
+    frame: FrameRenderer,
     // Widget tree
     root: WidgetNode,
     taffy: TaffyTree,
     root_node: NodeId,
     renderer: Renderer,
     focus_paths: Vec<Vec<usize>>,
+    /// The focus trap (`Widget::traps_focus`) seen last frame, and the focus
+    /// to give back when it is released
+    focus_trap: Option<Vec<usize>>,
+    focus_before_trap: Option<Vec<usize>>,
     focused_index: Option<usize>,
     modifiers: ModifiersState,
     cursor_pos: (f32, f32),
+    /// Nodes currently under the cursor
+    hovered_paths: Vec<Vec<usize>>,
+    /// Spots double clicks on the widgets of `root`
+    clicks: ClickTracker,
+    /// Pointer selection of read-only text (`Widget::selects_text`)
+    text_selection: TextSelection,
+    /// Physical pixels per logical pixel for the monitor this window is on.
+    /// Layout, drawing and events work in logical pixels.
+    scale_factor: f32,
     theme: Theme,
+    /// Overrides `theme.background` as the clear color when set.
+    clear_color: Option<[f32; 4]>,
     is_main: bool,
+    on_resize: Option<ResizeHandler>,
+    /// App shortcuts, checked before the focused widget sees a key
+    shortcuts: Vec<(KeyCombo, ShortcutHandler)>,
+    /// Receives pass timings while profiling; they're logged without one
+    on_gpu_timings: Option<GpuTimingsHandler>,
+    /// Logical size last passed to `on_resize`, which is also the size
+    /// last laid out
+    reported_size: Option<(f32, f32)>,
+    /// When the layout last switched to a new size
+    resized_layout_at: Option<std::time::Instant>,
+    // Hidden windows skip rendering until shown again
+    occluded: bool,
+    minimized: bool,
+    // Redraws happen on demand; an idle window isn't drawn at all
+    redraws: RedrawSchedule,
 }
 
 impl WindowState {
@@ -262,120 +590,121 @@ impl WindowState {
         window: Arc<Window>,
         mut root: WidgetNode,
         theme: Theme,
+        clear_color: Option<[f32; 4]>,
         gpu: &mut SharedGpu,
+        target: Option<SurfaceTarget>,
         is_main: bool,
     ) -> Self {
         let size = window.inner_size();
 
-        let surface = gpu.instance
-            .create_surface(window.clone())
-            .expect("create surface");
-
-        let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format: gpu.surface_format,
-            width: size.width.max(1),
-            height: size.height.max(1),
-            present_mode: wgpu::PresentMode::Fifo,
-            alpha_mode: wgpu::CompositeAlphaMode::Auto,
-            view_formats: vec![],
-            desired_maximum_frame_latency: 2,
-        };
-        surface.configure(&gpu.device, &config);
-
-        let text_cache = Cache::new(&gpu.device);
-        let text_viewport = Viewport::new(&gpu.device, &text_cache);
-        let text_renderer = TextRenderer::new(
-            &mut gpu.text_atlas,
-            &gpu.device,
-            wgpu::MultisampleState::default(),
-            None,
-        );
-        let overlay_text_renderer = TextRenderer::new(
-            &mut gpu.text_atlas,
-            &gpu.device,
-            wgpu::MultisampleState::default(),
-            None,
-        );
-
         let mut taffy = TaffyTree::new();
         let root_node = build_taffy(&mut root, &mut taffy);
         let mut focus_paths = Vec::new();
         collect_focus_paths(&root, &mut Vec::new(), &mut focus_paths);
 
-        let vertex_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Quad Vertex Buffer"),
-            size: 4,
-            usage: wgpu::BufferUsages::VERTEX,
-            mapped_at_creation: false,
-        });
-        let overlay_vertex_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Overlay Vertex Buffer"),
-            size: 4,
-            usage: wgpu::BufferUsages::VERTEX,
-            mapped_at_creation: false,
-        });
-        let image_vertex_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Image Vertex Buffer"),
-            size: 4,
-            usage: wgpu::BufferUsages::VERTEX,
-            mapped_at_creation: false,
-        });
+        let frame = FrameRenderer::new(gpu);
+        let scale_factor = window.scale_factor() as f32;
 
         let mut ws = Self {
             window,
-            surface,
-            config,
+            target,
             size,
-            vertex_buffer,
-            vertex_count: 0,
-            overlay_vertex_buffer,
-            overlay_vertex_count: 0,
-            draw_batches: Vec::new(),
-            overlay_draw_batches: Vec::new(),
-            image_vertex_buffer,
-            image_vertex_count: 0,
-            image_batches: Vec::new(),
-            text_renderer,
-            overlay_text_renderer,
-            text_viewport,
-            text_buffers: Vec::new(),
-            overlay_text_buffers: Vec::new(),
+            frame,
             root,
             taffy,
             root_node,
             renderer: Renderer::new(),
             focus_paths,
+            focus_trap: None,
+            focus_before_trap: None,
             focused_index: None,
             modifiers: ModifiersState::default(),
             cursor_pos: (0.0, 0.0),
+            hovered_paths: Vec::new(),
+            clicks: ClickTracker::new(),
+            text_selection: TextSelection::new(),
+            scale_factor,
             theme,
+            clear_color,
             is_main,
+            on_resize: None,
+            shortcuts: Vec::new(),
+            on_gpu_timings: None,
+            reported_size: None,
+            resized_layout_at: None,
+            occluded: false,
+            minimized: false,
+            redraws: RedrawSchedule::new(),
         };
 
-        if !ws.focus_paths.is_empty() {
-            ws.set_focus(Some(0));
+        let first = ws.focus_paths.iter().position(|path| is_path_interactive(&ws.root, path));
+        if first.is_some() {
+            ws.set_focus(first);
         }
 
         ws
     }
 
+    /// False while minimized or fully covered; such windows aren't redrawn.
+    fn is_visible(&self) -> bool {
+        !self.occluded && !self.minimized
+    }
+
+    /// Updates visibility; redraws right away when the window reappears.
+    fn set_hidden(&mut self, occluded: bool, minimized: bool) {
+        let was_visible = self.is_visible();
+        self.occluded = occluded;
+        self.minimized = minimized;
+        if self.is_visible() && !was_visible {
+            self.window.request_redraw();
+        }
+    }
+
     fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>, device: &wgpu::Device) {
-        if new_size.width == 0 || new_size.height == 0 {
+        // Some platforms report minimizing as a resize to 0×0
+        let zero = new_size.width == 0 || new_size.height == 0;
+        self.set_hidden(self.occluded, zero || self.window.is_minimized() == Some(true));
+        if zero {
             return;
         }
         self.size = new_size;
-        self.config.width = new_size.width;
-        self.config.height = new_size.height;
-        self.surface.configure(device, &self.config);
+        if let Some(target) = self.target.as_mut() {
+            target.config.width = new_size.width;
+            target.config.height = new_size.height;
+            target.surface.configure(device, &target.config);
+        }
+    }
+
+    /// Window size in logical pixels, the space widgets lay out in.
+    fn logical_size(&self) -> (f32, f32) {
+        (
+            self.size.width as f32 / self.scale_factor,
+            self.size.height as f32 / self.scale_factor,
+        )
     }
 
     fn update_layout(&mut self) {
-        let width = self.size.width as f32;
-        let height = self.size.height as f32;
+        let (width, height) = self.logical_size();
         if width == 0.0 || height == 0.0 {
             return;
         }
+        if self.reported_size != Some((width, height)) {
+            // Throttle relayouts while the size keeps changing; the surface
+            // already has the new size, and the last size always gets laid
+            // out once the interval has passed
+            let now = std::time::Instant::now();
+            if self
+                .resized_layout_at
+                .is_some_and(|at| now.duration_since(at) < RESIZE_LAYOUT_INTERVAL)
+            {
+                return;
+            }
+            self.resized_layout_at = Some(now);
+            self.reported_size = Some((width, height));
+            if let Some(handler) = self.on_resize.as_mut() {
+                handler(width, height);
+            }
+        }
         sync_styles(&mut self.root, &mut self.taffy, width, height, true);
         let available_space = Size {
             width: AvailableSpace::Definite(width),
@@ -386,220 +715,152 @@ impl WindowState {
             .expect("compute layout");
     }
 
+    /// Draws a frame into the window's own surface and presents it.
     fn render(&mut self, gpu: &mut SharedGpu) -> Result<(), wgpu::SurfaceError> {
-        self.update_layout();
-
-        let viewport = (self.size.width as f32, self.size.height as f32);
-        self.renderer.clear();
-        self.renderer.set_viewport_size(viewport);
-        draw_widgets(&self.root, &self.taffy, &mut self.renderer);
-
-        self.build_quad_vertices(viewport, &gpu.device);
-        self.build_overlay_vertices(viewport, &gpu.device);
-        self.build_image_vertices(viewport, gpu);
-
-        self.text_viewport.update(
-            &gpu.queue,
-            Resolution {
-                width: self.config.width,
-                height: self.config.height,
-            },
-        );
-
-        let text_areas = build_text_areas(
-            &self.renderer.text_commands,
-            &mut self.text_buffers,
-            &mut gpu.font_system,
-            &mut self.renderer.text_measures,
-        );
-
-        update_widget_measures(&mut self.root, &self.renderer.text_measures);
-
-        self.text_renderer
-            .prepare(
-                &gpu.device,
-                &gpu.queue,
-                &mut gpu.font_system,
-                &mut gpu.text_atlas,
-                &self.text_viewport,
-                text_areas,
-                &mut gpu.swash_cache,
-            )
-            .expect("prepare text");
+        let Some(target) = self.target.as_ref() else {
+            return Ok(());
+        };
+        // A redraw can arrive before the zero-size resize does; a 0×0 surface
+        // can't be acquired, so skip the frame instead of erroring
+        let inner = self.window.inner_size();
+        if inner.width == 0 || inner.height == 0 || target.config.width == 0 || target.config.height == 0 {
+            return Ok(());
+        }
+        let size = (target.config.width, target.config.height);
+        let premultiplied = target.config.alpha_mode == wgpu::CompositeAlphaMode::PreMultiplied;
 
-        let output = self.surface.get_current_texture()?;
+        let output = target.surface.get_current_texture()?;
         let view = output
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
+        self.render_to_view(gpu, &view, size, premultiplied);
+        output.present();
 
-        let mut encoder =
-            gpu.device
-                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: Some("Render Encoder"),
-                });
+        Ok(())
+    }
 
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    depth_slice: None,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: self.theme.background[0] as f64,
-                            g: self.theme.background[1] as f64,
-                            b: self.theme.background[2] as f64,
-                            a: 1.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                occlusion_query_set: None,
-                timestamp_writes: None,
-                multiview_mask: None,
-            });
+    /// Lays out, draws and submits a frame into `view` of physical `size`.
+    fn render_to_view(
+        &mut self,
+        gpu: &mut SharedGpu,
+        view: &wgpu::TextureView,
+        size: (u32, u32),
+        premultiplied: bool,
+    ) {
+        self.update_layout();
+        self.update_focus_trap();
 
-            let sw = self.size.width;
-            let sh = self.size.height;
+        // Switching pages or locking a busy subtree can take the focused widget
+        // out of reach; it must stop taking keys
+        let focused = self.focused_index.and_then(|index| self.focus_paths.get(index));
+        if focused.is_some_and(|path| !is_path_interactive(&self.root, path)) {
+            self.set_focus(None);
+        }
 
-            // Pass 1: Main quads
-            render_pass.set_pipeline(&gpu.render_pipeline);
-            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            for batch in &self.draw_batches {
-                if let Some((cx, cy, cw, ch)) = batch.clip {
-                    let sx = (cx.max(0.0) as u32).min(sw);
-                    let sy = (cy.max(0.0) as u32).min(sh);
-                    let right = ((cx + cw).max(0.0) as u32).min(sw);
-                    let bottom = ((cy + ch).max(0.0) as u32).min(sh);
-                    let swidth = right.saturating_sub(sx);
-                    let sheight = bottom.saturating_sub(sy);
-                    if swidth == 0 || sheight == 0 {
-                        continue;
-                    }
-                    render_pass.set_scissor_rect(sx, sy, swidth, sheight);
-                } else {
-                    render_pass.set_scissor_rect(0, 0, sw, sh);
+        let viewport = self.logical_size();
+        self.renderer.clear();
+        self.renderer.set_viewport_size(viewport);
+        self.renderer.set_theme(self.theme);
+        draw_widgets(&self.root, &self.taffy, &mut self.renderer);
+        self.text_selection.draw_highlight(&mut self.renderer);
+
+        let [r, g, b, a] = self.clear_color.unwrap_or([
+            self.theme.background[0],
+            self.theme.background[1],
+            self.theme.background[2],
+            1.0,
+        ]);
+        // Non-opaque surfaces composite premultiplied color
+        let clear = if premultiplied {
+            wgpu::Color { r: (r * a) as f64, g: (g * a) as f64, b: (b * a) as f64, a: a as f64 }
+        } else {
+            wgpu::Color { r: r as f64, g: g as f64, b: b as f64, a: a as f64 }
+        };
+        self.frame.render(gpu, &mut self.renderer, view, size, self.scale_factor, clear);
+        self.redraws.frame_drawn(self.renderer.redraw_at());
+        // A relayout put off by `update_layout` still gets its frame, once
+        // the interval has passed
+        if let Some(at) = self.resized_layout_at
+            && self.reported_size != Some(self.logical_size())
+        {
+            self.redraws.redraw_by(at + RESIZE_LAYOUT_INTERVAL);
+        }
+        update_widget_measures(&mut self.root, &self.renderer.text_measures);
+        self.text_selection.update(&self.renderer);
+        gpu.text_atlas.trim();
+
+        if let Some(timings) = self.frame.poll_gpu_timings(&gpu.device) {
+            match self.on_gpu_timings.as_mut() {
+                Some(handler) => handler(timings),
+                None => {
+                    let ms = |duration: std::time::Duration| duration.as_secs_f64() * 1000.0;
+                    eprintln!(
+                        "bexa-ui: gpu \"{}\": quads {:.3} ms, text {:.3} ms, overlay {:.3} ms ({} frames)",
+                        self.window.title(),
+                        ms(timings.quads),
+                        ms(timings.text),
+                        ms(timings.overlay),
+                        timings.frames,
+                    );
                 }
-                render_pass.draw(batch.start..batch.start + batch.count, 0..1);
             }
+        }
+    }
 
-            // Pass 2: Images
-            if self.image_vertex_count > 0 {
-                render_pass.set_pipeline(&gpu.image_pipeline);
-                render_pass.set_vertex_buffer(0, self.image_vertex_buffer.slice(..));
-                for batch in &self.image_batches {
-                    let Some(image) = gpu.images.get(&batch.key) else {
-                        continue;
-                    };
-                    if let Some((cx, cy, cw, ch)) = batch.clip {
-                        let sx = (cx.max(0.0) as u32).min(sw);
-                        let sy = (cy.max(0.0) as u32).min(sh);
-                        let right = ((cx + cw).max(0.0) as u32).min(sw);
-                        let bottom = ((cy + ch).max(0.0) as u32).min(sh);
-                        let swidth = right.saturating_sub(sx);
-                        let sheight = bottom.saturating_sub(sy);
-                        if swidth == 0 || sheight == 0 {
-                            continue;
-                        }
-                        render_pass.set_scissor_rect(sx, sy, swidth, sheight);
-                    } else {
-                        render_pass.set_scissor_rect(0, 0, sw, sh);
-                    }
-                    render_pass.set_bind_group(0, &image.bind_group, &[]);
-                    render_pass.draw(batch.start..batch.start + batch.count, 0..1);
-                }
+    /// Routes pointer, keyboard and scale events to the widget tree.
+    /// Returns false for events this doesn't handle.
+    fn handle_input(&mut self, event: &WindowEvent) -> bool {
+        match event {
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                // Only this window moved monitors; the new physical
+                // size arrives as a Resized right after
+                self.scale_factor = *scale_factor as f32;
+                self.window.request_redraw();
             }
-
-            // Pass 3: Main text
-            render_pass.set_scissor_rect(0, 0, sw, sh);
-            self.text_renderer
-                .render(&gpu.text_atlas, &self.text_viewport, &mut render_pass)
-                .expect("render text");
-
-            // Pass 4: Overlay quads
-            if self.overlay_vertex_count > 0 {
-                render_pass.set_pipeline(&gpu.render_pipeline);
-                render_pass.set_vertex_buffer(0, self.overlay_vertex_buffer.slice(..));
-                for batch in &self.overlay_draw_batches {
-                    if let Some((cx, cy, cw, ch)) = batch.clip {
-                        let sx = (cx.max(0.0) as u32).min(sw);
-                        let sy = (cy.max(0.0) as u32).min(sh);
-                        let right = ((cx + cw).max(0.0) as u32).min(sw);
-                        let bottom = ((cy + ch).max(0.0) as u32).min(sh);
-                        let swidth = right.saturating_sub(sx);
-                        let sheight = bottom.saturating_sub(sy);
-                        if swidth == 0 || sheight == 0 {
-                            continue;
-                        }
-                        render_pass.set_scissor_rect(sx, sy, swidth, sheight);
-                    } else {
-                        render_pass.set_scissor_rect(0, 0, sw, sh);
-                    }
-                    render_pass.draw(batch.start..batch.start + batch.count, 0..1);
+            WindowEvent::CursorMoved { .. }
+            | WindowEvent::CursorLeft { .. }
+            | WindowEvent::MouseInput { .. } => {
+                self.handle_window_event(event);
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                self.handle_mouse_wheel(event, *delta);
+            }
+            WindowEvent::KeyboardInput { event, .. } => {
+                if event.state == ElementState::Pressed {
+                    self.handle_keyboard_input(event);
                 }
             }
-        }
-
-        // Pass 4: Overlay text
-        if !self.renderer.overlay_text_commands.is_empty() {
-            let overlay_text_areas = build_text_areas(
-                &self.renderer.overlay_text_commands,
-                &mut self.overlay_text_buffers,
-                &mut gpu.font_system,
-                &mut vec![],
-            );
-
-            self.overlay_text_renderer
-                .prepare(
-                    &gpu.device,
-                    &gpu.queue,
-                    &mut gpu.font_system,
-                    &mut gpu.text_atlas,
-                    &self.text_viewport,
-                    overlay_text_areas,
-                    &mut gpu.swash_cache,
-                )
-                .expect("prepare overlay text");
-
-            {
-                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Overlay Text Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &view,
-                        depth_slice: None,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Load,
-                            store: wgpu::StoreOp::Store,
-                        },
-                    })],
-                    depth_stencil_attachment: None,
-                    occlusion_query_set: None,
-                    timestamp_writes: None,
-                    multiview_mask: None,
-                });
-
-                let sw = self.size.width;
-                let sh = self.size.height;
-                render_pass.set_scissor_rect(0, 0, sw, sh);
-                self.overlay_text_renderer
-                    .render(&gpu.text_atlas, &self.text_viewport, &mut render_pass)
-                    .expect("render overlay text");
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers.state();
+                // Lets widgets track modifiers for mouse gestures
+                self.handle_window_event(event);
             }
+            _ => return false,
         }
-
-        gpu.queue.submit(Some(encoder.finish()));
-        output.present();
-        gpu.text_atlas.trim();
-
-        Ok(())
+        true
     }
 
     fn handle_window_event(&mut self, event: &WindowEvent) {
-        if let WindowEvent::CursorMoved { position, .. } = event {
-            self.cursor_pos = (position.x as f32, position.y as f32);
+        // Widgets see logical positions, matching their layout
+        let logical_event;
+        let event = if let WindowEvent::CursorMoved { device_id, position } = event {
+            let scale = self.scale_factor as f64;
+            logical_event = WindowEvent::CursorMoved {
+                device_id: *device_id,
+                position: winit::dpi::PhysicalPosition::new(position.x / scale, position.y / scale),
+            };
+            &logical_event
+        } else {
+            event
+        };
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor_pos = (position.x as f32, position.y as f32);
+                self.update_hover(true);
+                self.text_selection.drag_to(self.cursor_pos);
+            }
+            WindowEvent::CursorLeft { .. } => self.update_hover(false),
+            _ => {}
         }
 
         if handle_scrollbar_event(&mut self.root, &self.taffy, event) {
@@ -614,6 +875,7 @@ impl WindowState {
         {
             let (cx, cy) = self.cursor_pos;
             if try_start_scrollbar_drag(&mut self.root, &self.taffy, cx, cy) {
+                dismiss_popups(&mut self.root, None, self.cursor_pos);
                 return;
             }
         }
@@ -625,42 +887,89 @@ impl WindowState {
         } = event
         {
             release_scrollbar_drag(&mut self.root);
+            self.text_selection.end_drag();
         }
 
         let mut path = Vec::new();
-        if let Some(consumed_path) =
-            dispatch_event(&mut self.root, &self.taffy, event, &mut path)
-        {
-            if matches!(
-                event,
-                WindowEvent::MouseInput {
-                    state: ElementState::Pressed,
-                    button: MouseButton::Left,
-                    ..
-                }
-            ) {
+        let consumed = dispatch_event(&mut self.root, &self.taffy, event, &mut path, self.cursor_pos, &mut self.clicks);
+        if is_left_press(event) {
+            // Open popups elsewhere close even when another widget took the press
+            dismiss_popups(&mut self.root, consumed.as_deref(), self.cursor_pos);
+            if let Some(consumed_path) = consumed {
+                self.text_selection.clear();
                 self.set_focus_by_path(&consumed_path);
+            } else if self.text_selection.begin(self.cursor_pos) {
+                // Ctrl+C must reach the selection, not a focused input
+                self.set_focus(None);
             }
+        } else if is_press(event, MouseButton::Right) || is_press(event, MouseButton::Middle) {
+            // Opening a context menu closes the other popups
+            dismiss_popups(&mut self.root, consumed.as_deref(), self.cursor_pos);
         }
     }
 
-    fn handle_mouse_wheel(&mut self, delta: MouseScrollDelta) {
+    fn handle_mouse_wheel(&mut self, event: &WindowEvent, delta: MouseScrollDelta) {
+        // Widgets that use the wheel themselves (e.g. zoom) get it first
+        let mut path = Vec::new();
+        if dispatch_event(&mut self.root, &self.taffy, event, &mut path, self.cursor_pos, &mut self.clicks).is_some() {
+            return;
+        }
+
         let delta_y = match delta {
             MouseScrollDelta::LineDelta(_, y) => y * 40.0,
-            MouseScrollDelta::PixelDelta(d) => d.y as f32,
+            MouseScrollDelta::PixelDelta(d) => d.y as f32 / self.scale_factor,
         };
         let (cx, cy) = self.cursor_pos;
         dispatch_scroll(&mut self.root, delta_y, cx, cy, &self.taffy);
+        // Content moved under a still cursor
+        self.update_hover(true);
+    }
+
+    /// Fires pointer enter/leave callbacks for the nodes under the cursor,
+    /// or for none once it has left the window.
+    fn update_hover(&mut self, in_window: bool) {
+        let hovered = if in_window {
+            hovered_paths(&self.root, &self.taffy, self.cursor_pos)
+        } else {
+            Vec::new()
+        };
+        if hovered != self.hovered_paths {
+            notify_pointer_transitions(&mut self.root, &self.hovered_paths, &hovered);
+            self.hovered_paths = hovered;
+        }
     }
 
     fn handle_keyboard_input(&mut self, event: &winit::event::KeyEvent) {
-        if let Some(idx) = self.focused_index {
-            if let Some(path) = self.focus_paths.get(idx).cloned() {
-                if let Some(widget) = widget_mut_at_path(&mut self.root, &path) {
-                    if widget.handle_key_event(event, self.modifiers) {
-                        return;
-                    }
+        // App shortcuts win over every widget, so Ctrl+S saves from a text input
+        if let Some((_, handler)) = self
+            .shortcuts
+            .iter_mut()
+            .find(|(combo, _)| combo.matches(event, self.modifiers))
+        {
+            handler();
+            return;
+        }
+
+        // The focused widget gets the key next; the built-in keys (Tab focus,
+        // Enter/Space activation) only apply to keys it didn't consume.
+        if let Some(idx) = self.focused_index
+            && let Some(path) = self.focus_paths.get(idx).cloned()
+        {
+            // A focused scroll container pages through its content, ahead of
+            // the default Space activation
+            if scroll_by_key(&mut self.root, &self.taffy, &path, event, self.modifiers) {
+                return;
+            }
+            if let Some(widget) = widget_mut_at_path(&mut self.root, &path)
+                && widget.handle_key_event(event, self.modifiers)
+            {
+                // Keep what the key moved to (e.g. a selected row) in view
+                if let Some(rect) = widget.take_scroll_target()
+                    && scroll_rect_into_view(&mut self.root, &self.taffy, &path, rect)
+                {
+                    self.update_hover(true);
                 }
+                return;
             }
         }
 
@@ -669,229 +978,585 @@ impl WindowState {
                 let reverse = self.modifiers.shift_key();
                 self.focus_next(reverse);
             }
-            Key::Named(NamedKey::Enter) | Key::Named(NamedKey::Space) => {
+            Key::Named(NamedKey::Enter) | Key::Named(NamedKey::Space) if !event.repeat => {
                 self.activate_focused();
             }
             Key::Named(NamedKey::Escape) => {
                 self.clear_active();
+                self.text_selection.clear();
+            }
+            _ if KeyCombo::primary("c").matches(event, self.modifiers) => {
+                self.text_selection.copy_to_clipboard();
             }
             _ => {}
         }
     }
 
+    /// Moves focus into a focus trap that just appeared, and back to the
+    /// widget focused before it once it is gone.
+    fn update_focus_trap(&mut self) {
+        let trap = focus_trap_path(&self.root);
+        if trap == self.focus_trap {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.focus_trap, trap.clone());
+        match trap {
+            Some(trap) => {
+                if previous.is_none() {
+                    self.focus_before_trap = self.focused_index.and_then(|i| self.focus_paths.get(i)).cloned();
+                }
+                // The first widget inside, else the trap itself
+                let inside = |path: &Vec<usize>| path.len() > trap.len() && path.starts_with(&trap);
+                let first = self
+                    .focus_paths
+                    .iter()
+                    .position(|path| inside(path) && is_path_interactive(&self.root, path))
+                    .or_else(|| self.focus_paths.iter().position(|path| *path == trap));
+                self.set_focus(first);
+            }
+            None => {
+                let restore = self
+                    .focus_before_trap
+                    .take()
+                    .filter(|path| is_path_interactive(&self.root, path))
+                    .and_then(|path| self.focus_paths.iter().position(|p| *p == path));
+                self.set_focus(restore);
+            }
+        }
+    }
+
     fn focus_next(&mut self, reverse: bool) {
         if self.focus_paths.is_empty() {
             return;
         }
         let count = self.focus_paths.len();
         let current = self.focused_index.unwrap_or(0);
-        let next = if reverse {
-            (current + count - 1) % count
-        } else {
-            (current + 1) % count
+        // An open modal keeps focus among the widgets inside it
+        let trap = focus_trap_path(&self.root);
+        let in_trap = |path: &Vec<usize>| {
+            trap.as_ref().is_none_or(|trap| path.len() > trap.len() && path.starts_with(trap))
         };
-        self.set_focus(Some(next));
+        // Skip widgets on hidden branches (e.g. inactive router pages)
+        let next = (1..=count)
+            .map(|step| {
+                if reverse {
+                    (current + count - step) % count
+                } else {
+                    (current + step) % count
+                }
+            })
+            .find(|&index| {
+                let path = &self.focus_paths[index];
+                in_trap(path) && is_path_interactive(&self.root, path)
+            });
+        if let Some(index) = next {
+            self.set_focus(next);
+            let path = self.focus_paths[index].clone();
+            if scroll_to(&mut self.root, &self.taffy, &path) {
+                self.update_hover(true);
+            }
+        }
     }
 
     fn activate_focused(&mut self) {
         let Some(index) = self.focused_index else {
             return;
         };
-        if let Some(path) = self.focus_paths.get(index).cloned() {
-            if let Some(widget) = widget_mut_at_path(&mut self.root, &path) {
-                widget.activate();
+        if let Some(path) = self.focus_paths.get(index).cloned()
+            && let Some(widget) = widget_mut_at_path(&mut self.root, &path)
+        {
+            widget.activate();
+        }
+    }
+
+    fn clear_active(&mut self) {
+        clear_active_widgets(&mut self.root);
+    }
+
+    fn set_focus(&mut self, index: Option<usize>) {
+        let previous = self.focused_index.and_then(|i| self.focus_paths.get(i)).cloned();
+        self.focused_index = index;
+        for (i, path) in self.focus_paths.iter().enumerate() {
+            if let Some(widget) = widget_mut_at_path(&mut self.root, path) {
+                widget.set_focus(Some(i) == index);
             }
         }
+        let current = index.and_then(|i| self.focus_paths.get(i));
+        notify_focus_transition(&mut self.root, previous.as_deref(), current.map(Vec::as_slice));
+    }
+
+    fn set_focus_by_path(&mut self, path: &[usize]) {
+        if let Some(index) = self.focus_paths.iter().position(|p| p == path) {
+            self.set_focus(Some(index));
+        } else {
+            // Clicked on a non-focusable widget — clear focus so the
+            // previously focused widget (e.g. TextInput) stops capturing input.
+            self.set_focus(None);
+        }
+    }
+}
+
+/// Chooses a compositing mode: opaque unless the clear color asks for transparency.
+fn pick_alpha_mode(
+    surface: &wgpu::Surface<'static>,
+    display: &Display,
+    clear_color: Option<[f32; 4]>,
+) -> wgpu::CompositeAlphaMode {
+    let wants_alpha = clear_color.is_some_and(|c| c[3] < 1.0);
+    if !wants_alpha {
+        return wgpu::CompositeAlphaMode::Auto;
+    }
+    let modes = surface.get_capabilities(&display.adapter).alpha_modes;
+    [
+        wgpu::CompositeAlphaMode::PreMultiplied,
+        wgpu::CompositeAlphaMode::PostMultiplied,
+    ]
+    .into_iter()
+    .find(|m| modes.contains(m))
+    .unwrap_or(wgpu::CompositeAlphaMode::Auto)
+}
+
+/// The surface's mode for `mode`, or `Fifo` where it isn't supported.
+fn pick_present_mode(
+    surface: &wgpu::Surface<'static>,
+    display: &Display,
+    mode: PresentMode,
+) -> wgpu::PresentMode {
+    let wanted = match mode {
+        PresentMode::Fifo => return wgpu::PresentMode::Fifo,
+        PresentMode::Mailbox => wgpu::PresentMode::Mailbox,
+        PresentMode::Immediate => wgpu::PresentMode::Immediate,
+    };
+    let modes = surface.get_capabilities(&display.adapter).present_modes;
+    if modes.contains(&wanted) {
+        wanted
+    } else {
+        wgpu::PresentMode::Fifo
     }
+}
+
+// ── Frame renderer (GPU draw of one Renderer frame) ────────────────────
+
+/// Starts a render pass drawing into `view`.
+fn begin_pass<'e>(
+    encoder: &'e mut wgpu::CommandEncoder,
+    view: &wgpu::TextureView,
+    label: &str,
+    load: wgpu::LoadOp<wgpu::Color>,
+    timestamp_writes: Option<wgpu::RenderPassTimestampWrites<'_>>,
+) -> wgpu::RenderPass<'e> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            depth_slice: None,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load,
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        occlusion_query_set: None,
+        timestamp_writes,
+        multiview_mask: None,
+    })
+}
+
+/// GPU buffers and text renderers needed to draw a `Renderer`'s commands
+/// into a texture view. Owned per window; also used for offscreen export.
+struct FrameRenderer {
+    vertex_buffer: StreamBuffer,
+    vertex_count: u32,
+    overlay_vertex_buffer: StreamBuffer,
+    overlay_vertex_count: u32,
+    draw_batches: Vec<DrawBatch>,
+    overlay_draw_batches: Vec<DrawBatch>,
+    image_vertex_buffer: StreamBuffer,
+    image_vertex_count: u32,
+    image_batches: Vec<ImageBatch>,
+    quad_indices: QuadIndexBuffer,
+    text_renderer: TextRenderer,
+    overlay_text_renderer: TextRenderer,
+    text_viewport: Viewport,
+    text_buffers: Vec<Buffer>,
+    overlay_text_buffers: Vec<Buffer>,
+    profiler: Option<GpuProfiler>,
+}
+
+impl FrameRenderer {
+    fn new(gpu: &mut SharedGpu) -> Self {
+        let text_cache = Cache::new(&gpu.device);
+        let text_viewport = Viewport::new(&gpu.device, &text_cache);
+        let text_renderer = TextRenderer::new(
+            &mut gpu.text_atlas,
+            &gpu.device,
+            wgpu::MultisampleState::default(),
+            None,
+        );
+        let overlay_text_renderer = TextRenderer::new(
+            &mut gpu.text_atlas,
+            &gpu.device,
+            wgpu::MultisampleState::default(),
+            None,
+        );
+
+        let vertex = wgpu::BufferUsages::VERTEX;
+        let vertex_buffer = StreamBuffer::new(&gpu.device, "Quad Vertex Buffer", vertex);
+        let overlay_vertex_buffer = StreamBuffer::new(&gpu.device, "Overlay Vertex Buffer", vertex);
+        let image_vertex_buffer = StreamBuffer::new(&gpu.device, "Image Vertex Buffer", vertex);
+
+        Self {
+            vertex_buffer,
+            vertex_count: 0,
+            overlay_vertex_buffer,
+            overlay_vertex_count: 0,
+            draw_batches: Vec::new(),
+            overlay_draw_batches: Vec::new(),
+            image_vertex_buffer,
+            image_vertex_count: 0,
+            image_batches: Vec::new(),
+            quad_indices: QuadIndexBuffer::new(&gpu.device),
+            text_renderer,
+            overlay_text_renderer,
+            text_viewport,
+            text_buffers: Vec::new(),
+            overlay_text_buffers: Vec::new(),
+            profiler: gpu
+                .gpu_profiling
+                .then(|| GpuProfiler::new(&gpu.device, &gpu.queue))
+                .flatten(),
+        }
+    }
+
+    /// Averaged pass timings, about once a second while profiling.
+    fn poll_gpu_timings(&mut self, device: &wgpu::Device) -> Option<GpuTimings> {
+        self.profiler.as_mut()?.poll(device)
+    }
+
+    /// Draws all commands in `renderer` into `view` and submits the work.
+    /// Commands are in logical pixels; `scale` maps them onto the physical
+    /// `size`. Fills `renderer.text_measures` (logical) for the caller to
+    /// feed back to widgets.
+    fn render(
+        &mut self,
+        gpu: &mut SharedGpu,
+        renderer: &mut Renderer,
+        view: &wgpu::TextureView,
+        size: (u32, u32),
+        scale: f32,
+        clear: wgpu::Color,
+    ) {
+        let viewport = (size.0 as f32, size.1 as f32);
+        scale_commands(renderer, scale);
+
+        self.build_quad_vertices(renderer, viewport, &gpu.device, &gpu.queue);
+        self.build_overlay_vertices(renderer, viewport, &gpu.device, &gpu.queue);
+        self.build_image_vertices(renderer, viewport, gpu);
+
+        self.text_viewport.update(
+            &gpu.queue,
+            Resolution {
+                width: size.0,
+                height: size.1,
+            },
+        );
+
+        let text_areas = build_text_areas(
+            &renderer.text_commands,
+            &mut self.text_buffers,
+            &mut gpu.font_system,
+            &mut renderer.text_measures,
+            &mut renderer.glyph_extents,
+            gpu.text_hinting,
+            scale,
+        );
+
+        self.text_renderer
+            .prepare(
+                &gpu.device,
+                &gpu.queue,
+                &mut gpu.font_system,
+                &mut gpu.text_atlas,
+                &self.text_viewport,
+                text_areas,
+                &mut gpu.swash_cache,
+            )
+            .expect("prepare text");
+
+        let mut encoder =
+            gpu.device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Render Encoder"),
+                });
+
+        // While profiling, the draws are split into one pass per measured
+        // group, each timed by its own begin/end timestamps
+        let profiler = self.profiler.as_ref().filter(|profiler| profiler.is_sampling());
+        let writes = |begin: Option<u32>, end: Option<u32>| {
+            profiler.map(|profiler| profiler.timestamp_writes(begin, end))
+        };
+        let has_overlay_text = !renderer.overlay_text_commands.is_empty();
+
+        {
+            let mut render_pass = begin_pass(
+                &mut encoder,
+                view,
+                "Render Pass",
+                wgpu::LoadOp::Clear(clear),
+                writes(Some(PassGroup::Quads.begin()), Some(PassGroup::Quads.end())),
+            );
+
+            let (sw, sh) = size;
+
+            // Pass 1: Main quads
+            render_pass.set_pipeline(&gpu.render_pipeline);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice());
+            render_pass.set_index_buffer(self.quad_indices.slice(), wgpu::IndexFormat::Uint32);
+            for batch in &self.draw_batches {
+                if let Some((cx, cy, cw, ch)) = batch.clip {
+                    let sx = (cx.max(0.0) as u32).min(sw);
+                    let sy = (cy.max(0.0) as u32).min(sh);
+                    let right = ((cx + cw).max(0.0) as u32).min(sw);
+                    let bottom = ((cy + ch).max(0.0) as u32).min(sh);
+                    let swidth = right.saturating_sub(sx);
+                    let sheight = bottom.saturating_sub(sy);
+                    if swidth == 0 || sheight == 0 {
+                        continue;
+                    }
+                    render_pass.set_scissor_rect(sx, sy, swidth, sheight);
+                } else {
+                    render_pass.set_scissor_rect(0, 0, sw, sh);
+                }
+                render_pass.draw_indexed(batch.start..batch.start + batch.count, 0, 0..1);
+            }
+
+            // Pass 2: Images
+            if self.image_vertex_count > 0 {
+                render_pass.set_pipeline(&gpu.image_pipeline);
+                render_pass.set_vertex_buffer(0, self.image_vertex_buffer.slice());
+                render_pass.set_index_buffer(self.quad_indices.slice(), wgpu::IndexFormat::Uint32);
+                for batch in &self.image_batches {
+                    let Some(image) = gpu.images.get(&batch.key) else {
+                        continue;
+                    };
+                    if let Some((cx, cy, cw, ch)) = batch.clip {
+                        let sx = (cx.max(0.0) as u32).min(sw);
+                        let sy = (cy.max(0.0) as u32).min(sh);
+                        let right = ((cx + cw).max(0.0) as u32).min(sw);
+                        let bottom = ((cy + ch).max(0.0) as u32).min(sh);
+                        let swidth = right.saturating_sub(sx);
+                        let sheight = bottom.saturating_sub(sy);
+                        if swidth == 0 || sheight == 0 {
+                            continue;
+                        }
+                        render_pass.set_scissor_rect(sx, sy, swidth, sheight);
+                    } else {
+                        render_pass.set_scissor_rect(0, 0, sw, sh);
+                    }
+                    render_pass.set_bind_group(0, &image.bind_group, &[]);
+                    render_pass.draw_indexed(batch.start..batch.start + batch.count, 0, 0..1);
+                }
+            }
+
+            // Pass 3: Main text
+            if profiler.is_some() {
+                drop(render_pass);
+                render_pass = begin_pass(
+                    &mut encoder,
+                    view,
+                    "Text Pass",
+                    wgpu::LoadOp::Load,
+                    writes(Some(PassGroup::Text.begin()), Some(PassGroup::Text.end())),
+                );
+            }
+            render_pass.set_scissor_rect(0, 0, sw, sh);
+            self.text_renderer
+                .render(&gpu.text_atlas, &self.text_viewport, &mut render_pass)
+                .expect("render text");
+
+            // Pass 4: Overlay quads; the overlay group ends with its text
+            if profiler.is_some() {
+                drop(render_pass);
+                let end = (!has_overlay_text).then(|| PassGroup::Overlay.end());
+                render_pass = begin_pass(
+                    &mut encoder,
+                    view,
+                    "Overlay Pass",
+                    wgpu::LoadOp::Load,
+                    writes(Some(PassGroup::Overlay.begin()), end),
+                );
+            }
+            if self.overlay_vertex_count > 0 {
+                render_pass.set_pipeline(&gpu.render_pipeline);
+                render_pass.set_vertex_buffer(0, self.overlay_vertex_buffer.slice());
+                render_pass.set_index_buffer(self.quad_indices.slice(), wgpu::IndexFormat::Uint32);
+                for batch in &self.overlay_draw_batches {
+                    if let Some((cx, cy, cw, ch)) = batch.clip {
+                        let sx = (cx.max(0.0) as u32).min(sw);
+                        let sy = (cy.max(0.0) as u32).min(sh);
+                        let right = ((cx + cw).max(0.0) as u32).min(sw);
+                        let bottom = ((cy + ch).max(0.0) as u32).min(sh);
+                        let swidth = right.saturating_sub(sx);
+                        let sheight = bottom.saturating_sub(sy);
+                        if swidth == 0 || sheight == 0 {
+                            continue;
+                        }
+                        render_pass.set_scissor_rect(sx, sy, swidth, sheight);
+                    } else {
+                        render_pass.set_scissor_rect(0, 0, sw, sh);
+                    }
+                    render_pass.draw_indexed(batch.start..batch.start + batch.count, 0, 0..1);
+                }
+            }
+        }
+
+        // Pass 4: Overlay text
+        if has_overlay_text {
+            let overlay_text_areas = build_text_areas(
+                &renderer.overlay_text_commands,
+                &mut self.overlay_text_buffers,
+                &mut gpu.font_system,
+                &mut vec![],
+                &mut vec![],
+                gpu.text_hinting,
+                scale,
+            );
 
-    fn clear_active(&mut self) {
-        clear_active_widgets(&mut self.root);
-    }
+            self.overlay_text_renderer
+                .prepare(
+                    &gpu.device,
+                    &gpu.queue,
+                    &mut gpu.font_system,
+                    &mut gpu.text_atlas,
+                    &self.text_viewport,
+                    overlay_text_areas,
+                    &mut gpu.swash_cache,
+                )
+                .expect("prepare overlay text");
 
-    fn set_focus(&mut self, index: Option<usize>) {
-        self.focused_index = index;
-        for (i, path) in self.focus_paths.iter().enumerate() {
-            if let Some(widget) = widget_mut_at_path(&mut self.root, path) {
-                widget.set_focus(Some(i) == index);
+            {
+                let mut render_pass = begin_pass(
+                    &mut encoder,
+                    view,
+                    "Overlay Text Pass",
+                    wgpu::LoadOp::Load,
+                    writes(None, Some(PassGroup::Overlay.end())),
+                );
+
+                let (sw, sh) = size;
+                render_pass.set_scissor_rect(0, 0, sw, sh);
+                self.overlay_text_renderer
+                    .render(&gpu.text_atlas, &self.text_viewport, &mut render_pass)
+                    .expect("render overlay text");
             }
         }
-    }
 
-    fn set_focus_by_path(&mut self, path: &[usize]) {
-        if let Some(index) = self.focus_paths.iter().position(|p| p == path) {
-            self.set_focus(Some(index));
-        } else {
-            // Clicked on a non-focusable widget — clear focus so the
-            // previously focused widget (e.g. TextInput) stops capturing input.
-            self.set_focus(None);
+        if let Some(profiler) = profiler {
+            profiler.resolve(&mut encoder);
+        }
+        gpu.queue.submit(Some(encoder.finish()));
+        if let Some(profiler) = self.profiler.as_mut().filter(|profiler| profiler.is_sampling()) {
+            profiler.start_readback();
         }
     }
 
-    fn build_quad_vertices(&mut self, viewport: (f32, f32), device: &wgpu::Device) {
-        let mut vertices = Vec::with_capacity(self.renderer.quad_commands.len() * 6);
-        let (vw, vh) = viewport;
-
+    fn build_quad_vertices(
+        &mut self,
+        renderer: &Renderer,
+        viewport: (f32, f32),
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) {
+        let mut vertices = Vec::with_capacity(renderer.quad_commands.len() * 4);
         self.draw_batches.clear();
         let mut current_clip: Option<(f32, f32, f32, f32)> = None;
         let mut batch_start: u32 = 0;
 
-        for cmd in &self.renderer.quad_commands {
+        for cmd in &renderer.quad_commands {
             if cmd.clip != current_clip {
-                let vert_count = vertices.len() as u32;
-                if vert_count > batch_start {
+                let index_end = index_count(&vertices);
+                if index_end > batch_start {
                     self.draw_batches.push(DrawBatch {
                         start: batch_start,
-                        count: vert_count - batch_start,
+                        count: index_end - batch_start,
                         clip: current_clip,
                     });
                 }
                 current_clip = cmd.clip;
-                batch_start = vert_count;
+                batch_start = index_end;
             }
 
-            let (x, y, w, h) = cmd.rect;
-            let x0 = (x / vw) * 2.0 - 1.0;
-            let x1 = ((x + w) / vw) * 2.0 - 1.0;
-            let y0 = 1.0 - (y / vh) * 2.0;
-            let y1 = 1.0 - ((y + h) / vh) * 2.0;
-            let cx = x + w * 0.5;
-            let cy = y + h * 0.5;
-            let hx = w * 0.5;
-            let hy = h * 0.5;
-
-            let make_vertex = |px: f32, py: f32, u: f32, v: f32| Vertex {
-                position: [px, py],
-                uv: [u, v],
-                color: cmd.color,
-                rect_center: [cx, cy],
-                rect_half: [hx, hy],
-                border_radius: cmd.border_radius,
-                border_width: cmd.border_width,
-                border_color: cmd.border_color,
-            };
-
-            vertices.push(make_vertex(x0, y1, -1.0, 1.0));
-            vertices.push(make_vertex(x1, y1, 1.0, 1.0));
-            vertices.push(make_vertex(x1, y0, 1.0, -1.0));
-            vertices.push(make_vertex(x0, y1, -1.0, 1.0));
-            vertices.push(make_vertex(x1, y0, 1.0, -1.0));
-            vertices.push(make_vertex(x0, y0, -1.0, -1.0));
+            push_quad_vertices(&mut vertices, cmd, viewport);
         }
 
-        let vert_count = vertices.len() as u32;
-        if vert_count > batch_start {
+        let index_end = index_count(&vertices);
+        if index_end > batch_start {
             self.draw_batches.push(DrawBatch {
                 start: batch_start,
-                count: vert_count - batch_start,
+                count: index_end - batch_start,
                 clip: current_clip,
             });
         }
 
         self.vertex_count = vertices.len() as u32;
-        if vertices.is_empty() {
-            self.vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Quad Vertex Buffer"),
-                size: 4,
-                usage: wgpu::BufferUsages::VERTEX,
-                mapped_at_creation: false,
-            });
-        } else {
-            self.vertex_buffer =
-                device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                    label: Some("Quad Vertex Buffer"),
-                    contents: bytemuck::cast_slice(&vertices),
-                    usage: wgpu::BufferUsages::VERTEX,
-                });
-        }
+        self.vertex_buffer.upload(device, queue, bytemuck::cast_slice(&vertices));
+        self.quad_indices.reserve(device, queue, &vertices);
     }
 
-    fn build_overlay_vertices(&mut self, viewport: (f32, f32), device: &wgpu::Device) {
+    fn build_overlay_vertices(
+        &mut self,
+        renderer: &Renderer,
+        viewport: (f32, f32),
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) {
         let mut vertices =
-            Vec::with_capacity(self.renderer.overlay_quad_commands.len() * 6);
-        let (vw, vh) = viewport;
-
+            Vec::with_capacity(renderer.overlay_quad_commands.len() * 4);
         self.overlay_draw_batches.clear();
         let mut current_clip: Option<(f32, f32, f32, f32)> = None;
         let mut batch_start: u32 = 0;
 
-        for cmd in &self.renderer.overlay_quad_commands {
+        for cmd in &renderer.overlay_quad_commands {
             if cmd.clip != current_clip {
-                let vert_count = vertices.len() as u32;
-                if vert_count > batch_start {
+                let index_end = index_count(&vertices);
+                if index_end > batch_start {
                     self.overlay_draw_batches.push(DrawBatch {
                         start: batch_start,
-                        count: vert_count - batch_start,
+                        count: index_end - batch_start,
                         clip: current_clip,
                     });
                 }
                 current_clip = cmd.clip;
-                batch_start = vert_count;
+                batch_start = index_end;
             }
 
-            let (x, y, w, h) = cmd.rect;
-            let x0 = (x / vw) * 2.0 - 1.0;
-            let x1 = ((x + w) / vw) * 2.0 - 1.0;
-            let y0 = 1.0 - (y / vh) * 2.0;
-            let y1 = 1.0 - ((y + h) / vh) * 2.0;
-            let cx = x + w * 0.5;
-            let cy = y + h * 0.5;
-            let hx = w * 0.5;
-            let hy = h * 0.5;
-
-            let make_vertex = |px: f32, py: f32, u: f32, v: f32| Vertex {
-                position: [px, py],
-                uv: [u, v],
-                color: cmd.color,
-                rect_center: [cx, cy],
-                rect_half: [hx, hy],
-                border_radius: cmd.border_radius,
-                border_width: cmd.border_width,
-                border_color: cmd.border_color,
-            };
-
-            vertices.push(make_vertex(x0, y1, -1.0, 1.0));
-            vertices.push(make_vertex(x1, y1, 1.0, 1.0));
-            vertices.push(make_vertex(x1, y0, 1.0, -1.0));
-            vertices.push(make_vertex(x0, y1, -1.0, 1.0));
-            vertices.push(make_vertex(x1, y0, 1.0, -1.0));
-            vertices.push(make_vertex(x0, y0, -1.0, -1.0));
+            push_quad_vertices(&mut vertices, cmd, viewport);
         }
 
-        let vert_count = vertices.len() as u32;
-        if vert_count > batch_start {
+        let index_end = index_count(&vertices);
+        if index_end > batch_start {
             self.overlay_draw_batches.push(DrawBatch {
                 start: batch_start,
-                count: vert_count - batch_start,
+                count: index_end - batch_start,
                 clip: current_clip,
             });
         }
 
         self.overlay_vertex_count = vertices.len() as u32;
-        if vertices.is_empty() {
-            self.overlay_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Overlay Vertex Buffer"),
-                size: 4,
-                usage: wgpu::BufferUsages::VERTEX,
-                mapped_at_creation: false,
-            });
-        } else {
-            self.overlay_vertex_buffer =
-                device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                    label: Some("Overlay Vertex Buffer"),
-                    contents: bytemuck::cast_slice(&vertices),
-                    usage: wgpu::BufferUsages::VERTEX,
-                });
-        }
+        self.overlay_vertex_buffer.upload(device, queue, bytemuck::cast_slice(&vertices));
+        self.quad_indices.reserve(device, queue, &vertices);
     }
 
-    fn build_image_vertices(&mut self, viewport: (f32, f32), gpu: &mut SharedGpu) {
-        let mut vertices = Vec::with_capacity(self.renderer.image_commands.len() * 6);
+    fn build_image_vertices(&mut self, renderer: &Renderer, viewport: (f32, f32), gpu: &mut SharedGpu) {
+        let mut vertices = Vec::with_capacity(renderer.image_commands.len() * 4);
         let (vw, vh) = viewport;
 
         self.image_batches.clear();
+        gpu.images.begin_frame();
 
-        for cmd in &self.renderer.image_commands {
-            if ensure_image(gpu, &cmd.path).is_none() {
+        for cmd in &renderer.image_commands {
+            if ensure_image(gpu, &cmd.path, cmd.bytes.as_deref()).is_none() {
                 continue;
             }
 
@@ -899,7 +1564,7 @@ impl WindowState {
                 continue;
             };
 
-            let start = vertices.len() as u32;
+            let start = index_count(&vertices);
             let (x, y, w, h) = cmd.rect;
             if w <= 0.0 || h <= 0.0 {
                 continue;
@@ -954,6 +1619,7 @@ impl WindowState {
             let y0 = 1.0 - (draw_y / vh) * 2.0;
             let y1 = 1.0 - ((draw_y + draw_h) / vh) * 2.0;
 
+            let (clip_center, clip_half, clip_radius) = rounded_clip_fields(cmd.rounded_clip);
             let make_vertex = |px: f32, py: f32, u: f32, v: f32| Vertex {
                 position: [px, py],
                 uv: [u, v],
@@ -963,16 +1629,17 @@ impl WindowState {
                 border_radius: 0.0,
                 border_width: 0.0,
                 border_color: [0.0; 4],
+                clip_center,
+                clip_half,
+                clip_radius,
             };
 
             vertices.push(make_vertex(x0, y1, u0, v1));
             vertices.push(make_vertex(x1, y1, u1, v1));
             vertices.push(make_vertex(x1, y0, u1, v0));
-            vertices.push(make_vertex(x0, y1, u0, v1));
-            vertices.push(make_vertex(x1, y0, u1, v0));
             vertices.push(make_vertex(x0, y0, u0, v0));
 
-            let count = vertices.len() as u32 - start;
+            let count = index_count(&vertices) - start;
             if count > 0 {
                 self.image_batches.push(ImageBatch {
                     start,
@@ -984,30 +1651,49 @@ impl WindowState {
         }
 
         self.image_vertex_count = vertices.len() as u32;
-        if vertices.is_empty() {
-            self.image_vertex_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Image Vertex Buffer"),
-                size: 4,
-                usage: wgpu::BufferUsages::VERTEX,
-                mapped_at_creation: false,
-            });
-        } else {
-            self.image_vertex_buffer = gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("Image Vertex Buffer"),
-                contents: bytemuck::cast_slice(&vertices),
-                usage: wgpu::BufferUsages::VERTEX,
-            });
-        }
+        self.image_vertex_buffer
+            .upload(&gpu.device, &gpu.queue, bytemuck::cast_slice(&vertices));
+        self.quad_indices.reserve(&gpu.device, &gpu.queue, &vertices);
     }
 }
 
 // ── App (public API) ────────────────────────────────────────────────────
 
+/// How text glyphs are rasterized and placed, set with `App::with_text_hinting`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextHinting {
+    /// Hinted glyphs at fractional positions, as laid out.
+    #[default]
+    Subpixel,
+    /// Hinted glyphs with each text run snapped to whole pixels. Often
+    /// crisper for small text on low-DPI displays.
+    PixelSnapped,
+    /// Glyphs drawn from the raw outlines at fractional positions: closest
+    /// to the font's design, but softer at small sizes.
+    Unhinted,
+}
+
+type ResizeHandler = Box<dyn FnMut(f32, f32)>;
+type ShortcutHandler = Box<dyn FnMut()>;
+type GpuTimingsHandler = Box<dyn FnMut(GpuTimings)>;
+
 pub struct App {
     root: WidgetNode,
     theme: Theme,
+    clear_color: Option<[f32; 4]>,
+    text_hinting: TextHinting,
+    present_mode: PresentMode,
     title: String,
     window_requests: Option<WindowRequests>,
+    on_resize: Option<ResizeHandler>,
+    shortcuts: Vec<(KeyCombo, ShortcutHandler)>,
+    gpu_profiling: bool,
+    on_gpu_timings: Option<GpuTimingsHandler>,
+    fonts: Vec<Vec<u8>>,
+    image_cache_budget: u64,
+    spawner: UiSpawner,
+    #[cfg(feature = "theme-reload")]
+    theme_file: Option<std::path::PathBuf>,
 }
 
 impl App {
@@ -1015,8 +1701,20 @@ impl App {
         Self {
             root,
             theme: Theme::ocean(),
+            clear_color: None,
+            text_hinting: TextHinting::default(),
+            present_mode: PresentMode::default(),
             title: "BexaUI".to_string(),
             window_requests: None,
+            on_resize: None,
+            shortcuts: Vec::new(),
+            gpu_profiling: false,
+            on_gpu_timings: None,
+            fonts: Vec::new(),
+            image_cache_budget: DEFAULT_IMAGE_CACHE_BUDGET,
+            spawner: UiSpawner::new(),
+            #[cfg(feature = "theme-reload")]
+            theme_file: None,
         }
     }
 
@@ -1025,16 +1723,142 @@ impl App {
         self
     }
 
+    /// Clear color for the main window, independent of the theme.
+    /// An alpha below 1.0 makes the window transparent where supported.
+    pub fn with_clear_color(mut self, color: [f32; 4]) -> Self {
+        self.clear_color = Some(color);
+        self
+    }
+
+    /// Text rendering mode for every window of the app.
+    pub fn with_text_hinting(mut self, mode: TextHinting) -> Self {
+        self.text_hinting = mode;
+        self
+    }
+
+    /// How frames are presented, for every window of the app unless its
+    /// `WindowRequest` says otherwise. `Mailbox` or `Immediate` uncap the
+    /// frame rate for latency-sensitive views; unsupported modes fall back
+    /// to `Fifo` (vsync).
+    pub fn present_mode(mut self, mode: PresentMode) -> Self {
+        self.present_mode = mode;
+        self
+    }
+
     pub fn title(mut self, title: impl Into<String>) -> Self {
         self.title = title.into();
         self
     }
 
+    /// Handle that widgets push `WindowRequest`s into to open child windows.
+    /// Child windows may share signals with this one; a signal write
+    /// redraws every window, so shared state stays in sync across them.
     pub fn with_requests(mut self, requests: WindowRequests) -> Self {
         self.window_requests = Some(requests);
         self
     }
 
+    /// Called with the main window's size in logical pixels on its first
+    /// layout and whenever the size actually changes (resizes, moving to a
+    /// monitor with another scale factor), before that frame is laid out.
+    /// Set signals here to switch layouts at a breakpoint:
+    ///
+    /// ```ignore
+    /// let (narrow, set_narrow) = create_signal(false);
+    /// let root = router(narrow, vec![(false, desktop_view), (true, mobile_view)]);
+    /// App::new(root).on_resize(move |width, _| set_narrow.set(width < 720.0));
+    /// ```
+    pub fn on_resize(mut self, handler: impl FnMut(f32, f32) + 'static) -> Self {
+        self.on_resize = Some(Box::new(handler));
+        self
+    }
+
+    /// Calls `handler` when `combo` is pressed in the main window, whatever
+    /// has focus. Shortcuts are checked before any widget sees the key, and
+    /// a matched key goes no further. Key repeat calls it again.
+    ///
+    /// ```ignore
+    /// App::new(root)
+    ///     .on_shortcut(KeyCombo::new(Key::Named(NamedKey::F5), ModifiersState::empty()), refresh)
+    ///     .on_shortcut(KeyCombo::primary("q"), request_exit)
+    ///     .run();
+    /// ```
+    pub fn on_shortcut(mut self, combo: KeyCombo, handler: impl FnMut() + 'static) -> Self {
+        self.shortcuts.push((combo, Box::new(handler)));
+        self
+    }
+
+    /// Adds a font (TTF, OTF or TTC bytes) for every window. Its families
+    /// can be picked by name (`Label::with_font_family`), and fill in
+    /// glyphs a text's own font lacks before the platform's common
+    /// fallbacks do. Add a color emoji font here (e.g. Noto Color Emoji or
+    /// Twemoji) to draw emoji in color where the system has none; emoji
+    /// mixed into regular text use it automatically.
+    ///
+    /// ```ignore
+    /// App::new(root)
+    ///     .with_font_data(include_bytes!("../assets/NotoColorEmoji.ttf").to_vec())
+    ///     .run();
+    /// ```
+    pub fn with_font_data(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.fonts.push(data.into());
+        self
+    }
+
+    /// GPU memory kept for decoded images, shared by all windows. Past it,
+    /// the least recently drawn images are dropped and decoded again when
+    /// they next show up. Defaults to `DEFAULT_IMAGE_CACHE_BUDGET`; check
+    /// the usage with `image_cache_stats`.
+    pub fn with_image_cache_budget(mut self, bytes: u64) -> Self {
+        self.image_cache_budget = bytes;
+        self
+    }
+
+    /// Measures GPU time of each window's quad, text and overlay passes
+    /// with timestamp queries, about once a second. Splits each frame into
+    /// more render passes, so keep it to investigations. Does nothing, apart
+    /// from a notice, when the adapter lacks `Features::TIMESTAMP_QUERY`.
+    ///
+    /// The per-frame averages go to stderr, one line per window, as
+    /// `bexa-ui: gpu "<title>": quads .. ms, text .. ms, overlay .. ms`.
+    /// `on_gpu_timings` takes the main window's instead; other windows
+    /// keep logging.
+    pub fn with_gpu_profiling(mut self, enabled: bool) -> Self {
+        self.gpu_profiling = enabled;
+        self
+    }
+
+    /// Enables GPU profiling (see `with_gpu_profiling`) and hands the main
+    /// window's timings to `handler` instead of logging them.
+    pub fn on_gpu_timings(mut self, handler: impl FnMut(GpuTimings) + 'static) -> Self {
+        self.gpu_profiling = true;
+        self.on_gpu_timings = Some(Box::new(handler));
+        self
+    }
+
+    /// Load the main window theme from a TOML (or `.json`) file and reload it
+    /// whenever the file changes. Invalid edits are logged and ignored, keeping
+    /// the last good theme. Falls back to `theme()` if the file can't be read.
+    #[cfg(feature = "theme-reload")]
+    pub fn with_theme_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.theme_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Handle for background threads to run work on the UI thread, e.g. to
+    /// feed loaded data into signals. Create the app on the thread that
+    /// calls `run`.
+    ///
+    /// ```ignore
+    /// let app = App::new(root);
+    /// let rows = app.spawner().channel(move |rows: Vec<Row>| set_rows.set(rows));
+    /// std::thread::spawn(move || rows.send(fetch_rows()));
+    /// app.run();
+    /// ```
+    pub fn spawner(&self) -> UiSpawner {
+        self.spawner.clone()
+    }
+
     /// Create a shared `WindowRequests` handle for widgets to request new windows.
     pub fn window_requests() -> WindowRequests {
         bexa_ui_core::create_window_requests()
@@ -1042,20 +1866,66 @@ impl App {
 
     pub fn run(self) {
         let event_loop = EventLoop::new().expect("create event loop");
+        let proxy = event_loop.create_proxy();
+        let redraw_proxy = proxy.clone();
+        self.spawner.set_waker(move || {
+            let _ = proxy.send_event(());
+        });
+        bexa_ui_core::set_redraw_waker(move || {
+            let _ = redraw_proxy.send_event(());
+        });
+        let spawner = self.spawner;
 
         // Create initial window
         let window = Arc::new(
             WindowBuilder::new()
                 .with_title(&self.title)
+                .with_transparent(self.clear_color.is_some_and(|c| c[3] < 1.0))
                 .build(&event_loop)
                 .expect("create window"),
         );
 
         // Initialize shared GPU resources
-        let mut gpu = pollster::block_on(init_gpu(window.clone()));
+        let (display, mut gpu) = pollster::block_on(init_gpu(window.clone(), self.gpu_profiling));
+        gpu.text_hinting = self.text_hinting;
+        gpu.present_mode = self.present_mode;
+        gpu.add_fonts(self.fonts);
+        gpu.images.set_budget(self.image_cache_budget);
+        gpu.gpu_profiling = gpu.device.features().contains(wgpu::Features::TIMESTAMP_QUERY);
+        if self.gpu_profiling && !gpu.gpu_profiling {
+            eprintln!("bexa-ui: GPU profiling unavailable, the adapter has no timestamp queries");
+        }
+
+        #[cfg(feature = "theme-reload")]
+        let theme_watcher = self
+            .theme_file
+            .clone()
+            .and_then(theme_file::ThemeWatcher::new);
+        #[cfg(feature = "theme-reload")]
+        let theme = match &self.theme_file {
+            Some(path) => theme_file::load_theme(path).unwrap_or_else(|err| {
+                eprintln!("bexa-ui: using default theme, {}: {err}", path.display());
+                self.theme
+            }),
+            None => self.theme,
+        };
+        #[cfg(not(feature = "theme-reload"))]
+        let theme = self.theme;
 
         // Create main window state
-        let main_ws = WindowState::new(window.clone(), self.root, self.theme, &mut gpu, true);
+        let target = SurfaceTarget::new(&display, &window, &gpu, self.clear_color, None);
+        let mut main_ws = WindowState::new(
+            window.clone(),
+            self.root,
+            theme,
+            self.clear_color,
+            &mut gpu,
+            Some(target),
+            true,
+        );
+        main_ws.on_resize = self.on_resize;
+        main_ws.shortcuts = self.shortcuts;
+        main_ws.on_gpu_timings = self.on_gpu_timings;
         let main_id = main_ws.window.id();
 
         let mut windows: HashMap<WindowId, WindowState> = HashMap::new();
@@ -1065,16 +1935,22 @@ impl App {
 
         event_loop
             .run(move |event, elwt| {
-                elwt.set_control_flow(winit::event_loop::ControlFlow::Poll);
                 match event {
                 Event::WindowEvent {
                     event: ref win_event,
                     window_id,
                 } => {
                     if let Some(ws) = windows.get_mut(&window_id) {
+                        if !matches!(win_event, WindowEvent::RedrawRequested) {
+                            ws.redraws.mark_dirty();
+                        }
                         match win_event {
                             WindowEvent::CloseRequested => {
+                                // Dropping a window's state drops its widgets, which
+                                // release what they own (e.g. `Terminal` shells)
                                 if ws.is_main {
+                                    // Not left to the loop's return, which some platforms skip
+                                    windows.clear();
                                     elwt.exit();
                                 } else {
                                     windows.remove(&window_id);
@@ -1083,21 +1959,15 @@ impl App {
                             WindowEvent::Resized(size) => {
                                 ws.resize(*size, &gpu.device);
                             }
-                            WindowEvent::CursorMoved { .. }
-                            | WindowEvent::MouseInput { .. } => {
-                                ws.handle_window_event(win_event);
+                            WindowEvent::Occluded(occluded) => {
+                                ws.set_hidden(*occluded, ws.minimized);
                             }
-                            WindowEvent::MouseWheel { delta, .. } => {
-                                ws.handle_mouse_wheel(*delta);
-                            }
-                            WindowEvent::KeyboardInput { event, .. } => {
-                                if event.state == ElementState::Pressed {
-                                    ws.handle_keyboard_input(event);
-                                }
-                            }
-                            WindowEvent::ModifiersChanged(modifiers) => {
-                                ws.modifiers = modifiers.state();
+                            WindowEvent::Focused(true) => {
+                                // Not every platform reports un-occlusion; focus implies visible
+                                let minimized = ws.window.is_minimized() == Some(true);
+                                ws.set_hidden(false, minimized);
                             }
+                            WindowEvent::RedrawRequested if !ws.is_visible() => {}
                             WindowEvent::RedrawRequested => {
                                 match ws.render(&mut gpu) {
                                     Ok(()) => {}
@@ -1109,7 +1979,9 @@ impl App {
                                     Err(_) => {}
                                 }
                             }
-                            _ => {}
+                            _ => {
+                                ws.handle_input(win_event);
+                            }
                         }
                     }
                 }
@@ -1124,6 +1996,7 @@ impl App {
                             let new_window = Arc::new(
                                 WindowBuilder::new()
                                     .with_title(&req.title)
+                                    .with_transparent(req.clear_color.is_some_and(|c| c[3] < 1.0))
                                     .with_inner_size(winit::dpi::LogicalSize::new(
                                         req.width, req.height,
                                     ))
@@ -1131,21 +2004,67 @@ impl App {
                                     .expect("create child window"),
                             );
                             let new_id = new_window.id();
+                            let target = SurfaceTarget::new(
+                                &display,
+                                &new_window,
+                                &gpu,
+                                req.clear_color,
+                                req.present_mode,
+                            );
                             let ws = WindowState::new(
                                 new_window,
                                 req.root,
                                 req.theme,
+                                req.clear_color,
                                 &mut gpu,
+                                Some(target),
                                 false,
                             );
                             windows.insert(new_id, ws);
                         }
                     }
 
-                    // Request redraw for all windows
-                    for ws in windows.values() {
-                        ws.window.request_redraw();
+                    #[cfg(feature = "theme-reload")]
+                    if let Some(theme) = theme_watcher.as_ref().and_then(|w| w.poll())
+                        && let Some(ws) = windows.get_mut(&main_id)
+                    {
+                        ws.theme = theme;
+                        ws.redraws.mark_dirty();
+                    }
+
+                    // Work queued by other threads, then frame-driven
+                    // signals (debounce, throttle, ...)
+                    spawner.run_pending();
+                    bexa_ui_core::run_frame_tasks();
+
+                    if bexa_ui_core::take_exit_request() {
+                        windows.clear();
+                        elwt.exit();
+                        return;
+                    }
+
+                    // Signals are shared between windows, so a write redraws
+                    // them all. Redraw visible windows with something to show;
+                    // otherwise sleep until the next widget redraw time or
+                    // frame-task tick, or until the next event when neither
+                    // is pending
+                    mark_windows_for_redraw(windows.values_mut().map(|ws| &mut ws.redraws));
+                    let now = std::time::Instant::now();
+                    let mut wake = (bexa_ui_core::frame_task_count() > 0).then(|| now + IDLE_TICK);
+                    for ws in windows.values_mut() {
+                        if !ws.is_visible() {
+                            continue;
+                        }
+                        if ws.redraws.wants_redraw(now) {
+                            ws.window.request_redraw();
+                        } else if let Some(at) = ws.redraws.redraw_at() {
+                            wake = Some(wake.map_or(at, |wake| wake.min(at)));
+                        }
                     }
+                    elwt.set_control_flow(match wake {
+                        Some(wake) => winit::event_loop::ControlFlow::WaitUntil(wake),
+                        None => winit::event_loop::ControlFlow::Wait,
+                    });
                 }
                 _ => {}
             }})
@@ -1153,9 +2072,269 @@ impl App {
     }
 }
 
+// ── Embedding ───────────────────────────────────────────────────────────
+
+/// A widget tree drawn into a window that a host application manages.
+///
+/// `App::run` owns the event loop, the windows and their swapchains. To put
+/// BexaUI inside an app that already drives winit and wgpu (e.g. next to a
+/// 3D viewport), build a `BexaSurface` from the host's window, device and
+/// queue instead. The host forwards window events to `handle_event`, calls
+/// `bexa_ui_core::run_frame_tasks` once per frame, and renders into the
+/// texture view of its choice, usually the current surface texture:
+///
+/// ```ignore
+/// let mut ui = BexaSurface::new(window.clone(), device.clone(), queue.clone(), format, root);
+/// // In the event loop:
+/// Event::WindowEvent { event: WindowEvent::RedrawRequested, .. } => {
+///     run_frame_tasks();
+///     let frame = surface.get_current_texture()?;
+///     let view = frame.texture.create_view(&Default::default());
+///     ui.render(&view);
+///     frame.present();
+/// }
+/// Event::WindowEvent { event, .. } => {
+///     ui.handle_event(&event);
+/// }
+/// ```
+///
+/// The view must use `format` and match the window's inner size. Each frame
+/// clears it first, to the theme background or `with_clear_color`. Child
+/// windows from `WindowRequests` need `App`; they aren't opened here. For
+/// work from other threads, the host keeps a `UiSpawner` and calls its
+/// `run_pending` each frame, next to `run_frame_tasks`.
+pub struct BexaSurface {
+    gpu: SharedGpu,
+    ws: WindowState,
+}
+
+impl BexaSurface {
+    pub fn new(
+        window: Arc<Window>,
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        format: wgpu::TextureFormat,
+        root: WidgetNode,
+    ) -> Self {
+        let mut gpu = create_shared_gpu(device, queue, format);
+        let ws = WindowState::new(window, root, Theme::ocean(), None, &mut gpu, None, true);
+        Self { gpu, ws }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.ws.theme = theme;
+        self
+    }
+
+    /// Clear color for each frame, independent of the theme.
+    pub fn with_clear_color(mut self, color: [f32; 4]) -> Self {
+        self.ws.clear_color = Some(color);
+        self
+    }
+
+    pub fn with_text_hinting(mut self, mode: TextHinting) -> Self {
+        self.gpu.text_hinting = mode;
+        self
+    }
+
+    /// Same as `App::with_font_data`.
+    pub fn with_font_data(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.gpu.add_fonts(vec![data.into()]);
+        self
+    }
+
+    /// Same as `App::on_shortcut`. For `request_exit`, the host checks
+    /// `bexa_ui_core::take_exit_request` each turn of its loop.
+    pub fn on_shortcut(mut self, combo: KeyCombo, handler: impl FnMut() + 'static) -> Self {
+        self.ws.shortcuts.push((combo, Box::new(handler)));
+        self
+    }
+
+    /// Same as `App::with_image_cache_budget`.
+    pub fn with_image_cache_budget(mut self, bytes: u64) -> Self {
+        self.gpu.images.set_budget(bytes);
+        self
+    }
+
+    /// Same as `App::on_resize`, for the host window's logical size.
+    pub fn on_resize(mut self, handler: impl FnMut(f32, f32) + 'static) -> Self {
+        self.ws.on_resize = Some(Box::new(handler));
+        self
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.ws.theme = theme;
+    }
+
+    /// Feeds a window event to the widget tree. Returns true if it was
+    /// pointer, keyboard, resize or scale input that BexaUI acts on; the
+    /// host can skip its own handling for those while the UI covers the
+    /// window.
+    pub fn handle_event(&mut self, event: &WindowEvent) -> bool {
+        self.ws.redraws.mark_dirty();
+        if let WindowEvent::Resized(size) = event {
+            self.ws.resize(*size, &self.gpu.device);
+            return true;
+        }
+        self.ws.handle_input(event)
+    }
+
+    /// Lays out, draws and submits one frame into `view`.
+    pub fn render(&mut self, view: &wgpu::TextureView) {
+        let size = self.ws.window.inner_size();
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+        self.ws.size = size;
+        self.ws.render_to_view(&mut self.gpu, view, (size.width, size.height), false);
+    }
+
+    /// Whether the UI has something new to show: after events, signal
+    /// writes, or when a widget asked for a frame (spinners, caret blink).
+    /// Hosts that redraw on demand check this each turn of their loop, and
+    /// can wake an idle loop with `bexa_ui_core::set_redraw_waker`.
+    pub fn needs_redraw(&mut self) -> bool {
+        mark_windows_for_redraw([&mut self.ws.redraws]);
+        self.ws.redraws.wants_redraw(std::time::Instant::now())
+    }
+
+    /// When a widget next wants a frame, for hosts that sleep between
+    /// redraws. `None` when only events and signal writes will change the UI.
+    pub fn next_redraw_at(&self) -> Option<std::time::Instant> {
+        self.ws.redraws.redraw_at()
+    }
+}
+
+// ── Offscreen export ────────────────────────────────────────────────────
+
+/// Renders a widget subtree standalone to a PNG file of `width` × `height` pixels.
+///
+/// The subtree is laid out as if it were a window root of that size and drawn
+/// on a transparent background, so widgets that rely on a parent's backdrop
+/// (e.g. a chart inside a panel) export without it. Useful for saving charts.
+///
+/// Creates its own headless GPU device; call it outside the event loop or
+/// from a callback, not per frame.
+pub fn export_widget_png(
+    mut node: WidgetNode,
+    width: u32,
+    height: u32,
+    path: impl AsRef<Path>,
+) -> Result<(), Box<dyn Error>> {
+    let width = width.max(1);
+    let height = height.max(1);
+    let mut gpu = pollster::block_on(init_headless_gpu())?;
+
+    let mut taffy = TaffyTree::new();
+    let root_node = build_taffy(&mut node, &mut taffy);
+    sync_styles(&mut node, &mut taffy, width as f32, height as f32, true);
+    taffy.compute_layout(
+        root_node,
+        Size {
+            width: AvailableSpace::Definite(width as f32),
+            height: AvailableSpace::Definite(height as f32),
+        },
+    )?;
+
+    let texture = gpu.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Export Texture"),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: gpu.surface_format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let mut frame = FrameRenderer::new(&mut gpu);
+    let mut renderer = Renderer::new();
+    // Two passes so measured text (inputs, cursors) settles before capture
+    for _ in 0..2 {
+        renderer.clear();
+        renderer.set_viewport_size((width as f32, height as f32));
+        draw_widgets(&node, &taffy, &mut renderer);
+        frame.render(&mut gpu, &mut renderer, &view, (width, height), 1.0, wgpu::Color::TRANSPARENT);
+        update_widget_measures(&mut node, &renderer.text_measures);
+    }
+
+    // Copy texture → buffer (rows padded to the required alignment)
+    let unpadded_row = width * 4;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let padded_row = unpadded_row.div_ceil(align) * align;
+    let readback = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Export Readback Buffer"),
+        size: (padded_row * height) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = gpu
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Export Encoder"),
+        });
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &readback,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row),
+                rows_per_image: Some(height),
+            },
+        },
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+    gpu.queue.submit(Some(encoder.finish()));
+
+    let slice = readback.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| {});
+    gpu.device.poll(wgpu::PollType::wait_indefinitely())?;
+
+    let mut pixels = Vec::with_capacity((unpadded_row * height) as usize);
+    {
+        let data = slice.get_mapped_range();
+        for row in data.chunks(padded_row as usize).take(height as usize) {
+            pixels.extend_from_slice(&row[..unpadded_row as usize]);
+        }
+    }
+    readback.unmap();
+
+    // Alpha blending onto a transparent target leaves premultiplied color;
+    // PNG expects straight alpha.
+    for px in pixels.chunks_exact_mut(4) {
+        let a = px[3] as u32;
+        if a > 0 && a < 255 {
+            for c in &mut px[..3] {
+                *c = ((*c as u32 * 255 + a / 2) / a).min(255) as u8;
+            }
+        }
+    }
+
+    image::save_buffer(path, &pixels, width, height, image::ColorType::Rgba8)?;
+    Ok(())
+}
+
 // ── GPU Initialization ──────────────────────────────────────────────────
 
-async fn init_gpu(window: Arc<Window>) -> SharedGpu {
+/// `timestamps` requests `Features::TIMESTAMP_QUERY` for profiling, when
+/// the adapter has it.
+async fn init_gpu(window: Arc<Window>, timestamps: bool) -> (Display, SharedGpu) {
     let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
         backends: wgpu::Backends::PRIMARY,
         ..Default::default()
@@ -1173,10 +2352,15 @@ async fn init_gpu(window: Arc<Window>) -> SharedGpu {
         .await
         .expect("find GPU adapter");
 
+    let required_features = if timestamps {
+        adapter.features() & wgpu::Features::TIMESTAMP_QUERY
+    } else {
+        wgpu::Features::empty()
+    };
     let (device, queue) = adapter
         .request_device(&wgpu::DeviceDescriptor {
             label: None,
-            required_features: wgpu::Features::empty(),
+            required_features,
             required_limits: wgpu::Limits::default(),
             ..Default::default()
         })
@@ -1191,6 +2375,44 @@ async fn init_gpu(window: Arc<Window>) -> SharedGpu {
         .find(|format| format.is_srgb())
         .unwrap_or(surface_caps.formats[0]);
 
+    let gpu = create_shared_gpu(device, queue, surface_format);
+    (Display { instance, adapter }, gpu)
+}
+
+async fn init_headless_gpu() -> Result<SharedGpu, Box<dyn Error>> {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::PRIMARY,
+        ..Default::default()
+    });
+
+    let adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        })
+        .await?;
+
+    let (device, queue) = adapter
+        .request_device(&wgpu::DeviceDescriptor {
+            label: None,
+            required_features: wgpu::Features::empty(),
+            required_limits: wgpu::Limits::default(),
+            ..Default::default()
+        })
+        .await?;
+
+    let mut gpu = create_shared_gpu(device, queue, wgpu::TextureFormat::Rgba8UnormSrgb);
+    gpu.images = gpu.images.unreported();
+    Ok(gpu)
+}
+
+/// Builds pipelines, samplers and text resources for a device rendering to `surface_format`.
+fn create_shared_gpu(
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    surface_format: wgpu::TextureFormat,
+) -> SharedGpu {
     let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: Some("Quad SDF Shader"),
         source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
@@ -1322,27 +2544,37 @@ async fn init_gpu(window: Arc<Window>) -> SharedGpu {
     let text_atlas = TextAtlas::new(&device, &queue, &text_cache, surface_format);
 
     SharedGpu {
-        instance,
         device,
         queue,
         render_pipeline,
         image_pipeline,
         image_bind_group_layout,
         image_sampler,
-        images: HashMap::new(),
+        images: ImageCache::new(),
         font_system,
         swash_cache,
         text_atlas,
         surface_format,
+        text_hinting: TextHinting::default(),
+        present_mode: PresentMode::default(),
+        gpu_profiling: false,
+        font_families: Vec::new(),
     }
 }
 
-fn ensure_image(gpu: &mut SharedGpu, path: &str) -> Option<()> {
-    if gpu.images.contains_key(path) {
+/// Uploads the image cached under `path` on first use: decoded from `bytes`
+/// when given, else read from the file. Images that fail to load are
+/// skipped.
+fn ensure_image(gpu: &mut SharedGpu, path: &str, bytes: Option<&[u8]>) -> Option<()> {
+    if gpu.images.touch(path) {
         return Some(());
     }
 
-    let image = image::open(path).ok()?;
+    let image = match bytes {
+        Some(bytes) => image::load_from_memory(bytes),
+        None => image::open(path),
+    }
+    .ok()?;
     let rgba = image.to_rgba8();
     let (width, height) = image.dimensions();
 
@@ -1413,15 +2645,44 @@ fn ensure_image(gpu: &mut SharedGpu, path: &str) -> Option<()> {
 
 // ── Text area builder (shared) ──────────────────────────────────────────
 
+/// Extent of every glyph cluster in a shaped buffer, with byte ranges into
+/// the whole text rather than its line.
+fn glyph_extents(buffer: &Buffer) -> Vec<GlyphExtent> {
+    let mut line_starts = Vec::with_capacity(buffer.lines.len());
+    let mut start = 0;
+    for line in &buffer.lines {
+        line_starts.push(start);
+        start += line.text().len() + line.ending().as_str().len();
+    }
+    let mut extents = Vec::new();
+    for run in buffer.layout_runs() {
+        let offset = line_starts.get(run.line_i).copied().unwrap_or(0);
+        extents.extend(run.glyphs.iter().map(|glyph| GlyphExtent {
+            start: offset + glyph.start,
+            end: offset + glyph.end,
+            x: glyph.x,
+            width: glyph.w,
+            line_top: run.line_top,
+            line_height: run.line_height,
+        }));
+    }
+    extents
+}
+
 fn build_text_areas<'a>(
     commands: &'a [bexa_ui_core::TextCommand],
     text_buffers: &'a mut Vec<Buffer>,
     font_system: &mut FontSystem,
     measures_out: &mut Vec<Vec<f32>>,
+    glyphs_out: &mut Vec<Vec<GlyphExtent>>,
+    hinting: TextHinting,
+    scale: f32,
 ) -> Vec<TextArea<'a>> {
     let mut areas = Vec::with_capacity(commands.len());
     measures_out.clear();
     measures_out.resize(commands.len(), vec![]);
+    glyphs_out.clear();
+    glyphs_out.resize(commands.len(), vec![]);
 
     if text_buffers.len() < commands.len() {
         for _ in text_buffers.len()..commands.len() {
@@ -1437,7 +2698,10 @@ fn build_text_areas<'a>(
             Some(name) => Family::Name(name),
             None => Family::SansSerif,
         };
-        let attrs = Attrs::new().family(family).weight(Weight::MEDIUM);
+        let mut attrs = Attrs::new().family(family).weight(Weight::MEDIUM);
+        if hinting == TextHinting::Unhinted {
+            attrs = attrs.cache_key_flags(CacheKeyFlags::DISABLE_HINTING);
+        }
 
         buffer.set_text(
             font_system,
@@ -1475,32 +2739,44 @@ fn build_text_areas<'a>(
             }
             measures_out[idx] = results;
         }
+
+        if command.measure_glyphs {
+            glyphs_out[idx] = glyph_extents(buffer);
+        }
     }
 
     for (idx, command) in commands.iter().enumerate() {
         let buffer = &text_buffers[idx];
 
-        let mut left = command.pos.0 as i32;
-        let mut top = command.pos.1 as i32;
-        let mut right = (command.pos.0 + command.bounds.0) as i32;
-        let mut bottom = (command.pos.1 + command.bounds.1) as i32;
+        // Buffers are shaped in logical pixels; glyphon scales them up
+        let (x, y) = (command.pos.0 * scale, command.pos.1 * scale);
+        let mut left = x as i32;
+        let mut top = y as i32;
+        let mut right = (x + command.bounds.0 * scale) as i32;
+        let mut bottom = (y + command.bounds.1 * scale) as i32;
 
         if let Some((cx, cy, cw, ch)) = command.clip {
-            left = left.max(cx as i32);
-            top = top.max(cy as i32);
-            right = right.min((cx + cw) as i32);
-            bottom = bottom.min((cy + ch) as i32);
+            left = left.max((cx * scale) as i32);
+            top = top.max((cy * scale) as i32);
+            right = right.min(((cx + cw) * scale) as i32);
+            bottom = bottom.min(((cy + ch) * scale) as i32);
         }
 
         if right <= left || bottom <= top {
             continue;
         }
 
+        let (area_left, area_top) = if hinting == TextHinting::PixelSnapped {
+            (x.round(), y.round())
+        } else {
+            (x, y)
+        };
+
         areas.push(TextArea {
             buffer,
-            left: command.pos.0,
-            top: command.pos.1,
-            scale: 1.0,
+            left: area_left,
+            top: area_top,
+            scale,
             bounds: TextBounds {
                 left,
                 top,
@@ -0,0 +1,85 @@
+//! Theme files with live reload (`theme-reload` feature).
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+
+use bexa_ui_core::Theme;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Parses a theme file: `.json` as JSON, anything else as TOML.
+pub(crate) fn load_theme(path: &Path) -> Result<Theme, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    if is_json {
+        Theme::from_json_str(&text).map_err(|e| e.to_string())
+    } else {
+        Theme::from_toml_str(&text).map_err(|e| e.to_string())
+    }
+}
+
+/// Watches a theme file and hands out the new theme after each valid change.
+pub(crate) struct ThemeWatcher {
+    path: PathBuf,
+    rx: Receiver<()>,
+    // Kept alive for as long as we want events
+    _watcher: RecommendedWatcher,
+}
+
+impl ThemeWatcher {
+    pub(crate) fn new(path: PathBuf) -> Option<Self> {
+        let (tx, rx) = channel();
+        let file_name = path.file_name().map(|n| n.to_os_string());
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let touches_file = event
+                    .paths
+                    .iter()
+                    .any(|p| p.file_name().map(|n| n.to_os_string()) == file_name);
+                if touches_file {
+                    let _ = tx.send(());
+                    // Wakes an idle event loop to pick the change up
+                    bexa_ui_core::request_redraw();
+                }
+            }
+        })
+        .map_err(|e| eprintln!("bexa-ui: cannot watch theme file: {e}"))
+        .ok()?;
+
+        // Watch the directory: editors often save by replacing the file
+        let dir = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|e| eprintln!("bexa-ui: cannot watch {}: {e}", dir.display()))
+            .ok()?;
+
+        Some(Self {
+            path,
+            rx,
+            _watcher: watcher,
+        })
+    }
+
+    /// Returns the reloaded theme if the file changed since the last call.
+    /// Parse errors are logged and the last good theme stays active.
+    pub(crate) fn poll(&self) -> Option<Theme> {
+        let mut changed = false;
+        while self.rx.try_recv().is_ok() {
+            changed = true;
+        }
+        if !changed {
+            return None;
+        }
+        match load_theme(&self.path) {
+            Ok(theme) => Some(theme),
+            Err(err) => {
+                eprintln!("bexa-ui: keeping previous theme, {}: {err}", self.path.display());
+                None
+            }
+        }
+    }
+}
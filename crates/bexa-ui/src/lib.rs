@@ -3,18 +3,21 @@
 // See LICENSE and LICENSE-COMMERCIAL for details.
 
 pub use bexa_ui_core::*;
-pub use bexa_ui_render::App;
+pub use bexa_ui_render::{
+    export_widget_png, image_cache_stats, wgpu, winit, App, BexaSurface, GpuTimings, ImageCacheStats, TextHinting,
+    DEFAULT_IMAGE_CACHE_BUDGET,
+};
 
 pub mod prelude {
     pub use bexa_ui_core::{
-        Align, Bar, BarChart, Button, Checkbox, Column, Container, Flex, Icon, Image, ImageFit, Label, Metrics,
-        Modal, RadioButton, radio_group, Renderer, ScrollView, Select, Slider, Table, Tabs, TextInput, Toggle, Theme,
-        Tooltip, TooltipPosition, TreeNode, TreeView, Widget, WidgetNode,
-        Signal, SetSignal, create_signal, icons,
-        WindowRequest, WindowRequests, create_window_requests,
+        Align, Bar, BarChart, BarMode, Button, Canvas, CaretStyle, CellContent, Checkbox, Column, Container, ContextMenu, Dimension, Drawer, Flex, GradientDirection, Icon, Image, ImageFit, KeyCombo, Label, Legend, LineChart, loading_overlay, LoadingOverlay, Metrics,
+        Modal, ModifiersState, popover, Popover, PopoverAlign, PopoverSide, RadioButton, RadioGroup, radio_group, Renderer, router, Router, ScrollView, Select, Slider, Spacer, Spinner, StatusBar, StatusSegment, Table, Tabs, tab_view, TextInput, Toggle, Toolbar, ToolbarOrientation, Theme,
+        TextStyle, Tooltip, TooltipPosition, TreeNode, TreeView, Widget, WidgetNode, DrawContext,
+        Signal, SetSignal, create_signal, debounce_signal, throttle_signal, on_frame, icons,
+        PresentMode, WindowRequest, WindowRequests, create_window_requests, UiSender, UiSpawner, request_exit,
         ui,
     };
     #[cfg(feature = "terminal")]
     pub use bexa_ui_core::Terminal;
-    pub use bexa_ui_render::App;
+    pub use bexa_ui_render::{App, TextHinting};
 }
@@ -0,0 +1,190 @@
+use bexa_ui::prelude::*;
+
+// Application shell: toolbar on top, collapsible navigation drawer on the
+// left, page content on the right and a status bar at the bottom. The
+// toolbar's page buttons switch the content through a Router; pages keep
+// their state while hidden (type a note, switch away and back). Refresh
+// covers just the page area with a loading overlay for a moment. Hovering
+// the navigation and focusing the note input report to the status bar.
+
+#[derive(Clone, Copy, PartialEq)]
+enum Page {
+    Overview,
+    Servers,
+    Notes,
+}
+
+fn nav_item(icon: &'static str, text: &str, metrics: Metrics, set_status: &SetSignal<String>) -> WidgetNode {
+    let item = ui! {
+        Flex::row(14.0) => {
+            Icon::new(icon, 20.0, [180, 200, 230]).with_padding(6.0),
+            Label::new(text, metrics, [210, 215, 225]).with_padding(6.0),
+        }
+    };
+    let hint = format!("Open {text}");
+    let enter_status = set_status.clone();
+    let leave_status = set_status.clone();
+    item.on_pointer_enter(move || enter_status.set(hint.clone()))
+        .on_pointer_leave(move || leave_status.set("Ready".to_string()))
+}
+
+fn page_header(title: &str, body: &str, title_metrics: Metrics, metrics: Metrics) -> Vec<WidgetNode> {
+    vec![
+        ui!(Label::new(title, title_metrics, [230, 232, 240])),
+        ui!(Label::new(body, metrics, [170, 175, 190])),
+    ]
+}
+
+fn main() {
+    let metrics = Metrics::new(14.0, 20.0);
+    let title_metrics = Metrics::new(20.0, 28.0);
+
+    let (drawer_open, set_drawer_open) = create_signal(true);
+    let (status, set_status) = create_signal("Ready".to_string());
+    let (page, set_page) = create_signal(Page::Overview);
+    let (loading, set_loading) = create_signal(false);
+
+    let nav = ui! {
+        Container::new().with_padding(8.0).with_gap(4.0) => {
+            nav_item(icons::DASHBOARD, "Overview", metrics, &set_status),
+            nav_item(icons::SERVER, "Servers", metrics, &set_status),
+            nav_item(icons::DATABASE, "Databases", metrics, &set_status),
+            nav_item(icons::CHART_BAR, "Metrics", metrics, &set_status),
+            nav_item(icons::COG, "Settings", metrics, &set_status),
+        }
+    };
+    let drawer = WidgetNode::new(
+        Drawer::new(drawer_open.clone())
+            .with_open_width(200.0)
+            .with_rail_width(52.0),
+        vec![nav],
+    );
+
+    let show_page = |target: Page, name: &'static str| {
+        let set_page = set_page.clone();
+        let set_status = set_status.clone();
+        move || {
+            set_page.set(target);
+            set_status.set(format!("{name} page"));
+        }
+    };
+
+    let toggle_open = drawer_open.clone();
+    let toggle_set = set_drawer_open.clone();
+    let toggle_status = set_status.clone();
+    let refresh_status = set_status.clone();
+    let toolbar = Toolbar::new()
+        .with_item(icons::BARS, "Toggle sidebar", move || {
+            let open = !toggle_open.get();
+            toggle_set.set(open);
+            toggle_status.set(if open { "Sidebar expanded" } else { "Sidebar collapsed" }.to_string());
+        })
+        .with_shortcut(&KeyCombo::primary("b"))
+        .with_separator()
+        .with_item(icons::REFRESH, "Refresh", move || {
+            // Fake a slow reload: lock the pages, then release them
+            set_loading.set(true);
+            refresh_status.set("Refreshing...".to_string());
+            let set_loading = set_loading.clone();
+            let refresh_status = refresh_status.clone();
+            let done_at = std::time::Instant::now() + std::time::Duration::from_millis(1500);
+            on_frame(move |now| {
+                if now < done_at {
+                    return true;
+                }
+                set_loading.set(false);
+                refresh_status.set("Refreshed".to_string());
+                false
+            });
+        })
+        .with_shortcut(&KeyCombo::primary("r"))
+        .with_separator()
+        .with_item(icons::DASHBOARD, "Overview", show_page(Page::Overview, "Overview"))
+        .with_item(icons::SERVER, "Servers", show_page(Page::Servers, "Servers"))
+        .with_item(icons::PENCIL, "Notes", show_page(Page::Notes, "Notes"));
+
+    let account_status = set_status.clone();
+    let account_toolbar = Toolbar::new().with_item(icons::USER, "Account", move || {
+        account_status.set("Signed in as admin".to_string());
+    });
+
+    let (_note, set_note) = create_signal(String::new());
+    let overview = WidgetNode::new(
+        Container::new().with_padding(24.0).with_gap(12.0),
+        page_header(
+            "App Shell",
+            "Toggle the sidebar from the toolbar to collapse it to an icon rail.",
+            title_metrics,
+            metrics,
+        ),
+    );
+    let servers = WidgetNode::new(
+        Container::new().with_padding(24.0).with_gap(12.0),
+        page_header("Servers", "3 servers online, 1 in maintenance.", title_metrics, metrics),
+    );
+    let mut notes_items = page_header(
+        "Notes",
+        "Hidden pages keep their state, so this draft survives page switches.",
+        title_metrics,
+        metrics,
+    );
+    let focus_status = set_status.clone();
+    let blur_status = set_status.clone();
+    notes_items.push(
+        ui!(TextInput::new(set_note)
+            .with_placeholder("Write a note...")
+            .with_multiline(true)
+            .with_max_rows(8))
+            .on_focus(move || focus_status.set("Editing note".to_string()))
+            .on_blur(move || blur_status.set("Note saved".to_string())),
+    );
+    let notes = WidgetNode::new(Container::new().with_padding(24.0).with_gap(12.0), notes_items);
+
+    let pages = WidgetNode::new(
+        LoadingOverlay::new(loading).with_grow(1.0),
+        vec![router(
+            page,
+            vec![
+                (Page::Overview, overview),
+                (Page::Servers, servers),
+                (Page::Notes, notes),
+            ],
+        )],
+    );
+
+    let root = ui! {
+        Container::new().with_background([0.07, 0.08, 0.11]) => {
+            Container::new()
+                .with_background([0.09, 0.10, 0.14])
+                .with_padding(4.0)
+                .with_max_height(48.0) => {
+                Flex::row(0.0) => {
+                    toolbar,
+                    Spacer::new(),
+                    account_toolbar,
+                },
+            },
+            Flex::row(0.0) => {
+                drawer,
+                pages,
+            },
+            StatusBar::new(Metrics::new(12.0, 16.0))
+                .with_left(StatusSegment::new(status).with_icon(icons::INFO_CIRCLE))
+                .with_right(StatusSegment::new("v0.2.0")),
+        }
+    };
+
+    // Collapse the sidebar to its rail when the window gets narrow, and
+    // expand it again when it widens; toggling in between is left alone
+    let mut was_narrow = None;
+    App::new(root)
+        .title("BexaUI - App Shell")
+        .on_resize(move |width, _| {
+            let narrow = width < 720.0;
+            if was_narrow != Some(narrow) {
+                was_narrow = Some(narrow);
+                set_drawer_open.set(!narrow);
+            }
+        })
+        .run();
+}
@@ -40,6 +40,25 @@ fn main() {
         *status_remote.borrow_mut() = "Connecting to remote...".to_string();
     });
 
+    // Hold-to-repeat stepper
+    let volume = Rc::new(RefCell::new(50_i32));
+    let (volume_label, set_volume_label) = create_signal("Volume: 50".to_string());
+    let stepper = |text: &str, delta: i32| {
+        let mut button = Button::new(text, button_metrics)
+            .with_padding(12.0)
+            .with_repeat(Button::REPEAT_DELAY, Button::REPEAT_INTERVAL);
+        let volume = volume.clone();
+        let set_volume_label = set_volume_label.clone();
+        button.set_on_click(move || {
+            let mut v = volume.borrow_mut();
+            *v = (*v + delta).clamp(0, 100);
+            set_volume_label.set(format!("Volume: {v}"));
+        });
+        button
+    };
+    let volume_down = stepper("-", -1);
+    let volume_up = stepper("+", 1);
+
     let status_label = Label::new(status.clone(), status_metrics, theme.text_primary)
         .with_align(Align::Left)
         .with_padding(6.0);
@@ -55,6 +74,11 @@ fn main() {
                         remote_button,
                     },
                 },
+                Flex::row(12.0) => {
+                    volume_down,
+                    Label::new(volume_label, status_metrics, theme.text_primary).with_padding(6.0),
+                    volume_up,
+                },
                 status_label,
             },
         }
@@ -0,0 +1,91 @@
+use bexa_ui::prelude::*;
+
+// Node graph drawn on a Canvas: drag to pan, Ctrl + wheel to zoom,
+// click a node to select it.
+
+struct GraphNode {
+    label: &'static str,
+    rect: (f32, f32, f32, f32),
+}
+
+fn contains(rect: (f32, f32, f32, f32), x: f32, y: f32) -> bool {
+    x >= rect.0 && x <= rect.0 + rect.2 && y >= rect.1 && y <= rect.1 + rect.3
+}
+
+fn main() {
+    let nodes = [
+        GraphNode { label: "Ingest", rect: (40.0, 60.0, 140.0, 56.0) },
+        GraphNode { label: "Parse", rect: (260.0, 20.0, 140.0, 56.0) },
+        GraphNode { label: "Validate", rect: (260.0, 140.0, 140.0, 56.0) },
+        GraphNode { label: "Store", rect: (480.0, 80.0, 140.0, 56.0) },
+    ];
+    let edges = [(0, 1), (0, 2), (1, 3), (2, 3)];
+
+    let (selected, set_selected) = create_signal(None::<usize>);
+    let (status, set_status) = create_signal("Click a node".to_string());
+
+    let hit_rects: Vec<_> = nodes.iter().map(|n| (n.label, n.rect)).collect();
+    let selected_draw = selected.clone();
+
+    let canvas = Canvas::new(move |ctx| {
+        // Edges from the right side of one node to the left side of the next
+        for &(from, to) in &edges {
+            let a = nodes[from].rect;
+            let b = nodes[to].rect;
+            ctx.line(
+                (a.0 + a.2, a.1 + a.3 / 2.0),
+                (b.0, b.1 + b.3 / 2.0),
+                2.0,
+                [0.45, 0.55, 0.70, 1.0],
+            );
+        }
+
+        let label_style = TextStyle::new(Metrics::new(14.0, 20.0), [220, 225, 235]).with_align(Align::Center);
+
+        let selected = selected_draw.get();
+        for (i, node) in nodes.iter().enumerate() {
+            let border = if selected == Some(i) {
+                ctx.theme.focus_ring
+            } else {
+                [0.30, 0.36, 0.48, 1.0]
+            };
+            let (x, y, w, h) = node.rect;
+            ctx.gradient_rect(
+                node.rect,
+                8.0,
+                [0.17, 0.22, 0.31, 1.0],
+                [0.12, 0.15, 0.22, 1.0],
+                GradientDirection::Vertical,
+            );
+            ctx.stroke_rect(node.rect, 8.0, 2.0, border);
+            ctx.text(node.label, (x, y + 18.0), (w, 20.0), &label_style);
+            // Ports where the edges attach
+            for port in [(x, y + h / 2.0), (x + w, y + h / 2.0)] {
+                ctx.circle(port, 5.0, [0.45, 0.55, 0.70, 1.0]);
+            }
+        }
+    })
+    .with_background([0.06, 0.07, 0.10, 1.0])
+    .with_on_click(move |x, y| {
+        let hit = hit_rects.iter().position(|&(_, rect)| contains(rect, x, y));
+        set_selected.set(hit);
+        let text = match hit {
+            Some(i) => format!("Selected {}", hit_rects[i].0),
+            None => format!("Empty space at ({x:.0}, {y:.0})"),
+        };
+        set_status.set(text);
+    });
+
+    let root = ui! {
+        Container::new().with_background([0.08, 0.09, 0.12]) => {
+            canvas,
+            StatusBar::new(Metrics::new(12.0, 16.0))
+                .with_left(StatusSegment::new(status))
+                .with_right(StatusSegment::new("Drag to pan, Ctrl + wheel to zoom")),
+        }
+    };
+
+    App::new(root)
+        .title("BexaUI - Canvas")
+        .run();
+}
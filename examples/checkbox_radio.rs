@@ -52,9 +52,9 @@ fn main() {
         metrics,
     );
 
-    // Radio group 2
+    // Radio group 2: one tab stop, arrow keys move the selection
     let (priority, set_priority) = create_signal(1usize);
-    let priority_radios = radio_group(
+    let priority_radios = RadioGroup::new(
         &["Low", "Medium", "High"],
         priority,
         set_priority,
@@ -87,7 +87,8 @@ fn main() {
         env,
         set_env,
         metrics,
-    );
+    )
+    .with_searchable(true);
 
     let root = ui! {
         Container::new().with_padding(32.0).with_gap(20.0) => {
@@ -138,7 +139,7 @@ fn main() {
                     .with_border_radius(8.0) => {
                     Flex::column(8.0, 0.0) => {
                         section_title("Priority", title_metrics, theme.text_primary),
-                        WidgetNode::new(Flex::column(6.0, 0.0), priority_radios),
+                        priority_radios,
                     },
                 },
             },
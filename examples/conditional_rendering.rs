@@ -3,6 +3,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 fn main() {
+    let theme = Theme::ocean();
     let metrics = Metrics::new(14.0, 20.0);
 
     // State for conditional rendering
@@ -133,8 +134,8 @@ fn main() {
                 Label::new(
                     Rc::new(RefCell::new(format!(
                         "Panel A: {} | Extra Info: {}",
-                        if (should_show_a) { "ON" } else { "OFF" },
-                        if (should_show_info) { "ON" } else { "OFF" }
+                        if should_show_a { "ON" } else { "OFF" },
+                        if should_show_info { "ON" } else { "OFF" }
                     ))),
                     metrics,
                     theme.text_secondary,
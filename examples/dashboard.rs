@@ -174,10 +174,11 @@ fn build_list_section(theme: &Theme, metrics: Metrics, title_metrics: Metrics) -
     ui! {
         panel(theme)
             .with_border(2.0, [0.3, 0.5, 0.7, 1.0]) => {
-            section_title("List View (scroll me!)", title_metrics, theme.text_primary),
+            section_title("List View (scroll me, or Tab here and use the keys)", title_metrics, theme.text_primary),
             Container::new()
                 .with_max_height(250.0)
-                .with_scroll() => {
+                .with_scroll()
+                .with_focusable() => {
                 WidgetNode::new(Flex::column(6.0, 0.0), items),
             },
         }
@@ -251,19 +252,28 @@ fn build_input_section(
 ) -> WidgetNode {
     let (name_val, name_set) = create_signal(String::new());
     let (email_val, email_set) = create_signal(String::new());
+    let (password_val, password_set) = create_signal(String::new());
 
-    let name_input = TextInput::new(name_set)
+    // Bound to their own signals, so the Clear button below empties them
+    let name_input = TextInput::bound(name_val.clone(), name_set.clone())
         .with_placeholder("Your name...")
         .with_metrics(metrics)
         .with_padding(10.0)
         .with_border_radius(6.0);
 
-    let email_input = TextInput::new(email_set)
+    let email_input = TextInput::bound(email_val.clone(), email_set.clone())
         .with_placeholder("email@example.com")
         .with_metrics(metrics)
         .with_padding(10.0)
         .with_border_radius(6.0);
 
+    let password_input = TextInput::bound(password_val, password_set.clone())
+        .with_placeholder("Password")
+        .with_mask('•')
+        .with_metrics(metrics)
+        .with_padding(10.0)
+        .with_border_radius(6.0);
+
     let name_preview = Label::new(name_val, metrics, [100, 220, 160])
         .with_align(Align::Left)
         .with_padding(4.0);
@@ -272,6 +282,15 @@ fn build_input_section(
         .with_align(Align::Left)
         .with_padding(4.0);
 
+    let mut clear = Button::new("Clear", metrics)
+        .with_padding(8.0)
+        .with_border_radius(6.0);
+    clear.set_on_click(move || {
+        name_set.set(String::new());
+        email_set.set(String::new());
+        password_set.set(String::new());
+    });
+
     ui! {
         panel(theme).with_border(1.0, [0.4, 0.55, 0.8, 0.8]) => {
             section_title("Text Input", title_metrics, theme.text_primary),
@@ -279,9 +298,12 @@ fn build_input_section(
             name_input,
             section_title("Email:", metrics, theme.text_secondary),
             email_input,
+            section_title("Password:", metrics, theme.text_secondary),
+            password_input,
             section_title("Live preview:", metrics, theme.text_secondary),
             name_preview,
             email_preview,
+            clear,
         }
     }
 }
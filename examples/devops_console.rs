@@ -1,4 +1,5 @@
 use bexa_ui::prelude::*;
+use bexa_ui::{Key, NamedKey};
 
 // ── Helpers ──
 
@@ -92,6 +93,12 @@ fn main() {
     let s = set_status.clone();
     btn_stop.set_on_click(move || s.set("Stopping all containers...".to_string()));
 
+    // ── Shortcuts: F5 refreshes, Ctrl+Q (Cmd+Q on macOS) quits ──
+    let refresh_key = KeyCombo::new(Key::Named(NamedKey::F5), ModifiersState::empty());
+    let quit_key = KeyCombo::primary("q");
+    let shortcut_hint = format!("{} refresh  {} quit", refresh_key.display(), quit_key.display());
+    let refresh_status = set_status.clone();
+
     // ── Stats row (built manually because children are WidgetNodes) ──
     let stats = WidgetNode::new(Flex::row(10.0), vec![
         stat_card(icons::COG, "CPU", "42%", [0.14, 0.38, 0.28], metrics),
@@ -125,9 +132,17 @@ fn main() {
         .with_background([0.06, 0.06, 0.09, 1.0]);
 
     // ── Status bar ──
-    let status_label = Label::new(status, Metrics::new(12.0, 16.0), [120, 200, 160])
-        .with_align(Align::Left)
-        .with_padding(4.0);
+    let status_bar = StatusBar::new(Metrics::new(12.0, 16.0))
+        .with_colors([0.06, 0.07, 0.10, 1.0], [0.16, 0.18, 0.24, 1.0], [0.12, 0.14, 0.20, 1.0])
+        .with_left(
+            StatusSegment::new(status)
+                .with_icon(icons::CHECK_CIRCLE)
+                .with_color([120, 200, 160])
+                .with_icon_color([80, 200, 140]),
+        )
+        .with_right(StatusSegment::new(shortcut_hint))
+        .with_right(StatusSegment::new("prod-us-east-1").with_icon(icons::CLOUD))
+        .with_right(StatusSegment::new("UTF-8"));
 
     // ── Title ──
     let (title_sig, _) = create_signal("DevOps Console".to_string());
@@ -150,21 +165,27 @@ fn main() {
         ],
     );
 
-    // ── Logs panel ──
+    // ── Logs panel: fixed width, the terminal takes the rest of the row;
+    // drag over the log lines to select them, Ctrl+C copies ──
     let logs_panel = WidgetNode::new(
         Container::new()
             .with_background([0.10, 0.11, 0.14])
             .with_border_radius(8.0)
             .with_border(1.0, [0.2, 0.3, 0.5, 0.6])
             .with_padding(8.0)
-            .with_height(400.0),
+            .with_height(400.0)
+            .with_flex_basis(Dimension::Length(360.0))
+            .with_flex_grow(0.0)
+            .with_flex_shrink(0.0),
         vec![
             WidgetNode::new(Flex::column(6.0, 0.0), vec![
                 section_header(icons::FILE_CODE, "Logs", header_metrics, [140, 180, 220]),
                 WidgetNode::new(
                     Container::new()
                         .with_max_height(360.0)
-                        .with_scroll(),
+                        .with_scroll()
+                        .with_scrollbar_autohide(true)
+                        .with_text_selection(true),
                     vec![WidgetNode::new(Flex::column(3.0, 0.0), logs)],
                 ),
             ]),
@@ -200,17 +221,7 @@ fn main() {
             WidgetNode::new(Flex::row(12.0), vec![terminal_panel, logs_panel]),
 
             // Status bar
-            ui! {
-                Container::new()
-                    .with_background([0.06, 0.07, 0.10])
-                    .with_padding(6.0)
-                    .with_border_radius(4.0) => {
-                    Flex::row(8.0) => {
-                        Icon::new(icons::CHECK_CIRCLE, 14.0, [80, 200, 140]),
-                        status_label,
-                    },
-                }
-            },
+            WidgetNode::new(status_bar, vec![]),
         ],
     );
 
@@ -220,5 +231,7 @@ fn main() {
             ..theme
         })
         .title("BexaUI - DevOps Console")
+        .on_shortcut(refresh_key, move || refresh_status.set("Refreshing metrics...".to_string()))
+        .on_shortcut(quit_key, request_exit)
         .run();
 }
@@ -0,0 +1,28 @@
+use bexa_ui::export_widget_png;
+use bexa_ui::prelude::*;
+
+fn main() {
+    let bars = vec![
+        Bar::new("Mon", 1240.0, [0.25, 0.60, 0.85, 1.0]),
+        Bar::new("Tue", 1580.0, [0.28, 0.63, 0.87, 1.0]),
+        Bar::new("Wed", 2120.0, [0.32, 0.68, 0.90, 1.0]),
+        Bar::new("Thu", 1870.0, [0.28, 0.63, 0.87, 1.0]),
+        Bar::new("Fri", 2450.0, [0.35, 0.72, 0.92, 1.0]),
+    ];
+    let (sig, _) = create_signal(bars);
+    let chart = BarChart::new(sig, Metrics::new(13.0, 18.0), 220.0)
+        .with_bar_radius(5.0)
+        .with_bar_gap(8.0);
+
+    let root = ui! {
+        Container::new().with_padding(16.0) => {
+            chart,
+        }
+    };
+
+    let path = "chart.png";
+    match export_widget_png(root, 480, 260, path) {
+        Ok(()) => println!("Saved {path}"),
+        Err(err) => eprintln!("Export failed: {err}"),
+    }
+}
@@ -8,8 +8,8 @@ fn label_node(text: &str, metrics: Metrics) -> WidgetNode {
     )
 }
 
-fn image_card(title: &str, fit: ImageFit, path: &str, metrics: Metrics) -> WidgetNode {
-    let image = Image::new(path)
+fn image_card(title: &str, fit: ImageFit, image: Image, metrics: Metrics) -> WidgetNode {
+    let image = image
         .with_size(260.0, 170.0)
         .with_fit(fit)
         .with_background([0.10, 0.12, 0.16, 1.0])
@@ -19,7 +19,8 @@ fn image_card(title: &str, fit: ImageFit, path: &str, metrics: Metrics) -> Widge
         Container::new()
             .with_background([0.08, 0.10, 0.14])
             .with_border_radius(10.0)
-            .with_border(1.0, [0.18, 0.22, 0.32, 1.0]),
+            .with_border(1.0, [0.18, 0.22, 0.32, 1.0])
+            .with_clip(true),
         vec![WidgetNode::new(image, vec![])],
     );
 
@@ -33,13 +34,16 @@ fn main() {
     let theme = Theme::ocean();
     let metrics = Metrics::new(14.0, 20.0);
     let path = "examples/assets/placeholder.ppm";
+    // Same picture, embedded in the binary
+    let bytes: &[u8] = include_bytes!("assets/placeholder.ppm");
 
     let row = WidgetNode::new(
         Flex::row(12.0),
         vec![
-            image_card("Fill (stretch)", ImageFit::Fill, path, metrics),
-            image_card("Contain", ImageFit::Contain, path, metrics),
-            image_card("Cover", ImageFit::Cover, path, metrics),
+            image_card("Fill (stretch)", ImageFit::Fill, Image::from_path(path), metrics),
+            image_card("Contain", ImageFit::Contain, Image::from_path(path), metrics),
+            image_card("Cover", ImageFit::Cover, Image::from_path(path), metrics),
+            image_card("Contain (from bytes)", ImageFit::Contain, Image::from_bytes(bytes), metrics),
         ],
     );
 
@@ -26,6 +26,13 @@ fn main() {
         items.push(list_item(&label, metrics));
     }
 
+    // The row count comes from a fake background load
+    let (status, set_status) = create_signal("Loading...".to_string());
+    items.insert(
+        0,
+        WidgetNode::new(Label::new(status, metrics, [170, 180, 200]).with_align(Align::Left), vec![]),
+    );
+
     let list = WidgetNode::new(
         Flex::column(8.0, 0.0),
         items,
@@ -39,8 +46,13 @@ fn main() {
         }
     };
 
-    App::new(root)
-        .theme(theme)
-        .title("BexaUI - List")
-        .run();
+    let app = App::new(root).theme(theme).title("BexaUI - List");
+    let loaded = app
+        .spawner()
+        .channel(move |count: usize| set_status.set(format!("{count} items loaded")));
+    std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(800));
+        loaded.send(8);
+    });
+    app.run();
 }
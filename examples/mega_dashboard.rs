@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use bexa_ui::prelude::*;
+use bexa_ui::signal_changed;
 
 // ─── Helpers ─────────────────────────────────────────────────────────
 
@@ -18,15 +21,28 @@ fn panel(bg: [f32; 3]) -> Container {
         .with_border(1.0, [0.20, 0.25, 0.35, 1.0])
 }
 
-fn stat_card(icon: &'static str, label: &str, value: &str, accent: [f32; 3], tooltip_text: &str) -> WidgetNode {
+fn stat_card(
+    icon: &'static str,
+    label: &str,
+    value: &str,
+    accent: [f32; 3],
+    tooltip_text: &str,
+    set_status: &SetSignal<String>,
+) -> WidgetNode {
     let (val_sig, _) = create_signal(value.to_string());
     let (lbl_sig, _) = create_signal(label.to_string());
 
+    let status = set_status.clone();
+    let message = format!("{label}: {value}");
+    let shade = |factor: f32| accent.map(|c| (c * factor).min(1.0));
     let card = WidgetNode::new(
         Container::new()
             .with_background(accent)
+            .with_hover_background(shade(1.2))
+            .with_active_background(shade(0.85))
             .with_padding(12.0)
-            .with_border_radius(8.0),
+            .with_border_radius(8.0)
+            .with_on_click(move || status.set(message.clone())),
         vec![
             WidgetNode::new(
                 Flex::row(8.0),
@@ -54,11 +70,38 @@ fn stat_card(icon: &'static str, label: &str, value: &str, accent: [f32; 3], too
 
     // Wrap with Tooltip
     WidgetNode::new(
-        Tooltip::new(tooltip_text).with_position(TooltipPosition::Bottom),
+        Tooltip::new(tooltip_text)
+            .with_position(TooltipPosition::Bottom)
+            .with_delay(Duration::from_millis(400))
+            .with_fade(Duration::from_millis(120)),
         vec![card],
     )
 }
 
+// ─── Toolbar ─────────────────────────────────────────────────────────
+
+fn build_toolbar(set_status: &SetSignal<String>, set_modal_open: &SetSignal<bool>) -> WidgetNode {
+    let refresh_status = set_status.clone();
+    let export_status = set_status.clone();
+    let deploy_modal = set_modal_open.clone();
+    let settings_status = set_status.clone();
+    WidgetNode::new(
+        Toolbar::new()
+            .with_item(icons::REFRESH, "Refresh metrics", move || {
+                refresh_status.set("Metrics refreshed".to_string());
+            })
+            .with_item(icons::DOWNLOAD, "Export report", move || {
+                export_status.set("Report exported".to_string());
+            })
+            .with_separator()
+            .with_item(icons::ROCKET, "Deploy", move || deploy_modal.set(true))
+            .with_item(icons::COG, "Settings", move || {
+                settings_status.set("Settings are not available in this demo".to_string());
+            }),
+        vec![],
+    )
+}
+
 // ─── Tabs ────────────────────────────────────────────────────────────
 
 fn build_tabs(metrics: Metrics) -> (WidgetNode, Signal<usize>) {
@@ -79,15 +122,15 @@ fn build_tabs(metrics: Metrics) -> (WidgetNode, Signal<usize>) {
 
 // ─── Stat Cards Row ──────────────────────────────────────────────────
 
-fn build_stats_row() -> WidgetNode {
+fn build_stats_row(set_status: &SetSignal<String>) -> WidgetNode {
     WidgetNode::new(
         Flex::row(10.0),
         vec![
-            stat_card(icons::DASHBOARD, "CPU", "42%", [0.16, 0.42, 0.32], "Average CPU usage across all instances"),
-            stat_card(icons::DATABASE, "RAM", "7.8 GB", [0.20, 0.32, 0.52], "Total memory used / 16 GB available"),
-            stat_card(icons::CLOUD, "Network", "1.2 MB/s", [0.42, 0.28, 0.52], "Inbound + outbound bandwidth"),
-            stat_card(icons::CLOCK, "Uptime", "14d 3h", [0.48, 0.35, 0.20], "Since last restart on Jan 23"),
-            stat_card(icons::ROCKET, "Deploy", "#847", [0.22, 0.52, 0.42], "Latest deploy: 2h ago by CI/CD"),
+            stat_card(icons::DASHBOARD, "CPU", "42%", [0.16, 0.42, 0.32], "Average CPU usage across all instances", set_status),
+            stat_card(icons::DATABASE, "RAM", "7.8 GB", [0.20, 0.32, 0.52], "Total memory used / 16 GB available", set_status),
+            stat_card(icons::CLOUD, "Network", "1.2 MB/s", [0.42, 0.28, 0.52], "Inbound + outbound bandwidth", set_status),
+            stat_card(icons::CLOCK, "Uptime", "14d 3h", [0.48, 0.35, 0.20], "Since last restart on Jan 23", set_status),
+            stat_card(icons::ROCKET, "Deploy", "#847", [0.22, 0.52, 0.42], "Latest deploy: 2h ago by CI/CD", set_status),
         ],
     )
 }
@@ -120,16 +163,35 @@ fn build_requests_chart(metrics: Metrics, title_metrics: Metrics) -> WidgetNode
 
 // ─── Table (services) ────────────────────────────────────────────────
 
-fn build_services_table(metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
+fn status_badge(status: &str) -> CellContent {
+    let bg = match status {
+        "Running" => [0.16, 0.50, 0.32, 1.0],
+        "Warning" => [0.70, 0.50, 0.12, 1.0],
+        "Stopped" => [0.62, 0.20, 0.22, 1.0],
+        _ => return CellContent::Text(status.to_string()),
+    };
+    CellContent::Badge {
+        text: status.to_string(),
+        bg,
+        color: [245, 245, 245],
+    }
+}
+
+fn build_services_table(
+    metrics: Metrics,
+    title_metrics: Metrics,
+    search: Signal<String>,
+    set_status: &SetSignal<String>,
+) -> WidgetNode {
     let columns = vec![
         Column::new("Service", 2.5),
-        Column::new("Status", 1.2),
-        Column::new("CPU", 0.8),
-        Column::new("Memory", 1.2),
+        Column::new("Status", 1.2).with_renderer(status_badge),
+        Column::new("CPU", 0.8).with_align(Align::Right),
+        Column::new("Memory", 1.2).with_align(Align::Right),
         Column::new("Region", 1.2),
     ];
 
-    let data = vec![
+    let data: Vec<Vec<String>> = vec![
         vec!["api-gateway".into(), "Running".into(), "12.4%".into(), "256 MB".into(), "us-east-1".into()],
         vec!["auth-service".into(), "Running".into(), "8.2%".into(), "128 MB".into(), "us-east-1".into()],
         vec!["db-primary".into(), "Running".into(), "45.1%".into(), "2.1 GB".into(), "us-east-1".into()],
@@ -144,16 +206,57 @@ fn build_services_table(metrics: Metrics, title_metrics: Metrics) -> WidgetNode
         vec!["notification".into(), "Running".into(), "1.8%".into(), "48 MB".into(), "eu-west-1".into()],
     ];
 
-    let (rows, _) = create_signal(data);
+    let (rows, set_rows) = create_signal(data.clone());
     let (selected, set_selected) = create_signal(None);
+
+    // Re-filter only after typing pauses
+    let query = debounce_signal(search, Duration::from_millis(250));
+    let mut last_query = String::new();
+    let clear_selection = set_selected.clone();
+    on_frame(move |_| {
+        if signal_changed(&query, &mut last_query, |q| q.trim().to_lowercase()) {
+            let filtered = data
+                .iter()
+                .filter(|row| row.iter().any(|cell| cell.to_lowercase().contains(&last_query)))
+                .cloned()
+                .collect();
+            set_rows.set(filtered);
+            clear_selection.set(None);
+        }
+        true
+    });
+    let (menu_rows, menu_selected) = (rows.clone(), selected.clone());
+    let open_rows = rows.clone();
+    let open_status = set_status.clone();
     let table = Table::new(columns, rows, selected, set_selected, metrics)
-        .with_max_visible(8);
+        .with_max_visible(8)
+        .with_resizable_columns(true)
+        .with_on_row_double_click(move |row| {
+            if let Some(cells) = open_rows.get().get(row) {
+                open_status.set(format!("Opened {}", cells[0]));
+            }
+        });
+
+    // Right-click actions on the selected service
+    let s = set_status.clone();
+    let actions = ContextMenu::new(metrics)
+        .with_item("Restart")
+        .with_item("Stop")
+        .with_item("View logs")
+        .with_on_select(move |index| {
+            let service = menu_selected
+                .get()
+                .and_then(|row| menu_rows.get().get(row).map(|cells| cells[0].clone()))
+                .unwrap_or_else(|| "all services".into());
+            let action = ["Restarting", "Stopping", "Opening logs for"][index];
+            s.set(format!("{action} {service}..."));
+        });
 
     WidgetNode::new(
         panel([0.08, 0.10, 0.14]),
         vec![
             label_node("Services", title_metrics, [170, 185, 210]),
-            WidgetNode::new(table, vec![]),
+            WidgetNode::new(actions, vec![WidgetNode::new(table, vec![])]),
         ],
     )
 }
@@ -203,26 +306,65 @@ fn build_infra_tree(metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
 
 // ─── Health BarChart ─────────────────────────────────────────────────
 
+fn health_color(value: f32) -> [f32; 4] {
+    if value >= 90.0 {
+        [0.22, 0.72, 0.42, 1.0]
+    } else if value >= 70.0 {
+        [0.90, 0.65, 0.20, 1.0]
+    } else {
+        [0.88, 0.30, 0.28, 1.0]
+    }
+}
+
+fn health_bars(values: &[(&str, f32)]) -> Vec<Bar> {
+    values
+        .iter()
+        .map(|&(label, value)| Bar::new(label, value, health_color(value)))
+        .collect()
+}
+
 fn build_health_chart(metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
-    let bars = vec![
-        Bar::new("API", 98.0, [0.22, 0.72, 0.42, 1.0]),
-        Bar::new("Auth", 95.0, [0.25, 0.75, 0.45, 1.0]),
-        Bar::new("DB", 88.0, [0.90, 0.65, 0.20, 1.0]),
-        Bar::new("Cache", 99.0, [0.22, 0.72, 0.42, 1.0]),
-        Bar::new("Queue", 52.0, [0.88, 0.30, 0.28, 1.0]),
-        Bar::new("CDN", 97.0, [0.22, 0.72, 0.42, 1.0]),
+    let mut values = vec![
+        ("API", 98.0),
+        ("Auth", 95.0),
+        ("DB", 88.0),
+        ("Cache", 99.0),
+        ("Queue", 52.0),
+        ("CDN", 97.0),
     ];
-    let (sig, _) = create_signal(bars);
+    let (sig, set_sig) = create_signal(health_bars(&values));
+
+    // Simulated live probes: nudge every value every couple of seconds
+    let mut seed: u32 = 0x2545_f491;
+    let mut last_tick = std::time::Instant::now();
+    on_frame(move |now| {
+        if now.duration_since(last_tick) >= Duration::from_secs(2) {
+            last_tick = now;
+            for (_, value) in values.iter_mut() {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let jitter = (seed >> 16) as f32 / 65_535.0 * 16.0 - 8.0;
+                *value = (*value + jitter).clamp(35.0, 100.0);
+            }
+            set_sig.set(health_bars(&values));
+        }
+        true
+    });
     let chart = BarChart::new(sig, metrics, 180.0)
         .with_max_value(100.0)
+        .with_value_formatter(|v| format!("{v:.0}%"))
         .with_bar_radius(4.0)
         .with_bar_gap(10.0);
+    let legend = Legend::new(Metrics::new(12.0, 16.0))
+        .with_entry("Healthy (90%+)", health_color(100.0))
+        .with_entry("Degraded (70%+)", health_color(80.0))
+        .with_entry("Failing", health_color(0.0));
 
     WidgetNode::new(
         panel([0.08, 0.10, 0.14]),
         vec![
             label_node("Service Health (%)", title_metrics, [170, 185, 210]),
             WidgetNode::new(chart, vec![]),
+            WidgetNode::new(legend, vec![]),
         ],
     )
 }
@@ -234,11 +376,13 @@ fn build_controls_panel(
     title_metrics: Metrics,
     set_status: &SetSignal<String>,
     set_modal: &SetSignal<bool>,
+    set_search: SetSignal<String>,
 ) -> WidgetNode {
-    // TextInput - search
-    let (_search_val, search_set) = create_signal(String::new());
-    let search_input = TextInput::new(search_set)
+    // TextInput - search (filters the services table)
+    let search_input = TextInput::new(set_search)
         .with_placeholder("Search services...")
+        .with_leading_icon(icons::SEARCH)
+        .with_clear_button(true)
         .with_metrics(metrics)
         .with_padding(8.0)
         .with_border_radius(6.0);
@@ -357,15 +501,17 @@ fn main() {
 
     let (status, set_status) = create_signal("All systems operational".to_string());
     let (modal_open, set_modal_open) = create_signal(false);
+    let (search, set_search) = create_signal(String::new());
 
     let (tabs_node, _active_tab) = build_tabs(metrics);
+    let toolbar = build_toolbar(&set_status, &set_modal_open);
 
     // Left column: chart + table
     let left_col = WidgetNode::new(
         Flex::column(12.0, 0.0),
         vec![
             build_requests_chart(metrics, title_metrics),
-            build_services_table(metrics, title_metrics),
+            build_services_table(metrics, title_metrics, search, &set_status),
         ],
     );
 
@@ -375,11 +521,28 @@ fn main() {
         vec![
             build_infra_tree(metrics, title_metrics),
             build_health_chart(metrics, title_metrics),
-            build_controls_panel(metrics, title_metrics, &set_status, &set_modal_open),
+            build_controls_panel(metrics, title_metrics, &set_status, &set_modal_open, set_search),
         ],
     );
 
     // Deploy confirmation modal
+    let mut btn_cancel = Button::new("Cancel", metrics)
+        .with_padding(12.0)
+        .with_border_radius(6.0);
+    let mut btn_confirm = Button::new("Deploy", metrics)
+        .with_colors([0.20, 0.65, 0.40], [0.28, 0.72, 0.48], [0.15, 0.55, 0.32], [0.24, 0.68, 0.44])
+        .with_padding(12.0)
+        .with_border_radius(6.0)
+        .with_text_colors([255, 255, 255], [255, 255, 255], [255, 255, 255]);
+    let m = set_modal_open.clone();
+    btn_cancel.set_on_click(move || m.set(false));
+    let m = set_modal_open.clone();
+    let s = set_status.clone();
+    btn_confirm.set_on_click(move || {
+        s.set("Deploying main to us-east-1...".into());
+        m.set(false);
+    });
+
     let deploy_modal = Modal::new(modal_open, set_modal_open)
         .with_title("Confirm Deploy")
         .with_body(vec![
@@ -388,10 +551,19 @@ fn main() {
             "Target: us-east-1 (3 instances)".into(),
             "Branch: main (commit #a3f7b2c)".into(),
             "Pipeline: CI passed (all 247 tests)".into(),
-            "".into(),
-            "Click anywhere or press Escape to close.".into(),
         ])
         .with_width(420.0);
+    let deploy_modal = WidgetNode::new(
+        deploy_modal,
+        vec![WidgetNode::new(
+            Flex::row(8.0),
+            vec![
+                WidgetNode::new(Spacer::new(), vec![]),
+                WidgetNode::new(btn_cancel, vec![]),
+                WidgetNode::new(btn_confirm, vec![]),
+            ],
+        )],
+    );
 
     let root = WidgetNode::new(
         Container::new()
@@ -401,13 +573,19 @@ fn main() {
             .with_scroll(),
         vec![
             // Modal (takes no space, renders in overlay)
-            WidgetNode::new(deploy_modal, vec![]),
+            deploy_modal,
             // Header
-            label_node("Infrastructure Dashboard", header_metrics, [220, 225, 240]),
+            WidgetNode::new(
+                Flex::row(12.0),
+                vec![
+                    label_node("Infrastructure Dashboard", header_metrics, [220, 225, 240]),
+                    toolbar,
+                ],
+            ),
             // Tabs
             tabs_node,
             // Stat cards row
-            build_stats_row(),
+            build_stats_row(&set_status),
             // Main 2-column layout
             WidgetNode::new(Flex::row(12.0), vec![left_col, right_col]),
             // Status bar
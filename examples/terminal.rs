@@ -19,6 +19,9 @@ fn main() {
                 height: 600,
                 root: ui!(term),
                 theme: Theme::ocean(),
+                clear_color: None,
+                // Lower input latency for the shell; falls back to vsync
+                present_mode: Some(PresentMode::Mailbox),
             });
         }
     });
@@ -1,31 +1,62 @@
 use bexa_ui::prelude::*;
 
-fn palette_card(title: &str, bg: [f32; 3], text: [u8; 3], metrics: Metrics) -> WidgetNode {
-    let label = Label::new(
-        std::rc::Rc::new(std::cell::RefCell::new(title.to_string())),
-        metrics,
-        text,
-    )
-    .with_align(Align::Left)
-    .with_padding(8.0);
+fn rgba(c: [f32; 3]) -> [f32; 4] {
+    [c[0], c[1], c[2], 1.0]
+}
 
+fn swatch(color: [f32; 4]) -> WidgetNode {
     ui! {
-        Container::new().with_background(bg).with_padding(8.0) => {
-            label,
+        Container::new()
+            .with_background_alpha(color)
+            .with_height(20.0)
+            .with_border_radius(4.0) => {}
+    }
+}
+
+fn palette_card(title: &str, theme: &Theme, metrics: Metrics) -> WidgetNode {
+    let label = |text: &str, color: [u8; 3]| {
+        Label::new(std::rc::Rc::new(std::cell::RefCell::new(text.to_string())), metrics, color)
+            .with_align(Align::Left)
+    };
+
+    ui! {
+        Container::new()
+            .with_background(theme.panel)
+            .with_border(1.0, theme.border)
+            .with_border_radius(8.0)
+            .with_padding(12.0)
+            .with_gap(8.0) => {
+            label(title, theme.text_primary),
+            label("Secondary text", theme.text_secondary),
+            Flex::row(6.0) => {
+                swatch(rgba(theme.button)),
+                swatch(theme.success),
+                swatch(theme.warning),
+                swatch(theme.error),
+                swatch(theme.info),
+                swatch(theme.focus_ring),
+            },
         }
     }
 }
 
 fn main() {
-    let theme = Theme::ocean();
+    // The window uses the preset named on the command line
+    let theme = match std::env::args().nth(1).as_deref() {
+        Some("dark") => Theme::dark(),
+        Some("light") => Theme::light(),
+        Some("high-contrast") => Theme::high_contrast(),
+        _ => Theme::ocean(),
+    };
     let metrics = Metrics::new(18.0, 24.0);
 
     let root = ui! {
         Container::new().with_padding(32.0) => {
             Flex::row(16.0) => {
-                palette_card("Ocean", [0.18, 0.30, 0.40], [230, 230, 230], metrics),
-                palette_card("Light", [0.88, 0.90, 0.92], [20, 20, 20], metrics),
-                palette_card("Dark", [0.12, 0.14, 0.18], [230, 230, 230], metrics),
+                palette_card("Ocean", &Theme::ocean(), metrics),
+                palette_card("Dark", &Theme::dark(), metrics),
+                palette_card("Light", &Theme::light(), metrics),
+                palette_card("High contrast", &Theme::high_contrast(), metrics),
             },
         }
     };
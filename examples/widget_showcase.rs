@@ -12,17 +12,21 @@ fn label_node(text: &str, metrics: Metrics, color: [u8; 3]) -> WidgetNode {
     )
 }
 
-fn panel(bg: [f32; 3]) -> Container {
+fn panel(theme: &Theme) -> Container {
     Container::new()
-        .with_background(bg)
+        .with_background(theme.panel)
         .with_padding(16.0)
         .with_border_radius(10.0)
-        .with_border(1.0, [0.25, 0.30, 0.40, 1.0])
+        .with_border(1.0, theme.border)
+}
+
+fn text_rgb(color: [f32; 4]) -> [u8; 3] {
+    [color[0], color[1], color[2]].map(|c| (c * 255.0) as u8)
 }
 
 // --- Sections ---
 
-fn build_tabs_panel(metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
+fn build_tabs_panel(theme: &Theme, metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
     let (active_tab, set_active_tab) = create_signal(0_usize);
 
     let tabs = Tabs::new(
@@ -37,23 +41,53 @@ fn build_tabs_panel(metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
         metrics,
     );
 
+    let tab_panel = |text: &str| {
+        WidgetNode::new(
+            Container::new().with_padding(8.0),
+            vec![label_node(text, metrics, theme.text_secondary)],
+        )
+    };
+    let (_, set_note) = create_signal(String::new());
+    let (volume, set_volume) = create_signal(60.0_f32);
+    let settings = WidgetNode::new(
+        Container::new().with_padding(8.0).with_gap(6.0),
+        vec![
+            label_node("Typed text stays here while other tabs are shown.", metrics, theme.text_secondary),
+            WidgetNode::new(TextInput::new(set_note).with_placeholder("Display name"), vec![]),
+            label_node("Volume", metrics, theme.text_secondary),
+            WidgetNode::new(
+                Slider::new(volume, set_volume, metrics)
+                    .with_step(5.0)
+                    .with_value_label(|v| format!("{v:.0}%")),
+                vec![],
+            ),
+        ],
+    );
+
     WidgetNode::new(
-        panel([0.10, 0.12, 0.16]),
+        panel(theme),
         vec![
-            label_node("Tabs", title_metrics, [180, 190, 220]),
-            WidgetNode::new(tabs, vec![]),
-            label_node("Switch tabs with click or arrow keys when focused.", metrics, [140, 140, 160]),
+            label_node("Tabs", title_metrics, theme.text_primary),
+            tab_view(
+                tabs,
+                vec![
+                    tab_panel("Switch tabs with click or arrow keys when focused."),
+                    tab_panel("Metrics: 42 req/s, p95 120 ms."),
+                    tab_panel("Logs: no new entries."),
+                    settings,
+                ],
+            ),
         ],
     )
 }
 
-fn build_table_panel(metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
+fn build_table_panel(theme: &Theme, metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
     let columns = vec![
-        Column::new("Service", 2.0),
-        Column::new("Status", 1.0),
-        Column::new("CPU %", 1.0),
-        Column::new("Memory", 1.0),
-        Column::new("Uptime", 1.5),
+        Column::new("Service", 2.0).with_min_width(150.0),
+        Column::new("Status", 1.0).with_min_width(110.0),
+        Column::new("CPU %", 1.0).with_align(Align::Right).with_min_width(110.0),
+        Column::new("Memory", 1.0).with_align(Align::Right).with_min_width(110.0),
+        Column::new("Uptime", 1.5).with_min_width(140.0),
     ];
 
     let data = vec![
@@ -72,18 +106,28 @@ fn build_table_panel(metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
     let (rows, _set_rows) = create_signal(data);
     let (selected, set_selected) = create_signal(None);
 
-    let table = Table::new(columns, rows, selected, set_selected, metrics);
+    let (ok, warn, bad) = (text_rgb(theme.success), text_rgb(theme.warning), text_rgb(theme.error));
+    let table = Table::new(columns, rows, selected, set_selected, metrics)
+        .with_striping(false)
+        .with_column_lines(true)
+        .with_frozen_columns(1)
+        .with_cell_style(move |_, col, status| match (col, status) {
+            (1, "Running") => Some(ok),
+            (1, "Warning") => Some(warn),
+            (1, "Stopped") => Some(bad),
+            _ => None,
+        });
 
     WidgetNode::new(
-        panel([0.10, 0.12, 0.16]),
+        panel(theme),
         vec![
-            label_node("Table / DataGrid", title_metrics, [180, 190, 220]),
+            label_node("Table / DataGrid", title_metrics, theme.text_primary),
             WidgetNode::new(table, vec![]),
         ],
     )
 }
 
-fn build_tree_panel(metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
+fn build_tree_panel(theme: &Theme, metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
     let tree = TreeView::new(
         vec![
             TreeNode::branch("src", vec![
@@ -117,15 +161,15 @@ fn build_tree_panel(metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
     );
 
     WidgetNode::new(
-        panel([0.10, 0.12, 0.16]),
+        panel(theme),
         vec![
-            label_node("TreeView", title_metrics, [180, 190, 220]),
+            label_node("TreeView", title_metrics, theme.text_primary),
             WidgetNode::new(tree, vec![]),
         ],
     )
 }
 
-fn build_chart_panel(metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
+fn build_chart_panel(theme: &Theme, metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
     let bars = vec![
         Bar::new("Mon", 320.0, [0.20, 0.65, 0.85, 1.0]),
         Bar::new("Tue", 480.0, [0.25, 0.70, 0.88, 1.0]),
@@ -141,28 +185,94 @@ fn build_chart_panel(metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
         .with_bar_radius(6.0)
         .with_bar_gap(10.0);
 
-    // Second chart with different colors
+    // Second chart, horizontal: ranked services with longer names
     let bars2 = vec![
-        Bar::new("API", 95.0, [0.20, 0.70, 0.40, 1.0]),
-        Bar::new("Auth", 87.0, [0.25, 0.75, 0.45, 1.0]),
-        Bar::new("DB", 72.0, [0.90, 0.55, 0.20, 1.0]),
-        Bar::new("Cache", 99.0, [0.30, 0.80, 0.50, 1.0]),
-        Bar::new("Queue", 45.0, [0.85, 0.25, 0.25, 1.0]),
+        Bar::new("cache-redis", 99.0, [0.30, 0.80, 0.50, 1.0]),
+        Bar::new("api-gateway", 95.0, [0.20, 0.70, 0.40, 1.0]),
+        Bar::new("auth-service", 87.0, [0.25, 0.75, 0.45, 1.0]),
+        Bar::new("db-primary", 72.0, [0.90, 0.55, 0.20, 1.0]),
+        Bar::new("worker-queue", 45.0, [0.85, 0.25, 0.25, 1.0]),
     ];
     let (bars2_sig, _set_bars2) = create_signal(bars2);
 
     let chart2 = BarChart::new(bars2_sig, metrics, 200.0)
+        .with_horizontal(true)
         .with_max_value(100.0)
+        .with_value_formatter(|v| format!("{v:.0}%"))
         .with_bar_radius(4.0)
         .with_bar_gap(12.0);
 
+    // Profit/loss: negative bars hang below the zero axis
+    let gain = [0.22, 0.70, 0.45, 1.0];
+    let loss = [0.85, 0.30, 0.28, 1.0];
+    let pnl = vec![
+        Bar::new("Q1", 1200.0, gain),
+        Bar::new("Q2", -450.0, loss),
+        Bar::new("Q3", 800.0, gain),
+        Bar::new("Q4", -1300.0, loss),
+    ];
+    let (pnl_sig, _) = create_signal(pnl);
+    let chart3 = BarChart::new(pnl_sig, metrics, 220.0)
+        .with_value_formatter(|v| {
+            let sign = if v < 0.0 { "-" } else { "" };
+            format!("{sign}${:.1}k", v.abs() / 1000.0)
+        })
+        .with_bar_radius(4.0)
+        .with_bar_gap(16.0);
+
+    // CPU per host split by user / system / I/O wait
+    let cpu = vec![
+        Bar::multi("web-1", vec![38.0, 12.0, 4.0]),
+        Bar::multi("web-2", vec![31.0, 10.0, 6.0]),
+        Bar::multi("db-1", vec![22.0, 18.0, 27.0]),
+        Bar::multi("batch-1", vec![64.0, 8.0, 3.0]),
+    ];
+    let (cpu_sig, _) = create_signal(cpu);
+    let chart4 = BarChart::new(cpu_sig, metrics, 220.0)
+        .with_mode(BarMode::Stacked)
+        .with_series("user", [0.25, 0.60, 0.90, 1.0])
+        .with_series("system", [0.85, 0.55, 0.25, 1.0])
+        .with_series("iowait", [0.60, 0.40, 0.85, 1.0])
+        .with_max_value(100.0)
+        .with_value_formatter(|v| format!("{v:.0}%"))
+        .with_bar_gap(16.0);
+    let cpu_legend = chart4.legend(Metrics::new(12.0, 16.0));
+
+    // Latency over the last minute, one point every 5 seconds
+    let wave = |base: f32, amp: f32, phase: f32| -> Vec<(f32, f32)> {
+        (0..=12)
+            .map(|i| {
+                let t = i as f32 * 5.0;
+                (t, base + amp * (t / 9.0 + phase).sin())
+            })
+            .collect()
+    };
+    let (p50, _) = create_signal(wave(42.0, 8.0, 0.0));
+    let (p99, _) = create_signal(wave(120.0, 30.0, 1.3));
+    let line_chart = LineChart::new(metrics, 220.0)
+        .with_series("p50", p50, [0.25, 0.70, 0.90, 1.0])
+        .with_series("p99", p99, [0.90, 0.45, 0.30, 1.0])
+        .with_min_value(0.0)
+        .with_point_radius(3.0)
+        .with_value_formatter(|v| format!("{v:.0} ms"))
+        .with_x_formatter(|t| format!("{t:.0}s"));
+    let latency_legend = line_chart.legend(Metrics::new(12.0, 16.0));
+
     WidgetNode::new(
-        panel([0.10, 0.12, 0.16]),
+        panel(theme),
         vec![
-            label_node("BarChart \u{2014} Requests/day", title_metrics, [180, 190, 220]),
+            label_node("BarChart \u{2014} Requests/day", title_metrics, theme.text_primary),
             WidgetNode::new(chart, vec![]),
-            label_node("Service Health (%)", title_metrics, [180, 190, 220]),
+            label_node("Service Health (%)", title_metrics, theme.text_primary),
             WidgetNode::new(chart2, vec![]),
+            label_node("Profit / Loss", title_metrics, theme.text_primary),
+            WidgetNode::new(chart3, vec![]),
+            label_node("CPU by host", title_metrics, theme.text_primary),
+            WidgetNode::new(chart4, vec![]),
+            WidgetNode::new(cpu_legend, vec![]),
+            label_node("LineChart \u{2014} Latency", title_metrics, theme.text_primary),
+            WidgetNode::new(line_chart, vec![]),
+            WidgetNode::new(latency_legend, vec![]),
         ],
     )
 }
@@ -170,33 +280,38 @@ fn build_chart_panel(metrics: Metrics, title_metrics: Metrics) -> WidgetNode {
 // --- Main ---
 
 fn main() {
-    let theme = Theme::ocean();
+    // The preset named on the command line, e.g. `-- light`
+    let theme = match std::env::args().nth(1).as_deref() {
+        Some("dark") => Theme::dark(),
+        Some("light") => Theme::light(),
+        Some("high-contrast") => Theme::high_contrast(),
+        _ => Theme::ocean(),
+    };
     let metrics = Metrics::new(14.0, 20.0);
     let title_metrics = Metrics::new(18.0, 26.0);
 
     let left_col = WidgetNode::new(
         Flex::column(16.0, 0.0),
         vec![
-            build_tabs_panel(metrics, title_metrics),
-            build_table_panel(metrics, title_metrics),
+            build_tabs_panel(&theme, metrics, title_metrics),
+            build_table_panel(&theme, metrics, title_metrics),
         ],
     );
 
     let right_col = WidgetNode::new(
         Flex::column(16.0, 0.0),
         vec![
-            build_tree_panel(metrics, title_metrics),
-            build_chart_panel(metrics, title_metrics),
+            build_tree_panel(&theme, metrics, title_metrics),
+            build_chart_panel(&theme, metrics, title_metrics),
         ],
     );
 
     let root = WidgetNode::new(
         Container::new()
-            .with_background([0.06, 0.07, 0.10])
             .with_padding(20.0)
             .with_gap(16.0),
         vec![
-            label_node("BexaUI \u{2014} Widget Showcase", Metrics::new(24.0, 34.0), [220, 225, 240]),
+            label_node("BexaUI \u{2014} Widget Showcase", Metrics::new(24.0, 34.0), theme.text_primary),
             WidgetNode::new(Flex::row(16.0), vec![left_col, right_col]),
         ],
     );